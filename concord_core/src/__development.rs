@@ -8,10 +8,10 @@
 pub use crate::development_executor::{
     CapturedBodyCategory, CapturedNativeRequest, DeterministicBodyGate, DeterministicExecutionKind,
     DeterministicExecutorInstallationError, DeterministicFakeCredential,
-    DeterministicNativeExecutor, RequestBodyTerminalObservation, ScriptedNativeResponse,
-    ScriptedResponseBodyStep, SyntheticExecutionFailure, UnsafeCredentialPlacementExpectations,
-    UnsafeDeterministicFakeBody, UnsafeRequestBodyExpectations, configure_application_executor,
-    configure_provider_executor,
+    DeterministicNativeExecutor, InjectedFault, RequestBodyDigest, RequestBodyTerminalObservation,
+    ScriptedNativeResponse, ScriptedResponseBodyStep, SyntheticExecutionFailure,
+    UnsafeCredentialPlacementExpectations, UnsafeDeterministicFakeBody,
+    UnsafeRequestBodyExpectations, configure_application_executor, configure_provider_executor,
 };
 
 /// Authentication lifecycle observations used to verify deterministic
@@ -86,13 +86,25 @@ where
     }
 
     #[doc(hidden)]
-    pub async fn set_manual(&self, value: P::Credential) -> Result<(), crate::auth::AuthError> {
-        self.state.set_manual(value).await
+    pub async fn set_manual(
+        &self,
+        value: P::Credential,
+        store: Option<&std::sync::Arc<dyn crate::auth::CredentialStore>>,
+        clock: &std::sync::Arc<dyn crate::clock::Clock>,
+    ) -> Result<(), crate::auth::AuthError> {
+        self.state
+            .set_manual_with_store(value, store.map(|store| store.as_ref()), clock.as_ref())
+            .await
     }
 
     #[doc(hidden)]
-    pub async fn clear_manual(&self) -> Result<(), crate::auth::AuthError> {
-        self.state.clear_manual().await
+    pub async fn clear_manual(
+        &self,
+        store: Option<&std::sync::Arc<dyn crate::auth::CredentialStore>>,
+    ) -> Result<(), crate::auth::AuthError> {
+        self.state
+            .clear_manual_with_store(store.map(|store| store.as_ref()))
+            .await
     }
 
     #[doc(hidden)]
@@ -101,6 +113,14 @@ where
     }
 }
 
+/// Obtain generated client-auth configuration for one short read.
+#[doc(hidden)]
+pub fn generated_auth_read<T>(
+    lock: &std::sync::RwLock<T>,
+) -> Result<std::sync::RwLockReadGuard<'_, T>, crate::auth::AuthError> {
+    crate::auth::read_auth_lock(lock, "generated auth configuration lock poisoned")
+}
+
 /// Obtain generated client-auth configuration for one short mutation.
 #[doc(hidden)]
 pub fn generated_auth_write<T>(
@@ -368,6 +388,54 @@ impl GeneratedApiDescriptor {
     }
 }
 
+/// Names the client context behind a generated API client type.
+///
+/// Emitted once per generated client so test tooling can bound endpoint types
+/// by `GeneratedEndpoint<C::Cx>` starting from the client type alone.
+#[doc(hidden)]
+pub trait GeneratedClient: Clone + Send + Sync + 'static {
+    type Cx: crate::client::ClientContext;
+}
+
+/// OpenAPI export surface driven by generated `openapi()` functions.
+#[cfg(feature = "openapi")]
+#[doc(hidden)]
+pub use crate::openapi::{
+    OpenApiBuilder, OpenApiJsonSchema, OpenApiOpaqueSchema, OpenApiOperation, OpenApiParamLocation,
+    OpenApiSchemaProbe, OpenApiSchemaSource, OpenApiSchemas, OpenApiSecurityScheme,
+};
+
+/// The [`OpenApiSchemaSource`] for a concrete type: its `JsonSchema` with the
+/// `schemars` feature when it has one, otherwise the opaque description.
+#[cfg(feature = "openapi")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __openapi_schema {
+    ($ty:ty) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{OpenApiJsonSchema as _, OpenApiOpaqueSchema as _};
+        (&&$crate::__private::OpenApiSchemaProbe::<$ty>::new()).openapi_schema()
+    }};
+}
+
+/// Keeps a generated `openapi()` item only when `concord_core` is built with
+/// the `openapi` feature, so the export costs nothing when it is off.
+#[cfg(feature = "openapi")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __openapi_item {
+    ($($item:tt)*) => {
+        $($item)*
+    };
+}
+
+#[cfg(not(feature = "openapi"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __openapi_item {
+    ($($item:tt)*) => {};
+}
+
 /// Construct a generated client from the macro-emitted API descriptor.
 ///
 /// `__private` is a cross-crate macro integration surface, not a Rust privacy
@@ -537,6 +605,12 @@ impl GeneratedRateLimitBucketDescriptor {
         self
     }
 
+    #[doc(hidden)]
+    pub fn with_concurrency(mut self, limit: std::num::NonZeroU32) -> Self {
+        self.0 = self.0.with_concurrency(limit);
+        self
+    }
+
     #[doc(hidden)]
     pub fn with_windows(mut self, windows: Vec<GeneratedRateLimitWindowDescriptor>) -> Self {
         self.0 = self
@@ -816,6 +890,9 @@ pub struct GeneratedBytesResponse;
 pub struct GeneratedNoContentResponse;
 #[doc(hidden)]
 pub struct GeneratedRawStreamResponse<M>(std::marker::PhantomData<fn() -> M>);
+#[cfg(feature = "json")]
+#[doc(hidden)]
+pub struct GeneratedJsonLinesResponse<T>(std::marker::PhantomData<fn() -> T>);
 
 macro_rules! impl_generated_buffered_response {
     ($marker:ty, $runtime:ty) => {
@@ -887,6 +964,26 @@ where
     }
 }
 
+#[cfg(feature = "json")]
+impl<Cx, T> generated_response_sealed::Adapter<Cx> for GeneratedJsonLinesResponse<T>
+where
+    Cx: crate::client::ClientContext,
+    T: serde::de::DeserializeOwned + Send + 'static,
+{
+    type Output = crate::stream_response::JsonLinesStream<T>;
+    fn plan(
+        ctx: crate::error::ErrorContext,
+    ) -> Result<crate::io::ResponseEntityPlan, crate::error::ApiClientError> {
+        <crate::io::JsonLines<T> as crate::io::ResponseEntity>::plan(ctx)
+    }
+    fn execute<'a>(
+        client: &'a crate::client::ApiClient<Cx>,
+        plan: crate::endpoint::RequestPlan,
+    ) -> crate::endpoint::EndpointFuture<'a, Self::Output> {
+        <crate::io::JsonLines<T> as crate::io::ResponseEntity>::execute(client, plan)
+    }
+}
+
 #[doc(hidden)]
 pub struct GeneratedResponsePreparation<Cx, Output>
 where
@@ -910,6 +1007,41 @@ where
     pub fn is_no_content(&self) -> bool {
         self.plan.no_content
     }
+
+    /// Attach one resolved `on_error` mapping. `None` is the status-less
+    /// fallback; the macro has already validated explicit statuses.
+    #[doc(hidden)]
+    pub fn on_error<C>(
+        mut self,
+        status: Option<u16>,
+        ctx: &crate::error::ErrorContext,
+    ) -> Result<Self, crate::error::ApiClientError>
+    where
+        C: crate::codec::ResponseCodec,
+        C::Value: Sync,
+    {
+        let status = status
+            .map(http::StatusCode::from_u16)
+            .transpose()
+            .map_err(|_| crate::error::ApiClientError::invalid_param(ctx.clone(), "on_error"))?;
+        self.plan.errors.push(
+            status,
+            std::any::type_name::<C::Value>(),
+            decode_generated_error_body::<C>,
+        );
+        Ok(self)
+    }
+}
+
+fn decode_generated_error_body<C>(
+    bytes: bytes::Bytes,
+    ctx: crate::codec::DecodeContext<'_>,
+) -> Result<crate::error::ErrorBody, crate::codec::CodecError>
+where
+    C: crate::codec::ResponseCodec,
+    C::Value: Sync,
+{
+    C::decode(bytes, ctx).map(crate::error::ErrorBody::new)
 }
 
 #[doc(hidden)]
@@ -945,7 +1077,8 @@ pub fn prepare_generated_policy(
     policy: crate::policy::ClientPolicyBuilder,
     auth: GeneratedAuthBuilder,
 ) -> PreparedEndpointPolicy {
-    let (headers, query, timeout, mut rate_limit) = policy.into_inner().into_parts();
+    let (headers, query, timeout, mut rate_limit, cache, idempotency_key, coalesce, hedge_after) =
+        policy.into_inner().into_parts();
     rate_limit.canonicalize();
     PreparedEndpointPolicy(crate::policy::ResolvedPolicy {
         headers,
@@ -953,6 +1086,10 @@ pub fn prepare_generated_policy(
         timeout,
         auth: auth.into_plan(),
         rate_limit,
+        cache,
+        idempotency_key,
+        coalesce,
+        hedge_after,
     })
 }
 
@@ -999,9 +1136,6 @@ where
     })
 }
 
-#[cfg(feature = "json")]
-#[doc(hidden)]
-pub use crate::auth::OAuth2ClientCredentialsProvider;
 #[doc(hidden)]
 pub use crate::auth::{
     AuthChallengePolicy as GeneratedChallengePolicy,
@@ -1010,6 +1144,12 @@ pub use crate::auth::{
     StaticBasicProvider as GeneratedStaticBasicProvider,
     StaticBearerProvider as GeneratedStaticBearerProvider,
 };
+#[cfg(feature = "json")]
+#[doc(hidden)]
+pub use crate::auth::{
+    OAuth2AuthorizationCode, OAuth2ClientCredentialsProvider, OAuth2Pkce,
+    OAuth2RefreshTokenProvider,
+};
 #[doc(hidden)]
 pub use crate::codec::{
     BodyCodec, CodecError, ContentType, DecodeContext, Decodes, EncodeContext, EncodedBody,
@@ -1017,8 +1157,8 @@ pub use crate::codec::{
 };
 #[doc(hidden)]
 pub use crate::endpoint::{
-    GeneratedEndpoint, GeneratedIntoPreparedCall, GeneratedPaginatedEndpoint,
-    GeneratedResponseTerminalEndpoint, GeneratedReusableEndpoint,
+    GeneratedEndpoint, GeneratedEndpointFields, GeneratedIntoPreparedCall,
+    GeneratedPaginatedEndpoint, GeneratedResponseTerminalEndpoint, GeneratedReusableEndpoint,
     PaginationMarker as GeneratedPaginationMarker,
 };
 #[doc(hidden)]
@@ -2,7 +2,10 @@ use super::errors::{AuthError, AuthErrorKind, CredentialRefreshReason, Invalidat
 use super::future::AuthFuture;
 use super::http::AuthHttpExecutor;
 use super::ids::CredentialId;
+use super::store::{CredentialStore, StoredCredential};
 use crate::client::ClientContext;
+use crate::clock::Clock;
+use crate::telemetry::CredentialTrace;
 use std::marker::PhantomData;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex, MutexGuard};
@@ -14,6 +17,18 @@ pub trait CredentialMaterial: Clone + Send + Sync + 'static {
     fn expires_at(&self) -> Option<Instant> {
         None
     }
+
+    /// Converts the material for a [`CredentialStore`]. Materials that return
+    /// `None` are never persisted. Expiry deadlines are measured on `clock`.
+    fn to_stored(&self, clock: &dyn Clock) -> Option<StoredCredential> {
+        let _ = clock;
+        None
+    }
+
+    fn from_stored(stored: &StoredCredential, clock: &dyn Clock) -> Option<Self> {
+        let _ = (stored, clock);
+        None
+    }
 }
 
 pub trait SecretCredential: CredentialMaterial {
@@ -27,6 +42,8 @@ pub struct CredentialContext<'a, Cx: ClientContext> {
     pub executor: &'a dyn AuthHttpExecutor,
     pub credential_id: CredentialId,
     pub reason: CredentialRefreshReason,
+    /// The client's time source; providers compute expiry deadlines from it.
+    pub clock: &'a dyn Clock,
 }
 
 impl<Cx: ClientContext> Clone for CredentialContext<'_, Cx> {
@@ -38,6 +55,7 @@ impl<Cx: ClientContext> Clone for CredentialContext<'_, Cx> {
             executor: self.executor,
             credential_id: self.credential_id.clone(),
             reason: self.reason,
+            clock: self.clock,
         }
     }
 }
@@ -226,10 +244,15 @@ pub enum CredentialLifecycleEvent {
     },
 }
 
-enum SlotAction<T> {
+enum SlotAction<'s, T> {
     Wait {
         notified: Pin<Box<OwnedNotified>>,
     },
+    Restore {
+        store: &'s dyn CredentialStore,
+        generation: u64,
+        guard: RefreshGuard<T>,
+    },
     Acquire {
         generation: u64,
         guard: RefreshGuard<T>,
@@ -369,10 +392,23 @@ where
         self.id.clone()
     }
 
+    #[cfg_attr(not(test), allow(dead_code))]
     pub async fn get_or_refresh<'a>(
         &'a self,
         ctx: CredentialContext<'a, Cx>,
         policy: AuthStepPolicy,
+    ) -> Result<CredentialLease<P::Credential>, AuthError> {
+        self.get_or_refresh_with_store(ctx, policy, None).await
+    }
+
+    /// Returns the cached credential or acquires/refreshes it. With a
+    /// `store`, the first generation is restored from it and every acquired or
+    /// refreshed credential is saved back.
+    pub async fn get_or_refresh_with_store<'a>(
+        &'a self,
+        ctx: CredentialContext<'a, Cx>,
+        policy: AuthStepPolicy,
+        store: Option<&'a dyn CredentialStore>,
     ) -> Result<CredentialLease<P::Credential>, AuthError> {
         let provider = self.provider_ref()?;
 
@@ -381,7 +417,7 @@ where
                 let mut inner = lock_slot_inner(&self.inner);
                 match &inner.state {
                     CredentialSlotState::Valid { value, generation }
-                        if credential_refresh_reason(value, policy)?.is_none() =>
+                        if credential_refresh_reason(value, policy, ctx.clock.now())?.is_none() =>
                     {
                         return Ok(CredentialLease {
                             value: value.clone(),
@@ -393,7 +429,7 @@ where
                         let current = value.clone();
                         let previous_generation = *generation;
                         let generation = next_generation(&inner.state)?;
-                        let reason = credential_refresh_reason(value, policy)?
+                        let reason = credential_refresh_reason(value, policy, ctx.clock.now())?
                             .unwrap_or(CredentialRefreshReason::ExpiringSoon);
                         let previous = RefreshPrevious::Valid {
                             value: value.clone(),
@@ -440,7 +476,7 @@ where
                         error,
                         retry_after,
                     } => {
-                        if retry_after.is_some_and(|retry_at| retry_at > Instant::now()) {
+                        if retry_after.is_some_and(|retry_at| retry_at > ctx.clock.now()) {
                             return Err(error.clone());
                         }
                         let notify = Arc::new(Notify::new());
@@ -465,7 +501,10 @@ where
                             },
                         }
                     }
-                    CredentialSlotState::Empty { .. } => {
+                    CredentialSlotState::Empty {
+                        generation: previous_generation,
+                    } => {
+                        let restore_from = store.filter(|_| *previous_generation == 0);
                         let notify = Arc::new(Notify::new());
                         let owner = inner.next_refresh_owner()?;
                         let generation = next_generation(&inner.state)?;
@@ -475,13 +514,18 @@ where
                             owner,
                             previous: RefreshPrevious::Empty,
                         };
-                        SlotAction::Acquire {
-                            generation,
-                            guard: RefreshGuard {
-                                inner: self.inner.clone(),
-                                owner,
-                                disarmed: false,
+                        let guard = RefreshGuard {
+                            inner: self.inner.clone(),
+                            owner,
+                            disarmed: false,
+                        };
+                        match restore_from {
+                            Some(store) => SlotAction::Restore {
+                                store,
+                                generation,
+                                guard,
                             },
+                            None => SlotAction::Acquire { generation, guard },
                         }
                     }
                 }
@@ -491,12 +535,47 @@ where
                 SlotAction::Wait { notified } => {
                     notified.await;
                 }
+                SlotAction::Restore {
+                    store,
+                    generation,
+                    mut guard,
+                } => {
+                    if let Some(value) = self.restore_from_store(store, policy, ctx.clock).await {
+                        // Re-enter the loop so a restored credential that is
+                        // about to expire goes through the normal refresh path.
+                        match self.commit_slot_result(
+                            generation,
+                            &mut guard,
+                            Ok(value),
+                            ctx.clock,
+                        )? {
+                            CommitOutcome::Failed(error) => return Err(error),
+                            CommitOutcome::Stored(_) | CommitOutcome::StaleOwner => continue,
+                        }
+                    }
+                    let result = CredentialTrace::open(&self.id, "acquire", &ctx.reason)
+                        .run(provider.acquire(ctx.clone()))
+                        .await;
+                    match self
+                        .save_and_commit(generation, &mut guard, result, Some(store), ctx.clock)
+                        .await?
+                    {
+                        CommitOutcome::Stored(lease) => return Ok(lease),
+                        CommitOutcome::Failed(error) => return Err(error),
+                        CommitOutcome::StaleOwner => continue,
+                    }
+                }
                 SlotAction::Acquire {
                     generation,
                     mut guard,
                 } => {
-                    let result = provider.acquire(ctx.clone()).await;
-                    match self.commit_slot_result(generation, &mut guard, result)? {
+                    let result = CredentialTrace::open(&self.id, "acquire", &ctx.reason)
+                        .run(provider.acquire(ctx.clone()))
+                        .await;
+                    match self
+                        .save_and_commit(generation, &mut guard, result, store, ctx.clock)
+                        .await?
+                    {
                         CommitOutcome::Stored(lease) => return Ok(lease),
                         CommitOutcome::Failed(error) => return Err(error),
                         CommitOutcome::StaleOwner => continue,
@@ -508,8 +587,13 @@ where
                     mut guard,
                     reason,
                 } => {
-                    let result = provider.refresh(ctx.with_reason(reason), &current).await;
-                    match self.commit_slot_result(generation, &mut guard, result)? {
+                    let result = CredentialTrace::open(&self.id, "refresh", &reason)
+                        .run(provider.refresh(ctx.with_reason(reason), &current))
+                        .await;
+                    match self
+                        .save_and_commit(generation, &mut guard, result, store, ctx.clock)
+                        .await?
+                    {
                         CommitOutcome::Stored(lease) => return Ok(lease),
                         CommitOutcome::Failed(error) => return Err(error),
                         CommitOutcome::StaleOwner => continue,
@@ -519,27 +603,67 @@ where
         }
     }
 
+    #[cfg_attr(not(test), allow(dead_code))]
     pub async fn invalidate_generation<'a>(
         &'a self,
         ctx: CredentialContext<'a, Cx>,
         generation: Option<u64>,
         reason: InvalidateReason,
+    ) -> Result<(), AuthError> {
+        self.invalidate_generation_with_store(ctx, generation, reason, None)
+            .await
+    }
+
+    /// Invalidates `generation` and removes it from `store` before the
+    /// provider is notified.
+    pub async fn invalidate_generation_with_store<'a>(
+        &'a self,
+        ctx: CredentialContext<'a, Cx>,
+        generation: Option<u64>,
+        reason: InvalidateReason,
+        store: Option<&'a dyn CredentialStore>,
     ) -> Result<(), AuthError> {
         let provider = self.provider_ref()?;
         let (current, current_generation) = self.invalidate_generation_state(generation)?;
         self.observe_generation_invalidation(generation, current_generation, current.is_some());
-        provider.invalidate(ctx, current.as_ref(), reason).await
+        if current.is_some()
+            && let Some(store) = store
+        {
+            store.remove(&self.id).await?;
+        }
+        CredentialTrace::open(&self.id, "invalidate", &reason)
+            .run(provider.invalidate(ctx, current.as_ref(), reason))
+            .await
     }
 
     /// Invalidates only the local slot state. This is intentionally separate
     /// from provider invalidation so terminal response handling cannot access
     /// an executor or initiate network/provider work.
+    #[cfg_attr(not(test), allow(dead_code))]
     pub fn invalidate_generation_local(&self, generation: Option<u64>) -> Result<(), AuthError> {
         let (current, current_generation) = self.invalidate_generation_state(generation)?;
         self.observe_generation_invalidation(generation, current_generation, current.is_some());
         Ok(())
     }
 
+    /// [`Self::invalidate_generation_local`], also removing the invalidated
+    /// generation from `store`. That removal is the only I/O this path
+    /// performs.
+    pub async fn invalidate_generation_local_with_store(
+        &self,
+        generation: Option<u64>,
+        store: Option<&dyn CredentialStore>,
+    ) -> Result<(), AuthError> {
+        let (current, current_generation) = self.invalidate_generation_state(generation)?;
+        self.observe_generation_invalidation(generation, current_generation, current.is_some());
+        if current.is_some()
+            && let Some(store) = store
+        {
+            store.remove(&self.id).await?;
+        }
+        Ok(())
+    }
+
     fn invalidate_generation_state(
         &self,
         generation: Option<u64>,
@@ -652,7 +776,30 @@ where
         if let Some(error) = &self.init_error {
             return Err(error.clone());
         }
+        self.publish_manual(value)
+    }
+
+    /// Sets a manual credential and saves it to `store` when the material
+    /// supports persistence, measuring its expiry on `clock`. Save failures
+    /// keep the credential in memory only.
+    pub async fn set_manual_with_store(
+        &self,
+        value: P::Credential,
+        store: Option<&dyn CredentialStore>,
+        clock: &dyn Clock,
+    ) -> Result<(), AuthError> {
+        if let Some(error) = &self.init_error {
+            return Err(error.clone());
+        }
+        if let Some(store) = store
+            && let Some(stored) = value.to_stored(clock)
+        {
+            let _ = store.save(&self.id, stored).await;
+        }
+        self.publish_manual(value)
+    }
 
+    fn publish_manual(&self, value: P::Credential) -> Result<(), AuthError> {
         let notify = {
             let mut inner = lock_slot_inner(&self.inner);
             let generation = next_generation(&inner.state)?;
@@ -670,6 +817,14 @@ where
     }
 
     pub async fn clear_manual(&self) -> Result<(), AuthError> {
+        self.clear_manual_with_store(None).await
+    }
+
+    /// Clears the cached credential and removes it from `store`.
+    pub async fn clear_manual_with_store(
+        &self,
+        store: Option<&dyn CredentialStore>,
+    ) -> Result<(), AuthError> {
         if let Some(error) = &self.init_error {
             return Err(error.clone());
         }
@@ -687,6 +842,9 @@ where
         if let Some(notify) = notify {
             notify.notify_waiters();
         }
+        if let Some(store) = store {
+            store.remove(&self.id).await?;
+        }
         Ok(())
     }
 
@@ -713,11 +871,52 @@ where
         }
     }
 
+    async fn restore_from_store(
+        &self,
+        store: &dyn CredentialStore,
+        policy: AuthStepPolicy,
+        clock: &dyn Clock,
+    ) -> Option<P::Credential> {
+        let stored = store.load(&self.id).await.ok().flatten()?;
+        let restored = (!stored.is_expired())
+            .then(|| P::Credential::from_stored(&stored, clock))
+            .flatten()
+            .filter(|value| {
+                !matches!(
+                    credential_refresh_reason(value, policy, clock.now()),
+                    Ok(Some(CredentialRefreshReason::Expired)) | Err(_)
+                )
+            });
+        if restored.is_none() {
+            let _ = store.remove(&self.id).await;
+        }
+        restored
+    }
+
+    /// Saves a fresh credential before publishing it, so an invalidation that
+    /// observes the new generation always runs after the save.
+    async fn save_and_commit(
+        &self,
+        attempt_generation: u64,
+        guard: &mut RefreshGuard<P::Credential>,
+        result: Result<P::Credential, AuthError>,
+        store: Option<&dyn CredentialStore>,
+        clock: &dyn Clock,
+    ) -> Result<CommitOutcome<P::Credential>, AuthError> {
+        if let (Ok(value), Some(store)) = (&result, store)
+            && let Some(stored) = value.to_stored(clock)
+        {
+            let _ = store.save(&self.id, stored).await;
+        }
+        self.commit_slot_result(attempt_generation, guard, result, clock)
+    }
+
     fn commit_slot_result(
         &self,
         attempt_generation: u64,
         guard: &mut RefreshGuard<P::Credential>,
         result: Result<P::Credential, AuthError>,
+        clock: &dyn Clock,
     ) -> Result<CommitOutcome<P::Credential>, AuthError> {
         let notify = {
             let inner = lock_slot_inner(&self.inner);
@@ -767,7 +966,7 @@ where
                         .retry_after()
                         .map(|wait| {
                             checked_auth_instant_add(
-                                Instant::now(),
+                                clock.now(),
                                 wait,
                                 "auth retry-after overflowed",
                             )
@@ -826,11 +1025,11 @@ fn notify_refresh_wait_registered() {}
 fn credential_refresh_reason<T: CredentialMaterial>(
     value: &T,
     policy: AuthStepPolicy,
+    now: Instant,
 ) -> Result<Option<CredentialRefreshReason>, AuthError> {
     value
         .expires_at()
         .map(|expires_at| {
-            let now = Instant::now();
             if expires_at <= now {
                 Ok(Some(CredentialRefreshReason::Expired))
             } else if expires_at
@@ -1763,6 +1962,7 @@ mod tests {
             executor: &NOOP_EXECUTOR,
             credential_id: CredentialId::new("test", "credential"),
             reason: CredentialRefreshReason::Missing,
+            clock: &crate::clock::SystemClock,
         }
     }
 
@@ -69,8 +69,7 @@ pub enum InvalidateReason {
     ProviderRejected,
 }
 
-#[cfg(test)]
-fn read_auth_lock<'a, T>(
+pub fn read_auth_lock<'a, T>(
     lock: &'a std::sync::RwLock<T>,
     message: &'static str,
 ) -> Result<std::sync::RwLockReadGuard<'a, T>, AuthError> {
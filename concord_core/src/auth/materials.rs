@@ -1,4 +1,6 @@
 use super::credentials::{CredentialMaterial, SecretCredential};
+use super::store::{StoredCredential, restored_expiry, stored_expiry};
+use crate::clock::Clock;
 use crate::secret::SecretString;
 use serde::Deserialize;
 use std::time::Instant;
@@ -35,6 +37,46 @@ impl CredentialMaterial for AccessToken {
     fn expires_at(&self) -> Option<Instant> {
         self.expires_at
     }
+
+    fn to_stored(&self, clock: &dyn Clock) -> Option<StoredCredential> {
+        let mut stored = StoredCredential::new("access_token")
+            .with_field("token", self.token.clone())
+            .with_expires_at(stored_expiry(self.expires_at, clock));
+        if let Some(refresh_token) = &self.refresh_token {
+            stored = stored.with_field("refresh_token", refresh_token.clone());
+        }
+        if !self.scope.is_empty() {
+            stored = stored.with_field("scope", self.scope.join(" "));
+        }
+        if let Some(audience) = &self.audience {
+            stored = stored.with_field("audience", audience.clone());
+        }
+        Some(stored)
+    }
+
+    fn from_stored(stored: &StoredCredential, clock: &dyn Clock) -> Option<Self> {
+        if stored.kind() != "access_token" {
+            return None;
+        }
+        Some(Self {
+            token: stored.field("token")?.clone(),
+            expires_at: restored_expiry(stored.expires_at(), clock),
+            refresh_token: stored.field("refresh_token").cloned(),
+            scope: stored
+                .field("scope")
+                .map(|scope| {
+                    scope
+                        .expose_secret()
+                        .split_whitespace()
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            audience: stored
+                .field("audience")
+                .map(|audience| audience.expose_secret().to_string()),
+        })
+    }
 }
 
 impl SecretCredential for AccessToken {
@@ -85,7 +127,17 @@ impl ApiKey {
     }
 }
 
-impl CredentialMaterial for ApiKey {}
+impl CredentialMaterial for ApiKey {
+    fn to_stored(&self, _clock: &dyn Clock) -> Option<StoredCredential> {
+        Some(StoredCredential::new("api_key").with_field("value", self.value.clone()))
+    }
+
+    fn from_stored(stored: &StoredCredential, _clock: &dyn Clock) -> Option<Self> {
+        (stored.kind() == "api_key")
+            .then(|| stored.field("value").cloned().map(Self::new))
+            .flatten()
+    }
+}
 
 impl SecretCredential for ApiKey {
     fn secret_value(&self) -> &str {
@@ -109,7 +161,25 @@ impl BasicCredential {
     }
 }
 
-impl CredentialMaterial for BasicCredential {}
+impl CredentialMaterial for BasicCredential {
+    fn to_stored(&self, _clock: &dyn Clock) -> Option<StoredCredential> {
+        Some(
+            StoredCredential::new("basic")
+                .with_field("username", self.username.clone())
+                .with_field("password", self.password.clone()),
+        )
+    }
+
+    fn from_stored(stored: &StoredCredential, _clock: &dyn Clock) -> Option<Self> {
+        if stored.kind() != "basic" {
+            return None;
+        }
+        Some(Self::new(
+            stored.field("username")?.clone(),
+            stored.field("password")?.clone(),
+        ))
+    }
+}
 
 #[cfg(test)]
 mod tests {
@@ -126,4 +196,31 @@ mod tests {
         assert!(!rendered.contains("BASIC_USERNAME_SENTINEL_DO_NOT_DEBUG"));
         assert!(!rendered.contains("BASIC_PASSWORD_SENTINEL_DO_NOT_DEBUG"));
     }
+
+    #[test]
+    fn access_token_round_trips_through_stored_credential() {
+        let clock = crate::clock::test_system_clock().as_ref();
+        let mut token = AccessToken::new("STORED_ACCESS_SENTINEL")
+            .expires_at(Instant::now() + std::time::Duration::from_secs(300));
+        token.refresh_token = Some("STORED_REFRESH_SENTINEL".into());
+        token.scope = vec!["read".to_string(), "write".to_string()];
+
+        let stored = token.to_stored(clock).expect("access tokens are storable");
+        let rendered = format!("{stored:?}");
+        assert!(!rendered.contains("STORED_ACCESS_SENTINEL"));
+        assert!(!rendered.contains("STORED_REFRESH_SENTINEL"));
+
+        let restored = AccessToken::from_stored(&stored, clock).expect("restored token");
+        assert_eq!(restored.token.expose_secret(), "STORED_ACCESS_SENTINEL");
+        assert_eq!(
+            restored
+                .refresh_token
+                .as_ref()
+                .map(SecretString::expose_secret),
+            Some("STORED_REFRESH_SENTINEL")
+        );
+        assert_eq!(restored.scope, ["read", "write"]);
+        assert!(restored.expires_at.is_some());
+        assert!(ApiKey::from_stored(&stored, clock).is_none());
+    }
 }
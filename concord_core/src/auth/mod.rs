@@ -7,6 +7,7 @@ mod materials;
 mod orchestrator;
 mod plan;
 mod providers;
+mod store;
 
 #[cfg(any(test, feature = "dangerous-dev-tools"))]
 pub(crate) use credentials::CredentialLifecycleObservationTarget;
@@ -17,7 +18,8 @@ pub use credentials::{
 #[cfg(any(test, feature = "dangerous-dev-tools"))]
 pub use credentials::{CredentialGenerationSnapshot, CredentialLifecycleEvent};
 pub use errors::{
-    AuthError, AuthErrorKind, CredentialRefreshReason, InvalidateReason, write_auth_lock,
+    AuthError, AuthErrorKind, CredentialRefreshReason, InvalidateReason, read_auth_lock,
+    write_auth_lock,
 };
 pub use future::AuthFuture;
 pub use http::{
@@ -41,11 +43,15 @@ pub use plan::{
     PlannedAuthPlacement, PreparedAuthCredential, apply_basic_credential, apply_secret_credential,
     auth_decision_for_status,
 };
-#[cfg(feature = "json")]
-pub use providers::OAuth2ClientCredentialsProvider;
 pub use providers::{
     ManualCredentialProvider, StaticApiKeyProvider, StaticBasicProvider, StaticBearerProvider,
 };
+#[cfg(feature = "json")]
+pub use providers::{
+    OAuth2AuthorizationCode, OAuth2ClientCredentialsProvider, OAuth2Pkce,
+    OAuth2RefreshTokenProvider,
+};
+pub use store::{CredentialStore, FileCredentialStore, InMemoryCredentialStore, StoredCredential};
 
 #[derive(Clone, Default)]
 pub struct NoAuthState;
@@ -2,10 +2,11 @@ use super::{
     AuthApplication, AuthApplicationRequest, AuthAppliedCredential, AuthError, AuthErrorKind,
     AuthFuture, AuthHttpExecutor, AuthPreparationReuse, AuthRejectionAction, AuthRequirement,
     AuthStepPolicy, BasicCredential, CredentialContext, CredentialLease, CredentialProvider,
-    CredentialRefreshReason, CredentialSlot, InvalidateReason, SecretCredential,
+    CredentialRefreshReason, CredentialSlot, CredentialStore, InvalidateReason, SecretCredential,
     apply_basic_credential, apply_secret_credential, auth_decision_for_status,
 };
 use crate::client::ClientContext;
+use crate::clock::Clock;
 use std::any::Any;
 
 struct ErasedCredentialLease {
@@ -20,6 +21,7 @@ trait ErasedCredentialSlot<Cx: ClientContext>: Send + Sync {
         &'a self,
         ctx: CredentialContext<'a, Cx>,
         policy: AuthStepPolicy,
+        store: Option<&'a dyn CredentialStore>,
     ) -> AuthFuture<'a, Result<ErasedCredentialLease, AuthError>>;
 
     fn invalidate<'a>(
@@ -27,9 +29,14 @@ trait ErasedCredentialSlot<Cx: ClientContext>: Send + Sync {
         ctx: CredentialContext<'a, Cx>,
         generation: Option<u64>,
         reason: InvalidateReason,
+        store: Option<&'a dyn CredentialStore>,
     ) -> AuthFuture<'a, Result<(), AuthError>>;
 
-    fn invalidate_local(&self, generation: Option<u64>) -> Result<(), AuthError>;
+    fn invalidate_local<'a>(
+        &'a self,
+        generation: Option<u64>,
+        store: Option<&'a dyn CredentialStore>,
+    ) -> AuthFuture<'a, Result<(), AuthError>>;
 
     #[cfg(any(test, feature = "dangerous-dev-tools"))]
     fn lifecycle_observation_target(&self) -> Option<super::CredentialLifecycleObservationTarget>;
@@ -48,9 +55,11 @@ where
         &'a self,
         ctx: CredentialContext<'a, Cx>,
         policy: AuthStepPolicy,
+        store: Option<&'a dyn CredentialStore>,
     ) -> AuthFuture<'a, Result<ErasedCredentialLease, AuthError>> {
         Box::pin(async move {
-            let CredentialLease { value, generation } = self.get_or_refresh(ctx, policy).await?;
+            let CredentialLease { value, generation } =
+                self.get_or_refresh_with_store(ctx, policy, store).await?;
             Ok(ErasedCredentialLease {
                 value: Box::new(value),
                 generation,
@@ -63,12 +72,23 @@ where
         ctx: CredentialContext<'a, Cx>,
         generation: Option<u64>,
         reason: InvalidateReason,
+        store: Option<&'a dyn CredentialStore>,
     ) -> AuthFuture<'a, Result<(), AuthError>> {
-        Box::pin(async move { self.invalidate_generation(ctx, generation, reason).await })
+        Box::pin(async move {
+            self.invalidate_generation_with_store(ctx, generation, reason, store)
+                .await
+        })
     }
 
-    fn invalidate_local(&self, generation: Option<u64>) -> Result<(), AuthError> {
-        self.invalidate_generation_local(generation)
+    fn invalidate_local<'a>(
+        &'a self,
+        generation: Option<u64>,
+        store: Option<&'a dyn CredentialStore>,
+    ) -> AuthFuture<'a, Result<(), AuthError>> {
+        Box::pin(async move {
+            self.invalidate_generation_local_with_store(generation, store)
+                .await
+        })
     }
 
     #[cfg(any(test, feature = "dangerous-dev-tools"))]
@@ -270,6 +290,26 @@ where
         self.slot.clear_manual().await
     }
 
+    /// Sets a manual credential and persists it to `store` when one is
+    /// configured, measuring its expiry on `clock`.
+    pub async fn set_manual_with_store(
+        &self,
+        value: P::Credential,
+        store: Option<&dyn CredentialStore>,
+        clock: &dyn Clock,
+    ) -> Result<(), AuthError> {
+        self.slot.set_manual_with_store(value, store, clock).await
+    }
+
+    /// Clears the credential and removes it from `store` when one is
+    /// configured.
+    pub async fn clear_manual_with_store(
+        &self,
+        store: Option<&dyn CredentialStore>,
+    ) -> Result<(), AuthError> {
+        self.slot.clear_manual_with_store(store).await
+    }
+
     pub async fn has_value(&self) -> bool {
         self.slot.has_value().await
     }
@@ -291,6 +331,7 @@ where
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn prepare_binding<Cx: ClientContext>(
     binding: AuthProviderBinding<'_, Cx>,
     requirement: &AuthRequirement,
@@ -299,6 +340,8 @@ async fn prepare_binding<Cx: ClientContext>(
     auth: &Cx::AuthVars,
     auth_state: &Cx::AuthState,
     executor: &dyn AuthHttpExecutor,
+    clock: &dyn Clock,
+    store: Option<&dyn CredentialStore>,
 ) -> Result<super::PreparedAuthCredential, AuthError> {
     binding.validate_requirement(requirement)?;
     let credential_ctx = CredentialContext {
@@ -308,10 +351,11 @@ async fn prepare_binding<Cx: ClientContext>(
         executor,
         credential_id: requirement.credential.id.clone(),
         reason: CredentialRefreshReason::Missing,
+        clock,
     };
     let lease = binding
         .slot
-        .get_or_refresh(credential_ctx, AuthStepPolicy::default())
+        .get_or_refresh(credential_ctx, AuthStepPolicy::default(), store)
         .await?;
     let application = (binding.materializer)(lease.value.as_ref(), request, requirement)?;
     let applied = AuthAppliedCredential {
@@ -368,7 +412,9 @@ async fn apply_binding_rejection<Cx: ClientContext>(
     auth: &Cx::AuthVars,
     auth_state: &Cx::AuthState,
     executor: &dyn AuthHttpExecutor,
+    clock: &dyn Clock,
     status: http::StatusCode,
+    store: Option<&dyn CredentialStore>,
 ) -> Result<(), AuthError> {
     binding.validate_requirement(requirement)?;
     if !action.matches(requirement, applied) {
@@ -388,13 +434,17 @@ async fn apply_binding_rejection<Cx: ClientContext>(
             executor,
             credential_id: applied.credential_id.clone(),
             reason: CredentialRefreshReason::Rejected,
+            clock,
         };
         binding
             .slot
-            .invalidate(credential_ctx, applied.generation, reason)
+            .invalidate(credential_ctx, applied.generation, reason, store)
             .await
     } else {
-        binding.slot.invalidate_local(applied.generation)
+        binding
+            .slot
+            .invalidate_local(applied.generation, store)
+            .await
     }
 }
 
@@ -410,12 +460,13 @@ fn rejection_invalidation_reason(
     })
 }
 
-fn apply_binding_rejection_invalidation_only<Cx: ClientContext>(
+async fn apply_binding_rejection_invalidation_only<Cx: ClientContext>(
     binding: AuthProviderBinding<'_, Cx>,
     action: &AuthRejectionAction,
     requirement: &AuthRequirement,
     applied: &AuthAppliedCredential,
     status: http::StatusCode,
+    store: Option<&dyn CredentialStore>,
 ) -> Result<(), AuthError> {
     binding.validate_requirement(requirement)?;
     if !action.matches(requirement, applied) {
@@ -425,7 +476,10 @@ fn apply_binding_rejection_invalidation_only<Cx: ClientContext>(
         ));
     }
     if rejection_invalidation_reason(action, status).is_some() {
-        binding.slot.invalidate_local(applied.generation)?;
+        binding
+            .slot
+            .invalidate_local(applied.generation, store)
+            .await?;
     }
     Ok(())
 }
@@ -439,7 +493,9 @@ pub(crate) async fn prepare<Cx: ClientContext>(
     auth: &Cx::AuthVars,
     auth_state: &Cx::AuthState,
     executor: &dyn AuthHttpExecutor,
+    clock: &dyn Clock,
     _meta: &crate::execution_meta::RequestExecutionMeta,
+    store: Option<&dyn CredentialStore>,
 ) -> Result<super::PreparedAuthCredential, AuthError> {
     match Cx::auth_provider_binding(&requirement.credential.id, auth_state) {
         Some(binding) => {
@@ -451,6 +507,8 @@ pub(crate) async fn prepare<Cx: ClientContext>(
                 auth,
                 auth_state,
                 executor,
+                clock,
+                store,
             )
             .await
         }
@@ -463,7 +521,8 @@ pub(crate) async fn prepare<Cx: ClientContext>(
 
 /// Applies only generation-conditional local invalidation for a challenged
 /// credential. This path has no provider executor and therefore cannot perform
-/// provider I/O or acquire a replacement credential.
+/// provider I/O or acquire a replacement credential; it only removes the
+/// invalidated generation from the configured credential store.
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn apply_rejection_invalidation_only<Cx: ClientContext>(
     action: &AuthRejectionAction,
@@ -474,10 +533,19 @@ pub(crate) async fn apply_rejection_invalidation_only<Cx: ClientContext>(
     auth_state: &Cx::AuthState,
     _meta: &crate::execution_meta::RequestExecutionMeta,
     status: http::StatusCode,
+    store: Option<&dyn CredentialStore>,
 ) -> Result<(), AuthError> {
     match Cx::auth_provider_binding(&requirement.credential.id, auth_state) {
         Some(binding) => {
-            apply_binding_rejection_invalidation_only(binding, action, requirement, applied, status)
+            apply_binding_rejection_invalidation_only(
+                binding,
+                action,
+                requirement,
+                applied,
+                status,
+                store,
+            )
+            .await
         }
         None => Err(AuthError::new(
             AuthErrorKind::InvalidConfiguration,
@@ -519,8 +587,10 @@ pub(crate) async fn apply_rejection<Cx: ClientContext>(
     auth: &Cx::AuthVars,
     auth_state: &Cx::AuthState,
     executor: &dyn AuthHttpExecutor,
+    clock: &dyn Clock,
     _meta: &crate::execution_meta::RequestExecutionMeta,
     status: http::StatusCode,
+    store: Option<&dyn CredentialStore>,
 ) -> Result<(), AuthError> {
     match Cx::auth_provider_binding(&requirement.credential.id, auth_state) {
         Some(binding) => {
@@ -533,7 +603,9 @@ pub(crate) async fn apply_rejection<Cx: ClientContext>(
                 auth,
                 auth_state,
                 executor,
+                clock,
                 status,
+                store,
             )
             .await
         }
@@ -551,6 +623,7 @@ mod tests {
         ApiKey, AuthChallengeMode, AuthPlan, AuthPreparationMode, AuthProvenance, AuthUsageId,
         CredentialId, CredentialRef,
     };
+    use crate::clock::SystemClock;
     use crate::types::RouteBuilder;
     use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
@@ -674,6 +747,8 @@ mod tests {
             &(),
             &state,
             &NoHttp,
+            &SystemClock,
+            None,
         )
         .await
         .expect("first acquisition");
@@ -686,6 +761,8 @@ mod tests {
             &(),
             &state,
             &NoHttp,
+            &SystemClock,
+            None,
         )
         .await
         .expect("cache hit");
@@ -709,7 +786,9 @@ mod tests {
             &(),
             &state,
             &NoHttp,
+            &SystemClock,
             http::StatusCode::UNAUTHORIZED,
+            None,
         )
         .await
         .expect("provider invalidation");
@@ -724,6 +803,8 @@ mod tests {
             &(),
             &state,
             &NoHttp,
+            &SystemClock,
+            None,
         )
         .await
         .expect("reacquisition");
@@ -731,6 +812,132 @@ mod tests {
         assert_ne!(second.applied.generation, third.applied.generation);
     }
 
+    #[tokio::test]
+    async fn credential_store_restores_saves_and_forgets_invalidated_generations() {
+        use crate::auth::{CredentialStore, InMemoryCredentialStore, StoredCredential};
+
+        let acquired = Arc::new(AtomicUsize::new(0));
+        let provider = TestProvider {
+            acquired: acquired.clone(),
+            invalidated: Arc::new(AtomicUsize::new(0)),
+        };
+        let store = InMemoryCredentialStore::new();
+        let id = CredentialId::new("test", "token");
+        store
+            .save(
+                &id,
+                StoredCredential::new("api_key").with_field("value", "restored-secret"),
+            )
+            .await
+            .expect("seed store");
+        let state = TestState {
+            slot: Arc::new(CredentialSlot::new(provider.clone())),
+        };
+        let requirement = requirement();
+        let placement = super::super::AuthPlacementPlan::from_auth_plan(&AuthPlan {
+            requirements: vec![requirement.clone()],
+        })
+        .expect("valid placement");
+        let binding = AuthProviderBinding::secret(
+            state.slot.as_ref(),
+            AuthPreparationMode::RequestLocal,
+            AuthChallengeMode::InvalidateOnly,
+        );
+
+        let mut request = AuthApplicationRequest::new(&placement.slots[0]);
+        let restored = prepare_binding(
+            binding,
+            &requirement,
+            &mut request,
+            &(),
+            &(),
+            &state,
+            &NoHttp,
+            &SystemClock,
+            Some(&store),
+        )
+        .await
+        .expect("restored credential");
+        assert_eq!(acquired.load(Ordering::SeqCst), 0);
+        let cached = state.slot.get_cached().await.expect("restored value");
+        assert_eq!(cached.value.value.expose_secret(), "restored-secret");
+
+        let action = plan_binding_rejection(
+            binding,
+            &requirement,
+            &restored.applied,
+            http::StatusCode::UNAUTHORIZED,
+        )
+        .expect("challenge plan");
+        apply_binding_rejection_invalidation_only(
+            binding,
+            &action,
+            &requirement,
+            &restored.applied,
+            http::StatusCode::UNAUTHORIZED,
+            Some(&store),
+        )
+        .await
+        .expect("terminal invalidation");
+        assert!(store.load(&id).await.expect("load").is_none());
+
+        let mut request = AuthApplicationRequest::new(&placement.slots[0]);
+        prepare_binding(
+            binding,
+            &requirement,
+            &mut request,
+            &(),
+            &(),
+            &state,
+            &NoHttp,
+            &SystemClock,
+            Some(&store),
+        )
+        .await
+        .expect("reacquisition");
+        assert_eq!(acquired.load(Ordering::SeqCst), 1);
+        let saved = store.load(&id).await.expect("load").expect("saved value");
+        assert_eq!(
+            saved.field("value").map(|value| value.expose_secret()),
+            Some("secret-1")
+        );
+
+        store
+            .save(
+                &id,
+                StoredCredential::new("api_key")
+                    .with_field("value", "expired-secret")
+                    .with_expires_at(Some(std::time::UNIX_EPOCH)),
+            )
+            .await
+            .expect("seed expired value");
+        let fresh_state = TestState {
+            slot: Arc::new(CredentialSlot::new(provider)),
+        };
+        let binding = AuthProviderBinding::secret(
+            fresh_state.slot.as_ref(),
+            AuthPreparationMode::RequestLocal,
+            AuthChallengeMode::InvalidateOnly,
+        );
+        let mut request = AuthApplicationRequest::new(&placement.slots[0]);
+        prepare_binding(
+            binding,
+            &requirement,
+            &mut request,
+            &(),
+            &(),
+            &fresh_state,
+            &NoHttp,
+            &SystemClock,
+            Some(&store),
+        )
+        .await
+        .expect("expired entry is replaced");
+        assert_eq!(acquired.load(Ordering::SeqCst), 2);
+        let cached = fresh_state.slot.get_cached().await.expect("acquired value");
+        assert_eq!(cached.value.value.expose_secret(), "secret-2");
+    }
+
     #[tokio::test]
     async fn generation_terminal_invalidation_is_local_and_forces_later_reacquisition() {
         let acquired = Arc::new(AtomicUsize::new(0));
@@ -761,6 +968,8 @@ mod tests {
             &(),
             &state,
             &NoHttp,
+            &SystemClock,
+            None,
         )
         .await
         .expect("initial generation");
@@ -780,7 +989,9 @@ mod tests {
             &(),
             &state,
             &NoHttp,
+            &SystemClock,
             http::StatusCode::UNAUTHORIZED,
+            None,
         )
         .await
         .expect("first provider-capable invalidation");
@@ -794,6 +1005,8 @@ mod tests {
             &(),
             &state,
             &NoHttp,
+            &SystemClock,
+            None,
         )
         .await
         .expect("replacement generation");
@@ -810,7 +1023,9 @@ mod tests {
             &requirement,
             &replacement.applied,
             http::StatusCode::UNAUTHORIZED,
+            None,
         )
+        .await
         .expect("terminal local invalidation");
 
         assert_eq!(acquired.load(Ordering::SeqCst), 2);
@@ -826,6 +1041,8 @@ mod tests {
             &(),
             &state,
             &NoHttp,
+            &SystemClock,
+            None,
         )
         .await
         .expect("later top-level generation");
@@ -865,6 +1082,8 @@ mod tests {
             &(),
             &state,
             &NoHttp,
+            &SystemClock,
+            None,
         )
         .await
         .expect("challenged generation");
@@ -897,7 +1116,9 @@ mod tests {
             &requirement,
             &challenged.applied,
             http::StatusCode::UNAUTHORIZED,
+            None,
         )
+        .await
         .expect("stale terminal invalidation is conditional");
 
         assert_eq!(
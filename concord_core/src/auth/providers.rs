@@ -7,14 +7,19 @@ use crate::client::ClientContext;
 use std::marker::PhantomData;
 
 #[cfg(feature = "json")]
-use super::http::{AuthHttpRequest, AuthInternalPolicy, AuthMode};
+use super::http::{
+    AuthHttpExecutor, AuthHttpRequest, AuthHttpResponse, AuthInternalPolicy, AuthMode,
+};
 #[cfg(feature = "json")]
-use crate::secret::SecretString;
+use crate::clock::Clock;
 #[cfg(feature = "json")]
+use crate::secret::SecretString;
 #[cfg(feature = "json")]
 use base64::Engine;
 #[cfg(feature = "json")]
-use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::engine::general_purpose::{
+    STANDARD as BASE64_STANDARD, URL_SAFE_NO_PAD as BASE64_URL_SAFE_NO_PAD,
+};
 #[cfg(feature = "json")]
 use bytes::Bytes;
 #[cfg(feature = "json")]
@@ -24,7 +29,11 @@ use http::header::{AUTHORIZATION, HeaderValue};
 #[cfg(feature = "json")]
 use serde::Deserialize;
 #[cfg(feature = "json")]
-use std::time::{Duration, Instant};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "json")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "json")]
+use std::time::Duration;
 #[cfg(feature = "json")]
 use url::Url;
 
@@ -201,14 +210,12 @@ impl OAuth2ClientCredentialsProvider {
         client_id: impl Into<SecretString>,
         client_secret: impl Into<SecretString>,
     ) -> Result<Self, AuthError> {
-        validate_oauth2_token_url_raw(token_url)?;
-        let token_url = token_url.parse::<Url>().map_err(|err| {
-            AuthError::new(
-                AuthErrorKind::InvalidConfiguration,
-                format!("invalid oauth2 token URL: {err}"),
-            )
-        })?;
-        Self::new(id, token_url, client_id, client_secret)
+        Self::new(
+            id,
+            parse_oauth2_token_url(token_url)?,
+            client_id,
+            client_secret,
+        )
     }
 
     #[inline]
@@ -231,93 +238,502 @@ impl<Cx: ClientContext> CredentialProvider<Cx> for OAuth2ClientCredentialsProvid
         ctx: CredentialContext<'a, Cx>,
     ) -> AuthFuture<'a, Result<Self::Credential, AuthError>> {
         Box::pin(async move {
-            let mut headers = HeaderMap::new();
-            let raw = format!(
-                "{}:{}",
-                self.client_id.expose_secret(),
-                self.client_secret.expose_secret()
-            );
-            let basic = format!("Basic {}", BASE64_STANDARD.encode(raw));
-            headers.insert(
-                AUTHORIZATION,
-                HeaderValue::from_str(&basic).map_err(|_| {
-                    AuthError::new(AuthErrorKind::InvalidConfiguration, "invalid client secret")
-                })?,
-            );
-
-            let body = {
+            let req = {
                 let mut form = url::form_urlencoded::Serializer::new(String::new());
                 form.append_pair("grant_type", "client_credentials");
                 if let Some(scope) = &self.scope {
                     form.append_pair("scope", scope);
                 }
-                form.finish()
+                oauth2_token_request(
+                    &self.token_url,
+                    &self.client_id,
+                    Some(&self.client_secret),
+                    form,
+                )?
             };
-
-            let resp = ctx
-                .executor
-                .send(AuthHttpRequest {
-                    method: http::Method::POST,
-                    url: self.token_url.clone(),
-                    headers,
-                    body: crate::io::PreparedBody::reusable_bytes(
-                        Bytes::from(body.into_bytes()),
-                        Some(HeaderValue::from_static(
-                            "application/x-www-form-urlencoded",
-                        )),
-                    ),
-                    mode: AuthMode::SkipAuth,
-                    policy: AuthInternalPolicy::default(),
-                })
-                .await?;
-
+            let resp = ctx.executor.send(req).await?;
             if !resp.status.is_success() {
                 return Err(AuthError::new(
                     AuthErrorKind::AcquireFailed,
                     format!("oauth2 token endpoint returned {}", resp.status),
                 ));
             }
+            decode_oauth2_token(&resp, ctx.clock)
+        })
+    }
+}
 
-            let token: OAuth2TokenResponse = serde_json::from_slice(&resp.body).map_err(|e| {
-                AuthError::new(
-                    AuthErrorKind::AcquireFailed,
-                    format!("oauth2 token response decode failed: {e}"),
-                )
-            })?;
+/// OAuth2 `refresh_token` grant provider.
+///
+/// The provider owns the latest refresh token: a rotated token returned by the
+/// token endpoint replaces the stored one, and an `invalid_grant` rejection
+/// revokes it so later acquisitions fail without contacting the server.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug)]
+pub struct OAuth2RefreshTokenProvider {
+    id: CredentialId,
+    token_url: Url,
+    client_id: SecretString,
+    client_secret: Option<SecretString>,
+    scope: Option<String>,
+    missing_hint: Option<&'static str>,
+    grant: Arc<Mutex<RefreshGrant>>,
+}
 
-            if let Some(token_type) = &token.token_type
-                && !token_type.eq_ignore_ascii_case("bearer")
-            {
-                return Err(AuthError::new(
-                    AuthErrorKind::UnsupportedScheme,
-                    "oauth2 token response used an unsupported token_type",
-                ));
+#[cfg(feature = "json")]
+#[derive(Debug)]
+enum RefreshGrant {
+    Missing,
+    Active(SecretString),
+    Revoked,
+}
+
+#[cfg(feature = "json")]
+impl OAuth2RefreshTokenProvider {
+    /// Creates a provider. Without a refresh token, acquisition reports a
+    /// missing credential until one is supplied.
+    #[inline]
+    pub fn new(
+        id: CredentialId,
+        token_url: Url,
+        client_id: impl Into<SecretString>,
+        refresh_token: Option<SecretString>,
+    ) -> Result<Self, AuthError> {
+        validate_oauth2_token_url(&token_url)?;
+        Ok(Self {
+            id,
+            token_url,
+            client_id: client_id.into(),
+            client_secret: None,
+            scope: None,
+            missing_hint: None,
+            grant: Arc::new(Mutex::new(match refresh_token {
+                Some(token) => RefreshGrant::Active(token),
+                None => RefreshGrant::Missing,
+            })),
+        })
+    }
+
+    #[inline]
+    pub fn from_validated_token_url(
+        id: CredentialId,
+        token_url: &'static str,
+        client_id: impl Into<SecretString>,
+        refresh_token: Option<SecretString>,
+    ) -> Result<Self, AuthError> {
+        Self::new(
+            id,
+            parse_oauth2_token_url(token_url)?,
+            client_id,
+            refresh_token,
+        )
+    }
+
+    /// Authenticates to the token endpoint with HTTP Basic client credentials.
+    /// Without a secret, `client_id` is sent in the form body as a public client.
+    #[inline]
+    pub fn client_secret(mut self, client_secret: impl Into<SecretString>) -> Self {
+        self.client_secret = Some(client_secret.into());
+        self
+    }
+
+    #[inline]
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope = Some(scope.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_missing_hint(mut self, hint: &'static str) -> Self {
+        self.missing_hint = Some(hint);
+        self
+    }
+
+    fn stored_refresh_token(&self) -> Result<SecretString, AuthError> {
+        match &*lock_grant(&self.grant) {
+            RefreshGrant::Active(token) => Ok(token.clone()),
+            RefreshGrant::Revoked => Err(revoked_grant_error()),
+            RefreshGrant::Missing => Err(AuthError::new(
+                AuthErrorKind::MissingCredential,
+                match self.missing_hint {
+                    Some(hint) => format!(
+                        "missing oauth2 refresh token for `{}`; call `{hint}` first",
+                        self.id.name()
+                    ),
+                    None => format!("missing oauth2 refresh token for `{}`", self.id.name()),
+                },
+            )),
+        }
+    }
+
+    async fn exchange(
+        &self,
+        executor: &dyn AuthHttpExecutor,
+        clock: &dyn Clock,
+        refresh_token: SecretString,
+        failure: AuthErrorKind,
+    ) -> Result<AccessToken, AuthError> {
+        let req = {
+            let mut form = url::form_urlencoded::Serializer::new(String::new());
+            form.append_pair("grant_type", "refresh_token");
+            form.append_pair("refresh_token", refresh_token.expose_secret());
+            if let Some(scope) = &self.scope {
+                form.append_pair("scope", scope);
             }
+            oauth2_token_request(
+                &self.token_url,
+                &self.client_id,
+                self.client_secret.as_ref(),
+                form,
+            )?
+        };
+        let resp = executor.send(req).await?;
+        if is_invalid_grant(&resp) {
+            *lock_grant(&self.grant) = RefreshGrant::Revoked;
+            return Err(revoked_grant_error());
+        }
+        if !resp.status.is_success() {
+            return Err(AuthError::new(
+                failure,
+                format!("oauth2 token endpoint returned {}", resp.status),
+            ));
+        }
+        let mut token = decode_oauth2_token(&resp, clock)?;
+        let mut grant = lock_grant(&self.grant);
+        match &token.refresh_token {
+            Some(rotated) => *grant = RefreshGrant::Active(rotated.clone()),
+            None => token.refresh_token = Some(refresh_token),
+        }
+        Ok(token)
+    }
+}
 
-            let mut out = AccessToken::new(token.access_token);
-            out.expires_at = token
-                .expires_in
-                .map(|seconds| {
-                    Instant::now()
-                        .checked_add(Duration::from_secs(seconds))
-                        .ok_or_else(|| {
-                            AuthError::new(
-                                AuthErrorKind::InvalidConfiguration,
-                                "oauth2 expires_in overflowed",
-                            )
-                        })
-                })
-                .transpose()?;
-            out.refresh_token = token.refresh_token.map(SecretString::new);
-            out.scope = token
-                .scope
-                .unwrap_or_default()
-                .split_whitespace()
-                .map(ToOwned::to_owned)
-                .collect();
-            Ok(out)
+#[cfg(feature = "json")]
+impl<Cx: ClientContext> CredentialProvider<Cx> for OAuth2RefreshTokenProvider {
+    type Credential = AccessToken;
+
+    fn id(&self) -> CredentialId {
+        self.id.clone()
+    }
+
+    fn acquire<'a>(
+        &'a self,
+        ctx: CredentialContext<'a, Cx>,
+    ) -> AuthFuture<'a, Result<Self::Credential, AuthError>> {
+        Box::pin(async move {
+            let refresh_token = self.stored_refresh_token()?;
+            self.exchange(
+                ctx.executor,
+                ctx.clock,
+                refresh_token,
+                AuthErrorKind::AcquireFailed,
+            )
+            .await
         })
     }
+
+    /// Redeems the refresh token carried by `current`, falling back to the
+    /// stored one when the current token has none.
+    fn refresh<'a>(
+        &'a self,
+        ctx: CredentialContext<'a, Cx>,
+        current: &'a Self::Credential,
+    ) -> AuthFuture<'a, Result<Self::Credential, AuthError>> {
+        Box::pin(async move {
+            if matches!(*lock_grant(&self.grant), RefreshGrant::Revoked) {
+                return Err(revoked_grant_error());
+            }
+            let refresh_token = match &current.refresh_token {
+                Some(token) => token.clone(),
+                None => self.stored_refresh_token()?,
+            };
+            self.exchange(
+                ctx.executor,
+                ctx.clock,
+                refresh_token,
+                AuthErrorKind::RefreshFailed,
+            )
+            .await
+        })
+    }
+}
+
+/// PKCE (RFC 7636) verifier and `S256` challenge for an authorization-code flow.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug)]
+pub struct OAuth2Pkce {
+    verifier: SecretString,
+    challenge: String,
+}
+
+#[cfg(feature = "json")]
+impl OAuth2Pkce {
+    /// Generates a 43-character verifier from 32 bytes of OS randomness.
+    pub fn generate() -> Result<Self, AuthError> {
+        let mut bytes = [0_u8; 32];
+        getrandom::fill(&mut bytes).map_err(|_| {
+            AuthError::new(
+                AuthErrorKind::InvalidConfiguration,
+                "PKCE verifier generation requires OS randomness",
+            )
+        })?;
+        Self::from_verifier(BASE64_URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    /// Uses a caller-supplied verifier: 43 to 128 unreserved URL characters.
+    pub fn from_verifier(verifier: impl Into<SecretString>) -> Result<Self, AuthError> {
+        let verifier = verifier.into();
+        let raw = verifier.expose_secret();
+        if !(43..=128).contains(&raw.len())
+            || !raw
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~'))
+        {
+            return Err(AuthError::new(
+                AuthErrorKind::InvalidConfiguration,
+                "PKCE verifier must be 43 to 128 unreserved URL characters",
+            ));
+        }
+        let challenge = BASE64_URL_SAFE_NO_PAD.encode(Sha256::digest(raw.as_bytes()));
+        Ok(Self {
+            verifier,
+            challenge,
+        })
+    }
+
+    #[inline]
+    pub fn verifier(&self) -> &SecretString {
+        &self.verifier
+    }
+
+    /// The `code_challenge` authorization request parameter.
+    #[inline]
+    pub fn challenge(&self) -> &str {
+        &self.challenge
+    }
+
+    /// The `code_challenge_method` authorization request parameter.
+    #[inline]
+    pub fn challenge_method(&self) -> &'static str {
+        "S256"
+    }
+}
+
+/// OAuth2 `authorization_code` grant exchange for the initial token.
+#[cfg(feature = "json")]
+#[derive(Clone, Debug)]
+pub struct OAuth2AuthorizationCode {
+    token_url: Url,
+    client_id: SecretString,
+    client_secret: Option<SecretString>,
+    code: SecretString,
+    redirect_uri: String,
+    code_verifier: SecretString,
+}
+
+#[cfg(feature = "json")]
+impl OAuth2AuthorizationCode {
+    #[inline]
+    pub fn new(
+        token_url: Url,
+        client_id: impl Into<SecretString>,
+        code: impl Into<SecretString>,
+        redirect_uri: impl Into<String>,
+        pkce: &OAuth2Pkce,
+    ) -> Result<Self, AuthError> {
+        validate_oauth2_token_url(&token_url)?;
+        Ok(Self {
+            token_url,
+            client_id: client_id.into(),
+            client_secret: None,
+            code: code.into(),
+            redirect_uri: redirect_uri.into(),
+            code_verifier: pkce.verifier.clone(),
+        })
+    }
+
+    #[inline]
+    pub fn from_validated_token_url(
+        token_url: &'static str,
+        client_id: impl Into<SecretString>,
+        code: impl Into<SecretString>,
+        redirect_uri: impl Into<String>,
+        pkce: &OAuth2Pkce,
+    ) -> Result<Self, AuthError> {
+        Self::new(
+            parse_oauth2_token_url(token_url)?,
+            client_id,
+            code,
+            redirect_uri,
+            pkce,
+        )
+    }
+
+    #[inline]
+    pub fn client_secret(mut self, client_secret: impl Into<SecretString>) -> Self {
+        self.client_secret = Some(client_secret.into());
+        self
+    }
+
+    /// Redeems the code. The returned token carries the server's refresh
+    /// token, if any, for seeding an [`OAuth2RefreshTokenProvider`]; its
+    /// expiry is computed from `clock`.
+    pub async fn exchange(
+        &self,
+        executor: &dyn AuthHttpExecutor,
+        clock: &dyn Clock,
+    ) -> Result<AccessToken, AuthError> {
+        let req = {
+            let mut form = url::form_urlencoded::Serializer::new(String::new());
+            form.append_pair("grant_type", "authorization_code");
+            form.append_pair("code", self.code.expose_secret());
+            form.append_pair("redirect_uri", &self.redirect_uri);
+            form.append_pair("code_verifier", self.code_verifier.expose_secret());
+            oauth2_token_request(
+                &self.token_url,
+                &self.client_id,
+                self.client_secret.as_ref(),
+                form,
+            )?
+        };
+        let resp = executor.send(req).await?;
+        if !resp.status.is_success() {
+            return Err(AuthError::new(
+                AuthErrorKind::AcquireFailed,
+                format!("oauth2 token endpoint returned {}", resp.status),
+            ));
+        }
+        decode_oauth2_token(&resp, clock)
+    }
+}
+
+#[cfg(feature = "json")]
+fn lock_grant(grant: &Mutex<RefreshGrant>) -> std::sync::MutexGuard<'_, RefreshGrant> {
+    grant
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(feature = "json")]
+fn revoked_grant_error() -> AuthError {
+    AuthError::new(
+        AuthErrorKind::RejectedCredential,
+        "oauth2 refresh token was revoked (invalid_grant)",
+    )
+}
+
+#[cfg(feature = "json")]
+fn is_invalid_grant(resp: &AuthHttpResponse) -> bool {
+    #[derive(Deserialize)]
+    struct OAuth2ErrorResponse {
+        error: String,
+    }
+
+    matches!(resp.status.as_u16(), 400 | 401)
+        && serde_json::from_slice::<OAuth2ErrorResponse>(&resp.body)
+            .is_ok_and(|body| body.error == "invalid_grant")
+}
+
+#[cfg(feature = "json")]
+fn parse_oauth2_token_url(token_url: &'static str) -> Result<Url, AuthError> {
+    validate_oauth2_token_url_raw(token_url)?;
+    token_url.parse::<Url>().map_err(|err| {
+        AuthError::new(
+            AuthErrorKind::InvalidConfiguration,
+            format!("invalid oauth2 token URL: {err}"),
+        )
+    })
+}
+
+/// Builds a token endpoint form request. Confidential clients authenticate with
+/// HTTP Basic; public clients send `client_id` in the body.
+#[cfg(feature = "json")]
+fn oauth2_token_request(
+    token_url: &Url,
+    client_id: &SecretString,
+    client_secret: Option<&SecretString>,
+    mut form: url::form_urlencoded::Serializer<'_, String>,
+) -> Result<AuthHttpRequest, AuthError> {
+    let mut headers = HeaderMap::new();
+    match client_secret {
+        Some(client_secret) => {
+            let raw = format!(
+                "{}:{}",
+                client_id.expose_secret(),
+                client_secret.expose_secret()
+            );
+            let basic = format!("Basic {}", BASE64_STANDARD.encode(raw));
+            headers.insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&basic).map_err(|_| {
+                    AuthError::new(AuthErrorKind::InvalidConfiguration, "invalid client secret")
+                })?,
+            );
+        }
+        None => {
+            form.append_pair("client_id", client_id.expose_secret());
+        }
+    }
+
+    Ok(AuthHttpRequest {
+        method: http::Method::POST,
+        url: token_url.clone(),
+        headers,
+        body: crate::io::PreparedBody::reusable_bytes(
+            Bytes::from(form.finish().into_bytes()),
+            Some(HeaderValue::from_static(
+                "application/x-www-form-urlencoded",
+            )),
+        ),
+        mode: AuthMode::SkipAuth,
+        policy: AuthInternalPolicy::default(),
+    })
+}
+
+#[cfg(feature = "json")]
+fn decode_oauth2_token(
+    resp: &AuthHttpResponse,
+    clock: &dyn Clock,
+) -> Result<AccessToken, AuthError> {
+    let token: OAuth2TokenResponse = serde_json::from_slice(&resp.body).map_err(|e| {
+        AuthError::new(
+            AuthErrorKind::AcquireFailed,
+            format!("oauth2 token response decode failed: {e}"),
+        )
+    })?;
+
+    if let Some(token_type) = &token.token_type
+        && !token_type.eq_ignore_ascii_case("bearer")
+    {
+        return Err(AuthError::new(
+            AuthErrorKind::UnsupportedScheme,
+            "oauth2 token response used an unsupported token_type",
+        ));
+    }
+
+    let mut out = AccessToken::new(token.access_token);
+    out.expires_at = token
+        .expires_in
+        .map(|seconds| {
+            clock
+                .now()
+                .checked_add(Duration::from_secs(seconds))
+                .ok_or_else(|| {
+                    AuthError::new(
+                        AuthErrorKind::InvalidConfiguration,
+                        "oauth2 expires_in overflowed",
+                    )
+                })
+        })
+        .transpose()?;
+    out.refresh_token = token.refresh_token.map(SecretString::new);
+    out.scope = token
+        .scope
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(ToOwned::to_owned)
+        .collect();
+    Ok(out)
 }
 
 #[cfg(feature = "json")]
@@ -366,3 +782,50 @@ struct OAuth2TokenResponse {
     refresh_token: Option<String>,
     scope: Option<String>,
 }
+
+#[cfg(all(test, feature = "json"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pkce_s256_challenge_matches_rfc7636_vector() {
+        let pkce = OAuth2Pkce::from_verifier("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk")
+            .expect("valid verifier");
+        assert_eq!(
+            pkce.challenge(),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+        assert_eq!(pkce.challenge_method(), "S256");
+        assert!(!format!("{pkce:?}").contains("dBjftJeZ4CVP"));
+
+        let generated = OAuth2Pkce::generate().expect("os randomness");
+        assert_eq!(generated.verifier().expose_secret().len(), 43);
+        assert!(OAuth2Pkce::from_verifier("too-short").is_err());
+        assert!(OAuth2Pkce::from_verifier("a".repeat(42) + "!").is_err());
+    }
+
+    #[test]
+    fn invalid_grant_detection_requires_client_error_status() {
+        let response = |status: u16, body: &'static str| AuthHttpResponse {
+            status: http::StatusCode::from_u16(status).expect("status"),
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        };
+        assert!(is_invalid_grant(&response(
+            400,
+            r#"{"error":"invalid_grant"}"#
+        )));
+        assert!(is_invalid_grant(&response(
+            401,
+            r#"{"error":"invalid_grant"}"#
+        )));
+        assert!(!is_invalid_grant(&response(
+            400,
+            r#"{"error":"invalid_client"}"#
+        )));
+        assert!(!is_invalid_grant(&response(
+            500,
+            r#"{"error":"invalid_grant"}"#
+        )));
+    }
+}
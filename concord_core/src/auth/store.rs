@@ -0,0 +1,511 @@
+use super::errors::AuthError;
+use super::future::AuthFuture;
+use super::ids::CredentialId;
+use crate::clock::Clock;
+use crate::secret::SecretString;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Persists credential material across client instances and process restarts.
+///
+/// A credential slot loads its entry once, on first use, and saves after every
+/// successful acquire or refresh. Invalidated generations are removed before
+/// the rejection is reported, and expired entries are never restored.
+/// Load and save failures leave the credential cached in memory only; remove
+/// failures fail the operation that invalidated the credential.
+pub trait CredentialStore: Send + Sync + 'static {
+    fn load<'a>(
+        &'a self,
+        id: &'a CredentialId,
+    ) -> AuthFuture<'a, Result<Option<StoredCredential>, AuthError>>;
+
+    fn save<'a>(
+        &'a self,
+        id: &'a CredentialId,
+        credential: StoredCredential,
+    ) -> AuthFuture<'a, Result<(), AuthError>>;
+
+    fn remove<'a>(&'a self, id: &'a CredentialId) -> AuthFuture<'a, Result<(), AuthError>>;
+}
+
+/// Store-neutral form of one credential material.
+///
+/// `kind` names the material type, every field is secret, and `expires_at`
+/// uses wall-clock time so it stays meaningful in another process.
+#[derive(Clone)]
+pub struct StoredCredential {
+    kind: String,
+    fields: BTreeMap<String, SecretString>,
+    expires_at: Option<SystemTime>,
+}
+
+impl StoredCredential {
+    #[inline]
+    pub fn new(kind: impl Into<String>) -> Self {
+        Self {
+            kind: kind.into(),
+            fields: BTreeMap::new(),
+            expires_at: None,
+        }
+    }
+
+    #[inline]
+    pub fn with_field(mut self, name: impl Into<String>, value: impl Into<SecretString>) -> Self {
+        self.fields.insert(name.into(), value.into());
+        self
+    }
+
+    #[inline]
+    pub fn with_expires_at(mut self, expires_at: Option<SystemTime>) -> Self {
+        self.expires_at = expires_at;
+        self
+    }
+
+    #[inline]
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    #[inline]
+    pub fn field(&self, name: &str) -> Option<&SecretString> {
+        self.fields.get(name)
+    }
+
+    #[inline]
+    pub fn fields(&self) -> impl Iterator<Item = (&str, &SecretString)> {
+        self.fields
+            .iter()
+            .map(|(name, value)| (name.as_str(), value))
+    }
+
+    #[inline]
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        self.expires_at
+    }
+
+    pub(crate) fn is_expired(&self) -> bool {
+        self.expires_at
+            .is_some_and(|expires_at| expires_at <= SystemTime::now())
+    }
+}
+
+impl fmt::Debug for StoredCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoredCredential")
+            .field("kind", &self.kind)
+            .field("fields", &self.fields.keys().collect::<Vec<_>>())
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// Converts a deadline on `clock` to wall-clock time for storage.
+pub(crate) fn stored_expiry(expires_at: Option<Instant>, clock: &dyn Clock) -> Option<SystemTime> {
+    expires_at.map(|expires_at| {
+        let now = clock.now();
+        let system_now = SystemTime::now();
+        match expires_at.checked_duration_since(now) {
+            Some(remaining) => system_now.checked_add(remaining).unwrap_or(system_now),
+            None => system_now
+                .checked_sub(now.duration_since(expires_at))
+                .unwrap_or(UNIX_EPOCH),
+        }
+    })
+}
+
+/// Converts a stored wall-clock expiry back to a deadline on `clock`. Past
+/// expiries map to "now" so the slot treats the material as expired.
+pub(crate) fn restored_expiry(
+    expires_at: Option<SystemTime>,
+    clock: &dyn Clock,
+) -> Option<Instant> {
+    expires_at.map(|expires_at| {
+        let now = clock.now();
+        let remaining = expires_at
+            .duration_since(SystemTime::now())
+            .unwrap_or(Duration::ZERO);
+        now.checked_add(remaining).unwrap_or(now)
+    })
+}
+
+/// Process-local store, useful for sharing credentials between clients.
+#[derive(Clone, Default)]
+pub struct InMemoryCredentialStore {
+    entries: Arc<Mutex<HashMap<CredentialId, StoredCredential>>>,
+}
+
+impl InMemoryCredentialStore {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> std::sync::MutexGuard<'_, HashMap<CredentialId, StoredCredential>> {
+        self.entries
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl fmt::Debug for InMemoryCredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InMemoryCredentialStore")
+            .field("entries", &self.entries().len())
+            .finish()
+    }
+}
+
+impl CredentialStore for InMemoryCredentialStore {
+    fn load<'a>(
+        &'a self,
+        id: &'a CredentialId,
+    ) -> AuthFuture<'a, Result<Option<StoredCredential>, AuthError>> {
+        Box::pin(async move { Ok(self.entries().get(id).cloned()) })
+    }
+
+    fn save<'a>(
+        &'a self,
+        id: &'a CredentialId,
+        credential: StoredCredential,
+    ) -> AuthFuture<'a, Result<(), AuthError>> {
+        Box::pin(async move {
+            self.entries().insert(id.clone(), credential);
+            Ok(())
+        })
+    }
+
+    fn remove<'a>(&'a self, id: &'a CredentialId) -> AuthFuture<'a, Result<(), AuthError>> {
+        Box::pin(async move {
+            self.entries().remove(id);
+            Ok(())
+        })
+    }
+}
+
+/// JSON file store keyed by `namespace:name`.
+///
+/// Every write replaces the whole file through a freshly created, uniquely
+/// named temporary sibling, and a `path` that is a symlink is neither read
+/// nor replaced. On Unix the file is restricted to mode `0600` before any
+/// secret is written; elsewhere it inherits the directory's permissions, so
+/// place it in a user-private directory.
+#[derive(Clone)]
+pub struct FileCredentialStore {
+    path: PathBuf,
+    lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+#[derive(Default, Deserialize, Serialize)]
+struct CredentialFile {
+    credentials: BTreeMap<String, CredentialFileEntry>,
+}
+
+#[derive(Deserialize, Serialize)]
+struct CredentialFileEntry {
+    kind: String,
+    fields: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at_unix: Option<u64>,
+}
+
+impl FileCredentialStore {
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Arc::new(tokio::sync::Mutex::new(())),
+        }
+    }
+
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn refuse_symlink(&self) -> Result<(), AuthError> {
+        match tokio::fs::symlink_metadata(&self.path).await {
+            Ok(metadata) if metadata.file_type().is_symlink() => Err(AuthError::state_unavailable(
+                "credential store path is a symlink",
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    async fn read(&self) -> Result<CredentialFile, AuthError> {
+        self.refuse_symlink().await?;
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|_| AuthError::state_unavailable("credential store file is not valid")),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(CredentialFile::default()),
+            Err(_) => Err(AuthError::state_unavailable(
+                "credential store file could not be read",
+            )),
+        }
+    }
+
+    async fn write(&self, file: &CredentialFile) -> Result<(), AuthError> {
+        let bytes = serde_json::to_vec_pretty(file).map_err(|_| {
+            AuthError::state_unavailable("credential store file could not be encoded")
+        })?;
+        self.refuse_symlink().await?;
+        let tmp = self.temp_path();
+        let mut options = tokio::fs::OpenOptions::new();
+        // `create_new` refuses an existing entry at the temporary path,
+        // including a planted symlink.
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let write = async {
+            use tokio::io::AsyncWriteExt;
+            let mut handle = options.open(&tmp).await?;
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                // The open mode is filtered by the umask; pin it before any
+                // secret reaches the file.
+                handle
+                    .set_permissions(std::fs::Permissions::from_mode(0o600))
+                    .await?;
+            }
+            handle.write_all(&bytes).await?;
+            handle.sync_all().await?;
+            tokio::fs::rename(&tmp, &self.path).await
+        };
+        match write.await {
+            Ok(()) => Ok(()),
+            Err(_) => {
+                let _ = tokio::fs::remove_file(&tmp).await;
+                Err(AuthError::state_unavailable(
+                    "credential store file could not be written",
+                ))
+            }
+        }
+    }
+
+    /// A sibling path unique to this process and write.
+    fn temp_path(&self) -> PathBuf {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.subsec_nanos())
+            .unwrap_or_default();
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(format!(
+            ".{}.{}.{nanos:08x}.tmp",
+            std::process::id(),
+            NEXT.fetch_add(1, Ordering::Relaxed)
+        ));
+        PathBuf::from(tmp)
+    }
+}
+
+impl fmt::Debug for FileCredentialStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileCredentialStore")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl CredentialStore for FileCredentialStore {
+    fn load<'a>(
+        &'a self,
+        id: &'a CredentialId,
+    ) -> AuthFuture<'a, Result<Option<StoredCredential>, AuthError>> {
+        Box::pin(async move {
+            let _guard = self.lock.lock().await;
+            let mut file = self.read().await?;
+            Ok(file
+                .credentials
+                .remove(&id.safe_fragment())
+                .map(|entry| StoredCredential {
+                    kind: entry.kind,
+                    fields: entry
+                        .fields
+                        .into_iter()
+                        .map(|(name, value)| (name, SecretString::new(value)))
+                        .collect(),
+                    expires_at: entry
+                        .expires_at_unix
+                        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds)),
+                }))
+        })
+    }
+
+    fn save<'a>(
+        &'a self,
+        id: &'a CredentialId,
+        credential: StoredCredential,
+    ) -> AuthFuture<'a, Result<(), AuthError>> {
+        Box::pin(async move {
+            let _guard = self.lock.lock().await;
+            let mut file = self.read().await?;
+            let entry = CredentialFileEntry {
+                fields: credential
+                    .fields()
+                    .map(|(name, value)| (name.to_string(), value.expose_secret().to_string()))
+                    .collect(),
+                kind: credential.kind,
+                expires_at_unix: credential.expires_at.map(|expires_at| {
+                    expires_at
+                        .duration_since(UNIX_EPOCH)
+                        .map_or(0, |since| since.as_secs())
+                }),
+            };
+            file.credentials.insert(id.safe_fragment(), entry);
+            self.write(&file).await
+        })
+    }
+
+    fn remove<'a>(&'a self, id: &'a CredentialId) -> AuthFuture<'a, Result<(), AuthError>> {
+        Box::pin(async move {
+            let _guard = self.lock.lock().await;
+            let mut file = self.read().await?;
+            if file.credentials.remove(&id.safe_fragment()).is_some() {
+                self.write(&file).await?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_credential_debug_lists_field_names_only() {
+        let stored = StoredCredential::new("access_token")
+            .with_field("token", "STORED_TOKEN_SENTINEL")
+            .with_expires_at(Some(UNIX_EPOCH));
+        let rendered = format!("{stored:?}");
+
+        assert!(rendered.contains("token"));
+        assert!(!rendered.contains("STORED_TOKEN_SENTINEL"));
+        assert!(stored.is_expired());
+    }
+
+    /// A clock that stands a fixed offset ahead of the system clock.
+    struct AheadClock(Duration);
+
+    impl Clock for AheadClock {
+        fn now(&self) -> Instant {
+            Instant::now() + self.0
+        }
+
+        fn sleep(&self, duration: Duration) -> crate::clock::ClockFuture {
+            Box::pin(tokio::time::sleep(duration))
+        }
+    }
+
+    #[test]
+    fn expiry_round_trips_through_wall_clock_time() {
+        let clock = crate::clock::test_system_clock().as_ref();
+        let deadline = Instant::now() + Duration::from_secs(600);
+        let restored =
+            restored_expiry(stored_expiry(Some(deadline), clock), clock).expect("expiry");
+        let drift = restored
+            .checked_duration_since(deadline)
+            .unwrap_or_else(|| deadline.duration_since(restored));
+        assert!(drift < Duration::from_secs(1));
+
+        let past = restored_expiry(Some(UNIX_EPOCH), clock).expect("expiry");
+        assert!(past <= Instant::now());
+    }
+
+    #[test]
+    fn expiry_is_measured_on_the_configured_clock() {
+        let clock = AheadClock(Duration::from_secs(3600));
+        let stored =
+            stored_expiry(Some(clock.now() + Duration::from_secs(600)), &clock).expect("expiry");
+        let remaining = stored
+            .duration_since(SystemTime::now())
+            .expect("expiry is in the future");
+        assert!(remaining > Duration::from_secs(590) && remaining <= Duration::from_secs(600));
+
+        let restored = restored_expiry(Some(stored), &clock).expect("expiry");
+        assert!(restored > clock.now() + Duration::from_secs(590));
+    }
+
+    #[tokio::test]
+    async fn file_store_round_trips_and_restricts_permissions() {
+        let dir = std::env::temp_dir().join(format!(
+            "concord-credential-store-{}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let path = dir.join("credentials.json");
+        let _ = std::fs::remove_file(&path);
+        let store = FileCredentialStore::new(&path);
+        let id = CredentialId::new("Api", "session");
+
+        assert!(store.load(&id).await.expect("empty load").is_none());
+        store
+            .save(
+                &id,
+                StoredCredential::new("api_key").with_field("value", "FILE_SECRET"),
+            )
+            .await
+            .expect("save");
+        let loaded = store.load(&id).await.expect("load").expect("entry");
+        assert_eq!(loaded.kind(), "api_key");
+        assert_eq!(
+            loaded.field("value").map(SecretString::expose_secret),
+            Some("FILE_SECRET")
+        );
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path)
+                .expect("metadata")
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        store.remove(&id).await.expect("remove");
+        assert!(store.load(&id).await.expect("load").is_none());
+        let leftovers = std::fs::read_dir(&dir)
+            .expect("read dir")
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .count();
+        assert_eq!(leftovers, 0);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn file_store_refuses_to_write_through_a_symlink() {
+        let dir = std::env::temp_dir().join(format!(
+            "concord-credential-store-symlink-{}-{:?}",
+            std::process::id(),
+            std::thread::current().id()
+        ));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("temp dir");
+        let target = dir.join("elsewhere.json");
+        std::fs::write(&target, b"{}").expect("target");
+        let path = dir.join("credentials.json");
+        std::os::unix::fs::symlink(&target, &path).expect("symlink");
+        let store = FileCredentialStore::new(&path);
+
+        let err = store
+            .save(
+                &CredentialId::new("Api", "session"),
+                StoredCredential::new("api_key").with_field("value", "SYMLINK_SECRET"),
+            )
+            .await
+            .expect_err("symlinked store path");
+
+        assert!(err.to_string().contains("symlink"));
+        assert_eq!(std::fs::read(&target).expect("target"), b"{}");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
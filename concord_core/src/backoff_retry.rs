@@ -0,0 +1,328 @@
+//! Opt-in application-level retry of `429` and `503` responses.
+//!
+//! Unlike [`RetryMode`](crate::retry_mode::RetryMode), which configures
+//! Reqwest's hidden resends, every backoff retry is a Concord-visible
+//! execution: it re-acquires a rate-limit permit, reruns hooks, and reports
+//! its position through
+//! [`RequestExecutionMeta::retry`](crate::execution_meta::RequestExecutionMeta).
+//!
+//! A `Retry-After` header on the response sets the delay, capped at
+//! [`RuntimeConfig::max_rate_limit_cooldown`](crate::runtime::RuntimeConfig::max_rate_limit_cooldown).
+//! Without one the delay doubles from `initial_delay` up to `max_delay`, with
+//! half of it randomized unless jitter is disabled. A retry is only scheduled
+//! when its delay still fits in the call's deadline and the client-wide retry
+//! budget has room; otherwise the response is returned as it is. Delays,
+//! deadlines, and the budget window follow the configured
+//! [`Clock`](crate::clock::Clock).
+
+use crate::clock::Clock;
+
+use http::StatusCode;
+use std::num::NonZeroU32;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BackoffRetryConfig {
+    max_retries: NonZeroU32,
+    initial_delay: Duration,
+    max_delay: Duration,
+    deadline: Option<Duration>,
+    budget: Option<(u32, Duration)>,
+    jitter: bool,
+}
+
+impl BackoffRetryConfig {
+    /// Retry up to `max_retries` times per call, starting from
+    /// `initial_delay`. The delay is capped at 30 seconds, jitter is on, and
+    /// there is no deadline or budget.
+    pub const fn new(max_retries: NonZeroU32, initial_delay: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_delay,
+            max_delay: Duration::from_secs(30),
+            deadline: None,
+            budget: None,
+            jitter: true,
+        }
+    }
+
+    /// Cap for the computed exponential delay. `Retry-After` is not capped.
+    pub const fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+
+    /// Total time a call may spend, measured from its first execution, for a
+    /// retry to still be scheduled.
+    pub const fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// At most `retries` backoff retries across the whole client per `per`.
+    pub const fn with_retry_budget(mut self, retries: u32, per: Duration) -> Self {
+        self.budget = Some((retries, per));
+        self
+    }
+
+    pub const fn without_jitter(mut self) -> Self {
+        self.jitter = false;
+        self
+    }
+
+    #[inline]
+    pub const fn max_retries(&self) -> NonZeroU32 {
+        self.max_retries
+    }
+
+    #[inline]
+    pub const fn initial_delay(&self) -> Duration {
+        self.initial_delay
+    }
+
+    #[inline]
+    pub const fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    #[inline]
+    pub const fn deadline(&self) -> Option<Duration> {
+        self.deadline
+    }
+
+    #[inline]
+    pub const fn retry_budget(&self) -> Option<(u32, Duration)> {
+        self.budget
+    }
+
+    #[inline]
+    pub const fn jitter(&self) -> bool {
+        self.jitter
+    }
+
+    /// Exponential delay before retry number `retry` (1-based).
+    fn backoff(&self, retry: u32) -> Duration {
+        let factor = 1_u32
+            .checked_shl(retry.saturating_sub(1))
+            .unwrap_or(u32::MAX);
+        let delay = self
+            .initial_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+        if !self.jitter {
+            return delay;
+        }
+        let half = delay / 2;
+        let mut bytes = [0_u8; 4];
+        if getrandom::fill(&mut bytes).is_err() {
+            return delay;
+        }
+        let fraction = f64::from(u32::from_le_bytes(bytes)) / f64::from(u32::MAX);
+        half + half.mul_f64(fraction)
+    }
+}
+
+/// Client-wide backoff state shared by every call of one configuration.
+pub(crate) struct BackoffRetry {
+    config: BackoffRetryConfig,
+    budget: Mutex<Option<(Instant, u32)>>,
+}
+
+impl BackoffRetry {
+    pub(crate) fn new(config: BackoffRetryConfig) -> Self {
+        Self {
+            config,
+            budget: Mutex::new(None),
+        }
+    }
+
+    /// Start tracking one logical call. `max_retry_after` caps the delay a
+    /// `Retry-After` header can ask for.
+    pub(crate) fn start<'a>(
+        &'a self,
+        clock: &'a dyn Clock,
+        max_retry_after: Duration,
+    ) -> BackoffAttempts<'a> {
+        BackoffAttempts {
+            retry: self,
+            clock,
+            max_retry_after,
+            started: clock.now(),
+            retries: 0,
+        }
+    }
+
+    fn take_budget(&self, now: Instant) -> bool {
+        let Some((limit, per)) = self.config.budget else {
+            return true;
+        };
+        let mut guard = self.budget.lock().unwrap_or_else(|err| err.into_inner());
+        let window = guard.get_or_insert((now, 0));
+        if now.saturating_duration_since(window.0) >= per {
+            *window = (now, 0);
+        }
+        if window.1 >= limit {
+            return false;
+        }
+        window.1 += 1;
+        true
+    }
+}
+
+/// Retry bookkeeping for one logical call.
+pub(crate) struct BackoffAttempts<'a> {
+    retry: &'a BackoffRetry,
+    clock: &'a dyn Clock,
+    max_retry_after: Duration,
+    started: Instant,
+    retries: u32,
+}
+
+impl BackoffAttempts<'_> {
+    /// Backoff retries already scheduled for this call.
+    pub(crate) fn retries(&self) -> u32 {
+        self.retries
+    }
+
+    /// The delay before the next retry, or `None` when `status` is not
+    /// retryable or the call is out of retries, time, or budget. `503` is
+    /// only retried for idempotent executions; `429` means the request was
+    /// not processed and is retried for any method.
+    pub(crate) fn next_delay(
+        &mut self,
+        status: StatusCode,
+        idempotent: bool,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        let config = &self.retry.config;
+        let retryable = status == StatusCode::TOO_MANY_REQUESTS
+            || (status == StatusCode::SERVICE_UNAVAILABLE && idempotent);
+        if !retryable || self.retries >= config.max_retries.get() {
+            return None;
+        }
+        let delay = retry_after.map_or_else(
+            || config.backoff(self.retries + 1),
+            |delay| delay.min(self.max_retry_after),
+        );
+        let now = self.clock.now();
+        if let Some(deadline) = config.deadline
+            && now
+                .saturating_duration_since(self.started)
+                .saturating_add(delay)
+                > deadline
+        {
+            return None;
+        }
+        if !self.retry.take_budget(now) {
+            return None;
+        }
+        self.retries += 1;
+        Some(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
+    fn test_clock() -> &'static dyn Clock {
+        crate::clock::test_system_clock().as_ref()
+    }
+
+    fn config(max_retries: u32) -> BackoffRetryConfig {
+        BackoffRetryConfig::new(
+            NonZeroU32::new(max_retries).expect("non-zero"),
+            Duration::from_millis(100),
+        )
+        .with_max_delay(Duration::from_millis(250))
+    }
+
+    #[test]
+    fn delay_doubles_up_to_the_cap_and_honors_retry_after() {
+        let retry = BackoffRetry::new(config(4).without_jitter());
+        let mut call = retry.start(test_clock(), MAX_RETRY_AFTER);
+        let delays: Vec<_> = (0..4)
+            .map(|_| call.next_delay(StatusCode::TOO_MANY_REQUESTS, false, None))
+            .collect();
+        assert_eq!(
+            delays,
+            [100, 200, 250, 250].map(|ms| Some(Duration::from_millis(ms)))
+        );
+        assert_eq!(
+            call.next_delay(StatusCode::TOO_MANY_REQUESTS, false, None),
+            None
+        );
+
+        let mut call = retry.start(test_clock(), MAX_RETRY_AFTER);
+        assert_eq!(
+            call.next_delay(
+                StatusCode::SERVICE_UNAVAILABLE,
+                true,
+                Some(Duration::from_secs(2))
+            ),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            call.next_delay(
+                StatusCode::TOO_MANY_REQUESTS,
+                false,
+                Some(Duration::from_secs(86_400))
+            ),
+            Some(MAX_RETRY_AFTER)
+        );
+    }
+
+    #[test]
+    fn jitter_stays_within_the_upper_half() {
+        let config = config(1);
+        for _ in 0..32 {
+            let delay = config.backoff(1);
+            assert!(
+                (Duration::from_millis(50)..=Duration::from_millis(100)).contains(&delay),
+                "{delay:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn status_method_deadline_and_budget_limit_retries() {
+        let retry = BackoffRetry::new(
+            config(3)
+                .with_deadline(Duration::from_secs(1))
+                .with_retry_budget(2, Duration::from_secs(60)),
+        );
+        let mut call = retry.start(test_clock(), MAX_RETRY_AFTER);
+        assert_eq!(call.next_delay(StatusCode::BAD_GATEWAY, true, None), None);
+        assert_eq!(
+            call.next_delay(StatusCode::SERVICE_UNAVAILABLE, false, None),
+            None
+        );
+        assert_eq!(
+            call.next_delay(
+                StatusCode::TOO_MANY_REQUESTS,
+                false,
+                Some(Duration::from_secs(5))
+            ),
+            None
+        );
+        assert!(
+            call.next_delay(StatusCode::TOO_MANY_REQUESTS, false, None)
+                .is_some()
+        );
+        assert!(
+            retry
+                .start(test_clock(), MAX_RETRY_AFTER)
+                .next_delay(StatusCode::SERVICE_UNAVAILABLE, true, None)
+                .is_some()
+        );
+        assert_eq!(
+            call.next_delay(StatusCode::TOO_MANY_REQUESTS, false, None),
+            None
+        );
+        assert_eq!(call.retries(), 1);
+    }
+}
@@ -0,0 +1,199 @@
+use http::header::{AGE, CACHE_CONTROL, DATE, EXPIRES, VARY};
+use http::{HeaderMap, HeaderName};
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Default, PartialEq)]
+struct ResponseDirectives {
+    no_store: bool,
+    no_cache: bool,
+    max_age: Option<u64>,
+}
+
+fn response_directives(headers: &HeaderMap) -> ResponseDirectives {
+    let mut out = ResponseDirectives::default();
+    for value in headers.get_all(CACHE_CONTROL) {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        for directive in value.split(',') {
+            let directive = directive.trim();
+            let (name, arg) = match directive.split_once('=') {
+                Some((name, arg)) => (name.trim(), Some(arg.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+            if name.eq_ignore_ascii_case("no-store") {
+                out.no_store = true;
+            } else if name.eq_ignore_ascii_case("no-cache") {
+                out.no_cache = true;
+            } else if name.eq_ignore_ascii_case("max-age")
+                && let Some(seconds) = arg.and_then(|arg| arg.parse().ok())
+            {
+                out.max_age = Some(seconds);
+            }
+        }
+    }
+    out
+}
+
+fn header_date(headers: &HeaderMap, name: HeaderName) -> Option<SystemTime> {
+    let raw = headers.get(name)?.to_str().ok()?;
+    httpdate::parse_http_date(raw.trim()).ok()
+}
+
+/// Request header names selected by `Vary`, or `None` for `Vary: *`, which
+/// makes the response unusable for any later request.
+pub(crate) fn vary_names(headers: &HeaderMap) -> Option<Vec<HeaderName>> {
+    let mut names = Vec::new();
+    for value in headers.get_all(VARY) {
+        let value = value.to_str().ok()?;
+        for name in value
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+        {
+            if name == "*" {
+                return None;
+            }
+            names.push(HeaderName::from_bytes(name.as_bytes()).ok()?);
+        }
+    }
+    Some(names)
+}
+
+/// Freshness lifetime of a response received at `now`, or `None` when the
+/// response must not be stored.
+///
+/// `Cache-Control: max-age` (less `Age`) wins over `Expires` − `Date`; absent
+/// both, the endpoint policy's `default` applies. `no-cache` stores the entry
+/// with zero freshness so every use is revalidated.
+pub(crate) fn freshness_lifetime(
+    headers: &HeaderMap,
+    now: SystemTime,
+    default: Duration,
+) -> Option<Duration> {
+    let directives = response_directives(headers);
+    if directives.no_store || vary_names(headers).is_none() {
+        return None;
+    }
+    if directives.no_cache {
+        return Some(Duration::ZERO);
+    }
+    if let Some(max_age) = directives.max_age {
+        let age = headers
+            .get(AGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok())
+            .unwrap_or(0);
+        return Some(Duration::from_secs(max_age.saturating_sub(age)));
+    }
+    if let Some(expires) = headers.get(EXPIRES) {
+        let Some(expires) = expires
+            .to_str()
+            .ok()
+            .and_then(|raw| httpdate::parse_http_date(raw.trim()).ok())
+        else {
+            // An invalid Expires means "already expired".
+            return Some(Duration::ZERO);
+        };
+        let date = header_date(headers, DATE).unwrap_or(now);
+        return Some(expires.duration_since(date).unwrap_or_default());
+    }
+    Some(default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::HeaderValue;
+
+    fn headers(pairs: &[(&'static str, &'static str)]) -> HeaderMap {
+        let mut out = HeaderMap::new();
+        for (name, value) in pairs {
+            out.append(
+                HeaderName::from_static(name),
+                HeaderValue::from_static(value),
+            );
+        }
+        out
+    }
+
+    const DEFAULT: Duration = Duration::from_secs(300);
+
+    #[test]
+    fn no_store_and_vary_star_are_not_stored() {
+        let now = SystemTime::now();
+        assert_eq!(
+            freshness_lifetime(&headers(&[("cache-control", "no-store")]), now, DEFAULT),
+            None
+        );
+        assert_eq!(
+            freshness_lifetime(&headers(&[("vary", "Accept, *")]), now, DEFAULT),
+            None
+        );
+    }
+
+    #[test]
+    fn no_cache_is_stored_stale() {
+        let now = SystemTime::now();
+        assert_eq!(
+            freshness_lifetime(
+                &headers(&[("cache-control", "no-cache, max-age=60")]),
+                now,
+                DEFAULT
+            ),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn max_age_less_age_wins_over_expires_and_default() {
+        let now = SystemTime::now();
+        let h = headers(&[
+            ("cache-control", "public, max-age=\"60\""),
+            ("age", "15"),
+            ("expires", "Wed, 21 Oct 2015 07:28:00 GMT"),
+        ]);
+        assert_eq!(
+            freshness_lifetime(&h, now, DEFAULT),
+            Some(Duration::from_secs(45))
+        );
+    }
+
+    #[test]
+    fn expires_is_measured_against_date() {
+        let now = SystemTime::now();
+        let h = headers(&[
+            ("date", "Wed, 21 Oct 2015 07:28:00 GMT"),
+            ("expires", "Wed, 21 Oct 2015 07:30:00 GMT"),
+        ]);
+        assert_eq!(
+            freshness_lifetime(&h, now, DEFAULT),
+            Some(Duration::from_secs(120))
+        );
+        let invalid = headers(&[("expires", "0")]);
+        assert_eq!(
+            freshness_lifetime(&invalid, now, DEFAULT),
+            Some(Duration::ZERO)
+        );
+    }
+
+    #[test]
+    fn policy_default_applies_without_origin_freshness() {
+        assert_eq!(
+            freshness_lifetime(&HeaderMap::new(), SystemTime::now(), DEFAULT),
+            Some(DEFAULT)
+        );
+    }
+
+    #[test]
+    fn vary_names_are_collected_across_fields() {
+        let h = headers(&[("vary", "Accept"), ("vary", "accept-language, ")]);
+        assert_eq!(
+            vary_names(&h).unwrap(),
+            vec![
+                HeaderName::from_static("accept"),
+                HeaderName::from_static("accept-language")
+            ]
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use super::{CacheFuture, CacheKey, CachedResponse, ResponseCache};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+
+const DEFAULT_CAPACITY: usize = 256;
+
+/// Process-local least-recently-used response cache bounded by entry count.
+pub struct InMemoryResponseCache {
+    state: Mutex<LruState>,
+}
+
+#[derive(Default)]
+struct LruState {
+    capacity: usize,
+    tick: u64,
+    entries: HashMap<CacheKey, (u64, CachedResponse)>,
+    order: BTreeMap<u64, CacheKey>,
+}
+
+impl LruState {
+    fn touch(&mut self, key: &CacheKey) -> Option<CachedResponse> {
+        self.tick += 1;
+        let tick = self.tick;
+        let (used, response) = self.entries.get_mut(key)?;
+        self.order.remove(used);
+        *used = tick;
+        self.order.insert(tick, key.clone());
+        Some(response.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, response: CachedResponse) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.remove(&key);
+        while self.entries.len() >= self.capacity {
+            let Some((_, oldest)) = self.order.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+        }
+        self.tick += 1;
+        self.order.insert(self.tick, key.clone());
+        self.entries.insert(key, (self.tick, response));
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some((used, _)) = self.entries.remove(key) {
+            self.order.remove(&used);
+        }
+    }
+}
+
+impl InMemoryResponseCache {
+    /// Creates a cache holding at most `capacity` responses. A capacity of
+    /// zero stores nothing.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(LruState {
+                capacity,
+                ..LruState::default()
+            }),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.lock().capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.entries.clear();
+        state.order.clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LruState> {
+        self.state
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+impl Default for InMemoryResponseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl std::fmt::Debug for InMemoryResponseCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = self.lock();
+        f.debug_struct("InMemoryResponseCache")
+            .field("capacity", &state.capacity)
+            .field("len", &state.entries.len())
+            .finish()
+    }
+}
+
+impl ResponseCache for InMemoryResponseCache {
+    fn get<'a>(&'a self, key: &'a CacheKey) -> CacheFuture<'a, Option<CachedResponse>> {
+        let found = self.lock().touch(key);
+        Box::pin(async move { found })
+    }
+
+    fn put<'a>(&'a self, key: CacheKey, response: CachedResponse) -> CacheFuture<'a, ()> {
+        self.lock().insert(key, response);
+        Box::pin(async {})
+    }
+
+    fn remove<'a>(&'a self, key: &'a CacheKey) -> CacheFuture<'a, ()> {
+        self.lock().remove(key);
+        Box::pin(async {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+    use http::{HeaderMap, Method, StatusCode};
+    use std::time::{Duration, SystemTime};
+
+    fn key(path: &str) -> CacheKey {
+        let url = url::Url::parse(&format!("https://example.test/{path}")).unwrap();
+        CacheKey::new(&Method::GET, &url)
+    }
+
+    fn response(body: &'static str) -> CachedResponse {
+        CachedResponse::new(
+            StatusCode::OK,
+            HeaderMap::new(),
+            Bytes::from_static(body.as_bytes()),
+            SystemTime::now(),
+            Duration::from_secs(60),
+        )
+    }
+
+    #[tokio::test]
+    async fn evicts_the_least_recently_used_entry() {
+        let cache = InMemoryResponseCache::new(2);
+        cache.put(key("a"), response("a")).await;
+        cache.put(key("b"), response("b")).await;
+        assert!(cache.get(&key("a")).await.is_some());
+
+        cache.put(key("c"), response("c")).await;
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key("b")).await.is_none());
+        assert_eq!(cache.get(&key("a")).await.unwrap().body().as_ref(), b"a");
+        assert_eq!(cache.get(&key("c")).await.unwrap().body().as_ref(), b"c");
+    }
+
+    #[tokio::test]
+    async fn replacing_and_removing_keep_order_consistent() {
+        let cache = InMemoryResponseCache::new(2);
+        cache.put(key("a"), response("a1")).await;
+        cache.put(key("a"), response("a2")).await;
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.get(&key("a")).await.unwrap().body().as_ref(), b"a2");
+
+        cache.remove(&key("a")).await;
+        assert!(cache.is_empty());
+        cache.put(key("b"), response("b")).await;
+        cache.put(key("c"), response("c")).await;
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn zero_capacity_stores_nothing() {
+        let cache = InMemoryResponseCache::new(0);
+        cache.put(key("a"), response("a")).await;
+        assert!(cache.get(&key("a")).await.is_none());
+    }
+
+    #[test]
+    fn keys_distinguish_method_and_query() {
+        let url = url::Url::parse("https://example.test/a?x=1").unwrap();
+        assert_ne!(
+            CacheKey::new(&Method::GET, &url),
+            CacheKey::new(&Method::HEAD, &url)
+        );
+        assert_eq!(
+            CacheKey::new(&Method::GET, &url).as_str(),
+            "GET https://example.test/a?x=1"
+        );
+    }
+}
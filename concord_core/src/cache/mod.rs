@@ -0,0 +1,336 @@
+//! Opt-in client-side HTTP response cache.
+//!
+//! Caching is enabled per endpoint by a [`CachePolicy`] (the DSL `cache`
+//! clause) *and* a configured [`ResponseCache`] backend. Only buffered `GET`
+//! and `HEAD` executions participate. Fresh entries are decoded without
+//! touching the wire; stale entries carrying validators are revalidated with
+//! `If-None-Match` / `If-Modified-Since`, and a `304 Not Modified` is answered
+//! from the stored body through the endpoint's response codec.
+//!
+//! Cache hits do not run runtime hooks, rate limiting, or authentication.
+//! Keys cover the method and logical URL only, so a backend must not be shared
+//! between clients that hold different credentials.
+
+mod freshness;
+mod memory;
+
+use bytes::Bytes;
+use http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, SystemTime};
+
+pub(crate) use freshness::{freshness_lifetime, vary_names};
+pub use memory::InMemoryResponseCache;
+
+pub type CacheFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// Pluggable storage for cached responses.
+///
+/// Backends are best-effort: a failed lookup is a miss and a failed write is
+/// dropped. Implementations own their eviction policy.
+pub trait ResponseCache: Send + Sync + 'static {
+    fn get<'a>(&'a self, key: &'a CacheKey) -> CacheFuture<'a, Option<CachedResponse>>;
+
+    fn put<'a>(&'a self, key: CacheKey, response: CachedResponse) -> CacheFuture<'a, ()>;
+
+    fn remove<'a>(&'a self, key: &'a CacheKey) -> CacheFuture<'a, ()>;
+}
+
+/// Endpoint-level cache configuration lowered from the DSL `cache` clause.
+///
+/// `max_age` is the freshness lifetime used when the origin response carries
+/// neither `Cache-Control: max-age` nor `Expires`. Cache keys carry no
+/// principal, so endpoints with authentication requirements bypass the cache
+/// unless the policy is [`shared`](Self::shared).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CachePolicy {
+    max_age: Duration,
+    shared: bool,
+}
+
+impl CachePolicy {
+    pub const fn max_age(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            shared: false,
+        }
+    }
+
+    /// Declares authenticated responses identical for every principal, so
+    /// they may be stored and served across credential changes.
+    pub const fn shared(mut self) -> Self {
+        self.shared = true;
+        self
+    }
+
+    pub fn default_max_age(&self) -> Duration {
+        self.max_age
+    }
+
+    pub fn is_shared(&self) -> bool {
+        self.shared
+    }
+}
+
+/// How a [`DecodedResponse`](crate::transport::DecodedResponse) was produced.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash)]
+pub enum CacheStatus {
+    /// The endpoint or client does not use a response cache.
+    #[default]
+    Bypass,
+    /// No usable entry existed; the response came from the origin.
+    Miss,
+    /// A fresh entry answered the request without a network round trip.
+    Hit,
+    /// A stale entry was confirmed by a `304 Not Modified` from the origin.
+    Revalidated,
+}
+
+/// Cache lookup key: request method plus the logical (pre-authentication) URL.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct CacheKey(String);
+
+impl CacheKey {
+    pub fn new(method: &Method, url: &url::Url) -> Self {
+        Self(format!("{method} {url}"))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// A stored buffered response together with its freshness metadata.
+#[derive(Clone)]
+pub struct CachedResponse {
+    status: StatusCode,
+    headers: HeaderMap,
+    body: Bytes,
+    stored_at: SystemTime,
+    fresh_for: Duration,
+    vary: Vec<(HeaderName, Option<HeaderValue>)>,
+}
+
+impl CachedResponse {
+    pub fn new(
+        status: StatusCode,
+        headers: HeaderMap,
+        body: Bytes,
+        stored_at: SystemTime,
+        fresh_for: Duration,
+    ) -> Self {
+        Self {
+            status,
+            headers,
+            body,
+            stored_at,
+            fresh_for,
+            vary: Vec::new(),
+        }
+    }
+
+    /// Records the request header values selected by the response `Vary`.
+    pub fn with_vary(mut self, vary: Vec<(HeaderName, Option<HeaderValue>)>) -> Self {
+        self.vary = vary;
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn body(&self) -> &Bytes {
+        &self.body
+    }
+
+    pub fn stored_at(&self) -> SystemTime {
+        self.stored_at
+    }
+
+    pub fn fresh_for(&self) -> Duration {
+        self.fresh_for
+    }
+
+    pub fn vary(&self) -> &[(HeaderName, Option<HeaderValue>)] {
+        &self.vary
+    }
+
+    pub fn etag(&self) -> Option<&HeaderValue> {
+        self.headers.get(http::header::ETAG)
+    }
+
+    pub fn last_modified(&self) -> Option<&HeaderValue> {
+        self.headers.get(http::header::LAST_MODIFIED)
+    }
+
+    pub fn has_validators(&self) -> bool {
+        self.etag().is_some() || self.last_modified().is_some()
+    }
+
+    pub fn is_fresh(&self, now: SystemTime) -> bool {
+        let age = now.duration_since(self.stored_at).unwrap_or_default();
+        age < self.fresh_for
+    }
+
+    /// Whether the request headers select the same variant that was stored.
+    pub fn matches_vary(&self, request_headers: &HeaderMap) -> bool {
+        self.vary
+            .iter()
+            .all(|(name, stored)| request_headers.get(name) == stored.as_ref())
+    }
+
+    /// Conditional request headers for revalidating this entry.
+    pub(crate) fn validators(&self) -> Vec<(HeaderName, HeaderValue)> {
+        let mut out = Vec::new();
+        if let Some(etag) = self.etag() {
+            out.push((http::header::IF_NONE_MATCH, etag.clone()));
+        }
+        if let Some(modified) = self.last_modified() {
+            out.push((http::header::IF_MODIFIED_SINCE, modified.clone()));
+        }
+        out
+    }
+
+    /// Applies a `304 Not Modified` head: its end-to-end headers replace the
+    /// stored ones and the freshness clock restarts.
+    pub(crate) fn refresh(
+        mut self,
+        not_modified: &HeaderMap,
+        now: SystemTime,
+        policy: &CachePolicy,
+    ) -> Self {
+        for name in not_modified.keys() {
+            if is_hop_or_length_header(name) {
+                continue;
+            }
+            self.headers.remove(name);
+            for value in not_modified.get_all(name) {
+                self.headers.append(name.clone(), value.clone());
+            }
+        }
+        self.stored_at = now;
+        self.fresh_for =
+            freshness_lifetime(&self.headers, now, policy.default_max_age()).unwrap_or_default();
+        self
+    }
+}
+
+impl std::fmt::Debug for CachedResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CachedResponse")
+            .field("status", &self.status)
+            .field(
+                "headers",
+                &crate::debug::SanitizedHeaders::new(&self.headers),
+            )
+            .field("body_len", &self.body.len())
+            .field("stored_at", &self.stored_at)
+            .field("fresh_for", &self.fresh_for)
+            .finish()
+    }
+}
+
+fn is_hop_or_length_header(name: &HeaderName) -> bool {
+    *name == http::header::CONTENT_LENGTH
+        || *name == http::header::TRANSFER_ENCODING
+        || *name == http::header::CONNECTION
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use http::header::{CACHE_CONTROL, ETAG, LAST_MODIFIED};
+
+    fn entry(headers: HeaderMap, fresh_for: Duration) -> CachedResponse {
+        CachedResponse::new(
+            StatusCode::OK,
+            headers,
+            Bytes::from_static(b"body"),
+            SystemTime::UNIX_EPOCH,
+            fresh_for,
+        )
+    }
+
+    #[test]
+    fn freshness_is_measured_from_storage_time() {
+        let entry = entry(HeaderMap::new(), Duration::from_secs(60));
+        assert!(entry.is_fresh(SystemTime::UNIX_EPOCH + Duration::from_secs(59)));
+        assert!(!entry.is_fresh(SystemTime::UNIX_EPOCH + Duration::from_secs(60)));
+    }
+
+    #[test]
+    fn validators_map_to_conditional_request_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        let validators = entry(headers, Duration::ZERO).validators();
+        assert_eq!(
+            validators,
+            vec![
+                (
+                    http::header::IF_NONE_MATCH,
+                    HeaderValue::from_static("\"v1\"")
+                ),
+                (
+                    http::header::IF_MODIFIED_SINCE,
+                    HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn refresh_merges_not_modified_headers_and_restarts_freshness() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"v1\""));
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("max-age=1"));
+        let mut not_modified = HeaderMap::new();
+        not_modified.insert(CACHE_CONTROL, HeaderValue::from_static("max-age=30"));
+        not_modified.insert(http::header::CONTENT_LENGTH, HeaderValue::from_static("0"));
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(100);
+
+        let refreshed = entry(headers, Duration::from_secs(1)).refresh(
+            &not_modified,
+            now,
+            &CachePolicy::max_age(Duration::from_secs(5)),
+        );
+
+        assert_eq!(refreshed.stored_at(), now);
+        assert_eq!(refreshed.fresh_for(), Duration::from_secs(30));
+        assert_eq!(refreshed.etag().unwrap(), "\"v1\"");
+        assert!(
+            !refreshed
+                .headers()
+                .contains_key(http::header::CONTENT_LENGTH)
+        );
+        assert_eq!(refreshed.body().as_ref(), b"body");
+    }
+
+    #[test]
+    fn vary_selects_the_stored_variant() {
+        let name = HeaderName::from_static("accept-language");
+        let entry = entry(HeaderMap::new(), Duration::ZERO)
+            .with_vary(vec![(name.clone(), Some(HeaderValue::from_static("en")))]);
+        let mut request = HeaderMap::new();
+        assert!(!entry.matches_vary(&request));
+        request.insert(name.clone(), HeaderValue::from_static("en"));
+        assert!(entry.matches_vary(&request));
+        request.insert(name, HeaderValue::from_static("fr"));
+        assert!(!entry.matches_vary(&request));
+    }
+
+    #[test]
+    fn debug_output_omits_body_bytes() {
+        let rendered = format!("{:?}", entry(HeaderMap::new(), Duration::ZERO));
+        assert!(rendered.contains("body_len: 4"));
+        assert!(!rendered.contains("b\"body\""));
+    }
+}
@@ -0,0 +1,380 @@
+//! Opt-in circuit breaking in front of rate-limit acquisition.
+//!
+//! A circuit counts consecutive failed visible executions. After
+//! `failure_threshold` of them it opens and every execution that maps to it
+//! fails fast with [`ApiClientError::CircuitOpen`](crate::error::ApiClientError)
+//! without spending a rate-limit permit, running hooks, or touching the
+//! network. Once `open_for` has elapsed on the runtime clock the circuit is
+//! half-open and admits exactly one probe execution: success closes it,
+//! failure reopens it.
+//!
+//! Connect and timeout errors and `5xx` responses count as failures. Any other
+//! response resets a closed circuit's count, and closes a half-open circuit
+//! only when it answers the probe; executions admitted before the circuit
+//! opened cannot close it. Other errors, and executions abandoned before a
+//! result, leave the count unchanged and release a pending probe.
+
+use crate::clock::Clock;
+use crate::error::{ApiClientError, ErrorCategory};
+use crate::transport::ExecutionResponse;
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// What one circuit covers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CircuitScope {
+    /// Scheme, host, and port of the request URL.
+    Origin,
+    /// The generated endpoint name.
+    Endpoint,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CircuitBreakerConfig {
+    scope: CircuitScope,
+    failure_threshold: NonZeroU32,
+    open_for: Duration,
+}
+
+impl CircuitBreakerConfig {
+    /// One circuit per request origin.
+    pub const fn per_origin(failure_threshold: NonZeroU32, open_for: Duration) -> Self {
+        Self {
+            scope: CircuitScope::Origin,
+            failure_threshold,
+            open_for,
+        }
+    }
+
+    /// One circuit per endpoint.
+    pub const fn per_endpoint(failure_threshold: NonZeroU32, open_for: Duration) -> Self {
+        Self {
+            scope: CircuitScope::Endpoint,
+            failure_threshold,
+            open_for,
+        }
+    }
+
+    #[inline]
+    pub const fn scope(&self) -> CircuitScope {
+        self.scope
+    }
+
+    #[inline]
+    pub const fn failure_threshold(&self) -> NonZeroU32 {
+        self.failure_threshold
+    }
+
+    #[inline]
+    pub const fn open_for(&self) -> Duration {
+        self.open_for
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Circuit {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen { probing: bool },
+}
+
+/// How one visible execution ended, as far as its circuit is concerned.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum CircuitOutcome {
+    Success,
+    Failure,
+    Neutral,
+}
+
+impl CircuitOutcome {
+    pub(crate) fn of(result: &Result<ExecutionResponse, ApiClientError>) -> Self {
+        match result {
+            Ok(response) if response.status().is_server_error() => Self::Failure,
+            Ok(_) => Self::Success,
+            Err(error) => match error.category() {
+                ErrorCategory::Connect | ErrorCategory::Timeout => Self::Failure,
+                _ => Self::Neutral,
+            },
+        }
+    }
+}
+
+/// Shared state of every circuit configured by one [`CircuitBreakerConfig`].
+pub(crate) struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    circuits: Mutex<HashMap<String, Circuit>>,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(config: CircuitBreakerConfig) -> Arc<Self> {
+        Arc::new(Self {
+            config,
+            circuits: Mutex::new(HashMap::new()),
+        })
+    }
+
+    fn key(&self, endpoint: &'static str, url: &Url) -> String {
+        match self.config.scope {
+            CircuitScope::Origin => url.origin().ascii_serialization(),
+            CircuitScope::Endpoint => endpoint.to_string(),
+        }
+    }
+
+    /// Admit one visible execution, or report how long the circuit stays
+    /// open on `clock`. `None` means a half-open probe is already in flight.
+    pub(crate) fn admit(
+        self: &Arc<Self>,
+        endpoint: &'static str,
+        url: &Url,
+        clock: &dyn Clock,
+    ) -> Result<CircuitTicket, Option<Duration>> {
+        let key = self.key(endpoint, url);
+        let now = clock.now();
+        let mut circuits = self.circuits.lock().unwrap_or_else(|err| err.into_inner());
+        let circuit = circuits
+            .entry(key.clone())
+            .or_insert(Circuit::Closed { failures: 0 });
+        let probe = match *circuit {
+            Circuit::Closed { .. } => false,
+            Circuit::Open { until } if until > now => return Err(Some(until - now)),
+            Circuit::Open { .. } | Circuit::HalfOpen { probing: false } => {
+                *circuit = Circuit::HalfOpen { probing: true };
+                true
+            }
+            Circuit::HalfOpen { probing: true } => return Err(None),
+        };
+        Ok(CircuitTicket {
+            breaker: Arc::clone(self),
+            key,
+            probe,
+            settled: false,
+        })
+    }
+
+    fn settle(&self, key: &str, probe: bool, outcome: CircuitOutcome, clock: &dyn Clock) {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|err| err.into_inner());
+        let Some(circuit) = circuits.get_mut(key) else {
+            return;
+        };
+        let open = Circuit::Open {
+            until: clock.now() + self.config.open_for,
+        };
+        *circuit = match (*circuit, outcome) {
+            (Circuit::Closed { .. }, CircuitOutcome::Success) => Circuit::Closed { failures: 0 },
+            (Circuit::HalfOpen { .. }, CircuitOutcome::Success) if probe => {
+                Circuit::Closed { failures: 0 }
+            }
+            (Circuit::Closed { failures }, CircuitOutcome::Failure) => {
+                let failures = failures.saturating_add(1);
+                if failures >= self.config.failure_threshold.get() {
+                    open
+                } else {
+                    Circuit::Closed { failures }
+                }
+            }
+            (Circuit::HalfOpen { .. }, CircuitOutcome::Failure) if probe => open,
+            (Circuit::HalfOpen { .. }, CircuitOutcome::Neutral) if probe => {
+                Circuit::HalfOpen { probing: false }
+            }
+            // Stragglers admitted before the circuit opened neither close nor
+            // reopen it.
+            (state, _) => state,
+        };
+    }
+
+    /// The neutral outcome of an abandoned execution: a pending probe is
+    /// released and nothing else changes, so no time is needed.
+    fn release(&self, key: &str, probe: bool) {
+        let mut circuits = self.circuits.lock().unwrap_or_else(|err| err.into_inner());
+        if probe && let Some(circuit @ Circuit::HalfOpen { .. }) = circuits.get_mut(key) {
+            *circuit = Circuit::HalfOpen { probing: false };
+        }
+    }
+}
+
+/// Admission of one visible execution. Dropping it unsettled counts as a
+/// neutral outcome.
+pub(crate) struct CircuitTicket {
+    breaker: Arc<CircuitBreaker>,
+    key: String,
+    probe: bool,
+    settled: bool,
+}
+
+impl CircuitTicket {
+    pub(crate) fn settle(mut self, outcome: CircuitOutcome, clock: &dyn Clock) {
+        self.settled = true;
+        self.breaker.settle(&self.key, self.probe, outcome, clock);
+    }
+}
+
+impl Drop for CircuitTicket {
+    fn drop(&mut self) {
+        if !self.settled {
+            self.breaker.release(&self.key, self.probe);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(threshold: u32, open_for: Duration) -> Arc<CircuitBreaker> {
+        CircuitBreaker::new(CircuitBreakerConfig::per_origin(
+            NonZeroU32::new(threshold).expect("non-zero"),
+            open_for,
+        ))
+    }
+
+    fn clock() -> &'static dyn Clock {
+        crate::clock::test_system_clock().as_ref()
+    }
+
+    /// A clock that only moves when the test advances it.
+    struct StepClock(Mutex<Instant>);
+
+    impl StepClock {
+        fn advance(&self, by: Duration) {
+            *self.0.lock().expect("clock") += by;
+        }
+    }
+
+    impl Clock for StepClock {
+        fn now(&self) -> Instant {
+            *self.0.lock().expect("clock")
+        }
+
+        fn sleep(&self, duration: Duration) -> crate::clock::ClockFuture {
+            Box::pin(tokio::time::sleep(duration))
+        }
+    }
+
+    fn url(value: &str) -> Url {
+        Url::parse(value).expect("url")
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures_and_admits_one_probe() {
+        let breaker = breaker(2, Duration::ZERO);
+        let api = url("https://api.example.com/a");
+
+        for outcome in [
+            CircuitOutcome::Failure,
+            CircuitOutcome::Success,
+            CircuitOutcome::Failure,
+        ] {
+            breaker
+                .admit("A", &api, clock())
+                .expect("closed")
+                .settle(outcome, clock());
+        }
+        breaker
+            .admit("B", &url("https://api.example.com/b"), clock())
+            .expect("still closed")
+            .settle(CircuitOutcome::Failure, clock());
+
+        // `open_for` is zero, so the next admission is the half-open probe.
+        let probe = breaker.admit("A", &api, clock()).expect("probe");
+        assert_eq!(breaker.admit("A", &api, clock()).err(), Some(None));
+        assert!(
+            breaker
+                .admit("A", &url("https://other.example.com"), clock())
+                .is_ok()
+        );
+        drop(probe);
+
+        let probe = breaker.admit("A", &api, clock()).expect("released probe");
+        probe.settle(CircuitOutcome::Success, clock());
+        let ticket = breaker.admit("A", &api, clock()).expect("closed again");
+        ticket.settle(CircuitOutcome::Failure, clock());
+        breaker
+            .admit("A", &api, clock())
+            .expect("one failure stays closed")
+            .settle(CircuitOutcome::Neutral, clock());
+    }
+
+    #[test]
+    fn open_circuit_reports_remaining_time() {
+        let breaker = breaker(1, Duration::from_secs(30));
+        let api = url("https://api.example.com");
+        breaker
+            .admit("A", &api, clock())
+            .expect("closed")
+            .settle(CircuitOutcome::Failure, clock());
+
+        let remaining = breaker
+            .admit("A", &api, clock())
+            .err()
+            .flatten()
+            .expect("open with remaining time");
+        assert!(remaining > Duration::from_secs(29), "{remaining:?}");
+    }
+
+    #[test]
+    fn stragglers_admitted_while_closed_do_not_close_an_open_circuit() {
+        let breaker = breaker(1, Duration::from_secs(30));
+        let api = url("https://api.example.com");
+        let straggler = breaker.admit("A", &api, clock()).expect("closed");
+        breaker
+            .admit("A", &api, clock())
+            .expect("closed")
+            .settle(CircuitOutcome::Failure, clock());
+
+        straggler.settle(CircuitOutcome::Success, clock());
+
+        assert!(matches!(breaker.admit("A", &api, clock()), Err(Some(_))));
+    }
+
+    #[test]
+    fn stragglers_do_not_settle_a_half_open_circuit() {
+        let breaker = breaker(1, Duration::ZERO);
+        let api = url("https://api.example.com");
+        let straggler = breaker.admit("A", &api, clock()).expect("closed");
+        breaker
+            .admit("A", &api, clock())
+            .expect("closed")
+            .settle(CircuitOutcome::Failure, clock());
+        let probe = breaker.admit("A", &api, clock()).expect("probe");
+
+        straggler.settle(CircuitOutcome::Success, clock());
+        assert_eq!(breaker.admit("A", &api, clock()).err(), Some(None));
+
+        probe.settle(CircuitOutcome::Success, clock());
+        breaker
+            .admit("A", &api, clock())
+            .expect("closed by the probe")
+            .settle(CircuitOutcome::Neutral, clock());
+    }
+
+    #[test]
+    fn open_for_elapses_on_the_configured_clock() {
+        let breaker = breaker(1, Duration::from_secs(30));
+        let api = url("https://api.example.com");
+        let clock = StepClock(Mutex::new(Instant::now()));
+        breaker
+            .admit("A", &api, &clock)
+            .expect("closed")
+            .settle(CircuitOutcome::Failure, &clock);
+
+        clock.advance(Duration::from_secs(20));
+        assert_eq!(
+            breaker.admit("A", &api, &clock).err(),
+            Some(Some(Duration::from_secs(10)))
+        );
+
+        clock.advance(Duration::from_secs(10));
+        breaker
+            .admit("A", &api, &clock)
+            .expect("half-open probe")
+            .settle(CircuitOutcome::Success, &clock);
+        breaker
+            .admit("A", &api, &clock)
+            .expect("closed by the probe")
+            .settle(CircuitOutcome::Neutral, &clock);
+    }
+}
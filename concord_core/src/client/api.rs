@@ -12,6 +12,7 @@ pub struct ApiClient<Cx: ClientContext> {
     pub(super) debug_sink: Arc<dyn DebugSink>,
     pub(super) runtime_state: Arc<ClientRuntimeState>,
     pub(super) api_headers: http::HeaderMap,
+    pub(super) in_flight: Arc<super::coalesce::InFlightRequests>,
 }
 
 impl<Cx: ClientContext> ApiClient<Cx> {
@@ -169,6 +170,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             debug_sink: Arc::new(StderrDebugSink),
             runtime_state: Arc::new(ClientRuntimeState::default()),
             api_headers: http::HeaderMap::new(),
+            in_flight: Default::default(),
         }
     }
 
@@ -221,14 +223,17 @@ impl<Cx: ClientContext> ApiClient<Cx> {
     }
     #[inline]
     pub fn auth_vars_mut(&mut self) -> &mut Cx::AuthVars {
+        self.detach_in_flight();
         &mut self.auth_vars
     }
     #[inline]
     pub fn set_auth_vars(&mut self, auth_vars: Cx::AuthVars) {
+        self.detach_in_flight();
         self.auth_vars = auth_vars;
     }
     #[inline]
     pub fn update_auth_vars(&mut self, f: impl FnOnce(&mut Cx::AuthVars)) {
+        self.detach_in_flight();
         f(&mut self.auth_vars);
     }
 
@@ -327,6 +332,70 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         self
     }
 
+    #[inline]
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        self.runtime_state.clock()
+    }
+
+    #[inline]
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        Arc::make_mut(&mut self.runtime_state).set_clock(clock);
+    }
+
+    #[inline]
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        Arc::make_mut(&mut self.runtime_state).set_clock(clock);
+        self
+    }
+
+    #[inline]
+    pub fn metrics_recorder(&self) -> &Arc<dyn MetricsRecorder> {
+        self.runtime_state.metrics_recorder()
+    }
+
+    #[inline]
+    pub fn set_metrics_recorder(&mut self, recorder: Arc<dyn MetricsRecorder>) {
+        Arc::make_mut(&mut self.runtime_state).set_metrics_recorder(recorder);
+    }
+
+    #[inline]
+    pub fn with_metrics_recorder(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        Arc::make_mut(&mut self.runtime_state).set_metrics_recorder(recorder);
+        self
+    }
+
+    #[inline]
+    pub fn credential_store(&self) -> Option<&Arc<dyn CredentialStore>> {
+        self.runtime_state.credential_store()
+    }
+
+    #[inline]
+    pub fn set_credential_store(&mut self, store: Option<Arc<dyn CredentialStore>>) {
+        Arc::make_mut(&mut self.runtime_state).set_credential_store(store);
+    }
+
+    #[inline]
+    pub fn with_credential_store(mut self, store: Arc<dyn CredentialStore>) -> Self {
+        Arc::make_mut(&mut self.runtime_state).set_credential_store(Some(store));
+        self
+    }
+
+    #[inline]
+    pub fn response_cache(&self) -> Option<&Arc<dyn ResponseCache>> {
+        self.runtime_state.response_cache()
+    }
+
+    #[inline]
+    pub fn set_response_cache(&mut self, cache: Option<Arc<dyn ResponseCache>>) {
+        Arc::make_mut(&mut self.runtime_state).set_response_cache(cache);
+    }
+
+    #[inline]
+    pub fn with_response_cache(mut self, cache: Arc<dyn ResponseCache>) -> Self {
+        Arc::make_mut(&mut self.runtime_state).set_response_cache(Some(cache));
+        self
+    }
+
     #[inline]
     pub fn runtime_state(&self) -> &Arc<ClientRuntimeState> {
         &self.runtime_state
@@ -359,6 +428,12 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         let mut config = crate::runtime::RuntimeConfig {
             hooks: self.runtime_state.hooks().clone(),
             rate_limiter: self.runtime_state.rate_limiter().clone(),
+            clock: self.runtime_state.clock().clone(),
+            metrics: self.runtime_state.metrics_recorder().clone(),
+            credential_store: self.runtime_state.credential_store().cloned(),
+            response_cache: self.runtime_state.response_cache().cloned(),
+            circuit_breaker: self.runtime_state.circuit_breaker().cloned(),
+            backoff_retry: self.runtime_state.backoff_retry().cloned(),
             max_rate_limit_cooldown: self.runtime_state.max_rate_limit_cooldown(),
             pagination_detect_loops: self.pagination_detect_loops,
             debug: crate::runtime::DebugConfig {
@@ -368,6 +443,9 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             max_response_body_bytes: self.runtime_state.max_response_body_bytes(),
             max_request_body_bytes: self.runtime_state.max_request_body_bytes(),
             max_stream_response_body_bytes: self.runtime_state.max_stream_response_body_bytes(),
+            max_stream_event_bytes: self.runtime_state.max_stream_event_bytes(),
+            max_stream_line_bytes: self.runtime_state.max_stream_line_bytes(),
+            propagate_traceparent: self.runtime_state.propagate_traceparent(),
         };
         f(&mut config);
         self.debug_level = config.debug.level;
@@ -385,6 +463,12 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         PendingRequest::new(self, ep)
     }
 
+    /// Endpoint-matcher indices from an installed development executor.
+    #[cfg(any(test, feature = "dangerous-dev-tools"))]
+    pub(crate) fn development_endpoint_matches(&self, endpoint: &dyn std::any::Any) -> Vec<usize> {
+        self.managed_client.development_endpoint_matches(endpoint)
+    }
+
     #[inline]
     pub fn plan_context(&self) -> crate::__private::GeneratedPlanContext<'_, Cx> {
         crate::__private::GeneratedPlanContext::new(self.vars(), self.auth_vars())
@@ -124,6 +124,7 @@ impl<Cx: ClientContext> AuthHttpExecutor for ClientAuthHttpExecutor<'_, Cx> {
                     method,
                     idempotent: false,
                     page_index: 0,
+                    retry: 0,
                 };
 
                 let base_request = super::build::PublicRequestHead {
@@ -194,7 +195,9 @@ impl<Cx: ClientContext> AuthHttpExecutor for ClientAuthHttpExecutor<'_, Cx> {
                                 self.client.auth_vars(),
                                 auth_state_snapshot.as_ref(),
                                 self,
+                                self.client.clock().as_ref(),
                                 &base_request.meta,
+                                self.client.credential_store().map(|store| store.as_ref()),
                             )
                             .await
                         };
@@ -277,6 +280,24 @@ impl<Cx: ClientContext> AuthHttpExecutor for ClientAuthHttpExecutor<'_, Cx> {
     }
 }
 
+#[cfg(feature = "json")]
+impl<Cx: ClientContext> ApiClient<Cx> {
+    /// Redeems an OAuth2 authorization code through the client's provider
+    /// HTTP path, with the same TLS, timeout and body-limit handling as
+    /// credential providers.
+    pub async fn exchange_oauth2_authorization_code(
+        &self,
+        grant: &crate::auth::OAuth2AuthorizationCode,
+    ) -> Result<crate::auth::AccessToken, AuthError> {
+        grant
+            .exchange(
+                &ClientAuthHttpExecutor { client: self },
+                self.runtime_state.clock().as_ref(),
+            )
+            .await
+    }
+}
+
 fn auth_body_limit_error(error: crate::body::BodyError) -> AuthError {
     AuthError::new(
         AuthErrorKind::ResponseTooLarge,
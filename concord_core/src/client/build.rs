@@ -88,6 +88,8 @@ impl PublicRequestHead {
             auth_query_keys,
             #[cfg(any(test, feature = "dangerous-dev-tools"))]
             protected_header_names,
+            #[cfg(any(test, feature = "dangerous-dev-tools"))]
+            endpoint_matches: Vec::new(),
         };
         Ok(BuiltRequest {
             message,
@@ -252,6 +254,7 @@ mod tests {
                     accept: None,
                     no_content: false,
                     format: crate::codec::Format::Text,
+                    errors: Default::default(),
                 },
                 pagination: None,
             },
@@ -280,6 +283,7 @@ mod tests {
                     method: http::Method::GET,
                     idempotent: true,
                     page_index: 0,
+                    retry: 0,
                 },
             )
             .expect("header preparation");
@@ -329,6 +333,7 @@ mod tests {
                 method: http::Method::GET,
                 idempotent: true,
                 page_index: 0,
+                retry: 0,
             },
         );
         let Err(error) = result else {
@@ -352,6 +357,7 @@ mod tests {
                 method: http::Method::GET,
                 idempotent: true,
                 page_index: 0,
+                retry: 0,
             },
         );
         assert!(matches!(result, Err(ApiClientError::Auth { .. })));
@@ -374,6 +380,7 @@ mod tests {
                 method: http::Method::GET,
                 idempotent: true,
                 page_index: 0,
+                retry: 0,
             },
         );
         let err = match endpoint_error {
@@ -398,6 +405,7 @@ mod tests {
                 method: http::Method::GET,
                 idempotent: true,
                 page_index: 0,
+                retry: 0,
             },
         );
         let err = match client_error {
@@ -467,6 +475,7 @@ mod tests {
                 method: http::Method::POST,
                 idempotent: false,
                 page_index: 0,
+                retry: 0,
             },
             url: "https://example.com/items".parse().expect("url"),
             headers,
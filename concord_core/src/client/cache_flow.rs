@@ -0,0 +1,142 @@
+// Client lifecycle phase modules intentionally share one private parent namespace.
+use super::*;
+use std::time::SystemTime;
+
+/// Per-execution response-cache state for a buffered `GET`/`HEAD` plan whose
+/// endpoint opted in and whose client has a cache backend configured.
+pub(super) struct CacheSession {
+    cache: Arc<dyn ResponseCache>,
+    key: CacheKey,
+    policy: crate::cache::CachePolicy,
+    request_headers: http::HeaderMap,
+    meta: RequestExecutionMeta,
+    url: url::Url,
+    entry: Option<CachedResponse>,
+}
+
+impl CacheSession {
+    /// Loads the stored entry for this request's variant, if any.
+    pub(super) async fn load(&mut self) {
+        self.entry = self
+            .cache
+            .get(&self.key)
+            .await
+            .filter(|entry| entry.matches_vary(&self.request_headers));
+    }
+
+    /// A stored response that is still fresh, ready for decoding. Freshness
+    /// is checked against the wall clock.
+    pub(super) fn fresh_response(&self, rate_limit: &RateLimitPlan) -> Option<BuiltResponse> {
+        let entry = self.entry.as_ref()?;
+        entry
+            .is_fresh(SystemTime::now())
+            .then(|| self.built_from(entry, self.meta.clone(), self.url.clone(), rate_limit))
+    }
+
+    /// Conditional headers for a stale entry; empty when it has no validators.
+    pub(super) fn validators(&self) -> Vec<(http::HeaderName, http::HeaderValue)> {
+        self.entry
+            .as_ref()
+            .map(CachedResponse::validators)
+            .unwrap_or_default()
+    }
+
+    /// Completes the execution with the origin response: a `304` is answered
+    /// from the refreshed entry, a storable `200` replaces it.
+    pub(super) async fn finish(self, resp: BuiltResponse) -> BuiltResponse {
+        let now = SystemTime::now();
+        if resp.status() == StatusCode::NOT_MODIFIED
+            && let Some(entry) = self.entry.clone()
+        {
+            let refreshed = entry.refresh(resp.headers(), now, &self.policy);
+            let (_, context) = resp.into_parts();
+            let built = self.built_from(
+                &refreshed,
+                context.meta,
+                context.logical_url,
+                &context.rate_limit,
+            );
+            self.cache.put(self.key, refreshed).await;
+            return built.with_cache_status(CacheStatus::Revalidated);
+        }
+        if resp.status() == StatusCode::OK
+            && let Some(fresh_for) =
+                crate::cache::freshness_lifetime(resp.headers(), now, self.policy.default_max_age())
+        {
+            let vary = crate::cache::vary_names(resp.headers())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|name| {
+                    let value = self.request_headers.get(&name).cloned();
+                    (name, value)
+                })
+                .collect();
+            let entry = CachedResponse::new(
+                resp.status(),
+                resp.headers().clone(),
+                resp.body().clone(),
+                now,
+                fresh_for,
+            )
+            .with_vary(vary);
+            if fresh_for > std::time::Duration::ZERO || entry.has_validators() {
+                self.cache.put(self.key, entry).await;
+            } else {
+                self.cache.remove(&self.key).await;
+            }
+        } else if resp.status() == StatusCode::OK {
+            self.cache.remove(&self.key).await;
+        }
+        resp.with_cache_status(CacheStatus::Miss)
+    }
+
+    fn built_from(
+        &self,
+        entry: &CachedResponse,
+        meta: RequestExecutionMeta,
+        url: url::Url,
+        rate_limit: &RateLimitPlan,
+    ) -> BuiltResponse {
+        let mut message = http::Response::new(entry.body().clone());
+        *message.status_mut() = entry.status();
+        *message.headers_mut() = entry.headers().clone();
+        BuiltResponse::from_http(message, meta, url, rate_limit.clone())
+    }
+}
+
+impl<Cx: ClientContext> ApiClient<Cx> {
+    /// Opens a cache session when the plan, method, and client all allow it.
+    /// The key is the logical URL, resolved before authentication placement,
+    /// so authenticated endpoints are only cached when marked shared.
+    /// The stored entry is loaded separately by [`CacheSession::load`].
+    pub(super) fn open_cache_session(
+        &self,
+        plan: &crate::endpoint::RequestPlanView,
+        body: &crate::io::PreparedBody,
+    ) -> Result<Option<CacheSession>, ApiClientError> {
+        let Some(policy) = plan.endpoint.policy.cache else {
+            return Ok(None);
+        };
+        if !policy.is_shared() && !plan.endpoint.policy.auth.requirements.is_empty() {
+            return Ok(None);
+        }
+        let Some(cache) = self.response_cache().cloned() else {
+            return Ok(None);
+        };
+        let method = &plan.endpoint.meta.method;
+        if *method != http::Method::GET && *method != http::Method::HEAD {
+            return Ok(None);
+        }
+        let meta = plan.endpoint.meta.request_meta(plan.overrides.page_index);
+        let head = self.resolve_public_request_head(plan, body, meta)?;
+        Ok(Some(CacheSession {
+            cache,
+            key: CacheKey::new(method, &head.url),
+            policy,
+            request_headers: head.headers,
+            meta: head.meta,
+            url: head.url,
+            entry: None,
+        }))
+    }
+}
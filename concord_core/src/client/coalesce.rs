@@ -0,0 +1,120 @@
+// Client lifecycle phase modules intentionally share one private parent namespace.
+use super::*;
+use crate::endpoint::RequestIdentity;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use tokio::sync::oneshot;
+
+type SharedOutcome = Result<BuiltResponse, ApiClientError>;
+
+/// One coalesced execution: the logical request identity plus whether the
+/// response body is read.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(super) struct CoalesceKey {
+    identity: RequestIdentity,
+    skip_body: bool,
+}
+
+/// Buffered executions currently in flight for coalescing endpoints.
+///
+/// Clones of a client share one registry, like they share authentication
+/// state. A clone that replaces its authentication variables detaches onto a
+/// fresh registry so calls made with different credentials never coalesce.
+#[derive(Default)]
+pub(super) struct InFlightRequests {
+    waiters: Mutex<HashMap<CoalesceKey, Vec<oneshot::Sender<SharedOutcome>>>>,
+}
+
+pub(super) enum Flight<'a> {
+    /// No identical call is in flight; this caller executes and publishes.
+    Leader(FlightLeader<'a>),
+    /// Resolves with the leader's outcome, or closes if the leader is dropped.
+    Follower(oneshot::Receiver<SharedOutcome>),
+}
+
+impl InFlightRequests {
+    pub(super) fn join(&self, key: CoalesceKey) -> Flight<'_> {
+        match self.lock().entry(key) {
+            Entry::Occupied(mut entry) => {
+                let (tx, rx) = oneshot::channel();
+                entry.get_mut().push(tx);
+                Flight::Follower(rx)
+            }
+            Entry::Vacant(entry) => {
+                let key = entry.key().clone();
+                entry.insert(Vec::new());
+                Flight::Leader(FlightLeader {
+                    registry: self,
+                    key: Some(key),
+                })
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, HashMap<CoalesceKey, Vec<oneshot::Sender<SharedOutcome>>>> {
+        self.waiters.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Registration of the executing caller. Dropping it without
+/// [`FlightLeader::complete`] (a cancelled leader) closes every follower's
+/// receiver so they can retry on their own.
+pub(super) struct FlightLeader<'a> {
+    registry: &'a InFlightRequests,
+    key: Option<CoalesceKey>,
+}
+
+impl FlightLeader<'_> {
+    /// Unregisters the call and hands each follower its own copy of the
+    /// outcome. Callers arriving after this start a new execution.
+    pub(super) fn complete(mut self, outcome: &SharedOutcome) {
+        let Some(key) = self.key.take() else {
+            return;
+        };
+        let followers = self.registry.lock().remove(&key).unwrap_or_default();
+        for follower in followers {
+            let shared = match outcome {
+                Ok(resp) => Ok(resp.duplicate()),
+                Err(err) => Err(err.share()),
+            };
+            let _ = follower.send(shared);
+        }
+    }
+}
+
+impl Drop for FlightLeader<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.registry.lock().remove(&key);
+        }
+    }
+}
+
+impl<Cx: ClientContext> ApiClient<Cx> {
+    pub(super) fn detach_in_flight(&mut self) {
+        self.in_flight = Arc::default();
+    }
+
+    /// The coalescing key for a canonical plan, or `None` when the endpoint
+    /// did not opt in or the method is not `GET`/`HEAD`. Client API headers
+    /// are part of the identity because they reach the origin request.
+    pub(super) fn coalesce_key(
+        &self,
+        plan: &crate::endpoint::RequestPlanView,
+        skip_body: bool,
+    ) -> Option<CoalesceKey> {
+        let method = &plan.endpoint.meta.method;
+        if !plan.endpoint.policy.coalesce
+            || (*method != http::Method::GET && *method != http::Method::HEAD)
+        {
+            return None;
+        }
+        let mut identity = RequestIdentity::of(&plan.endpoint);
+        identity.extend_headers(&self.api_headers);
+        Some(CoalesceKey {
+            identity,
+            skip_body,
+        })
+    }
+}
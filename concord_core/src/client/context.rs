@@ -38,6 +38,9 @@ pub(super) struct SendClassifyCtx<'a> {
     pub(super) url_str: &'a str,
     pub(super) error_ctx: &'a ErrorContext,
     pub(super) auth_materials: &'a [crate::auth::AuthTransportMaterial],
+    /// Notified once the request has its rate-limit permit and is handed to
+    /// the transport.
+    pub(super) on_wire: Option<&'a tokio::sync::Notify>,
 }
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -95,6 +98,7 @@ pub(super) struct ResponseObservationCtx<'a> {
     pub(super) url_host: Option<&'a str>,
     pub(super) page_index: u32,
     pub(super) idempotent: bool,
+    pub(super) retry: u32,
     pub(super) plan: &'a RateLimitPlan,
     pub(super) status: StatusCode,
     pub(super) headers: &'a http::HeaderMap,
@@ -1,7 +1,9 @@
 // Client lifecycle phase modules intentionally share one private parent namespace.
 use super::build::PublicRequestHead;
+use super::coalesce::Flight;
 use super::send_flow::ObservedExecutionResponse;
 use super::*;
+use crate::telemetry::{ExecutionKind, ExecutionTrace};
 
 enum AuthRejectionStep {
     Retry,
@@ -25,6 +27,7 @@ struct ChallengeTerminalStatusCtx<'a> {
     dbg_verbose: bool,
     url_str: &'a str,
     error_ctx: &'a ErrorContext,
+    errors: &'a crate::endpoint::ErrorResponsePlan,
 }
 
 enum RecoverableChallengeStep {
@@ -70,6 +73,9 @@ enum ExecutionFamily {
     Buffered {
         skip_body: bool,
         response_limit: Option<usize>,
+        /// A cached entry is being revalidated, so `304 Not Modified` is a
+        /// successful outcome rather than a status error.
+        revalidating: bool,
     },
     Stream {
         response_limit: Option<usize>,
@@ -272,8 +278,10 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                         self.auth_vars(),
                         ctx.auth_state,
                         ctx.auth_http,
+                        self.clock().as_ref(),
                         ctx.response_meta,
                         ctx.status,
+                        self.credential_store().map(|store| store.as_ref()),
                     )
                     .await
                 }
@@ -287,6 +295,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                         ctx.auth_state,
                         ctx.response_meta,
                         ctx.status,
+                        self.credential_store().map(|store| store.as_ref()),
                     )
                     .await
                 }
@@ -313,7 +322,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         }
     }
 
-    fn release_challenged_response(
+    async fn release_challenged_response(
         &self,
         observed: ObservedExecutionResponse,
         intent: &AuthResendIntent,
@@ -347,31 +356,43 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             response,
             rate_limit_action,
         } = observed;
-        let terminal = terminal_status.map(|terminal| {
-            if terminal.dbg_verbose {
-                self.debug_sink.response_status(
-                    terminal.dbg,
-                    response.status(),
-                    terminal.url_str,
-                    false,
-                );
-                self.debug_sink.response_headers(
-                    terminal.dbg,
-                    crate::debug::SanitizedHeaders::new(response.headers()),
-                );
+        // Releasing the native response here is the single challenged-response
+        // transition, and it always precedes credential mutation. Recovery
+        // drops the response unread; a terminal status reads only the bounded
+        // error body its `on_error` mapping asks for.
+        let terminal = match terminal_status {
+            Some(terminal) => {
+                if terminal.dbg_verbose {
+                    self.debug_sink.response_status(
+                        terminal.dbg,
+                        response.status(),
+                        terminal.url_str,
+                        false,
+                    );
+                    self.debug_sink.response_headers(
+                        terminal.dbg,
+                        crate::debug::SanitizedHeaders::new(response.headers()),
+                    );
+                }
+                let status = response.status();
+                let headers = Box::new(crate::redaction::sanitize_header_map(response.headers()));
+                let body = self
+                    .decode_error_body(response, terminal.errors, terminal.error_ctx)
+                    .await;
+                Some(ApiClientError::HttpStatus {
+                    ctx: terminal.error_ctx.clone(),
+                    status,
+                    headers,
+                    rate_limit: (!matches!(rate_limit_action, RateLimitResponseAction::Continue))
+                        .then_some(Box::new(rate_limit_action)),
+                    body,
+                })
             }
-            ApiClientError::HttpStatus {
-                ctx: terminal.error_ctx.clone(),
-                status: response.status(),
-                headers: Box::new(crate::redaction::sanitize_header_map(response.headers())),
-                rate_limit: (!matches!(rate_limit_action, RateLimitResponseAction::Continue))
-                    .then_some(Box::new(rate_limit_action)),
+            None => {
+                drop(response);
+                None
             }
-        });
-
-        // Dropping the native response here is the single challenged-response
-        // transition. No body frame is polled before credential mutation.
-        drop(response);
+        };
 
         #[cfg(any(test, feature = "dangerous-dev-tools"))]
         for target in matched_targets {
@@ -454,9 +475,35 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         // Request-local auth preparation cache, reused for the recovery unless
         // the challenge handling asked for a refreshed credential state.
         let mut cached_auth_preparation: Option<CachedAuthPreparation> = None;
+        // Opt-in backoff retries of `429`/`503`, each a further visible
+        // execution of this call.
+        let mut backoff = self.runtime_state.backoff_retry().map(|retry| {
+            retry.start(
+                self.runtime_state.clock().as_ref(),
+                self.runtime_state.max_rate_limit_cooldown(),
+            )
+        });
+        // Set when the previous response is retried; the delay runs after
+        // that response has been released.
+        let mut pending_backoff: Option<std::time::Duration> = None;
 
         loop {
-            let meta = plan.endpoint.meta.request_meta(plan.overrides.page_index);
+            if let Some(delay) = pending_backoff.take() {
+                self.runtime_state.clock().sleep(delay).await;
+            }
+            let mut meta = plan.endpoint.meta.request_meta(plan.overrides.page_index);
+            meta.retry = backoff.as_ref().map_or(0, |backoff| backoff.retries());
+            let execution = ExecutionTrace::open(
+                &meta,
+                if pending_auth.is_some() {
+                    ExecutionKind::AuthRecovery
+                } else if meta.retry > 0 {
+                    ExecutionKind::BackoffRetry
+                } else {
+                    ExecutionKind::Initial
+                },
+                &plan.overrides.trace,
+            );
             let mut head = self.resolve_public_request_head(plan, body, meta)?;
             let auth_plan = match auth_placement_plan.as_ref() {
                 Some(existing) => existing,
@@ -488,8 +535,8 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             if let Some(intent) = pending_auth.take() {
                 // pending_auth is only ever set for a rebuildable body, so the
                 // credential refresh below always has a reconstructable request.
-                let step = self
-                    .apply_auth_rejection_step(AuthRejectionStepCtx {
+                let step = execution
+                    .run(self.apply_auth_rejection_step(AuthRejectionStepCtx {
                         plan,
                         auth_state: auth_state_snapshot,
                         auth_http: &auth_http,
@@ -499,7 +546,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                         status: intent.status,
                         error_ctx: &intent.error_ctx,
                         auth_rebuildable,
-                    })
+                    }))
                     .await?;
                 match step {
                     AuthRejectionStep::Retry => {
@@ -510,7 +557,8 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             }
             let auth_preparation = if cached_auth_preparation.is_none() {
                 Some(
-                    self.prepare_auth(plan, auth_state_snapshot, &auth_http, &head)
+                    execution
+                        .run(self.prepare_auth(plan, auth_state_snapshot, &auth_http, &head))
                         .await?,
                 )
             } else {
@@ -528,18 +576,45 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                 prepared
             };
             let execution_body = self.produce_execution_body(body, &ctx)?;
-            let built = head.finish(&self.managed_client.client, execution_body, &ctx)?;
+            let mut built = head.finish(&self.managed_client.client, execution_body, &ctx)?;
+            #[cfg(any(test, feature = "dangerous-dev-tools"))]
+            built
+                .context
+                .endpoint_matches
+                .clone_from(&plan.overrides.endpoint_matches);
+            if self.runtime_state.propagate_traceparent() {
+                execution.inject_traceparent(built.message.headers_mut());
+            }
             let url_str = built.debug_url();
+            execution.record_request(&url_str, built.message.headers());
             let send_ctx = SendClassifyCtx {
                 dbg,
                 url_str: &url_str,
                 error_ctx: &ctx,
                 auth_materials: &auth_attempt.materials,
+                on_wire: None,
+            };
+            let hedge_after = plan.endpoint.policy.hedge_after.filter(|_| {
+                let meta = &built.context().meta;
+                meta.method == http::Method::GET && meta.idempotent
+            });
+            let send_result = match hedge_after {
+                Some(delay) => {
+                    execution
+                        .run(self.send_and_observe_hedged(built, send_ctx, delay))
+                        .await
+                }
+                None => {
+                    execution
+                        .run(self.send_and_observe_once(built, send_ctx))
+                        .await
+                }
             };
-            let send_result = self.send_and_observe_once(built, send_ctx).await;
 
             match send_result {
                 Ok(observed) => {
+                    execution
+                        .record_response(observed.response.status(), observed.response.headers());
                     let response_status = observed.response.status();
                     let response_meta = &observed.response.context.meta;
                     let response_headers = observed.response.headers();
@@ -574,39 +649,50 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                                             dbg_verbose,
                                             url_str: &url_str,
                                             error_ctx: &ctx,
+                                            errors: &plan.endpoint.response.errors,
                                         }),
                                     )
+                                    .await
                                     .expect("terminal challenge status must be captured");
                                 // Rebuildability controls only whether another
                                 // visible execution is possible. The rejected
                                 // applied generation must still be invalidated
                                 // before returning its original status path.
-                                self.invalidate_auth_challenge_only(
-                                    plan,
-                                    auth_state_snapshot,
-                                    &auth_http,
-                                    &intent,
-                                    auth_rebuildable,
-                                )
-                                .await?;
+                                execution
+                                    .run(self.invalidate_auth_challenge_only(
+                                        plan,
+                                        auth_state_snapshot,
+                                        &auth_http,
+                                        &intent,
+                                        auth_rebuildable,
+                                    ))
+                                    .await?;
                                 return Err(terminal);
                             } else {
                                 match Self::recoverable_challenge_step(&mut auth_recovery, intent) {
                                     RecoverableChallengeStep::Recover(intent) => {
-                                        self.release_challenged_response(observed, &intent, None);
+                                        self.runtime_state.metrics_recorder().auth_recovery(
+                                            MetricsKey {
+                                                endpoint: intent.response_meta.endpoint,
+                                                method: &intent.response_meta.method,
+                                            },
+                                        );
+                                        self.release_challenged_response(observed, &intent, None)
+                                            .await;
                                         pending_auth = Some(intent);
                                         continue;
                                     }
                                     RecoverableChallengeStep::InvalidateAndFail(intent) => {
-                                        self.release_challenged_response(observed, &intent, None);
-                                        let terminal = self
-                                            .invalidate_exhausted_auth_challenge(
+                                        self.release_challenged_response(observed, &intent, None)
+                                            .await;
+                                        let terminal = execution
+                                            .run(self.invalidate_exhausted_auth_challenge(
                                                 plan,
                                                 auth_state_snapshot,
                                                 &auth_http,
                                                 &intent,
                                                 auth_rebuildable,
-                                            )
+                                            ))
                                             .await?;
                                         return Err(terminal);
                                     }
@@ -625,9 +711,10 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                                     .lifecycle_observation_targets
                                     .clone(),
                             };
-                            self.release_challenged_response(observed, &intent, None);
-                            match self
-                                .apply_auth_rejection_step(AuthRejectionStepCtx {
+                            self.release_challenged_response(observed, &intent, None)
+                                .await;
+                            match execution
+                                .run(self.apply_auth_rejection_step(AuthRejectionStepCtx {
                                     plan,
                                     auth_state: auth_state_snapshot,
                                     auth_http: &auth_http,
@@ -637,7 +724,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                                     status: response_status,
                                     error_ctx: &ctx,
                                     auth_rebuildable,
-                                })
+                                }))
                                 .await?
                             {
                                 AuthRejectionStep::Fail(err) => return Err(err),
@@ -648,24 +735,51 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                         }
                         None => {}
                     }
+                    // A retried body must be rebuilt exactly like an
+                    // authentication recovery body.
+                    if auth_rebuildable
+                        && let Some(backoff) = backoff.as_mut()
+                        && let Some(delay) = backoff.next_delay(
+                            response_status,
+                            observed.response.context.meta.idempotent,
+                            crate::rate_limit::parse_retry_after(observed.response.headers()),
+                        )
+                    {
+                        pending_backoff = Some(delay);
+                        continue;
+                    }
                     // Both buffered and streaming families perform terminal
                     // status classification only after authentication has
                     // inspected the unconsumed response head. Hooks and
                     // rate-limit feedback were already run exactly once by
                     // `send_and_observe_once`.
                     let emit_success_debug = matches!(family, ExecutionFamily::Stream { .. });
-                    let resp = self.classify_observed_transport_response(
-                        observed,
-                        dbg,
-                        dbg_verbose,
-                        &url_str,
-                        &ctx,
-                        emit_success_debug,
-                    )?;
+                    let not_modified = matches!(
+                        family,
+                        ExecutionFamily::Buffered {
+                            revalidating: true,
+                            ..
+                        }
+                    ) && response_status == StatusCode::NOT_MODIFIED;
+                    let resp = if not_modified {
+                        observed.response
+                    } else {
+                        self.classify_observed_transport_response(
+                            observed,
+                            &plan.endpoint.response.errors,
+                            dbg,
+                            dbg_verbose,
+                            &url_str,
+                            &ctx,
+                            emit_success_debug,
+                        )
+                        .await?
+                    };
                     let resp = match family {
                         ExecutionFamily::Buffered {
                             skip_body,
                             response_limit,
+                            ..
                         } => {
                             let limit = (!skip_body).then_some(response_limit).flatten();
                             ExecutionTransportSuccess::Buffered(Self::limit_response_body(
@@ -685,6 +799,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                 Err(err) => {
                     // No general retry: Reqwest owns any hidden resend, so a
                     // transport or observation failure is terminal here.
+                    execution.record_error(&err);
                     return Err(err);
                 }
             }
@@ -698,34 +813,13 @@ impl<Cx: ClientContext> ApiClient<Cx> {
     where
         C: crate::codec::ResponseCodec,
     {
-        let (plan, mut body) = into_canonical_request_plan_view(plan);
         let ctx = ErrorContext {
             endpoint: plan.endpoint.meta.name,
             method: plan.endpoint.meta.method.clone(),
         };
-        let dbg = plan
-            .overrides
-            .debug_level
-            .unwrap_or_else(|| self.debug_level());
-        let resp = match self
-            .drive_executions(
-                &plan,
-                &mut body,
-                ctx.clone(),
-                dbg,
-                ExecutionFamily::Buffered {
-                    skip_body: plan.endpoint.response.no_content,
-                    response_limit: self.runtime_state.max_response_body_bytes(),
-                },
-            )
-            .await?
-        {
-            ExecutionTransportSuccess::Buffered(resp) => resp,
-            _ => unreachable!(),
-        };
-        let resp = Self::buffer_response(resp, plan.endpoint.response.no_content, &ctx).await?;
-        self.debug_planned_response(dbg, &resp, resp.url().as_str());
-        Self::decode_planned_response::<C>(&plan, resp, ctx.clone())
+        let no_content = plan.endpoint.response.no_content;
+        let resp = self.execute_plan_raw_with_body(plan, no_content).await?;
+        Self::decode_planned_response::<C>(no_content, resp, ctx)
     }
     pub(crate) async fn execute_plan_raw(
         &self,
@@ -746,15 +840,60 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         plan: RequestPlan,
         skip_body: bool,
     ) -> Result<BuiltResponse, ApiClientError> {
-        let (plan, mut body) = into_canonical_request_plan_view(plan);
-        let dbg = plan
-            .overrides
-            .debug_level
-            .unwrap_or_else(|| self.debug_level());
+        let (mut plan, body) = into_canonical_request_plan_view(plan);
         let ctx = ErrorContext {
             endpoint: plan.endpoint.meta.name,
             method: plan.endpoint.meta.method.clone(),
         };
+        crate::idempotency::attach_key(&mut plan, &ctx)?;
+        let Some(key) = self.coalesce_key(&plan, skip_body) else {
+            return self.execute_buffered_view(plan, body, skip_body, ctx).await;
+        };
+        loop {
+            match self.in_flight.join(key.clone()) {
+                Flight::Leader(leader) => {
+                    let outcome = self.execute_buffered_view(plan, body, skip_body, ctx).await;
+                    leader.complete(&outcome);
+                    return outcome;
+                }
+                Flight::Follower(shared) => {
+                    // A closed channel means the leader was cancelled; join
+                    // again and possibly execute this call itself.
+                    if let Ok(outcome) = shared.await {
+                        return outcome;
+                    }
+                }
+            }
+        }
+    }
+
+    async fn execute_buffered_view(
+        &self,
+        mut plan: crate::endpoint::RequestPlanView,
+        mut body: crate::io::PreparedBody,
+        skip_body: bool,
+        ctx: ErrorContext,
+    ) -> Result<BuiltResponse, ApiClientError> {
+        let dbg = plan
+            .overrides
+            .debug_level
+            .unwrap_or_else(|| self.debug_level());
+        let mut cache = self.open_cache_session(&plan, &body)?;
+        if let Some(session) = cache.as_mut() {
+            session.load().await;
+            if let Some(resp) = session.fresh_response(&plan.endpoint.policy.rate_limit) {
+                self.debug_planned_response(dbg, &resp, resp.url().as_str());
+                return Ok(resp.with_cache_status(CacheStatus::Hit));
+            }
+        }
+        let validators = cache
+            .as_ref()
+            .map(|session| session.validators())
+            .unwrap_or_default();
+        let revalidating = !validators.is_empty();
+        for (name, value) in validators {
+            plan.endpoint.policy.headers.insert(name, value);
+        }
         let resp = match self
             .drive_executions(
                 &plan,
@@ -764,6 +903,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                 ExecutionFamily::Buffered {
                     skip_body,
                     response_limit: self.runtime_state.max_response_body_bytes(),
+                    revalidating,
                 },
             )
             .await?
@@ -771,7 +911,21 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             ExecutionTransportSuccess::Buffered(resp) => resp,
             _ => unreachable!(),
         };
+        let reading = Instant::now();
         let resp = Self::buffer_response(resp, skip_body, &ctx).await?;
+        if !skip_body {
+            self.runtime_state.metrics_recorder().body_read(
+                MetricsKey {
+                    endpoint: ctx.endpoint,
+                    method: &ctx.method,
+                },
+                reading.elapsed(),
+            );
+        }
+        let resp = match cache {
+            Some(session) => session.finish(resp).await,
+            None => resp,
+        };
         self.debug_planned_response(dbg, &resp, resp.url().as_str());
         Ok(resp)
     }
@@ -797,7 +951,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                     .map_err(|_| ApiClientError::invalid_param(ctx.clone(), "content_type"))?,
             );
         }
-        let (plan, mut body) = into_canonical_request_plan_view(RequestPlan {
+        let (mut plan, mut body) = into_canonical_request_plan_view(RequestPlan {
             endpoint,
             body,
             overrides,
@@ -814,6 +968,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                 msg: "stream responses cannot use a no-content response plan",
             });
         }
+        crate::idempotency::attach_key(&mut plan, &ctx)?;
         let dbg = plan
             .overrides
             .debug_level
@@ -834,13 +989,15 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             ExecutionTransportSuccess::Transport(resp) => resp,
             _ => unreachable!(),
         };
-        if !Self::header_matches_media_type(resp.headers().get(CONTENT_TYPE), M::CONTENT_TYPE) {
+        if !Self::header_matches_media_type::<M>(resp.headers().get(CONTENT_TYPE)) {
             return Err(ApiClientError::response_contract(
                 ctx,
                 "stream response content type did not match expected media type",
             ));
         }
-        Ok(crate::stream_response::StreamResponse::new(resp))
+        Ok(crate::stream_response::StreamResponse::new(resp)
+            .with_event_limit(self.runtime_state.max_stream_event_bytes())
+            .with_line_limit(self.runtime_state.max_stream_line_bytes()))
     }
     async fn prepare_auth_plan(
         &self,
@@ -871,7 +1028,9 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                 self.auth_vars(),
                 auth_state,
                 executor,
+                self.clock().as_ref(),
                 &auth_meta,
+                self.credential_store().map(|store| store.as_ref()),
             )
             .await
             .map_err(|source| ApiClientError::Auth {
@@ -921,14 +1080,14 @@ impl<Cx: ClientContext> ApiClient<Cx> {
     }
 
     fn decode_planned_response<C>(
-        plan: &crate::endpoint::RequestPlanView,
+        plan_no_content: bool,
         resp: BuiltResponse,
         ctx: ErrorContext,
     ) -> Result<DecodedResponse<C::Value>, ApiClientError>
     where
         C: crate::codec::ResponseCodec,
     {
-        let no_content = plan.endpoint.response.no_content || C::is_no_content();
+        let no_content = plan_no_content || C::is_no_content();
         if resp.meta().method == http::Method::HEAD && !no_content {
             return Err(ApiClientError::HeadRequiresNoContent { ctx });
         }
@@ -968,6 +1127,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             status,
             headers: parts.headers,
             value,
+            cache: response_context.cache,
         })
     }
 
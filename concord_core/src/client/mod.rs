@@ -1,10 +1,15 @@
 use crate::auth::{
     AuthError, AuthErrorKind, AuthHttpExecutor, AuthHttpRequest, AuthHttpResponse, AuthMode,
+    CredentialStore,
 };
+use crate::cache::{CacheKey, CacheStatus, CachedResponse, ResponseCache};
+use crate::circuit_breaker::CircuitOutcome;
+use crate::clock::Clock;
 use crate::debug::{DebugLevel, DebugSink, StderrDebugSink};
 use crate::endpoint::RequestPlan;
 use crate::error::{ApiClientError, ErrorContext};
 use crate::execution_meta::RequestExecutionMeta;
+use crate::metrics::{MetricsKey, MetricsRecorder};
 use crate::rate_limit::{
     RateLimitContext, RateLimitPlan, RateLimitResponseAction, RateLimitResponseContext, RateLimiter,
 };
@@ -21,11 +26,14 @@ use http::header::CONTENT_TYPE;
 use http::uri::Scheme;
 use std::sync::Arc;
 use std::sync::RwLock;
+use std::time::Instant;
 
 // Request lifecycle is kept in phase modules while preserving one private client namespace.
 mod api;
 mod auth_http;
 mod build;
+mod cache_flow;
+mod coalesce;
 mod context;
 mod execute;
 mod send_flow;
@@ -14,6 +14,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             url: ctx.url,
             page_index: ctx.page_index,
             idempotent: ctx.idempotent,
+            retry: ctx.retry,
         };
         self.runtime_state
             .hooks()
@@ -23,6 +24,13 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                 headers: crate::debug::SanitizedHeaders::new(ctx.headers),
             })
             .await;
+        self.runtime_state.metrics_recorder().response_status(
+            MetricsKey {
+                endpoint: ctx.endpoint,
+                method: ctx.method,
+            },
+            ctx.status,
+        );
     }
 
     pub(super) async fn acquire_rate_limit_and_send(
@@ -32,6 +40,22 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         stream_request_limit: Option<usize>,
     ) -> Result<ExecutionResponse, ApiClientError> {
         let request_context = built.context();
+        // An open circuit fails before the limiter so it never spends a permit.
+        let circuit = self
+            .runtime_state
+            .circuit_breaker()
+            .map(|breaker| {
+                breaker.admit(
+                    request_context.meta.endpoint,
+                    &request_context.logical_url,
+                    self.runtime_state.clock().as_ref(),
+                )
+            })
+            .transpose()
+            .map_err(|retry_after| ApiClientError::CircuitOpen {
+                ctx: send_ctx.error_ctx.clone(),
+                retry_after,
+            })?;
         let rate_limit_meta = RateLimitContext {
             endpoint: request_context.meta.endpoint,
             method: &request_context.meta.method,
@@ -40,21 +64,30 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             page_index: request_context.meta.page_index,
             idempotent: request_context.meta.idempotent,
             max_cooldown: self.runtime_state.max_rate_limit_cooldown(),
+            clock: self.runtime_state.clock(),
             plan: &built.rate_limit,
         };
-        let _permit = self
+        let metrics_key = MetricsKey {
+            endpoint: request_context.meta.endpoint,
+            method: &request_context.meta.method,
+        };
+        let waiting = Instant::now();
+        let guard = self
             .runtime_state
             .rate_limiter()
-            .acquire(rate_limit_meta)
-            .await
-            .map_err(|err| {
-                wrap_rate_limit_error(
-                    send_ctx.error_ctx.clone(),
-                    crate::rate_limit::RateLimitErrorKind::AcquireFailed,
-                    "rate-limit acquire failed",
-                    err,
-                )
-            })?;
+            .acquire_guarded(rate_limit_meta)
+            .await;
+        self.runtime_state
+            .metrics_recorder()
+            .rate_limit_wait(metrics_key, waiting.elapsed());
+        let guard = guard.map_err(|err| {
+            wrap_rate_limit_error(
+                send_ctx.error_ctx.clone(),
+                crate::rate_limit::RateLimitErrorKind::AcquireFailed,
+                "rate-limit acquire failed",
+                err,
+            )
+        })?;
         if let Some(limit) = stream_request_limit {
             let hint = built
                 .message
@@ -81,6 +114,13 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                 };
                 let request_context = built.context();
                 let method = request_context.meta.method.clone();
+                self.runtime_state.metrics_recorder().request_error(
+                    MetricsKey {
+                        endpoint: request_context.meta.endpoint,
+                        method: &method,
+                    },
+                    terminal_error.category(),
+                );
                 self.runtime_state
                     .hooks()
                     .request_error(RequestErrorHookContext {
@@ -90,6 +130,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                             url: send_ctx.url_str,
                             page_index: request_context.meta.page_index,
                             idempotent: request_context.meta.idempotent,
+                            retry: request_context.meta.retry,
                         },
                         category: terminal_error.category(),
                     })
@@ -114,6 +155,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             url: send_ctx.url_str,
             page_index: request_context.meta.page_index,
             idempotent: request_context.meta.idempotent,
+            retry: request_context.meta.retry,
         };
         self.runtime_state
             .hooks()
@@ -122,13 +164,28 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                 headers: crate::debug::SanitizedHeaders::new(built.message.headers()),
             })
             .await?;
-        self.send_built_request(
-            built,
-            send_ctx.url_str,
-            send_ctx.auth_materials,
-            send_ctx.error_ctx,
-        )
-        .await
+        if let Some(on_wire) = send_ctx.on_wire {
+            on_wire.notify_one();
+        }
+        let mut result = self
+            .send_built_request(
+                built,
+                send_ctx.url_str,
+                send_ctx.auth_materials,
+                send_ctx.error_ctx,
+            )
+            .await;
+        if let Some(ticket) = circuit {
+            ticket.settle(
+                CircuitOutcome::of(&result),
+                self.runtime_state.clock().as_ref(),
+            );
+        }
+        // Concurrency slots stay taken while the response body is in flight.
+        if let Ok(response) = result.as_mut() {
+            response.body_mut().hold_rate_limit_guard(guard);
+        }
+        result
     }
 
     pub(super) async fn observe_rate_limit_response(
@@ -144,6 +201,7 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             page_index: ctx.page_index,
             idempotent: ctx.idempotent,
             max_cooldown: self.runtime_state.max_rate_limit_cooldown(),
+            clock: self.runtime_state.clock(),
             plan: ctx.plan,
         };
         self.runtime_state
@@ -177,10 +235,12 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         let method = request_context.meta.method.clone();
         let page_index = request_context.meta.page_index;
         let idempotent = request_context.meta.idempotent;
+        let retry = request_context.meta.retry;
         let response_context = crate::transport::ResponseContext {
             meta: request_context.meta.clone(),
             logical_url: request_context.logical_url.clone(),
             rate_limit: built.rate_limit.clone(),
+            cache: CacheStatus::Bypass,
         };
         let BuiltRequest {
             message,
@@ -198,12 +258,20 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         // One visible execution: exactly one call to reqwest::Client::execute.
         // Reqwest owns any hidden protocol/status resend internally; it does not
         // rerun Concord hooks, rate limiting, or authentication preparation.
+        let sending = Instant::now();
         let transport_result = self
             .managed_client
             .execute(native_request, Some(&context))
             .await;
+        let metrics_key = MetricsKey {
+            endpoint,
+            method: &method,
+        };
         match transport_result {
             Ok(message) => {
+                self.runtime_state
+                    .metrics_recorder()
+                    .time_to_headers(metrics_key, sending.elapsed());
                 let error_mapper = self.managed_client.response_error_mapper();
                 Ok(ExecutionResponse::new(
                     message,
@@ -229,12 +297,16 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                 } else {
                     ApiClientError::request_execution(ctx.clone(), e)
                 };
+                self.runtime_state
+                    .metrics_recorder()
+                    .request_error(metrics_key, terminal_error.category());
                 let hook_meta = HookMeta {
                     endpoint,
                     method: &method,
                     url: safe_url,
                     page_index,
                     idempotent,
+                    retry,
                 };
                 self.runtime_state
                     .hooks()
@@ -264,6 +336,56 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             .await
     }
 
+    /// Send `built`, and if no response head has been observed `delay` after
+    /// it went on the wire, race it against a second visible execution of the
+    /// same request. Time spent waiting for a rate-limit permit or in
+    /// `pre_send` does not count toward the delay. The first response wins
+    /// and the other execution is dropped; an execution that fails defers to
+    /// the other one. Both executions acquire their own rate-limit permit and
+    /// run every hook.
+    pub(super) async fn send_and_observe_hedged(
+        &self,
+        built: BuiltRequest,
+        send_ctx: SendClassifyCtx<'_>,
+        delay: std::time::Duration,
+    ) -> Result<ObservedExecutionResponse, ApiClientError> {
+        let Some(hedge) = built.try_clone() else {
+            return self.send_and_observe_once(built, send_ctx).await;
+        };
+        let on_wire = tokio::sync::Notify::new();
+        let primary = self.send_and_observe_once(
+            built,
+            SendClassifyCtx {
+                on_wire: Some(&on_wire),
+                ..send_ctx
+            },
+        );
+        tokio::pin!(primary);
+        tokio::select! {
+            biased;
+            result = &mut primary => return result,
+            () = on_wire.notified() => {}
+        }
+        tokio::select! {
+            biased;
+            result = &mut primary => return result,
+            () = self.runtime_state.clock().sleep(delay) => {}
+        }
+        let hedged = self.send_and_observe_once(hedge, send_ctx);
+        tokio::pin!(hedged);
+        tokio::select! {
+            biased;
+            result = &mut primary => match result {
+                Ok(observed) => Ok(observed),
+                Err(_) => hedged.await,
+            },
+            result = &mut hedged => match result {
+                Ok(observed) => Ok(observed),
+                Err(_) => primary.await,
+            },
+        }
+    }
+
     pub(super) async fn observe_transport_response(
         &self,
         response: ExecutionResponse,
@@ -279,9 +401,11 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         })
     }
 
-    pub(super) fn classify_observed_transport_response(
+    #[allow(clippy::too_many_arguments)]
+    pub(super) async fn classify_observed_transport_response(
         &self,
         observed: ObservedExecutionResponse,
+        errors: &crate::endpoint::ErrorResponsePlan,
         dbg: DebugLevel,
         dbg_verbose: bool,
         url_str: &str,
@@ -302,12 +426,16 @@ impl<Cx: ClientContext> ApiClient<Cx> {
                         crate::debug::SanitizedHeaders::new(response.headers()),
                     );
                 }
+                let status = response.status();
+                let headers = Box::new(crate::redaction::sanitize_header_map(response.headers()));
+                let body = self.decode_error_body(response, errors, ctx).await;
                 Err(ApiClientError::HttpStatus {
                     ctx: ctx.clone(),
-                    status: response.status(),
-                    headers: Box::new(crate::redaction::sanitize_header_map(response.headers())),
+                    status,
+                    headers,
                     rate_limit: (!matches!(rate_limit_action, RateLimitResponseAction::Continue))
                         .then_some(Box::new(rate_limit_action)),
+                    body,
                 })
             }
             ResponseClass::Success => {
@@ -324,6 +452,43 @@ impl<Cx: ClientContext> ApiClient<Cx> {
         }
     }
 
+    /// Read and decode a status-error body through the endpoint's `on_error`
+    /// mapping. The read is bounded by the buffered response limit; an
+    /// oversized, unreadable, or undecodable body leaves the status error
+    /// without a typed body rather than replacing it.
+    pub(super) async fn decode_error_body(
+        &self,
+        response: ExecutionResponse,
+        errors: &crate::endpoint::ErrorResponsePlan,
+        ctx: &ErrorContext,
+    ) -> Option<crate::error::ErrorBody> {
+        let decode = errors.decoder_for(response.status())?;
+        if response.context.meta.method == http::Method::HEAD {
+            return None;
+        }
+        let response =
+            Self::limit_response_body(response, self.runtime_state.max_response_body_bytes(), ctx)
+                .ok()?;
+        let content_type = response
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_owned);
+        let status = response.status();
+        let buffered = Self::buffer_response(response, false, ctx).await.ok()?;
+        let (message, _) = buffered.into_parts();
+        decode(
+            message.into_body(),
+            crate::codec::DecodeContext::new(
+                ctx.endpoint,
+                &ctx.method,
+                status,
+                content_type.as_deref(),
+            ),
+        )
+        .ok()
+    }
+
     pub(super) fn limit_response_body(
         mut resp: ExecutionResponse,
         limit: Option<usize>,
@@ -387,20 +552,25 @@ impl<Cx: ClientContext> ApiClient<Cx> {
             url_host: resp.logical_url().host_str(),
             page_index: resp.context.meta.page_index,
             idempotent: resp.context.meta.idempotent,
+            retry: resp.context.meta.retry,
             plan: &resp.context.rate_limit,
             status: resp.status(),
             headers: resp.headers(),
         }
     }
 
-    pub(super) fn header_matches_media_type(
+    pub(super) fn header_matches_media_type<M: crate::codec::ContentType>(
         value: Option<&http::HeaderValue>,
-        expected: &'static str,
     ) -> bool {
         value
             .and_then(|value| value.to_str().ok())
             .and_then(|value| value.split(';').next())
-            .map(|base| base.trim().eq_ignore_ascii_case(expected))
+            .map(str::trim)
+            .map(|base| {
+                std::iter::once(M::CONTENT_TYPE)
+                    .chain(M::CONTENT_TYPE_ALIASES.iter().copied())
+                    .any(|expected| base.eq_ignore_ascii_case(expected))
+            })
             .unwrap_or(false)
     }
 }
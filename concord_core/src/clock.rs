@@ -0,0 +1,49 @@
+//! Time source for rate limiting, cooldowns, and credential expiry.
+//!
+//! The client reads the time and waits through one [`Clock`], configured
+//! with [`RuntimeConfig::clock`](crate::runtime::RuntimeConfig::clock). Tests
+//! substitute a manually advanced clock to exercise window refills, cooldown
+//! expiry, and token refresh without sleeping.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+pub type ClockFuture = Pin<Box<dyn Future<Output = ()> + Send + 'static>>;
+
+pub trait Clock: Send + Sync + 'static {
+    fn now(&self) -> Instant;
+
+    /// Completes once [`Clock::now`] has advanced by at least `duration`.
+    fn sleep(&self, duration: Duration) -> ClockFuture;
+}
+
+impl fmt::Debug for dyn Clock {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Clock").field("now", &self.now()).finish()
+    }
+}
+
+/// The monotonic system clock, sleeping on the tokio timer.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    #[inline]
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn sleep(&self, duration: Duration) -> ClockFuture {
+        Box::pin(tokio::time::sleep(duration))
+    }
+}
+
+/// A shared [`SystemClock`] for contexts built by hand in tests.
+#[cfg(test)]
+pub(crate) fn test_system_clock() -> &'static std::sync::Arc<dyn Clock> {
+    static CLOCK: std::sync::LazyLock<std::sync::Arc<dyn Clock>> =
+        std::sync::LazyLock::new(|| std::sync::Arc::new(SystemClock));
+    &CLOCK
+}
@@ -6,6 +6,7 @@ use std::fmt;
 #[cfg(feature = "json")]
 pub(crate) mod json;
 
+pub(crate) mod form;
 pub(crate) mod text;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -28,6 +29,9 @@ pub trait FormatType {
 pub trait ContentType: Send + Sync + 'static {
     const CONTENT_TYPE: &'static str;
 
+    /// Other media types a response may declare for the same format.
+    const CONTENT_TYPE_ALIASES: &'static [&'static str] = &[];
+
     fn header_value() -> Result<HeaderValue, http::header::InvalidHeaderValue> {
         HeaderValue::from_str(Self::CONTENT_TYPE)
     }
@@ -0,0 +1,595 @@
+use crate::codec::{BodyCodec, CodecError, EncodeContext, EncodedBody};
+use crate::codec::{Encodes, Format, FormatType};
+use crate::media::FormUrlEncoded;
+use bytes::Bytes;
+use serde::Serialize;
+use serde::ser::{self, Impossible};
+use std::fmt;
+use std::marker::PhantomData;
+
+/// `application/x-www-form-urlencoded` request body codec.
+///
+/// `T` must serialize as a struct or map whose fields are scalars, options or
+/// sequences of scalars. `None` fields are omitted, sequences repeat their key
+/// and field order is preserved. Nested structs and maps fail to encode.
+pub struct Form<T = ()>(PhantomData<T>);
+
+impl FormatType for Form {
+    const FORMAT_TYPE: Format = Format::Text;
+}
+
+impl<T> Encodes<T> for Form
+where
+    T: Serialize,
+{
+    type Error = FormEncodeError;
+    fn encode(output: &T) -> Result<Bytes, Self::Error> {
+        let mut pairs = Pairs::new(String::new());
+        output.serialize(TopLevel { pairs: &mut pairs })?;
+        Ok(Bytes::from(pairs.finish()))
+    }
+}
+
+impl<T> BodyCodec for Form<T>
+where
+    T: Serialize + Send + Sync + 'static,
+{
+    type Value = T;
+    type Content = FormUrlEncoded;
+
+    fn format() -> Format {
+        <Form as FormatType>::FORMAT_TYPE
+    }
+
+    fn encode(value: Self::Value, _ctx: EncodeContext<'_>) -> Result<EncodedBody, CodecError> {
+        <Form as Encodes<T>>::encode(&value)
+            .map(|bytes| EncodedBody::from_bytes(bytes).text())
+            .map_err(|err| CodecError::with_source("form encode failed", err))
+    }
+}
+
+/// Shape error raised while flattening a value into form pairs.
+#[derive(Debug)]
+pub struct FormEncodeError(String);
+
+impl fmt::Display for FormEncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for FormEncodeError {}
+
+impl ser::Error for FormEncodeError {
+    fn custom<M: fmt::Display>(msg: M) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+type Pairs = url::form_urlencoded::Serializer<'static, String>;
+
+fn top_level_error() -> FormEncodeError {
+    FormEncodeError("form body must serialize as a struct or map".to_string())
+}
+
+fn field_error() -> FormEncodeError {
+    FormEncodeError("form fields must be scalars or sequences of scalars".to_string())
+}
+
+fn key_error() -> FormEncodeError {
+    FormEncodeError("form map keys must be scalars".to_string())
+}
+
+macro_rules! scalar_methods {
+    ($self:ident, $value:ident => $body:expr) => {
+        scalar_methods!(@ $self, $value => $body;
+            serialize_bool: bool,
+            serialize_i8: i8,
+            serialize_i16: i16,
+            serialize_i32: i32,
+            serialize_i64: i64,
+            serialize_u8: u8,
+            serialize_u16: u16,
+            serialize_u32: u32,
+            serialize_u64: u64,
+            serialize_f32: f32,
+            serialize_f64: f64,
+            serialize_char: char,
+            serialize_str: &str,
+        );
+    };
+    (@ $self:ident, $value:ident => $body:expr; $($method:ident: $ty:ty,)*) => {
+        $(
+            fn $method($self, $value: $ty) -> Result<Self::Ok, Self::Error> {
+                $body
+            }
+        )*
+    };
+}
+
+macro_rules! reject {
+    ($err:expr; $($method:ident)*) => {
+        $(reject!(@ $method $err);)*
+    };
+    (@ serialize_bytes $err:expr) => {
+        fn serialize_bytes(self, _: &[u8]) -> Result<Self::Ok, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_none $err:expr) => {
+        fn serialize_none(self) -> Result<Self::Ok, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_unit $err:expr) => {
+        fn serialize_unit(self) -> Result<Self::Ok, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_unit_struct $err:expr) => {
+        fn serialize_unit_struct(self, _: &'static str) -> Result<Self::Ok, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_unit_variant $err:expr) => {
+        fn serialize_unit_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+        ) -> Result<Self::Ok, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_newtype_variant $err:expr) => {
+        fn serialize_newtype_variant<V: ?Sized + Serialize>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &V,
+        ) -> Result<Self::Ok, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_seq $err:expr) => {
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_tuple $err:expr) => {
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_tuple_struct $err:expr) => {
+        fn serialize_tuple_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleStruct, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_tuple_variant $err:expr) => {
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_map $err:expr) => {
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_struct $err:expr) => {
+        fn serialize_struct(
+            self,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStruct, Self::Error> {
+            Err($err)
+        }
+    };
+    (@ serialize_struct_variant $err:expr) => {
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, Self::Error> {
+            Err($err)
+        }
+    };
+}
+
+/// Accepts the outer struct or map and hands each field to [`FieldWriter`].
+struct TopLevel<'a> {
+    pairs: &'a mut Pairs,
+}
+
+impl<'a> ser::Serializer for TopLevel<'a> {
+    type Ok = ();
+    type Error = FormEncodeError;
+    type SerializeSeq = Impossible<(), FormEncodeError>;
+    type SerializeTuple = Impossible<(), FormEncodeError>;
+    type SerializeTupleStruct = Impossible<(), FormEncodeError>;
+    type SerializeTupleVariant = Impossible<(), FormEncodeError>;
+    type SerializeMap = PairWriter<'a>;
+    type SerializeStruct = PairWriter<'a>;
+    type SerializeStructVariant = Impossible<(), FormEncodeError>;
+
+    scalar_methods!(self, _value => Err(top_level_error()));
+    reject!(top_level_error();
+        serialize_bytes serialize_none serialize_unit serialize_unit_struct
+        serialize_unit_variant serialize_newtype_variant serialize_seq serialize_tuple
+        serialize_tuple_struct serialize_tuple_variant serialize_struct_variant);
+
+    fn serialize_some<V: ?Sized + Serialize>(self, value: &V) -> Result<(), FormEncodeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<V: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &V,
+    ) -> Result<(), FormEncodeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<PairWriter<'a>, FormEncodeError> {
+        Ok(PairWriter {
+            pairs: self.pairs,
+            key: None,
+        })
+    }
+
+    fn serialize_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<PairWriter<'a>, FormEncodeError> {
+        Ok(PairWriter {
+            pairs: self.pairs,
+            key: None,
+        })
+    }
+}
+
+struct PairWriter<'a> {
+    pairs: &'a mut Pairs,
+    key: Option<String>,
+}
+
+impl ser::SerializeStruct for PairWriter<'_> {
+    type Ok = ();
+    type Error = FormEncodeError;
+
+    fn serialize_field<V: ?Sized + Serialize>(
+        &mut self,
+        key: &'static str,
+        value: &V,
+    ) -> Result<(), FormEncodeError> {
+        value.serialize(FieldWriter {
+            pairs: self.pairs,
+            key,
+            in_seq: false,
+        })
+    }
+
+    fn end(self) -> Result<(), FormEncodeError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for PairWriter<'_> {
+    type Ok = ();
+    type Error = FormEncodeError;
+
+    fn serialize_key<K: ?Sized + Serialize>(&mut self, key: &K) -> Result<(), FormEncodeError> {
+        self.key = Some(key.serialize(KeyWriter)?);
+        Ok(())
+    }
+
+    fn serialize_value<V: ?Sized + Serialize>(&mut self, value: &V) -> Result<(), FormEncodeError> {
+        let key = self.key.take().ok_or_else(key_error)?;
+        value.serialize(FieldWriter {
+            pairs: self.pairs,
+            key: &key,
+            in_seq: false,
+        })
+    }
+
+    fn end(self) -> Result<(), FormEncodeError> {
+        Ok(())
+    }
+}
+
+/// Writes one field; sequences repeat the key once per element.
+struct FieldWriter<'a> {
+    pairs: &'a mut Pairs,
+    key: &'a str,
+    in_seq: bool,
+}
+
+impl<'a> FieldWriter<'a> {
+    fn append(self, value: impl fmt::Display) -> Result<(), FormEncodeError> {
+        self.pairs.append_pair(self.key, &value.to_string());
+        Ok(())
+    }
+
+    fn repeated(self) -> Result<SeqWriter<'a>, FormEncodeError> {
+        if self.in_seq {
+            return Err(field_error());
+        }
+        Ok(SeqWriter {
+            pairs: self.pairs,
+            key: self.key,
+        })
+    }
+}
+
+impl<'a> ser::Serializer for FieldWriter<'a> {
+    type Ok = ();
+    type Error = FormEncodeError;
+    type SerializeSeq = SeqWriter<'a>;
+    type SerializeTuple = SeqWriter<'a>;
+    type SerializeTupleStruct = SeqWriter<'a>;
+    type SerializeTupleVariant = Impossible<(), FormEncodeError>;
+    type SerializeMap = Impossible<(), FormEncodeError>;
+    type SerializeStruct = Impossible<(), FormEncodeError>;
+    type SerializeStructVariant = Impossible<(), FormEncodeError>;
+
+    scalar_methods!(self, value => self.append(value));
+    reject!(field_error();
+        serialize_bytes serialize_newtype_variant serialize_tuple_variant serialize_map
+        serialize_struct serialize_struct_variant);
+
+    fn serialize_none(self) -> Result<(), FormEncodeError> {
+        Ok(())
+    }
+
+    fn serialize_unit(self) -> Result<(), FormEncodeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<(), FormEncodeError> {
+        Ok(())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<(), FormEncodeError> {
+        self.append(variant)
+    }
+
+    fn serialize_some<V: ?Sized + Serialize>(self, value: &V) -> Result<(), FormEncodeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<V: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &V,
+    ) -> Result<(), FormEncodeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<SeqWriter<'a>, FormEncodeError> {
+        self.repeated()
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<SeqWriter<'a>, FormEncodeError> {
+        self.repeated()
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _: &'static str,
+        _: usize,
+    ) -> Result<SeqWriter<'a>, FormEncodeError> {
+        self.repeated()
+    }
+}
+
+struct SeqWriter<'a> {
+    pairs: &'a mut Pairs,
+    key: &'a str,
+}
+
+impl SeqWriter<'_> {
+    fn element<V: ?Sized + Serialize>(&mut self, value: &V) -> Result<(), FormEncodeError> {
+        value.serialize(FieldWriter {
+            pairs: self.pairs,
+            key: self.key,
+            in_seq: true,
+        })
+    }
+}
+
+impl ser::SerializeSeq for SeqWriter<'_> {
+    type Ok = ();
+    type Error = FormEncodeError;
+
+    fn serialize_element<V: ?Sized + Serialize>(
+        &mut self,
+        value: &V,
+    ) -> Result<(), FormEncodeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), FormEncodeError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTuple for SeqWriter<'_> {
+    type Ok = ();
+    type Error = FormEncodeError;
+
+    fn serialize_element<V: ?Sized + Serialize>(
+        &mut self,
+        value: &V,
+    ) -> Result<(), FormEncodeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), FormEncodeError> {
+        Ok(())
+    }
+}
+
+impl ser::SerializeTupleStruct for SeqWriter<'_> {
+    type Ok = ();
+    type Error = FormEncodeError;
+
+    fn serialize_field<V: ?Sized + Serialize>(&mut self, value: &V) -> Result<(), FormEncodeError> {
+        self.element(value)
+    }
+
+    fn end(self) -> Result<(), FormEncodeError> {
+        Ok(())
+    }
+}
+
+struct KeyWriter;
+
+impl ser::Serializer for KeyWriter {
+    type Ok = String;
+    type Error = FormEncodeError;
+    type SerializeSeq = Impossible<String, FormEncodeError>;
+    type SerializeTuple = Impossible<String, FormEncodeError>;
+    type SerializeTupleStruct = Impossible<String, FormEncodeError>;
+    type SerializeTupleVariant = Impossible<String, FormEncodeError>;
+    type SerializeMap = Impossible<String, FormEncodeError>;
+    type SerializeStruct = Impossible<String, FormEncodeError>;
+    type SerializeStructVariant = Impossible<String, FormEncodeError>;
+
+    scalar_methods!(self, value => Ok(value.to_string()));
+    reject!(key_error();
+        serialize_bytes serialize_none serialize_unit serialize_unit_struct
+        serialize_newtype_variant serialize_seq serialize_tuple serialize_tuple_struct
+        serialize_tuple_variant serialize_map serialize_struct serialize_struct_variant);
+
+    fn serialize_unit_variant(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+    ) -> Result<String, FormEncodeError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_some<V: ?Sized + Serialize>(self, value: &V) -> Result<String, FormEncodeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_struct<V: ?Sized + Serialize>(
+        self,
+        _: &'static str,
+        value: &V,
+    ) -> Result<String, FormEncodeError> {
+        value.serialize(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[derive(Serialize)]
+    #[serde(rename_all = "snake_case")]
+    enum GrantType {
+        RefreshToken,
+    }
+
+    #[derive(Serialize)]
+    struct TokenRequest {
+        grant_type: GrantType,
+        refresh_token: &'static str,
+        scope: Vec<&'static str>,
+        client_id: Option<&'static str>,
+        max_age: u32,
+    }
+
+    fn encode<T: Serialize>(value: &T) -> Result<String, FormEncodeError> {
+        <Form as Encodes<T>>::encode(value).map(|bytes| String::from_utf8(bytes.to_vec()).unwrap())
+    }
+
+    #[test]
+    fn form_encodes_fields_in_order_and_repeats_sequences() {
+        let body = encode(&TokenRequest {
+            grant_type: GrantType::RefreshToken,
+            refresh_token: "a b&c=d",
+            scope: vec!["read", "write"],
+            client_id: None,
+            max_age: 60,
+        })
+        .expect("flat struct encodes");
+
+        assert_eq!(
+            body,
+            "grant_type=refresh_token&refresh_token=a+b%26c%3Dd&scope=read&scope=write&max_age=60"
+        );
+    }
+
+    #[test]
+    fn form_encodes_string_maps() {
+        let map = BTreeMap::from([("b", "2"), ("a", "1")]);
+        assert_eq!(encode(&map).expect("map encodes"), "a=1&b=2");
+    }
+
+    #[test]
+    fn form_rejects_nested_and_top_level_scalar_values() {
+        #[derive(Serialize)]
+        struct Outer {
+            inner: BTreeMap<&'static str, &'static str>,
+        }
+
+        let nested = encode(&Outer {
+            inner: BTreeMap::from([("k", "v")]),
+        })
+        .expect_err("nested maps are rejected");
+        assert_eq!(
+            nested.to_string(),
+            "form fields must be scalars or sequences of scalars"
+        );
+
+        let nested_seq = encode(&BTreeMap::from([("k", vec![vec![1]])]))
+            .expect_err("nested sequences are rejected");
+        assert_eq!(
+            nested_seq.to_string(),
+            "form fields must be scalars or sequences of scalars"
+        );
+
+        let scalar = encode(&"value").expect_err("top-level scalars are rejected");
+        assert_eq!(
+            scalar.to_string(),
+            "form body must serialize as a struct or map"
+        );
+    }
+
+    #[test]
+    fn form_body_codec_sets_content_type_and_wraps_errors() {
+        assert_eq!(
+            <<Form<TokenRequest> as BodyCodec>::Content as crate::codec::ContentType>::CONTENT_TYPE,
+            "application/x-www-form-urlencoded"
+        );
+
+        let method = http::Method::POST;
+        let err =
+            <Form<Vec<u8>> as BodyCodec>::encode(vec![1], EncodeContext::new("Token", &method))
+                .expect_err("sequence bodies are rejected");
+        assert_eq!(err.to_string(), "form encode failed");
+    }
+}
@@ -5,8 +5,11 @@
 use bytes::Bytes;
 use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
 use http_body::{Body, Frame, SizeHint};
+use sha2::{Digest, Sha256};
+use std::any::Any;
 use std::collections::VecDeque;
 use std::fmt;
+use std::future::Future;
 use std::pin::Pin;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
@@ -47,6 +50,7 @@ pub struct CapturedNativeRequest {
     body_category: CapturedBodyCategory,
     known_body_length: Option<u64>,
     timeout: Option<Duration>,
+    endpoint_matches: Vec<usize>,
 }
 
 impl CapturedNativeRequest {
@@ -97,6 +101,12 @@ impl CapturedNativeRequest {
     pub fn timeout(&self) -> Option<Duration> {
         self.timeout
     }
+
+    /// Indices returned by the executor's endpoint matcher for the endpoint
+    /// value that planned this request; empty without a matcher.
+    pub fn endpoint_matches(&self) -> &[usize] {
+        &self.endpoint_matches
+    }
 }
 
 /// Focused execution failures that public Reqwest APIs cannot synthesize.
@@ -118,6 +128,27 @@ pub enum RequestBodyTerminalObservation {
     NeverPolled,
 }
 
+/// SHA-256 digest of a fully drained request body, offered to request routers.
+///
+/// Routers never see request body bytes; the digest is the only body identity
+/// the executor exposes.
+#[derive(Clone, Copy, Eq, Hash, PartialEq)]
+pub struct RequestBodyDigest([u8; 32]);
+
+impl RequestBodyDigest {
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
+
+impl fmt::Debug for RequestBodyDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("RequestBodyDigest")
+            .field(&self.to_hex())
+            .finish()
+    }
+}
+
 /// A deterministic fake value used only by explicit unsafe placement checks.
 ///
 /// Callers must use non-production, deterministic test credentials. The value
@@ -380,6 +411,7 @@ enum ScriptedBodyStep {
     Chunk(Bytes),
     Trailers(HeaderMap),
     Gate(DeterministicBodyGate),
+    Delay(Duration),
     Failure,
 }
 
@@ -388,6 +420,8 @@ enum ScriptedBodyStep {
 pub enum ScriptedResponseBodyStep {
     Chunk(Bytes),
     Gate(DeterministicBodyGate),
+    /// Pause delivery on the tokio clock, so paused-clock tests stay fast.
+    Delay(Duration),
     Failure,
 }
 
@@ -399,6 +433,7 @@ impl fmt::Debug for ScriptedResponseBodyStep {
                 .field(&format_args!("<{} bytes>", bytes.len()))
                 .finish(),
             Self::Gate(gate) => f.debug_tuple("Gate").field(gate).finish(),
+            Self::Delay(delay) => f.debug_tuple("Delay").field(delay).finish(),
             Self::Failure => f.write_str("Failure"),
         }
     }
@@ -414,6 +449,7 @@ pub struct ScriptedNativeResponse {
     headers: HeaderMap,
     body: Vec<ScriptedBodyStep>,
     gate: Option<DeterministicBodyGate>,
+    latency: Option<Duration>,
     unsafe_expectations: UnsafeCredentialPlacementExpectations,
     unsafe_body_expectations: UnsafeRequestBodyExpectations,
 }
@@ -431,6 +467,7 @@ impl ScriptedNativeResponse {
             headers: HeaderMap::new(),
             body,
             gate: None,
+            latency: None,
             unsafe_expectations: UnsafeCredentialPlacementExpectations::new(),
             unsafe_body_expectations: UnsafeRequestBodyExpectations::new(),
         }
@@ -442,6 +479,7 @@ impl ScriptedNativeResponse {
             headers: HeaderMap::new(),
             body: chunks.into_iter().map(ScriptedBodyStep::Chunk).collect(),
             gate: None,
+            latency: None,
             unsafe_expectations: UnsafeCredentialPlacementExpectations::new(),
             unsafe_body_expectations: UnsafeRequestBodyExpectations::new(),
         }
@@ -459,10 +497,12 @@ impl ScriptedNativeResponse {
                 .map(|step| match step {
                     ScriptedResponseBodyStep::Chunk(bytes) => ScriptedBodyStep::Chunk(bytes),
                     ScriptedResponseBodyStep::Gate(gate) => ScriptedBodyStep::Gate(gate),
+                    ScriptedResponseBodyStep::Delay(delay) => ScriptedBodyStep::Delay(delay),
                     ScriptedResponseBodyStep::Failure => ScriptedBodyStep::Failure,
                 })
                 .collect(),
             gate: None,
+            latency: None,
             unsafe_expectations: UnsafeCredentialPlacementExpectations::new(),
             unsafe_body_expectations: UnsafeRequestBodyExpectations::new(),
         }
@@ -488,6 +528,65 @@ impl ScriptedNativeResponse {
         self
     }
 
+    /// Delay the response head on the tokio clock.
+    pub fn with_latency(mut self, latency: Duration) -> Self {
+        self.latency = Some(latency);
+        self
+    }
+
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    /// Total scripted body bytes.
+    pub fn body_len(&self) -> u64 {
+        self.body
+            .iter()
+            .map(|step| match step {
+                ScriptedBodyStep::Chunk(bytes) => bytes.len() as u64,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Deliver only the first `len` body bytes, then end the body cleanly.
+    /// Headers, including any `Content-Length`, are left unchanged.
+    pub fn truncate_body(mut self, len: u64) -> Self {
+        self.body = body_prefix(self.body, len);
+        self
+    }
+
+    /// Deliver the first `len` body bytes, then fail the body as a dropped
+    /// connection would.
+    pub fn fail_body_after(mut self, len: u64) -> Self {
+        self.body = body_prefix(self.body, len);
+        self.body.push(ScriptedBodyStep::Failure);
+        self
+    }
+
+    /// Re-split body bytes into `chunk_len` pieces, each delivered after
+    /// `interval` on the tokio clock.
+    pub fn drip_body(mut self, chunk_len: usize, interval: Duration) -> Self {
+        let chunk_len = chunk_len.max(1);
+        self.body = self
+            .body
+            .into_iter()
+            .flat_map(|step| match step {
+                ScriptedBodyStep::Chunk(bytes) => bytes
+                    .chunks(chunk_len)
+                    .flat_map(|piece| {
+                        [
+                            ScriptedBodyStep::Delay(interval),
+                            ScriptedBodyStep::Chunk(bytes.slice_ref(piece)),
+                        ]
+                    })
+                    .collect(),
+                other => vec![other],
+            })
+            .collect();
+        self
+    }
+
     pub fn with_unsafe_credential_placement_expectations(
         mut self,
         expectations: UnsafeCredentialPlacementExpectations,
@@ -522,6 +621,7 @@ impl fmt::Debug for ScriptedNativeResponse {
                 &crate::debug::SanitizedHeaders::new(&self.headers),
             )
             .field("body", &"<scripted native body>")
+            .field("latency", &self.latency)
             .field("unsafe_expectations", &self.unsafe_expectations)
             .field("unsafe_body_expectations", &self.unsafe_body_expectations)
             .finish()
@@ -541,12 +641,45 @@ enum ScriptedOutcome {
 
 type RequestObserver = Arc<dyn Fn() + Send + Sync>;
 type RequestBodyObserver = Arc<dyn Fn(RequestBodyTerminalObservation) + Send + Sync>;
+type RequestRouter = Arc<
+    dyn Fn(&CapturedNativeRequest, &RequestBodyDigest) -> Option<ScriptedNativeResponse>
+        + Send
+        + Sync,
+>;
+type EndpointMatcher = Arc<dyn Fn(&dyn Any) -> Vec<usize> + Send + Sync>;
+type FaultInjector = Arc<dyn Fn(&CapturedNativeRequest) -> Option<InjectedFault> + Send + Sync>;
+type ResponseRewrite = Box<dyn FnOnce(ScriptedNativeResponse) -> ScriptedNativeResponse + Send>;
+
+/// A development fault applied around whichever script or router answers a
+/// request.
+pub enum InjectedFault {
+    /// Answer with this response; no script or router is consulted.
+    Replace(ScriptedNativeResponse),
+    /// Fail before the request body is polled; no script or router is
+    /// consulted.
+    Fail(SyntheticExecutionFailure),
+    /// Let the script or router answer, then rewrite its response.
+    Rewrite(ResponseRewrite),
+}
+
+impl fmt::Debug for InjectedFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Replace(response) => f.debug_tuple("Replace").field(response).finish(),
+            Self::Fail(failure) => f.debug_tuple("Fail").field(failure).finish(),
+            Self::Rewrite(_) => f.write_str("Rewrite(<response rewrite>)"),
+        }
+    }
+}
 
 struct ExecutorState {
     kind: DeterministicExecutionKind,
     scripts: Mutex<VecDeque<ScriptedOutcome>>,
     captures: Mutex<Vec<CapturedNativeRequest>>,
     repeating: Mutex<Option<ScriptedOutcome>>,
+    router: Mutex<Option<RequestRouter>>,
+    endpoint_matcher: Mutex<Option<EndpointMatcher>>,
+    fault_injector: Mutex<Option<FaultInjector>>,
     unexpected_executions: Mutex<usize>,
     request_head_observer: Mutex<Option<RequestObserver>>,
     request_body_terminal_observer: Mutex<Option<RequestBodyObserver>>,
@@ -574,6 +707,9 @@ impl DeterministicNativeExecutor {
                 scripts: Mutex::new(VecDeque::new()),
                 captures: Mutex::new(Vec::new()),
                 repeating: Mutex::new(None),
+                router: Mutex::new(None),
+                endpoint_matcher: Mutex::new(None),
+                fault_injector: Mutex::new(None),
                 unexpected_executions: Mutex::new(0),
                 request_head_observer: Mutex::new(None),
                 request_body_terminal_observer: Mutex::new(None),
@@ -634,6 +770,48 @@ impl DeterministicNativeExecutor {
         });
     }
 
+    /// Answer requests that no queued script covers by inspecting the
+    /// sanitized capture and the digest of the drained request body.
+    ///
+    /// The router is consulted after queued scripts and before a repeating
+    /// script. Returning `None` counts as an unexpected execution.
+    pub fn route_requests(
+        &self,
+        router: impl Fn(&CapturedNativeRequest, &RequestBodyDigest) -> Option<ScriptedNativeResponse>
+        + Send
+        + Sync
+        + 'static,
+    ) {
+        *lock(&self.state.router) = Some(Arc::new(router));
+    }
+
+    /// Inspect each generated endpoint value before it is planned.
+    ///
+    /// The matcher receives the endpoint struct itself and returns opaque
+    /// indices that are attached to every capture planned from that value,
+    /// so routers can select replies by endpoint type, and by field values
+    /// read through `GeneratedEndpointFields`, instead of call order.
+    pub fn match_endpoints(
+        &self,
+        matcher: impl Fn(&dyn Any) -> Vec<usize> + Send + Sync + 'static,
+    ) {
+        *lock(&self.state.endpoint_matcher) = Some(Arc::new(matcher));
+    }
+
+    /// Decide a fault for every execution before any script or router is
+    /// consulted. `None` leaves the execution untouched.
+    pub fn inject_faults(
+        &self,
+        injector: impl Fn(&CapturedNativeRequest) -> Option<InjectedFault> + Send + Sync + 'static,
+    ) {
+        *lock(&self.state.fault_injector) = Some(Arc::new(injector));
+    }
+
+    pub(crate) fn endpoint_matches(&self, endpoint: &dyn Any) -> Vec<usize> {
+        let matcher = lock(&self.state.endpoint_matcher).clone();
+        matcher.map_or_else(Vec::new, |matcher| matcher(endpoint))
+    }
+
     pub fn set_request_head_observer(&self, observer: impl Fn() + Send + Sync + 'static) {
         *lock(&self.state.request_head_observer) = Some(Arc::new(observer));
     }
@@ -669,14 +847,49 @@ impl DeterministicNativeExecutor {
         };
         let body_category = body_category(&request);
         let capture = sanitize_capture(&request, context, self.state.kind, body_category);
-        lock(&self.state.captures).push(capture);
+        lock(&self.state.captures).push(capture.clone());
 
         if let Some(observer) = lock(&self.state.request_head_observer).clone() {
             observer();
         }
-        let outcome = lock(&self.state.scripts)
-            .pop_front()
-            .or_else(|| lock(&self.state.repeating).clone());
+        let injector = lock(&self.state.fault_injector).clone();
+        let mut rewrite = None;
+        match injector.and_then(|injector| injector(&capture)) {
+            Some(InjectedFault::Fail(failure)) => return Err(map_failure(failure)),
+            Some(InjectedFault::Replace(response)) => {
+                drain_request_body(
+                    &mut request,
+                    context,
+                    &UnsafeRequestBodyExpectations::new(),
+                    &mut body_terminal,
+                    None,
+                )
+                .await?;
+                return Ok(respond(response, None).await);
+            }
+            Some(InjectedFault::Rewrite(rewrite_response)) => rewrite = Some(rewrite_response),
+            None => {}
+        }
+        let scripted = lock(&self.state.scripts).pop_front();
+        let router = lock(&self.state.router).clone();
+        if let (None, Some(router)) = (&scripted, router) {
+            let mut digest = Sha256::new();
+            drain_request_body(
+                &mut request,
+                context,
+                &UnsafeRequestBodyExpectations::new(),
+                &mut body_terminal,
+                Some(&mut digest),
+            )
+            .await?;
+            let digest = RequestBodyDigest(digest.finalize().into());
+            if let Some(response) = router(&capture, &digest) {
+                return Ok(respond(response, rewrite).await);
+            }
+            *lock(&self.state.unexpected_executions) += 1;
+            return Err(map_failure(SyntheticExecutionFailure::Request));
+        }
+        let outcome = scripted.or_else(|| lock(&self.state.repeating).clone());
         let Some(outcome) = outcome else {
             *lock(&self.state.unexpected_executions) += 1;
             return Err(map_failure(SyntheticExecutionFailure::Request));
@@ -694,9 +907,10 @@ impl DeterministicNativeExecutor {
                     context,
                     &response.unsafe_body_expectations,
                     &mut body_terminal,
+                    None,
                 )
                 .await?;
-                Ok(response.into_native())
+                Ok(respond(response, rewrite).await)
             }
             ScriptedOutcome::Failure {
                 failure,
@@ -713,6 +927,7 @@ impl DeterministicNativeExecutor {
                         context,
                         &unsafe_body_expectations,
                         &mut body_terminal,
+                        None,
                     )
                     .await?;
                 }
@@ -815,6 +1030,7 @@ async fn drain_request_body(
     context: &crate::transport::RequestExecutionContext,
     expectations: &UnsafeRequestBodyExpectations,
     terminal: &mut RequestBodyTerminalGuard,
+    mut digest: Option<&mut Sha256>,
 ) -> Result<(), crate::transport::ReqwestError> {
     terminal.begin_polling();
     let Some(mut body) = request.body_mut().take() else {
@@ -856,6 +1072,9 @@ async fn drain_request_body(
         let Ok(data) = frame.into_data() else {
             continue;
         };
+        if let Some(digest) = digest.as_deref_mut() {
+            digest.update(&data);
+        }
         if let Some(expected) = &expectations.exact {
             let end = offset.saturating_add(data.len());
             if expected.0.get(offset..end) != Some(data.as_ref()) {
@@ -877,6 +1096,42 @@ async fn drain_request_body(
     Ok(())
 }
 
+async fn respond(
+    response: ScriptedNativeResponse,
+    rewrite: Option<ResponseRewrite>,
+) -> reqwest::Response {
+    let response = match rewrite {
+        Some(rewrite) => rewrite(response),
+        None => response,
+    };
+    if let Some(latency) = response.latency {
+        tokio::time::sleep(latency).await;
+    }
+    response.into_native()
+}
+
+fn body_prefix(steps: Vec<ScriptedBodyStep>, len: u64) -> Vec<ScriptedBodyStep> {
+    let mut remaining = len;
+    let mut prefix = Vec::new();
+    for step in steps {
+        match step {
+            ScriptedBodyStep::Chunk(bytes) => {
+                if remaining == 0 {
+                    break;
+                }
+                let take = bytes
+                    .len()
+                    .min(usize::try_from(remaining).unwrap_or(usize::MAX));
+                remaining -= take as u64;
+                prefix.push(ScriptedBodyStep::Chunk(bytes.slice(..take)));
+            }
+            ScriptedBodyStep::Trailers(_) | ScriptedBodyStep::Failure => break,
+            step @ (ScriptedBodyStep::Gate(_) | ScriptedBodyStep::Delay(_)) => prefix.push(step),
+        }
+    }
+    prefix
+}
+
 fn sanitize_capture(
     request: &reqwest::Request,
     context: &crate::transport::RequestExecutionContext,
@@ -931,6 +1186,7 @@ fn sanitize_capture(
         body_category,
         known_body_length: request.body().and_then(|body| body.size_hint().exact()),
         timeout: context.timeout,
+        endpoint_matches: context.endpoint_matches.clone(),
     }
 }
 
@@ -990,6 +1246,7 @@ impl std::error::Error for OpaqueSyntheticExecutionFailure {}
 struct ScriptedResponseBody {
     steps: VecDeque<ScriptedBodyStep>,
     gate: Option<DeterministicBodyGate>,
+    delay: Option<Pin<Box<tokio::time::Sleep>>>,
     terminal: bool,
 }
 
@@ -998,6 +1255,7 @@ impl ScriptedResponseBody {
         Self {
             steps: steps.into(),
             gate,
+            delay: None,
             terminal: false,
         }
     }
@@ -1032,6 +1290,12 @@ impl Body for ScriptedResponseBody {
         }
         self.gate = None;
         loop {
+            if let Some(delay) = &mut self.delay {
+                if delay.as_mut().poll(cx).is_pending() {
+                    return Poll::Pending;
+                }
+                self.delay = None;
+            }
             match self.steps.pop_front() {
                 Some(ScriptedBodyStep::Chunk(bytes)) => {
                     return Poll::Ready(Some(Ok(Frame::data(bytes))));
@@ -1046,6 +1310,9 @@ impl Body for ScriptedResponseBody {
                     self.steps.push_front(ScriptedBodyStep::Gate(gate));
                     return Poll::Pending;
                 }
+                Some(ScriptedBodyStep::Delay(delay)) => {
+                    self.delay = Some(Box::pin(tokio::time::sleep(delay)));
+                }
                 Some(ScriptedBodyStep::Failure) => {
                     self.terminal = true;
                     return Poll::Ready(Some(Err(ScriptedResponseBodyFailure)));
@@ -1059,7 +1326,7 @@ impl Body for ScriptedResponseBody {
     }
 
     fn is_end_stream(&self) -> bool {
-        self.terminal || (self.gate.is_none() && self.steps.is_empty())
+        self.terminal || (self.gate.is_none() && self.delay.is_none() && self.steps.is_empty())
     }
 
     fn size_hint(&self) -> SizeHint {
@@ -1071,6 +1338,7 @@ impl Body for ScriptedResponseBody {
                 ScriptedBodyStep::Chunk(bytes) => bytes.len() as u64,
                 ScriptedBodyStep::Trailers(_)
                 | ScriptedBodyStep::Gate(_)
+                | ScriptedBodyStep::Delay(_)
                 | ScriptedBodyStep::Failure => 0,
             })
         });
@@ -1139,12 +1407,14 @@ mod tests {
                 method: http::Method::POST,
                 idempotent: false,
                 page_index: 0,
+                retry: 0,
             },
             logical_url: "https://example.test/body".parse().expect("logical URL"),
             timeout: None,
             body_errors: Default::default(),
             auth_query_keys: Vec::new(),
             protected_header_names: Vec::new(),
+            endpoint_matches: Vec::new(),
         }
     }
 
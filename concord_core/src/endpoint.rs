@@ -3,11 +3,13 @@ use crate::error::ApiClientError;
 use std::future::Future;
 use std::pin::Pin;
 
+mod identity;
 pub mod plan;
+pub(crate) use identity::RequestIdentity;
 #[allow(unused_imports)]
 pub use plan::{
-    EndpointMeta, EndpointPlan, PaginationMarker, RequestOverrides, RequestPlan, RequestPlanView,
-    ResolvedRoute, ResponsePlan,
+    EndpointMeta, EndpointPlan, ErrorBodyDecodeFn, ErrorResponsePlan, PaginationMarker,
+    RequestOverrides, RequestPlan, RequestPlanView, ResolvedRoute, ResponsePlan,
 };
 
 #[doc(hidden)]
@@ -20,6 +22,19 @@ pub trait GeneratedEndpoint<Cx: ClientContext>: Send + Sized + 'static {
     type Response: Send + 'static;
 }
 
+/// Read-only view of a generated endpoint's fields.
+///
+/// The fields themselves stay private to the generated client's crate; test
+/// matchers such as typed mock expectations read them through this view.
+#[doc(hidden)]
+pub trait GeneratedEndpointFields {
+    type Fields<'a>
+    where
+        Self: 'a;
+
+    fn fields(&self) -> Self::Fields<'_>;
+}
+
 /// Marker for endpoints that expose a metadata-bearing decoded response terminal.
 ///
 /// Generated buffered endpoints implement this with their resolved response
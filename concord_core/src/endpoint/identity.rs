@@ -0,0 +1,58 @@
+use super::EndpointPlan;
+use std::cmp::Ordering;
+
+/// Logical request identity: everything a plan contributes to the origin
+/// request before authentication placement.
+///
+/// Pagination uses it to detect repeated page requests; single-flight
+/// coalescing uses it to match concurrent identical calls.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct RequestIdentity {
+    endpoint: &'static str,
+    method: http::Method,
+    scheme: http::uri::Scheme,
+    host: String,
+    path: String,
+    query: Vec<(String, String)>,
+    headers: Vec<RequestIdentityHeader>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct RequestIdentityHeader {
+    name: String,
+    value: Vec<u8>,
+}
+
+impl RequestIdentity {
+    pub(crate) fn of(plan: &EndpointPlan) -> Self {
+        let mut identity = Self {
+            endpoint: plan.meta.name,
+            method: plan.meta.method.clone(),
+            scheme: plan.route.scheme.clone(),
+            host: plan.route.host.clone(),
+            path: plan.route.path.clone(),
+            query: plan.policy.query.clone(),
+            headers: Vec::new(),
+        };
+        identity.extend_headers(&plan.policy.headers);
+        identity
+    }
+
+    /// Folds additional request headers, such as client-wide API headers,
+    /// into the identity.
+    pub(crate) fn extend_headers(&mut self, headers: &http::HeaderMap) {
+        self.headers
+            .extend(headers.iter().map(|(name, value)| RequestIdentityHeader {
+                name: name.as_str().to_string(),
+                value: value.as_bytes().to_vec(),
+            }));
+        self.headers.sort_unstable_by(|a, b| {
+            let name_order = a.name.cmp(&b.name);
+            if name_order == Ordering::Equal {
+                a.value.cmp(&b.value)
+            } else {
+                name_order
+            }
+        });
+    }
+}
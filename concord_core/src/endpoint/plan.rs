@@ -22,6 +22,7 @@ impl EndpointMeta {
             method: self.method.clone(),
             idempotent: self.idempotent,
             page_index,
+            retry: 0,
         }
     }
 }
@@ -40,6 +41,12 @@ pub struct RequestOverrides {
     pub debug_level: Option<crate::debug::DebugLevel>,
     pub timeout: Option<std::time::Duration>,
     pub page_index: u32,
+    pub idempotency_key: Option<HeaderValue>,
+    pub(crate) trace: crate::telemetry::CallTrace,
+    /// Development endpoint-matcher indices for the endpoint value that
+    /// produced this plan.
+    #[cfg(any(test, feature = "dangerous-dev-tools"))]
+    pub(crate) endpoint_matches: Vec<usize>,
 }
 
 #[derive(Debug)]
@@ -91,6 +98,7 @@ pub struct ResponsePlan {
     pub accept: Option<HeaderValue>,
     pub no_content: bool,
     pub format: crate::codec::Format,
+    pub errors: ErrorResponsePlan,
 }
 
 impl fmt::Debug for ResponsePlan {
@@ -99,10 +107,80 @@ impl fmt::Debug for ResponsePlan {
             .field("accept", &self.accept)
             .field("no_content", &self.no_content)
             .field("format", &self.format)
+            .field("errors", &self.errors)
             .finish_non_exhaustive()
     }
 }
 
+/// Decode one buffered error-response body into an opaque typed value.
+pub type ErrorBodyDecodeFn =
+    for<'a> fn(
+        bytes::Bytes,
+        crate::codec::DecodeContext<'a>,
+    ) -> Result<crate::error::ErrorBody, crate::codec::CodecError>;
+
+/// Typed error-response mappings resolved for one endpoint.
+///
+/// A mapping for the exact status wins over the status-less fallback. An
+/// empty plan keeps the historical behavior: error statuses never read the
+/// response body.
+#[derive(Clone, Default)]
+pub struct ErrorResponsePlan {
+    decoders: Vec<ErrorResponseDecoder>,
+}
+
+#[derive(Clone, Copy)]
+struct ErrorResponseDecoder {
+    status: Option<http::StatusCode>,
+    type_name: &'static str,
+    decode: ErrorBodyDecodeFn,
+}
+
+impl ErrorResponsePlan {
+    pub(crate) fn push(
+        &mut self,
+        status: Option<http::StatusCode>,
+        type_name: &'static str,
+        decode: ErrorBodyDecodeFn,
+    ) {
+        self.decoders.retain(|decoder| decoder.status != status);
+        self.decoders.push(ErrorResponseDecoder {
+            status,
+            type_name,
+            decode,
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.decoders.is_empty()
+    }
+
+    pub(crate) fn decoder_for(&self, status: http::StatusCode) -> Option<ErrorBodyDecodeFn> {
+        self.decoders
+            .iter()
+            .find(|decoder| decoder.status == Some(status))
+            .or_else(|| {
+                self.decoders
+                    .iter()
+                    .find(|decoder| decoder.status.is_none())
+            })
+            .map(|decoder| decoder.decode)
+    }
+}
+
+impl fmt::Debug for ErrorResponsePlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.decoders.iter().map(|decoder| {
+                (
+                    decoder.status.map(|status| status.as_u16()),
+                    decoder.type_name,
+                )
+            }))
+            .finish()
+    }
+}
+
 #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
 pub struct PaginationMarker;
 
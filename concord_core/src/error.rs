@@ -1,4 +1,5 @@
 use http::{HeaderMap, StatusCode};
+use std::any::Any;
 use std::borrow::Cow;
 use std::error::Error;
 use std::fmt::{self, Debug, Display};
@@ -73,6 +74,14 @@ impl RequestErrorSource {
     pub const fn body_kind(&self) -> Option<crate::body::BodyErrorKind> {
         self.body_kind
     }
+
+    fn share(&self) -> Self {
+        Self {
+            kind: self.kind,
+            body_kind: self.body_kind,
+            source: share_source(self.source.as_ref()),
+        }
+    }
 }
 
 impl Display for RequestErrorSource {
@@ -117,6 +126,54 @@ impl core::fmt::Display for ErrorContext {
     }
 }
 
+/// Typed error-response body decoded through an endpoint's `on_error` mapping.
+///
+/// The value is stored opaquely and recovered with
+/// [`ApiClientError::error_body`]. Diagnostics name only the decoded type;
+/// they never render body contents.
+#[derive(Clone)]
+pub struct ErrorBody(std::sync::Arc<ErrorBodyInner>);
+
+struct ErrorBodyInner {
+    type_name: &'static str,
+    value: Box<dyn Any + Send + Sync>,
+}
+
+impl ErrorBody {
+    pub(crate) fn new<T>(value: T) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        Self(std::sync::Arc::new(ErrorBodyInner {
+            type_name: std::any::type_name::<T>(),
+            value: Box::new(value),
+        }))
+    }
+
+    /// Borrow the decoded value when it has type `T`.
+    pub fn downcast_ref<T: 'static>(&self) -> Option<&T> {
+        self.0.value.downcast_ref::<T>()
+    }
+
+    /// Whether the decoded value has type `T`.
+    pub fn is<T: 'static>(&self) -> bool {
+        self.0.value.is::<T>()
+    }
+
+    /// Rust type name of the decoded value.
+    pub fn type_name(&self) -> &'static str {
+        self.0.type_name
+    }
+}
+
+impl Debug for ErrorBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ErrorBody")
+            .field("type", &self.0.type_name)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Error)]
 #[non_exhaustive]
 pub enum ApiClientError {
@@ -174,6 +231,12 @@ pub enum ApiClientError {
     #[error("{ctx}: response body exceeded limit {limit} bytes while reading")]
     ResponseBodyLimitExceeded { ctx: ErrorContext, limit: usize },
 
+    #[error("{ctx}: stream event exceeded limit {limit} bytes")]
+    StreamEventLimitExceeded { ctx: ErrorContext, limit: usize },
+
+    #[error("{ctx}: stream line exceeded limit {limit} bytes")]
+    StreamLineLimitExceeded { ctx: ErrorContext, limit: usize },
+
     #[error("{ctx}: response body read failed ({kind:?})")]
     ResponseBody {
         ctx: ErrorContext,
@@ -203,6 +266,7 @@ pub enum ApiClientError {
         status: StatusCode,
         headers: Box<HeaderMap>,
         rate_limit: Option<Box<crate::rate_limit::RateLimitResponseAction>>,
+        body: Option<ErrorBody>,
     },
 
     #[error("{ctx}: decode error: {source}")]
@@ -230,6 +294,14 @@ pub enum ApiClientError {
         source: crate::rate_limit::RateLimitError,
     },
 
+    #[error("{ctx}: circuit open")]
+    CircuitOpen {
+        ctx: ErrorContext,
+        /// Time until the circuit admits a probe; `None` while a probe is
+        /// already in flight.
+        retry_after: Option<std::time::Duration>,
+    },
+
     #[error("{ctx}: pagination: {source}")]
     Pagination {
         ctx: ErrorContext,
@@ -325,6 +397,16 @@ impl Debug for ApiClientError {
                 .field("ctx", ctx)
                 .field("limit", limit)
                 .finish(),
+            Self::StreamEventLimitExceeded { ctx, limit } => f
+                .debug_struct("StreamEventLimitExceeded")
+                .field("ctx", ctx)
+                .field("limit", limit)
+                .finish(),
+            Self::StreamLineLimitExceeded { ctx, limit } => f
+                .debug_struct("StreamLineLimitExceeded")
+                .field("ctx", ctx)
+                .field("limit", limit)
+                .finish(),
             Self::ResponseBody { ctx, kind } => f
                 .debug_struct("ResponseBody")
                 .field("ctx", ctx)
@@ -347,6 +429,7 @@ impl Debug for ApiClientError {
                 status,
                 headers,
                 rate_limit,
+                body,
             } => f
                 .debug_struct("HttpStatus")
                 .field("ctx", ctx)
@@ -356,6 +439,7 @@ impl Debug for ApiClientError {
                     &crate::debug::SanitizedHeaders::new(headers.as_ref()),
                 )
                 .field("rate_limit", rate_limit)
+                .field("body", body)
                 .finish(),
             Self::Decode { ctx, source } => f
                 .debug_struct("Decode")
@@ -386,6 +470,11 @@ impl Debug for ApiClientError {
                 .field("ctx", ctx)
                 .field("source", source)
                 .finish(),
+            Self::CircuitOpen { ctx, retry_after } => f
+                .debug_struct("CircuitOpen")
+                .field("ctx", ctx)
+                .field("retry_after", retry_after)
+                .finish(),
             Self::Pagination { ctx, source } => f
                 .debug_struct("Pagination")
                 .field("ctx", ctx)
@@ -448,6 +537,7 @@ pub enum ErrorCategory {
     Decode,
     Pagination,
     RateLimit,
+    CircuitOpen,
     ResponseContract,
     InternalInvariant,
 }
@@ -695,6 +785,8 @@ impl ApiClientError {
             | ApiClientError::RequestBody { ctx, .. }
             | ApiClientError::ResponseTooLarge { ctx, .. }
             | ApiClientError::ResponseBodyLimitExceeded { ctx, .. }
+            | ApiClientError::StreamEventLimitExceeded { ctx, .. }
+            | ApiClientError::StreamLineLimitExceeded { ctx, .. }
             | ApiClientError::ResponseBody { ctx, .. }
             | ApiClientError::ResponseFile { ctx, .. }
             | ApiClientError::RequestBodyLimitExceeded { ctx, .. }
@@ -705,6 +797,7 @@ impl ApiClientError {
             | ApiClientError::ResponseContract { ctx, .. }
             | ApiClientError::Codec { ctx, .. }
             | ApiClientError::RateLimit { ctx, .. }
+            | ApiClientError::CircuitOpen { ctx, .. }
             | ApiClientError::Pagination { ctx, .. }
             | ApiClientError::PaginationLimit { ctx, .. }
             | ApiClientError::Auth { ctx, .. }
@@ -727,6 +820,8 @@ impl ApiClientError {
             ApiClientError::RequestBody { .. } => ErrorCategory::RequestBody,
             ApiClientError::ResponseTooLarge { .. }
             | ApiClientError::ResponseBodyLimitExceeded { .. }
+            | ApiClientError::StreamEventLimitExceeded { .. }
+            | ApiClientError::StreamLineLimitExceeded { .. }
             | ApiClientError::ResponseBody { .. }
             | ApiClientError::ResponseFile { .. } => ErrorCategory::Decode,
             ApiClientError::RequestBodyLimitExceeded { .. } => ErrorCategory::RequestBody,
@@ -739,6 +834,7 @@ impl ApiClientError {
             | ApiClientError::NoContentStatusRequiresNoContent { .. }
             | ApiClientError::ResponseContract { .. } => ErrorCategory::ResponseContract,
             ApiClientError::RateLimit { .. } => ErrorCategory::RateLimit,
+            ApiClientError::CircuitOpen { .. } => ErrorCategory::CircuitOpen,
             ApiClientError::Pagination { .. } | ApiClientError::PaginationLimit { .. } => {
                 ErrorCategory::Pagination
             }
@@ -790,6 +886,18 @@ impl ApiClientError {
         }
     }
 
+    /// Typed error-response body decoded for this status, when the endpoint
+    /// declares an `on_error` mapping of type `T` and decoding succeeded.
+    #[inline]
+    pub fn error_body<T: 'static>(&self) -> Option<&T> {
+        match self {
+            ApiClientError::HttpStatus {
+                body: Some(body), ..
+            } => body.downcast_ref::<T>(),
+            _ => None,
+        }
+    }
+
     #[inline]
     pub fn decode_status(&self) -> Option<StatusCode> {
         match self {
@@ -828,6 +936,154 @@ impl ApiClientError {
         }
     }
 
+    /// A copy of this error for another caller of the same coalesced request.
+    ///
+    /// Structured fields are cloned; opaque sources keep their rendered
+    /// diagnostics but can no longer be downcast.
+    pub(crate) fn share(&self) -> Self {
+        match self {
+            Self::InvalidParam { ctx, param } => Self::InvalidParam {
+                ctx: ctx.clone(),
+                param: param.clone(),
+            },
+            Self::BuildUrl { ctx, source } => Self::BuildUrl {
+                ctx: ctx.clone(),
+                source: *source,
+            },
+            Self::TlsCapabilityUnavailable { ctx } => {
+                Self::TlsCapabilityUnavailable { ctx: ctx.clone() }
+            }
+            Self::Timeout { ctx, source } => Self::Timeout {
+                ctx: ctx.clone(),
+                source: source.share(),
+            },
+            Self::Connect { ctx, source } => Self::Connect {
+                ctx: ctx.clone(),
+                source: source.share(),
+            },
+            Self::RequestExecution { ctx, source } => Self::RequestExecution {
+                ctx: ctx.clone(),
+                source: source.share(),
+            },
+            Self::RequestBody { ctx, kind, source } => Self::RequestBody {
+                ctx: ctx.clone(),
+                kind: *kind,
+                source: source.share(),
+            },
+            Self::ResponseTooLarge { ctx, limit, actual } => Self::ResponseTooLarge {
+                ctx: ctx.clone(),
+                limit: *limit,
+                actual: *actual,
+            },
+            Self::ResponseBodyLimitExceeded { ctx, limit } => Self::ResponseBodyLimitExceeded {
+                ctx: ctx.clone(),
+                limit: *limit,
+            },
+            Self::StreamEventLimitExceeded { ctx, limit } => Self::StreamEventLimitExceeded {
+                ctx: ctx.clone(),
+                limit: *limit,
+            },
+            Self::StreamLineLimitExceeded { ctx, limit } => Self::StreamLineLimitExceeded {
+                ctx: ctx.clone(),
+                limit: *limit,
+            },
+            Self::ResponseBody { ctx, kind } => Self::ResponseBody {
+                ctx: ctx.clone(),
+                kind: *kind,
+            },
+            Self::ResponseFile { ctx, msg, source } => Self::ResponseFile {
+                ctx: ctx.clone(),
+                msg: msg.clone(),
+                source: share_source(source.as_ref()),
+            },
+            Self::RequestBodyLimitExceeded { ctx, limit, actual } => {
+                Self::RequestBodyLimitExceeded {
+                    ctx: ctx.clone(),
+                    limit: *limit,
+                    actual: *actual,
+                }
+            }
+            Self::HttpStatus {
+                ctx,
+                status,
+                headers,
+                rate_limit,
+                body,
+            } => Self::HttpStatus {
+                ctx: ctx.clone(),
+                status: *status,
+                headers: headers.clone(),
+                rate_limit: rate_limit.clone(),
+                body: body.clone(),
+            },
+            Self::Decode { ctx, source } => Self::Decode {
+                ctx: ctx.clone(),
+                source: share_source(source.as_ref()),
+            },
+            Self::HeadRequiresNoContent { ctx } => Self::HeadRequiresNoContent { ctx: ctx.clone() },
+            Self::NoContentStatusRequiresNoContent { ctx, status } => {
+                Self::NoContentStatusRequiresNoContent {
+                    ctx: ctx.clone(),
+                    status: *status,
+                }
+            }
+            Self::ResponseContract { ctx, msg } => Self::ResponseContract {
+                ctx: ctx.clone(),
+                msg: msg.clone(),
+            },
+            Self::Codec { ctx, source } => Self::Codec {
+                ctx: ctx.clone(),
+                source: share_source(source.as_ref()),
+            },
+            Self::RateLimit { ctx, source } => Self::RateLimit {
+                ctx: ctx.clone(),
+                source: source.share(),
+            },
+            Self::CircuitOpen { ctx, retry_after } => Self::CircuitOpen {
+                ctx: ctx.clone(),
+                retry_after: *retry_after,
+            },
+            Self::Pagination { ctx, source } => Self::Pagination {
+                ctx: ctx.clone(),
+                source: source.clone(),
+            },
+            Self::PaginationLimit { ctx, source } => Self::PaginationLimit {
+                ctx: ctx.clone(),
+                source: source.clone(),
+            },
+            Self::Auth { ctx, source } => Self::Auth {
+                ctx: ctx.clone(),
+                source: source.clone(),
+            },
+            Self::PolicyViolation { ctx, msg } => Self::PolicyViolation {
+                ctx: ctx.clone(),
+                msg,
+            },
+            Self::RuntimeState {
+                ctx,
+                subsystem,
+                msg,
+            } => Self::RuntimeState {
+                ctx: ctx.clone(),
+                subsystem,
+                msg,
+            },
+            Self::InvalidHostLabel {
+                ctx,
+                label,
+                index,
+                placeholder,
+                reason,
+            } => Self::InvalidHostLabel {
+                ctx: ctx.clone(),
+                label: label.clone(),
+                index: *index,
+                placeholder: *placeholder,
+                reason: *reason,
+            },
+        }
+    }
+
     #[inline]
     pub fn pagination_error_kind(&self) -> Option<PaginationErrorKind> {
         match self {
@@ -861,6 +1117,60 @@ impl Error for ContextualDecodeError {
     }
 }
 
+/// Rendered snapshot of an opaque source chain.
+///
+/// Boxed sources cannot be cloned, so an error delivered to several coalesced
+/// callers keeps each link's `Display` and `Debug` output instead. Downcasting
+/// a shared source to its original type is not supported.
+struct SharedErrorSource {
+    display: String,
+    debug: String,
+    source: Option<Box<SharedErrorSource>>,
+}
+
+impl SharedErrorSource {
+    fn capture(error: &(dyn Error + 'static)) -> Self {
+        Self {
+            display: error.to_string(),
+            debug: format!("{error:?}"),
+            source: error.source().map(|source| Box::new(Self::capture(source))),
+        }
+    }
+}
+
+impl Display for SharedErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display)
+    }
+}
+
+impl Debug for SharedErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.debug)
+    }
+}
+
+impl Error for SharedErrorSource {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|source| source as &(dyn Error + 'static))
+    }
+}
+
+/// Copies an opaque source chain for another receiver of the same failure.
+pub(crate) fn share_source(source: &(dyn Error + Send + Sync + 'static)) -> FxError {
+    // Keep decode context structured so `decode_status` still answers.
+    if let Some(decode) = source.downcast_ref::<ContextualDecodeError>() {
+        return Box::new(ContextualDecodeError {
+            status: decode.status,
+            content_type: decode.content_type.clone(),
+            source: share_source(decode.source.as_ref()),
+        });
+    }
+    Box::new(SharedErrorSource::capture(source))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -888,6 +1198,7 @@ mod tests {
                 target: RateLimitTarget::Request,
                 cooldown_stored: false,
             })),
+            body: None,
         };
 
         assert_eq!(err.context().endpoint, "Ping");
@@ -1024,4 +1335,30 @@ mod tests {
         assert!(err.to_string().contains("GET Protected"));
         assert!(err.to_string().contains("session"));
     }
+
+    #[test]
+    fn shared_errors_keep_structure_and_rendered_sources() {
+        let ctx = ErrorContext {
+            endpoint: "GetUser",
+            method: http::Method::GET,
+        };
+        let err = ApiClientError::decode_error(
+            ctx,
+            StatusCode::OK,
+            Some("application/json"),
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "bad json"),
+        );
+
+        let shared = err.share();
+
+        assert_eq!(shared.to_string(), err.to_string());
+        assert_eq!(shared.decode_status(), Some(StatusCode::OK));
+        assert_eq!(shared.decode_content_type(), Some("application/json"));
+        let inner = shared
+            .source()
+            .and_then(Error::source)
+            .expect("shared inner source");
+        assert_eq!(inner.to_string(), "bad json");
+        assert!(inner.downcast_ref::<std::io::Error>().is_none());
+    }
 }
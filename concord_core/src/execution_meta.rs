@@ -3,12 +3,16 @@ use http::Method;
 /// Stable metadata for one Concord-visible request execution.
 ///
 /// Reqwest-internal resends are deliberately not represented here. The
-/// metadata contains no physical-attempt index, retry counter, URL, headers,
-/// or mutable runtime state.
+/// metadata contains no physical-attempt index, URL, headers, or mutable
+/// runtime state; `retry` counts only Concord backoff retries, which are
+/// visible executions of their own.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RequestExecutionMeta {
     pub endpoint: &'static str,
     pub method: Method,
     pub idempotent: bool,
     pub page_index: u32,
+    /// Backoff retries of this call before this execution; `0` for the
+    /// first.
+    pub retry: u32,
 }
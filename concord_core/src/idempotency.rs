@@ -0,0 +1,92 @@
+//! Per-call idempotency keys for unsafe methods.
+//!
+//! A key is attached once to the logical call's plan, before the first visible
+//! execution, so the authentication-recovery execution and any Reqwest resend
+//! carry the same value.
+
+use crate::endpoint::RequestPlanView;
+use crate::error::{ApiClientError, ErrorContext};
+use http::{HeaderName, HeaderValue};
+
+/// Header used for a caller-supplied key when the endpoint declares none.
+pub(crate) const DEFAULT_HEADER: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Attaches the call's idempotency key to the plan headers.
+///
+/// A caller-supplied key always wins. Otherwise a key is generated only for
+/// unsafe methods whose policy declares an idempotency header that the
+/// endpoint did not already set explicitly. A plan carrying a key is reported
+/// as idempotent to hooks and rate limiters.
+pub(crate) fn attach_key(
+    plan: &mut RequestPlanView,
+    ctx: &ErrorContext,
+) -> Result<(), ApiClientError> {
+    let declared = plan.endpoint.policy.idempotency_key.clone();
+    let (name, value) = match plan.overrides.idempotency_key.clone() {
+        Some(value) => (declared.unwrap_or(DEFAULT_HEADER), value),
+        None => {
+            let Some(name) = declared else {
+                return Ok(());
+            };
+            if plan.endpoint.meta.method.is_safe()
+                || plan.endpoint.policy.headers.contains_key(&name)
+            {
+                return Ok(());
+            }
+            let value = generate_key().ok_or_else(|| ApiClientError::RuntimeState {
+                ctx: ctx.clone(),
+                subsystem: "idempotency",
+                msg: "idempotency key generation requires OS randomness",
+            })?;
+            (name, value)
+        }
+    };
+    plan.endpoint.policy.headers.insert(name, value);
+    plan.endpoint.meta.idempotent = true;
+    Ok(())
+}
+
+/// A random (version 4) UUID rendered as a header value.
+fn generate_key() -> Option<HeaderValue> {
+    let mut bytes = [0_u8; 16];
+    getrandom::fill(&mut bytes).ok()?;
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    HeaderValue::from_str(&format_uuid(&bytes)).ok()
+}
+
+fn format_uuid(bytes: &[u8; 16]) -> String {
+    let mut out = String::with_capacity(36);
+    for (index, byte) in bytes.iter().enumerate() {
+        if matches!(index, 4 | 6 | 8 | 10) {
+            out.push('-');
+        }
+        out.push_str(&format!("{byte:02x}"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_keys_are_distinct_v4_uuids() {
+        let first = generate_key().expect("key");
+        let second = generate_key().expect("key");
+        assert_ne!(first, second);
+        let text = first.to_str().expect("ascii");
+        assert_eq!(text.len(), 36);
+        assert_eq!(&text[14..15], "4");
+        assert!(matches!(&text[19..20], "8" | "9" | "a" | "b"));
+    }
+
+    #[test]
+    fn uuid_groups_are_hyphenated() {
+        let bytes = [
+            0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x46, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+            0xee, 0xff,
+        ];
+        assert_eq!(format_uuid(&bytes), "00112233-4455-4677-8899-aabbccddeeff");
+    }
+}
@@ -0,0 +1,250 @@
+//! In-process execution against a `tower::Service`.
+//!
+//! This module is absent unless the `dangerous-in-process` feature is
+//! selected. A service installed with [`configure_application_service`] or
+//! [`configure_provider_service`] receives each materialized native request,
+//! credentials included, in place of Reqwest, so a generated client can be
+//! exercised against an axum router or any other `tower::Service` without
+//! opening a socket.
+//!
+//! Everything Concord does before the wire still runs: authentication
+//! placement, rate limiting, runtime hooks, and request/response body limits.
+//! The per-request timeout bounds the service call. Connection-level builder
+//! settings (proxies, connect/read timeouts, pooling, TLS) have no effect on
+//! in-process executions.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use http_body::{Body as HttpBody, Frame, SizeHint};
+use tower_service::Service;
+
+use crate::transport::{
+    RequestExecutionContext, ReqwestError, ReqwestErrorKind, SafeReqwestBuilder,
+};
+
+/// Boxed error accepted from in-process services and their response bodies.
+pub type InProcessError = Box<dyn std::error::Error + Send + Sync>;
+
+type ServiceFuture =
+    Pin<Box<dyn Future<Output = Result<http::Response<reqwest::Body>, InProcessError>> + Send>>;
+
+/// Request body handed to an in-process service.
+///
+/// Frames are the exact bytes Concord would have written to the wire,
+/// including any encoding and the configured request body limit.
+pub struct InProcessBody(reqwest::Body);
+
+impl HttpBody for InProcessBody {
+    type Data = Bytes;
+    type Error = InProcessError;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, InProcessError>>> {
+        Pin::new(&mut self.0)
+            .poll_frame(cx)
+            .map_err(|error| Box::new(error) as InProcessError)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.0.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.0.size_hint()
+    }
+}
+
+impl std::fmt::Debug for InProcessBody {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InProcessBody").finish_non_exhaustive()
+    }
+}
+
+/// Concord's pre-authentication target for an in-process request.
+///
+/// Attached to every request handed to an [`InProcessService`] as an
+/// `http::Extensions` entry. Unlike the request URI, it never carries
+/// credential query placements, so it is safe to persist or log.
+#[derive(Clone, Debug)]
+pub struct LogicalTarget(url::Url);
+
+impl LogicalTarget {
+    pub fn url(&self) -> &url::Url {
+        &self.0
+    }
+}
+
+/// A type-erased `tower::Service` that executes requests in process.
+///
+/// The wrapped service is cloned for every request and driven through
+/// `poll_ready` before `call`, matching how tower middleware shares services.
+#[derive(Clone)]
+pub struct InProcessService {
+    inner: Arc<dyn CallService>,
+}
+
+impl InProcessService {
+    pub fn new<S, B>(service: S) -> Self
+    where
+        S: Service<http::Request<InProcessBody>, Response = http::Response<B>>
+            + Clone
+            + Send
+            + 'static,
+        S::Future: Send + 'static,
+        S::Error: Into<InProcessError>,
+        B: HttpBody + Send + 'static,
+        B::Data: Into<Bytes>,
+        B::Error: Into<InProcessError>,
+    {
+        Self {
+            inner: Arc::new(ServiceHandle(Mutex::new(service))),
+        }
+    }
+
+    pub(crate) async fn execute_native(
+        &self,
+        request: reqwest::Request,
+        context: Option<&RequestExecutionContext>,
+    ) -> Result<reqwest::Response, ReqwestError> {
+        let timeout = request.timeout().copied();
+        let mut request = http::Request::<reqwest::Body>::try_from(request)
+            .map_err(|_| failure(ReqwestErrorKind::Request))?
+            .map(InProcessBody);
+        if let Some(context) = context {
+            request
+                .extensions_mut()
+                .insert(LogicalTarget(context.logical_url.clone()));
+        }
+        let call = self.inner.call(request);
+        let response = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, call)
+                .await
+                .map_err(|_| failure(ReqwestErrorKind::Timeout))?,
+            None => call.await,
+        };
+        // Reqwest aborts a request whose body stream fails; a service that
+        // answers anyway must not turn a limit violation into a response.
+        if context.is_some_and(|context| context.body_errors.get().is_some()) {
+            return Err(failure(ReqwestErrorKind::Request));
+        }
+        response
+            .map(reqwest::Response::from)
+            .map_err(|_| failure(ReqwestErrorKind::Request))
+    }
+}
+
+impl std::fmt::Debug for InProcessService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InProcessService").finish_non_exhaustive()
+    }
+}
+
+/// Route application requests through `service` instead of Reqwest.
+pub fn configure_application_service(
+    builder: SafeReqwestBuilder,
+    service: InProcessService,
+) -> SafeReqwestBuilder {
+    builder.with_in_process_application_service(service)
+}
+
+/// Route credential-provider requests through `service` instead of Reqwest.
+pub fn configure_provider_service(
+    builder: SafeReqwestBuilder,
+    service: InProcessService,
+) -> SafeReqwestBuilder {
+    builder.with_in_process_provider_service(service)
+}
+
+trait CallService: Send + Sync {
+    fn call(&self, request: http::Request<InProcessBody>) -> ServiceFuture;
+}
+
+struct ServiceHandle<S>(Mutex<S>);
+
+impl<S, B> CallService for ServiceHandle<S>
+where
+    S: Service<http::Request<InProcessBody>, Response = http::Response<B>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Into<InProcessError>,
+    B: HttpBody + Send + 'static,
+    B::Data: Into<Bytes>,
+    B::Error: Into<InProcessError>,
+{
+    fn call(&self, request: http::Request<InProcessBody>) -> ServiceFuture {
+        let mut service = self
+            .0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone();
+        Box::pin(async move {
+            std::future::poll_fn(|cx| service.poll_ready(cx))
+                .await
+                .map_err(Into::into)?;
+            let response = service.call(request).await.map_err(Into::into)?;
+            Ok(response.map(|body| reqwest::Body::wrap(SyncBody(Mutex::new(Box::pin(body))))))
+        })
+    }
+}
+
+/// Adapts `Send`-only service bodies to the `Sync` bound Reqwest requires.
+struct SyncBody<B>(Mutex<Pin<Box<B>>>);
+
+impl<B> HttpBody for SyncBody<B>
+where
+    B: HttpBody + Send,
+    B::Data: Into<Bytes>,
+    B::Error: Into<InProcessError>,
+{
+    type Data = Bytes;
+    type Error = InProcessError;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Bytes>, InProcessError>>> {
+        let body = self
+            .get_mut()
+            .0
+            .get_mut()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        body.as_mut()
+            .poll_frame(cx)
+            .map_ok(|frame| frame.map_data(Into::into))
+            .map_err(Into::into)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .size_hint()
+    }
+}
+
+#[derive(Debug)]
+struct OpaqueInProcessFailure;
+
+impl std::fmt::Display for OpaqueInProcessFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("in-process service execution failed")
+    }
+}
+
+impl std::error::Error for OpaqueInProcessFailure {}
+
+fn failure(kind: ReqwestErrorKind) -> ReqwestError {
+    ReqwestError::with_kind(kind, OpaqueInProcessFailure)
+}
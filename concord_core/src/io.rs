@@ -848,7 +848,16 @@ impl PreparedEndpointCore {
         {
             policy.ensure_accept(accept);
         }
-        let (headers, query, timeout, mut rate_limit) = policy.into_parts();
+        let (
+            headers,
+            query,
+            timeout,
+            mut rate_limit,
+            cache,
+            idempotency_key,
+            coalesce,
+            hedge_after,
+        ) = policy.into_parts();
         rate_limit.canonicalize();
         let auth = self
             .authentication
@@ -871,6 +880,10 @@ impl PreparedEndpointCore {
                     timeout,
                     auth: crate::auth::AuthPlan { requirements: auth },
                     rate_limit,
+                    cache,
+                    idempotency_key,
+                    coalesce,
+                    hedge_after,
                 },
                 response,
                 pagination: None,
@@ -1022,6 +1035,7 @@ where
             ),
             no_content: false,
             format: crate::codec::Format::Binary,
+            errors: Default::default(),
         };
         let plan = self.core.into_plan(client, response)?;
         client.execute_stream_response::<M>(plan).await
@@ -1214,6 +1228,7 @@ impl ResponseEntity for BytesResponse {
                 accept: None,
                 no_content: false,
                 format: crate::codec::Format::Binary,
+                errors: Default::default(),
             },
             capabilities: ResponseEntityCapabilities {
                 supports_pagination: false,
@@ -1260,6 +1275,7 @@ impl ResponseEntity for NoContentResponse {
                 accept: None,
                 no_content: true,
                 format: crate::codec::Format::Text,
+                errors: Default::default(),
             },
             capabilities: ResponseEntityCapabilities {
                 supports_pagination: false,
@@ -1312,6 +1328,7 @@ where
                 ),
                 no_content: false,
                 format: crate::codec::Format::Binary,
+                errors: Default::default(),
             },
             capabilities: ResponseEntityCapabilities {
                 supports_pagination: false,
@@ -1332,6 +1349,37 @@ where
     }
 }
 
+/// Newline-delimited JSON response decoded lazily into one `T` per line.
+#[cfg(feature = "json")]
+pub struct JsonLines<T>(PhantomData<fn() -> T>);
+
+#[cfg(feature = "json")]
+impl<T> ResponseEntity for JsonLines<T>
+where
+    T: serde::de::DeserializeOwned + Send + 'static,
+{
+    type Output = crate::stream_response::JsonLinesStream<T>;
+
+    fn plan(ctx: ErrorContext) -> Result<ResponseEntityPlan, ApiClientError> {
+        <RawStreamResponse<crate::media::JsonLinesContentType> as ResponseEntity>::plan(ctx)
+    }
+
+    fn execute<'a, Cx>(
+        client: &'a ApiClient<Cx>,
+        plan: RequestPlan,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Output, ApiClientError>> + Send + 'a>>
+    where
+        Cx: ClientContext,
+    {
+        Box::pin(async move {
+            client
+                .execute_stream_response::<crate::media::JsonLinesContentType>(plan)
+                .await
+                .map(crate::stream_response::JsonLinesStream::new)
+        })
+    }
+}
+
 async fn execute_buffered_codec_response<Cx, C>(
     client: &ApiClient<Cx>,
     plan: RequestPlan,
@@ -1416,6 +1464,7 @@ where
             .map_err(|_| ApiClientError::invalid_param(ctx.clone(), "content_type"))?,
         no_content: C::is_no_content(),
         format: C::format(),
+        errors: Default::default(),
     })
 }
 
@@ -1457,6 +1506,7 @@ where
         status,
         headers: parts.headers,
         value,
+        cache: context.cache,
     })
 }
 
@@ -1473,6 +1523,7 @@ fn decode_bytes_response_with_meta(
         status,
         headers: parts.headers,
         value: body,
+        cache: context.cache,
     })
 }
 
@@ -1489,6 +1540,7 @@ fn decode_no_content_response_with_meta(
         status,
         headers: parts.headers,
         value: (),
+        cache: context.cache,
     })
 }
 
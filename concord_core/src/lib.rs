@@ -2,8 +2,12 @@
 extern crate self as concord_core;
 
 mod auth;
+mod backoff_retry;
 mod body;
+mod cache;
+mod circuit_breaker;
 mod client;
+mod clock;
 mod codec;
 mod debug;
 #[cfg(any(test, feature = "dangerous-dev-tools"))]
@@ -12,10 +16,16 @@ mod endpoint;
 pub mod error;
 mod execution_meta;
 mod header_ownership;
+mod idempotency;
+#[cfg(feature = "dangerous-in-process")]
+pub mod in_process;
 mod io;
 mod media;
+mod metrics;
 #[cfg(feature = "multipart")]
 mod multipart;
+#[cfg(feature = "openapi")]
+mod openapi;
 mod pagination;
 mod policy;
 mod rate_limit;
@@ -29,6 +39,7 @@ mod runtime_state;
 mod secret;
 mod stream_body;
 mod stream_response;
+mod telemetry;
 mod timeout;
 mod transport;
 mod types;
@@ -52,10 +63,11 @@ pub mod __development;
 
 pub mod prelude {
     pub use crate::auth::{AccessToken, ApiKey, AuthError, BasicCredential};
+    pub use crate::cache::{CacheStatus, InMemoryResponseCache};
     pub use crate::client::{ApiClient, ClientContext};
     #[cfg(feature = "json")]
     pub use crate::codec::json::Json;
-    pub use crate::codec::{ContentType, NoContent, text::Text};
+    pub use crate::codec::{ContentType, NoContent, form::Form, text::Text};
     pub use crate::debug::DebugLevel;
     pub use crate::error::{
         ApiClientError, ClientBuildErrorKind, ErrorCategory, PaginationError, PaginationErrorKind,
@@ -63,6 +75,8 @@ pub mod prelude {
     };
     pub use crate::execution_meta::RequestExecutionMeta;
     pub use crate::header_ownership::HeaderOwnershipError;
+    #[cfg(feature = "openapi")]
+    pub use crate::openapi::OpenApiDocument;
     pub use crate::pagination::{
         CursorPagination, HasNextCursor, OffsetLimitPagination, PageItems, PagedPagination,
         PaginationTermination,
@@ -72,7 +86,7 @@ pub mod prelude {
         RateLimitError, RateLimitErrorKind, RateLimitObservation, RateLimitObserver,
         RateLimitResponseContext,
     };
-    pub use crate::request::{PaginatedRequest, PendingRequest};
+    pub use crate::request::{ItemStream, PageStream, PaginatedRequest, PendingRequest};
     pub use crate::retry_mode::{
         ProviderOperationRetryMode, RetryMode, RetryModeError, StatusRetryConfig,
     };
@@ -81,17 +95,28 @@ pub mod prelude {
 }
 
 pub mod advanced {
-    #[cfg(feature = "json")]
-    pub use crate::auth::OAuth2ClientCredentialsProvider;
     pub use crate::auth::{
         AuthChallengeMode, AuthChallengePolicy, AuthError, AuthErrorKind, AuthFuture,
         AuthHttpExecutor, AuthHttpRequest, AuthHttpResponse, AuthInternalPolicy, AuthMode,
         AuthPlacement, AuthPlan, AuthPreparationMode, AuthProviderBinding, AuthRecoveryReason,
         AuthRejectionDecision, AuthRequirement, AuthRequirementId, AuthStepPolicy,
         CredentialContext, CredentialId, CredentialLease, CredentialMaterial, CredentialProvider,
-        CredentialProviderState, CredentialRefreshReason, InvalidateReason, SecretCredential,
+        CredentialProviderState, CredentialRefreshReason, CredentialStore, FileCredentialStore,
+        InMemoryCredentialStore, InvalidateReason, SecretCredential, StoredCredential,
+    };
+    #[cfg(feature = "json")]
+    pub use crate::auth::{
+        OAuth2AuthorizationCode, OAuth2ClientCredentialsProvider, OAuth2Pkce,
+        OAuth2RefreshTokenProvider,
     };
+    pub use crate::backoff_retry::BackoffRetryConfig;
     pub use crate::body::{BodyError, BodyErrorKind};
+    pub use crate::cache::{
+        CacheFuture, CacheKey, CachePolicy, CacheStatus, CachedResponse, InMemoryResponseCache,
+        ResponseCache,
+    };
+    pub use crate::circuit_breaker::{CircuitBreakerConfig, CircuitScope};
+    pub use crate::clock::{Clock, ClockFuture, SystemClock};
     pub use crate::codec::{
         BodyCodec, CodecError, ContentType, DecodeContext, EncodeContext, EncodedBody,
         ResponseCodec,
@@ -100,7 +125,8 @@ pub mod advanced {
         DebugSink, NoopDebugSink, SanitizedHeaderValue, SanitizedHeaders, StderrDebugSink,
     };
     pub use crate::error::{
-        ClientBuildErrorKind, ErrorContext, FxError, PaginationError, PaginationErrorKind,
+        ClientBuildErrorKind, ErrorBody, ErrorContext, FxError, PaginationError,
+        PaginationErrorKind,
     };
     pub use crate::execution_meta::RequestExecutionMeta;
     pub use crate::io::{
@@ -108,7 +134,14 @@ pub mod advanced {
         PreparedStreamEndpoint, RequestAuthentication, RequestEntity,
     };
     pub use crate::media::{
-        Jpeg, JsonContentType, Mp3, Mp4, OctetStream, Pdf, Png, TextContentType, Zip,
+        FormUrlEncoded, Jpeg, JsonContentType, JsonLinesContentType, Mp3, Mp4, OctetStream, Pdf,
+        Png, TextContentType, Zip,
+    };
+    #[cfg(feature = "metrics")]
+    pub use crate::metrics::MetricsCrateRecorder;
+    pub use crate::metrics::{
+        DurationSummary, EndpointMetrics, InMemoryMetricsRecorder, MetricsKey, MetricsRecorder,
+        NoopMetricsRecorder,
     };
     #[cfg(feature = "multipart")]
     pub use crate::multipart::{
@@ -123,13 +156,15 @@ pub mod advanced {
     };
     pub use crate::policy::ClientPolicyBuilder;
     pub use crate::rate_limit::{
-        DefaultRateLimitResponsePolicy, DefaultRateLimiter, GovernorRateLimiter, NoopRateLimiter,
-        RateLimitBucketId, RateLimitBucketUse, RateLimitContext, RateLimitError,
-        RateLimitErrorKind, RateLimitFuture, RateLimitKey, RateLimitKeyPart, RateLimitKeyValue,
-        RateLimitPermit, RateLimitPlan, RateLimitResponseAction, RateLimitResponseContext,
-        RateLimitResponsePolicy, RateLimitScopeHint, RateLimitSetting, RateLimitWindow,
-        RateLimiter, parse_retry_after,
-    };
+        DefaultRateLimitResponsePolicy, DefaultRateLimiter, GovernorRateLimiter,
+        IetfRateLimitHeaders, NoopRateLimiter, RateLimitBucketId, RateLimitBucketUse,
+        RateLimitContext, RateLimitError, RateLimitErrorKind, RateLimitFuture, RateLimitGuard,
+        RateLimitKey, RateLimitKeyPart, RateLimitKeyValue, RateLimitPermit, RateLimitPlan,
+        RateLimitResponseAction, RateLimitResponseContext, RateLimitResponsePolicy,
+        RateLimitScopeHint, RateLimitSetting, RateLimitWindow, RateLimitWindowLearning,
+        RateLimiter, RiotRateLimitHeaders, XRateLimitHeaders, parse_retry_after,
+    };
+    pub use crate::redaction::is_sensitive_name;
     pub use crate::retry_mode::{
         ProviderOperationRetryMode, RetryMode, RetryModeError, StatusRetryConfig,
     };
@@ -139,7 +174,9 @@ pub mod advanced {
         RequestErrorHookContext, RuntimeHooks,
     };
     pub use crate::stream_body::{StreamBody, StreamBodyError};
-    pub use crate::stream_response::StreamResponse;
+    #[cfg(feature = "json")]
+    pub use crate::stream_response::JsonLinesStream;
+    pub use crate::stream_response::{Event, EventData, EventStream, Events, StreamResponse};
     pub use crate::transport::{
         ReqwestClientBuildError, SafeProxy, SafeProxyError, SafeReqwestBuilder,
     };
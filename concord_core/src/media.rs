@@ -2,7 +2,7 @@ use crate::codec::ContentType;
 use std::fmt;
 
 macro_rules! content_marker {
-    ($name:ident, $content_type:literal) => {
+    ($name:ident, $content_type:literal $(, aliases = [$($alias:literal),+ $(,)?])?) => {
         #[derive(Clone, Copy, Default, Eq, PartialEq)]
         pub struct $name;
 
@@ -14,12 +14,19 @@ macro_rules! content_marker {
 
         impl ContentType for $name {
             const CONTENT_TYPE: &'static str = $content_type;
+            $(const CONTENT_TYPE_ALIASES: &'static [&'static str] = &[$($alias),+];)?
         }
     };
 }
 
 content_marker!(JsonContentType, "application/json");
+content_marker!(
+    JsonLinesContentType,
+    "application/x-ndjson",
+    aliases = ["application/jsonl", "application/x-jsonlines"]
+);
 content_marker!(TextContentType, "text/plain; charset=utf-8");
+content_marker!(FormUrlEncoded, "application/x-www-form-urlencoded");
 content_marker!(OctetStream, "application/octet-stream");
 content_marker!(Mp3, "audio/mpeg");
 content_marker!(Mp4, "video/mp4");
@@ -52,7 +59,17 @@ mod tests {
             http::HeaderValue::from_static("multipart/form-data")
         );
         assert_eq!(JsonContentType::CONTENT_TYPE, "application/json");
+        assert_eq!(JsonLinesContentType::CONTENT_TYPE, "application/x-ndjson");
+        assert_eq!(
+            JsonLinesContentType::CONTENT_TYPE_ALIASES,
+            ["application/jsonl", "application/x-jsonlines"]
+        );
+        assert!(JsonContentType::CONTENT_TYPE_ALIASES.is_empty());
         assert_eq!(TextContentType::CONTENT_TYPE, "text/plain; charset=utf-8");
+        assert_eq!(
+            FormUrlEncoded::CONTENT_TYPE,
+            "application/x-www-form-urlencoded"
+        );
         assert_eq!(OctetStream::CONTENT_TYPE, "application/octet-stream");
         assert_eq!(Mp3::CONTENT_TYPE, "audio/mpeg");
         assert_eq!(Mp4::CONTENT_TYPE, "video/mp4");
@@ -0,0 +1,409 @@
+//! Per-endpoint request metrics.
+//!
+//! A [`MetricsRecorder`] is fed at the execution phase boundaries of every
+//! visible execution. Measurements are keyed by the static endpoint name and
+//! the request method only, so label cardinality is bounded by the API
+//! definition rather than by URLs, parameters, or response content.
+
+use crate::error::ErrorCategory;
+use http::{Method, StatusCode};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Bounded label set for one measurement.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MetricsKey<'a> {
+    pub endpoint: &'static str,
+    pub method: &'a Method,
+}
+
+/// Receives timings and counts for visible executions.
+///
+/// Callbacks run inline on the request path and must not block. Every method
+/// defaults to doing nothing.
+pub trait MetricsRecorder: Send + Sync + 'static {
+    /// Time spent in `RateLimiter::acquire` before the execution was sent.
+    fn rate_limit_wait(&self, _key: MetricsKey<'_>, _waited: Duration) {}
+
+    /// Time from handing the request to Reqwest until response headers
+    /// arrived.
+    fn time_to_headers(&self, _key: MetricsKey<'_>, _elapsed: Duration) {}
+
+    /// Time spent reading a buffered response body. Streaming bodies are read
+    /// by the caller and are not measured.
+    fn body_read(&self, _key: MetricsKey<'_>, _elapsed: Duration) {}
+
+    /// Status of every response head, including authentication challenges.
+    fn response_status(&self, _key: MetricsKey<'_>, _status: StatusCode) {}
+
+    /// A visible execution that failed before producing a response.
+    fn request_error(&self, _key: MetricsKey<'_>, _category: ErrorCategory) {}
+
+    /// An authentication challenge that starts the recovery execution.
+    fn auth_recovery(&self, _key: MetricsKey<'_>) {}
+}
+
+#[derive(Default)]
+pub struct NoopMetricsRecorder;
+
+impl MetricsRecorder for NoopMetricsRecorder {}
+
+/// Count, sum, and maximum of one duration series.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DurationSummary {
+    pub count: u64,
+    pub total: Duration,
+    pub max: Duration,
+}
+
+impl DurationSummary {
+    fn observe(&mut self, value: Duration) {
+        self.count += 1;
+        self.total = self.total.saturating_add(value);
+        self.max = self.max.max(value);
+    }
+
+    pub fn mean(&self) -> Option<Duration> {
+        let count = u32::try_from(self.count).ok().filter(|count| *count > 0)?;
+        Some(self.total / count)
+    }
+}
+
+/// Aggregated measurements for one endpoint and method.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct EndpointMetrics {
+    pub rate_limit_wait: DurationSummary,
+    pub time_to_headers: DurationSummary,
+    pub body_read: DurationSummary,
+    pub statuses: BTreeMap<u16, u64>,
+    pub request_errors: u64,
+    pub auth_recoveries: u64,
+}
+
+/// Process-local [`MetricsRecorder`] that aggregates per endpoint and method.
+#[derive(Default)]
+pub struct InMemoryMetricsRecorder {
+    entries: Mutex<BTreeMap<(&'static str, String), EndpointMetrics>>,
+}
+
+impl InMemoryMetricsRecorder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Measurements recorded so far for `endpoint` and `method`.
+    pub fn endpoint(&self, endpoint: &str, method: &Method) -> Option<EndpointMetrics> {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries
+            .iter()
+            .find(|((name, recorded), _)| *name == endpoint && recorded == method.as_str())
+            .map(|(_, metrics)| metrics.clone())
+    }
+
+    /// Every endpoint and method with at least one measurement.
+    pub fn snapshot(&self) -> Vec<(&'static str, Method, EndpointMetrics)> {
+        let entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        entries
+            .iter()
+            .filter_map(|((endpoint, method), metrics)| {
+                let method = Method::from_bytes(method.as_bytes()).ok()?;
+                Some((*endpoint, method, metrics.clone()))
+            })
+            .collect()
+    }
+
+    fn update(&self, key: MetricsKey<'_>, f: impl FnOnce(&mut EndpointMetrics)) {
+        let mut entries = self.entries.lock().unwrap_or_else(|err| err.into_inner());
+        f(entries
+            .entry((key.endpoint, key.method.as_str().to_owned()))
+            .or_default());
+    }
+}
+
+impl MetricsRecorder for InMemoryMetricsRecorder {
+    fn rate_limit_wait(&self, key: MetricsKey<'_>, waited: Duration) {
+        self.update(key, |metrics| metrics.rate_limit_wait.observe(waited));
+    }
+
+    fn time_to_headers(&self, key: MetricsKey<'_>, elapsed: Duration) {
+        self.update(key, |metrics| metrics.time_to_headers.observe(elapsed));
+    }
+
+    fn body_read(&self, key: MetricsKey<'_>, elapsed: Duration) {
+        self.update(key, |metrics| metrics.body_read.observe(elapsed));
+    }
+
+    fn response_status(&self, key: MetricsKey<'_>, status: StatusCode) {
+        self.update(key, |metrics| {
+            *metrics.statuses.entry(status.as_u16()).or_default() += 1;
+        });
+    }
+
+    fn request_error(&self, key: MetricsKey<'_>, _category: ErrorCategory) {
+        self.update(key, |metrics| metrics.request_errors += 1);
+    }
+
+    fn auth_recovery(&self, key: MetricsKey<'_>) {
+        self.update(key, |metrics| metrics.auth_recoveries += 1);
+    }
+}
+
+/// [`MetricsRecorder`] that forwards to the `metrics` crate's global
+/// recorder, labelled `endpoint` and `method`.
+///
+/// Durations are histograms in seconds (`concord_rate_limit_wait_seconds`,
+/// `concord_time_to_headers_seconds`, `concord_body_read_seconds`). Counts are
+/// `concord_responses_total` (also labelled `status`),
+/// `concord_request_errors_total` (also labelled `category`), and
+/// `concord_auth_recoveries_total`.
+#[cfg(feature = "metrics")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetricsCrateRecorder;
+
+#[cfg(feature = "metrics")]
+impl MetricsCrateRecorder {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "metrics")]
+impl MetricsRecorder for MetricsCrateRecorder {
+    fn rate_limit_wait(&self, key: MetricsKey<'_>, waited: Duration) {
+        ::metrics::histogram!(
+            "concord_rate_limit_wait_seconds",
+            "endpoint" => key.endpoint,
+            "method" => key.method.as_str().to_owned(),
+        )
+        .record(waited);
+    }
+
+    fn time_to_headers(&self, key: MetricsKey<'_>, elapsed: Duration) {
+        ::metrics::histogram!(
+            "concord_time_to_headers_seconds",
+            "endpoint" => key.endpoint,
+            "method" => key.method.as_str().to_owned(),
+        )
+        .record(elapsed);
+    }
+
+    fn body_read(&self, key: MetricsKey<'_>, elapsed: Duration) {
+        ::metrics::histogram!(
+            "concord_body_read_seconds",
+            "endpoint" => key.endpoint,
+            "method" => key.method.as_str().to_owned(),
+        )
+        .record(elapsed);
+    }
+
+    fn response_status(&self, key: MetricsKey<'_>, status: StatusCode) {
+        ::metrics::counter!(
+            "concord_responses_total",
+            "endpoint" => key.endpoint,
+            "method" => key.method.as_str().to_owned(),
+            "status" => status.as_str().to_owned(),
+        )
+        .increment(1);
+    }
+
+    fn request_error(&self, key: MetricsKey<'_>, category: ErrorCategory) {
+        ::metrics::counter!(
+            "concord_request_errors_total",
+            "endpoint" => key.endpoint,
+            "method" => key.method.as_str().to_owned(),
+            "category" => category_label(category),
+        )
+        .increment(1);
+    }
+
+    fn auth_recovery(&self, key: MetricsKey<'_>) {
+        ::metrics::counter!(
+            "concord_auth_recoveries_total",
+            "endpoint" => key.endpoint,
+            "method" => key.method.as_str().to_owned(),
+        )
+        .increment(1);
+    }
+}
+
+#[cfg(feature = "metrics")]
+fn category_label(category: ErrorCategory) -> &'static str {
+    match category {
+        ErrorCategory::Config => "config",
+        ErrorCategory::MissingCredential => "missing_credential",
+        ErrorCategory::AuthRejected => "auth_rejected",
+        ErrorCategory::Connect => "connect",
+        ErrorCategory::RequestExecution => "request_execution",
+        ErrorCategory::RequestBody => "request_body",
+        ErrorCategory::Timeout => "timeout",
+        ErrorCategory::HttpStatus => "http_status",
+        ErrorCategory::Decode => "decode",
+        ErrorCategory::Pagination => "pagination",
+        ErrorCategory::RateLimit => "rate_limit",
+        ErrorCategory::CircuitOpen => "circuit_open",
+        ErrorCategory::ResponseContract => "response_contract",
+        ErrorCategory::InternalInvariant => "internal_invariant",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_recorder_aggregates_per_endpoint_and_method() {
+        let recorder = InMemoryMetricsRecorder::new();
+        let get = MetricsKey {
+            endpoint: "ListPets",
+            method: &Method::GET,
+        };
+        recorder.time_to_headers(get, Duration::from_millis(10));
+        recorder.time_to_headers(get, Duration::from_millis(30));
+        recorder.response_status(get, StatusCode::OK);
+        recorder.response_status(get, StatusCode::OK);
+        recorder.auth_recovery(get);
+        recorder.response_status(
+            MetricsKey {
+                endpoint: "ListPets",
+                method: &Method::HEAD,
+            },
+            StatusCode::NOT_FOUND,
+        );
+
+        let metrics = recorder
+            .endpoint("ListPets", &Method::GET)
+            .expect("recorded");
+        assert_eq!(metrics.time_to_headers.count, 2);
+        assert_eq!(metrics.time_to_headers.max, Duration::from_millis(30));
+        assert_eq!(
+            metrics.time_to_headers.mean(),
+            Some(Duration::from_millis(20))
+        );
+        assert_eq!(metrics.statuses, BTreeMap::from([(200, 2)]));
+        assert_eq!(metrics.auth_recoveries, 1);
+        assert_eq!(metrics.rate_limit_wait.mean(), None);
+        assert_eq!(recorder.snapshot().len(), 2);
+        assert!(recorder.endpoint("ListPets", &Method::POST).is_none());
+    }
+
+    #[cfg(feature = "metrics")]
+    #[test]
+    fn metrics_crate_recorder_emits_labelled_series() {
+        use ::metrics::{
+            Counter, CounterFn, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+            SharedString, Unit,
+        };
+        use std::sync::Arc;
+
+        type Series = Arc<Mutex<Vec<(String, Vec<(String, String)>, f64)>>>;
+
+        struct Handle {
+            key: Key,
+            series: Series,
+        }
+
+        impl Handle {
+            fn push(&self, value: f64) {
+                let labels = self
+                    .key
+                    .labels()
+                    .map(|label| (label.key().to_owned(), label.value().to_owned()))
+                    .collect();
+                self.series.lock().expect("series lock").push((
+                    self.key.name().to_owned(),
+                    labels,
+                    value,
+                ));
+            }
+        }
+
+        impl CounterFn for Handle {
+            fn increment(&self, value: u64) {
+                self.push(value as f64);
+            }
+
+            fn absolute(&self, _value: u64) {}
+        }
+
+        impl HistogramFn for Handle {
+            fn record(&self, value: f64) {
+                self.push(value);
+            }
+        }
+
+        #[derive(Default)]
+        struct Capture {
+            series: Series,
+        }
+
+        impl Recorder for Capture {
+            fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+            fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+
+            fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+                Counter::from_arc(Arc::new(Handle {
+                    key: key.clone(),
+                    series: self.series.clone(),
+                }))
+            }
+
+            fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+                Gauge::noop()
+            }
+
+            fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+                Histogram::from_arc(Arc::new(Handle {
+                    key: key.clone(),
+                    series: self.series.clone(),
+                }))
+            }
+        }
+
+        let capture = Capture::default();
+        let key = MetricsKey {
+            endpoint: "ListPets",
+            method: &Method::GET,
+        };
+        ::metrics::with_local_recorder(&capture, || {
+            let recorder = MetricsCrateRecorder::new();
+            recorder.time_to_headers(key, Duration::from_millis(250));
+            recorder.response_status(key, StatusCode::NOT_FOUND);
+            recorder.request_error(key, ErrorCategory::Timeout);
+        });
+
+        let labels = |extra: &[(&str, &str)]| {
+            let mut labels = vec![
+                ("endpoint".to_owned(), "ListPets".to_owned()),
+                ("method".to_owned(), "GET".to_owned()),
+            ];
+            labels.extend(
+                extra
+                    .iter()
+                    .map(|(name, value)| (name.to_string(), value.to_string())),
+            );
+            labels
+        };
+        assert_eq!(
+            *capture.series.lock().expect("series lock"),
+            vec![
+                (
+                    "concord_time_to_headers_seconds".to_owned(),
+                    labels(&[]),
+                    0.25
+                ),
+                (
+                    "concord_responses_total".to_owned(),
+                    labels(&[("status", "404")]),
+                    1.0
+                ),
+                (
+                    "concord_request_errors_total".to_owned(),
+                    labels(&[("category", "timeout")]),
+                    1.0
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,697 @@
+//! OpenAPI 3.1 export for generated clients.
+//!
+//! With the `openapi` feature, every `api!` client gains an associated
+//! `openapi()` function that returns an [`OpenApiDocument`] built from the
+//! same resolved routes, parameters, auth placements, codecs, and pagination
+//! bindings the client executes. The macro emits one builder call per fact;
+//! nothing is inferred at runtime.
+//!
+//! Without `schemars`, bodies are described by media type only and parameters
+//! by their wire form (strings, or arrays of strings for repeated query keys).
+//! The `schemars` feature takes body, error, and parameter schemas from
+//! `schemars::JsonSchema`, collecting named definitions under
+//! `components.schemas`. Types that do not implement `JsonSchema` keep the
+//! media-type or wire-form description, so enabling the feature never adds a
+//! bound to an API's types.
+
+use crate::codec::{BodyCodec, ContentType, ResponseCodec};
+use serde_json::{Map, Value, json};
+use std::marker::PhantomData;
+
+/// An OpenAPI 3.1 document describing one generated client.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OpenApiDocument {
+    value: Value,
+}
+
+impl OpenApiDocument {
+    pub fn as_value(&self) -> &Value {
+        &self.value
+    }
+
+    pub fn into_value(self) -> Value {
+        self.value
+    }
+
+    /// Pretty-printed JSON, ready to write to an `openapi.json` artifact.
+    pub fn to_json_pretty(&self) -> String {
+        serde_json::to_string_pretty(&self.value).expect("JSON values always serialize")
+    }
+}
+
+impl serde::Serialize for OpenApiDocument {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.value.serialize(serializer)
+    }
+}
+
+/// How the export obtains one type's schema, chosen where the type is named.
+#[doc(hidden)]
+pub type OpenApiSchemaSource = fn(&mut OpenApiSchemas) -> Option<Value>;
+
+/// Selects a type's [`OpenApiSchemaSource`] by method resolution: with
+/// `schemars`, `(&&OpenApiSchemaProbe::<T>::new()).openapi_schema()` picks
+/// [`OpenApiJsonSchema`] when `T: JsonSchema` and [`OpenApiOpaqueSchema`]
+/// otherwise. Use [`__openapi_schema!`](crate::__openapi_schema), which brings
+/// both traits into scope.
+#[doc(hidden)]
+pub struct OpenApiSchemaProbe<T: ?Sized>(PhantomData<fn(&T)>);
+
+impl<T: ?Sized> OpenApiSchemaProbe<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+#[doc(hidden)]
+pub trait OpenApiJsonSchema {
+    fn openapi_schema(&self) -> OpenApiSchemaSource;
+}
+
+#[cfg(feature = "schemars")]
+impl<T: ?Sized + schemars::JsonSchema> OpenApiJsonSchema for &OpenApiSchemaProbe<T> {
+    fn openapi_schema(&self) -> OpenApiSchemaSource {
+        OpenApiSchemas::json_schema::<T>
+    }
+}
+
+#[doc(hidden)]
+pub trait OpenApiOpaqueSchema {
+    fn openapi_schema(&self) -> OpenApiSchemaSource;
+}
+
+impl<T: ?Sized> OpenApiOpaqueSchema for OpenApiSchemaProbe<T> {
+    fn openapi_schema(&self) -> OpenApiSchemaSource {
+        OpenApiSchemas::opaque
+    }
+}
+
+/// Named schema definitions collected while the document is built.
+#[doc(hidden)]
+pub struct OpenApiSchemas {
+    #[cfg(feature = "schemars")]
+    generator: schemars::SchemaGenerator,
+}
+
+impl OpenApiSchemas {
+    #[cfg(feature = "schemars")]
+    fn new() -> Self {
+        let settings = schemars::generate::SchemaSettings::draft2020_12().with(|settings| {
+            settings.definitions_path = "/components/schemas".into();
+            settings.meta_schema = None;
+        });
+        Self {
+            generator: settings.into_generator(),
+        }
+    }
+
+    #[cfg(not(feature = "schemars"))]
+    fn new() -> Self {
+        Self {}
+    }
+
+    #[cfg(feature = "schemars")]
+    fn json_schema<T: ?Sized + schemars::JsonSchema>(&mut self) -> Option<Value> {
+        Some(self.generator.subschema_for::<T>().to_value())
+    }
+
+    fn opaque(&mut self) -> Option<Value> {
+        None
+    }
+
+    #[cfg(feature = "schemars")]
+    fn into_components(mut self) -> Map<String, Value> {
+        self.generator.take_definitions(true)
+    }
+
+    #[cfg(not(feature = "schemars"))]
+    fn into_components(self) -> Map<String, Value> {
+        Map::new()
+    }
+}
+
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OpenApiParamLocation {
+    Path,
+    Query,
+    Header,
+}
+
+impl OpenApiParamLocation {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Path => "path",
+            Self::Query => "query",
+            Self::Header => "header",
+        }
+    }
+}
+
+/// Security scheme derived from one credential and its placement.
+#[doc(hidden)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OpenApiSecurityScheme {
+    ApiKey {
+        location: OpenApiParamLocation,
+        name: &'static str,
+    },
+    Bearer,
+    Basic,
+    OAuth2ClientCredentials {
+        token_url: &'static str,
+        scope: Option<&'static str>,
+    },
+    OAuth2RefreshToken {
+        token_url: &'static str,
+    },
+}
+
+impl OpenApiSecurityScheme {
+    fn to_value(self) -> Value {
+        match self {
+            Self::ApiKey { location, name } => {
+                json!({ "type": "apiKey", "in": location.as_str(), "name": name })
+            }
+            Self::Bearer => json!({ "type": "http", "scheme": "bearer" }),
+            Self::Basic => json!({ "type": "http", "scheme": "basic" }),
+            Self::OAuth2ClientCredentials { token_url, scope } => {
+                let scopes = scope
+                    .into_iter()
+                    .flat_map(str::split_whitespace)
+                    .map(|scope| (scope.to_string(), Value::String(String::new())))
+                    .collect::<Map<_, _>>();
+                json!({
+                    "type": "oauth2",
+                    "flows": {
+                        "clientCredentials": { "tokenUrl": token_url, "scopes": scopes }
+                    }
+                })
+            }
+            // OpenAPI has no refresh-token-only flow; the bearer token is
+            // what the API itself sees.
+            Self::OAuth2RefreshToken { token_url } => json!({
+                "type": "http",
+                "scheme": "bearer",
+                "description": format!(
+                    "Access token obtained from `{token_url}` with the OAuth2 refresh-token grant."
+                ),
+            }),
+        }
+    }
+}
+
+/// Assembles the document from macro-emitted facts.
+#[doc(hidden)]
+pub struct OpenApiBuilder {
+    title: &'static str,
+    version: &'static str,
+    servers: Vec<Value>,
+    paths: Map<String, Value>,
+    security_schemes: Map<String, Value>,
+    schemas: OpenApiSchemas,
+}
+
+impl OpenApiBuilder {
+    pub fn new(title: &'static str, version: &'static str) -> Self {
+        Self {
+            title,
+            version,
+            servers: Vec::new(),
+            paths: Map::new(),
+            security_schemes: Map::new(),
+            schemas: OpenApiSchemas::new(),
+        }
+    }
+
+    /// A server shared by every operation. `variables` name the `{...}`
+    /// placeholders filled from client variables.
+    pub fn server(&mut self, url: &str, variables: &[&str]) {
+        self.servers.push(server(url, variables));
+    }
+
+    pub fn security_scheme(&mut self, name: &str, scheme: OpenApiSecurityScheme) {
+        self.security_schemes
+            .insert(name.to_string(), scheme.to_value());
+    }
+
+    pub fn operation(
+        &mut self,
+        method: &str,
+        path: &str,
+        operation_id: &str,
+        build: impl FnOnce(&mut OpenApiOperation<'_>),
+    ) {
+        let mut operation = OpenApiOperation {
+            schemas: &mut self.schemas,
+            value: Map::new(),
+            parameters: Vec::new(),
+            responses: Map::new(),
+            security: Map::new(),
+        };
+        operation
+            .value
+            .insert("operationId".into(), operation_id.into());
+        build(&mut operation);
+        let operation = operation.finish();
+        let item = self
+            .paths
+            .entry(path.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if let Value::Object(item) = item {
+            item.insert(method.to_ascii_lowercase(), operation);
+        }
+    }
+
+    pub fn finish(self) -> OpenApiDocument {
+        let mut document = Map::new();
+        document.insert("openapi".into(), "3.1.0".into());
+        document.insert(
+            "info".into(),
+            json!({ "title": self.title, "version": self.version }),
+        );
+        if !self.servers.is_empty() {
+            document.insert("servers".into(), Value::Array(self.servers));
+        }
+        document.insert("paths".into(), Value::Object(self.paths));
+        let mut components = Map::new();
+        let schemas = self.schemas.into_components();
+        if !schemas.is_empty() {
+            components.insert("schemas".into(), Value::Object(schemas));
+        }
+        if !self.security_schemes.is_empty() {
+            components.insert(
+                "securitySchemes".into(),
+                Value::Object(self.security_schemes),
+            );
+        }
+        if !components.is_empty() {
+            document.insert("components".into(), Value::Object(components));
+        }
+        OpenApiDocument {
+            value: Value::Object(document),
+        }
+    }
+}
+
+/// One operation under construction.
+#[doc(hidden)]
+pub struct OpenApiOperation<'a> {
+    schemas: &'a mut OpenApiSchemas,
+    value: Map<String, Value>,
+    parameters: Vec<Value>,
+    responses: Map<String, Value>,
+    security: Map<String, Value>,
+}
+
+impl OpenApiOperation<'_> {
+    pub fn tag(&mut self, tag: &str) {
+        self.value.insert("tags".into(), json!([tag]));
+    }
+
+    /// An operation-specific server, used when host labels vary per endpoint.
+    pub fn server(&mut self, url: &str, variables: &[&str]) {
+        self.value
+            .insert("servers".into(), json!([server(url, variables)]));
+    }
+
+    /// A parameter bound to an endpoint or client variable whose type has
+    /// `schema`. `repeated` marks query values sent as one key per element.
+    pub fn param(
+        &mut self,
+        location: OpenApiParamLocation,
+        name: &str,
+        required: bool,
+        repeated: bool,
+        schema: OpenApiSchemaSource,
+    ) {
+        let schema = schema(self.schemas).unwrap_or_else(|| wire_schema(repeated));
+        self.push_param(location, name, required, schema);
+    }
+
+    /// A parameter computed from an expression or `fmt[...]` template.
+    pub fn string_param(&mut self, location: OpenApiParamLocation, name: &str, required: bool) {
+        self.push_param(location, name, required, wire_schema(false));
+    }
+
+    /// A parameter the client always sends with a fixed value.
+    pub fn constant_param(&mut self, location: OpenApiParamLocation, name: &str, value: &str) {
+        self.push_param(
+            location,
+            name,
+            true,
+            json!({ "type": "string", "const": value }),
+        );
+    }
+
+    /// `schema` describes `C::Value`.
+    pub fn request_body<C: BodyCodec>(&mut self, schema: OpenApiSchemaSource) {
+        let media = self.media(schema);
+        self.set_request_body(<C::Content as ContentType>::CONTENT_TYPE, media);
+    }
+
+    pub fn stream_request<M: ContentType>(&mut self) {
+        self.set_request_body(M::CONTENT_TYPE, json!({}));
+    }
+
+    pub fn multipart_request(&mut self) {
+        self.set_request_body("multipart/form-data", json!({}));
+    }
+
+    /// `schema` describes `C::Value`.
+    pub fn success<C: ResponseCodec>(&mut self, schema: OpenApiSchemaSource) {
+        let media = self.media(schema);
+        self.set_success(Some((<C::Content as ContentType>::CONTENT_TYPE, media)));
+    }
+
+    pub fn success_bytes(&mut self) {
+        self.set_success(Some(("application/octet-stream", json!({}))));
+    }
+
+    pub fn success_no_content(&mut self) {
+        self.set_success(None);
+    }
+
+    pub fn success_stream<M: ContentType>(&mut self) {
+        self.set_success(Some((M::CONTENT_TYPE, json!({}))));
+    }
+
+    /// A newline-delimited JSON stream; the schema describes one line.
+    pub fn success_json_lines(&mut self, schema: OpenApiSchemaSource) {
+        let media = self.media(schema);
+        self.set_success(Some(("application/x-ndjson", media)));
+        self.responses["2XX"]["description"] =
+            "Newline-delimited JSON; each line matches the schema.".into();
+    }
+
+    /// A typed `on_error` mapping; `None` is the fallback for other statuses.
+    /// `schema` describes `C::Value`.
+    pub fn error<C: ResponseCodec>(&mut self, status: Option<u16>, schema: OpenApiSchemaSource) {
+        let media = self.media(schema);
+        let (key, description) = match status {
+            Some(status) => (status.to_string(), "Typed error response."),
+            None => ("default".to_string(), "Fallback typed error response."),
+        };
+        self.responses.insert(
+            key,
+            json!({
+                "description": description,
+                "content": { <C::Content as ContentType>::CONTENT_TYPE: media },
+            }),
+        );
+    }
+
+    /// Adds one scheme to the operation's single security requirement; every
+    /// placed credential is sent together.
+    pub fn security(&mut self, scheme: &str, scopes: &[&str]) {
+        self.security.insert(scheme.to_string(), json!(scopes));
+    }
+
+    /// Records the pagination controller and the parameters it advances.
+    pub fn pagination(&mut self, controller: &str, parameters: &[&str]) {
+        self.value.insert(
+            "x-concord-pagination".into(),
+            json!({ "controller": controller, "parameters": parameters }),
+        );
+    }
+
+    fn media(&mut self, schema: OpenApiSchemaSource) -> Value {
+        match schema(self.schemas) {
+            Some(schema) => json!({ "schema": schema }),
+            None => json!({}),
+        }
+    }
+
+    fn push_param(
+        &mut self,
+        location: OpenApiParamLocation,
+        name: &str,
+        required: bool,
+        schema: Value,
+    ) {
+        self.parameters.push(json!({
+            "name": name,
+            "in": location.as_str(),
+            "required": required || location == OpenApiParamLocation::Path,
+            "schema": schema,
+        }));
+    }
+
+    fn set_request_body(&mut self, content_type: &str, media: Value) {
+        self.value.insert(
+            "requestBody".into(),
+            json!({ "required": true, "content": { content_type: media } }),
+        );
+    }
+
+    fn set_success(&mut self, content: Option<(&str, Value)>) {
+        let mut response = json!({ "description": "Successful response." });
+        if let (Value::Object(response), Some((content_type, media))) = (&mut response, content) {
+            response.insert("content".into(), json!({ content_type: media }));
+        }
+        self.responses.insert("2XX".into(), response);
+    }
+
+    fn finish(mut self) -> Value {
+        if !self.parameters.is_empty() {
+            self.value
+                .insert("parameters".into(), Value::Array(self.parameters));
+        }
+        self.value
+            .insert("responses".into(), Value::Object(self.responses));
+        if !self.security.is_empty() {
+            self.value.insert(
+                "security".into(),
+                Value::Array(vec![Value::Object(self.security)]),
+            );
+        }
+        Value::Object(self.value)
+    }
+}
+
+fn server(url: &str, variables: &[&str]) -> Value {
+    let mut server = json!({ "url": url });
+    if !variables.is_empty() {
+        let variables = variables
+            .iter()
+            .map(|name| {
+                (
+                    name.to_string(),
+                    json!({
+                        "default": name,
+                        "description": format!("Host label taken from `{name}`."),
+                    }),
+                )
+            })
+            .collect::<Map<_, _>>();
+        if let Value::Object(server) = &mut server {
+            server.insert("variables".into(), Value::Object(variables));
+        }
+    }
+    server
+}
+
+/// Generated clients render path, query, and header values with `Display`.
+fn wire_schema(repeated: bool) -> Value {
+    if repeated {
+        json!({ "type": "array", "items": { "type": "string" } })
+    } else {
+        json!({ "type": "string" })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::Text;
+
+    #[test]
+    fn builder_assembles_operations_servers_and_security_schemes() {
+        let mut builder = OpenApiBuilder::new("UsersApi", "1.2.3");
+        builder.server("https://{region}.api.example.com", &["region"]);
+        builder.security_scheme(
+            "session",
+            OpenApiSecurityScheme::OAuth2ClientCredentials {
+                token_url: "https://auth.example.com/token",
+                scope: Some("users.read users.write"),
+            },
+        );
+        builder.operation("POST", "/users/{id}", "users.Rename", |op| {
+            op.tag("users");
+            op.param(
+                OpenApiParamLocation::Path,
+                "id",
+                false,
+                false,
+                crate::__openapi_schema!(u64),
+            );
+            op.param(
+                OpenApiParamLocation::Query,
+                "tag",
+                false,
+                true,
+                crate::__openapi_schema!(Vec<String>),
+            );
+            op.constant_param(OpenApiParamLocation::Header, "X-Client", "concord");
+            op.request_body::<Text<String>>(crate::__openapi_schema!(String));
+            op.success_no_content();
+            op.error::<Text<String>>(Some(404), crate::__openapi_schema!(String));
+            op.security("session", &["users.write"]);
+        });
+        let document = builder.finish().into_value();
+
+        assert_eq!(document["openapi"], "3.1.0");
+        assert_eq!(
+            document["info"],
+            json!({ "title": "UsersApi", "version": "1.2.3" })
+        );
+        assert_eq!(
+            document["servers"][0]["variables"]["region"]["default"],
+            "region"
+        );
+        assert_eq!(
+            document["components"]["securitySchemes"]["session"]["flows"]["clientCredentials"]["scopes"],
+            json!({ "users.read": "", "users.write": "" })
+        );
+
+        let operation = &document["paths"]["/users/{id}"]["post"];
+        assert_eq!(operation["operationId"], "users.Rename");
+        assert_eq!(
+            operation["security"],
+            json!([{ "session": ["users.write"] }])
+        );
+        // Path parameters are always required.
+        assert_eq!(operation["parameters"][0]["required"], true);
+        assert_eq!(operation["parameters"][1]["required"], false);
+        assert_eq!(
+            operation["parameters"][2]["schema"],
+            json!({ "type": "string", "const": "concord" })
+        );
+        assert!(
+            operation["requestBody"]["content"]
+                .get("text/plain; charset=utf-8")
+                .is_some()
+        );
+        assert_eq!(
+            operation["responses"]["2XX"],
+            json!({ "description": "Successful response." })
+        );
+        assert!(operation["responses"]["404"]["content"].is_object());
+    }
+
+    #[cfg(not(feature = "schemars"))]
+    #[test]
+    fn without_schemars_parameters_use_their_wire_form_and_bodies_have_no_schema() {
+        let mut builder = OpenApiBuilder::new("Api", "0.0.0");
+        builder.operation("GET", "/items", "List", |op| {
+            op.param(
+                OpenApiParamLocation::Query,
+                "page",
+                true,
+                false,
+                crate::__openapi_schema!(u64),
+            );
+            op.param(
+                OpenApiParamLocation::Query,
+                "id",
+                false,
+                true,
+                crate::__openapi_schema!(Vec<u64>),
+            );
+            op.success::<Text<String>>(crate::__openapi_schema!(String));
+        });
+        let document = builder.finish().into_value();
+        let operation = &document["paths"]["/items"]["get"];
+
+        assert_eq!(
+            operation["parameters"][0]["schema"],
+            json!({ "type": "string" })
+        );
+        assert_eq!(
+            operation["parameters"][1]["schema"],
+            json!({ "type": "array", "items": { "type": "string" } })
+        );
+        assert_eq!(
+            operation["responses"]["2XX"]["content"]["text/plain; charset=utf-8"],
+            json!({})
+        );
+        assert!(document.get("components").is_none());
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn with_schemars_schemas_come_from_json_schema_and_collect_components() {
+        struct User;
+
+        impl schemars::JsonSchema for User {
+            fn schema_name() -> std::borrow::Cow<'static, str> {
+                "User".into()
+            }
+
+            fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+                schemars::json_schema!({
+                    "type": "object",
+                    "properties": { "name": generator.subschema_for::<String>() },
+                })
+            }
+        }
+
+        let mut builder = OpenApiBuilder::new("Api", "0.0.0");
+        builder.operation("GET", "/users", "List", |op| {
+            op.param(
+                OpenApiParamLocation::Query,
+                "page",
+                true,
+                false,
+                crate::__openapi_schema!(u64),
+            );
+            op.success_json_lines(crate::__openapi_schema!(User));
+        });
+        let document = builder.finish().into_value();
+        let operation = &document["paths"]["/users"]["get"];
+
+        assert_eq!(operation["parameters"][0]["schema"]["type"], "integer");
+        assert_eq!(
+            operation["responses"]["2XX"]["content"]["application/x-ndjson"]["schema"],
+            json!({ "$ref": "#/components/schemas/User" })
+        );
+        assert_eq!(
+            document["components"]["schemas"]["User"]["properties"]["name"],
+            json!({ "type": "string" })
+        );
+    }
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn with_schemars_types_without_json_schema_keep_the_opaque_description() {
+        struct Opaque;
+
+        let mut builder = OpenApiBuilder::new("Api", "0.0.0");
+        builder.operation("GET", "/items/{id}", "Get", |op| {
+            op.param(
+                OpenApiParamLocation::Path,
+                "id",
+                true,
+                false,
+                crate::__openapi_schema!(Opaque),
+            );
+            op.success::<Text<String>>(crate::__openapi_schema!(String));
+            op.error::<Text<String>>(None, crate::__openapi_schema!(Opaque));
+        });
+        let document = builder.finish().into_value();
+        let operation = &document["paths"]["/items/{id}"]["get"];
+
+        assert_eq!(
+            operation["parameters"][0]["schema"],
+            json!({ "type": "string" })
+        );
+        assert_eq!(
+            operation["responses"]["2XX"]["content"]["text/plain; charset=utf-8"]["schema"],
+            json!({ "type": "string" })
+        );
+        assert_eq!(
+            operation["responses"]["default"]["content"]["text/plain; charset=utf-8"],
+            json!({})
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::cache::CachePolicy;
 use crate::rate_limit::RateLimitPlan;
 use core::time::Duration;
 use http::header::{ACCEPT, HeaderName};
@@ -15,6 +16,10 @@ pub type PolicySnapshot = (
     Vec<(String, String)>,
     Option<Duration>,
     RateLimitPlan,
+    Option<CachePolicy>,
+    Option<HeaderName>,
+    bool,
+    Option<Duration>,
 );
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
@@ -35,6 +40,10 @@ pub struct Policy {
     query: Vec<(String, String)>,
     timeout: Option<Duration>,
     rate_limit: RateLimitPlan,
+    cache: Option<CachePolicy>,
+    idempotency_key: Option<HeaderName>,
+    coalesce: bool,
+    hedge_after: Option<Duration>,
     // Current layer used for provenance decisions (not exposed in into_parts()).
     layer: PolicyLayer,
 
@@ -108,6 +117,39 @@ impl ClientPolicyBuilder {
         self.inner.clear_rate_limit();
     }
 
+    /// Opts the endpoint into the configured response cache.
+    pub fn set_cache(&mut self, cache: CachePolicy) {
+        self.inner.set_cache(cache);
+    }
+
+    pub fn clear_cache(&mut self) {
+        self.inner.clear_cache();
+    }
+
+    /// Sends a per-call idempotency key in `header` for unsafe methods.
+    pub fn set_idempotency_key(&mut self, header: HeaderName) {
+        self.inner.set_idempotency_key(header);
+    }
+
+    pub fn clear_idempotency_key(&mut self) {
+        self.inner.clear_idempotency_key();
+    }
+
+    /// Lets concurrent identical buffered `GET`/`HEAD` calls share one execution.
+    pub fn set_coalesce(&mut self, coalesce: bool) {
+        self.inner.set_coalesce(coalesce);
+    }
+
+    /// Sends a second execution of an idempotent `GET` when the first has
+    /// not produced response headers within `delay`.
+    pub fn set_hedge(&mut self, delay: Duration) {
+        self.inner.set_hedge(delay);
+    }
+
+    pub fn clear_hedge(&mut self) {
+        self.inner.clear_hedge();
+    }
+
     #[doc(hidden)]
     pub fn add_generated_rate_limit(
         &mut self,
@@ -156,6 +198,10 @@ impl Policy {
             query: Vec::new(),
             timeout: None,
             rate_limit: RateLimitPlan::new(),
+            cache: None,
+            idempotency_key: None,
+            coalesce: false,
+            hedge_after: None,
             layer: PolicyLayer::Client,
             accept_explicit_by_endpoint: false,
             accept_explicit_by_runtime: false,
@@ -192,6 +238,41 @@ impl Policy {
         self.rate_limit = RateLimitPlan::new();
     }
 
+    #[inline]
+    pub fn set_cache(&mut self, cache: CachePolicy) {
+        self.cache = Some(cache);
+    }
+
+    #[inline]
+    pub fn clear_cache(&mut self) {
+        self.cache = None;
+    }
+
+    #[inline]
+    pub fn set_idempotency_key(&mut self, header: HeaderName) {
+        self.idempotency_key = Some(header);
+    }
+
+    #[inline]
+    pub fn clear_idempotency_key(&mut self) {
+        self.idempotency_key = None;
+    }
+
+    #[inline]
+    pub fn set_coalesce(&mut self, coalesce: bool) {
+        self.coalesce = coalesce;
+    }
+
+    #[inline]
+    pub fn set_hedge(&mut self, delay: Duration) {
+        self.hedge_after = Some(delay);
+    }
+
+    #[inline]
+    pub fn clear_hedge(&mut self) {
+        self.hedge_after = None;
+    }
+
     #[cfg(test)]
     pub fn headers(&self) -> &HeaderMap {
         &self.headers
@@ -251,7 +332,16 @@ impl Policy {
     }
 
     pub fn into_parts(self) -> PolicySnapshot {
-        (self.headers, self.query, self.timeout, self.rate_limit)
+        (
+            self.headers,
+            self.query,
+            self.timeout,
+            self.rate_limit,
+            self.cache,
+            self.idempotency_key,
+            self.coalesce,
+            self.hedge_after,
+        )
     }
 }
 
@@ -262,6 +352,10 @@ impl From<ResolvedPolicy> for Policy {
             query: resolved.query,
             timeout: resolved.timeout,
             rate_limit: resolved.rate_limit,
+            cache: resolved.cache,
+            idempotency_key: resolved.idempotency_key,
+            coalesce: resolved.coalesce,
+            hedge_after: resolved.hedge_after,
             layer: PolicyLayer::Runtime,
             accept_explicit_by_endpoint: true,
             accept_explicit_by_runtime: true,
@@ -1,8 +1,9 @@
 #![allow(dead_code)]
 
 use crate::auth::AuthPlan;
+use crate::cache::CachePolicy;
 use crate::rate_limit::RateLimitPlan;
-use http::HeaderMap;
+use http::{HeaderMap, HeaderName};
 use std::time::Duration;
 
 #[derive(Clone, Debug, Default, PartialEq)]
@@ -12,4 +13,8 @@ pub struct ResolvedPolicy {
     pub timeout: Option<Duration>,
     pub auth: AuthPlan,
     pub rate_limit: RateLimitPlan,
+    pub cache: Option<CachePolicy>,
+    pub idempotency_key: Option<HeaderName>,
+    pub coalesce: bool,
+    pub hedge_after: Option<Duration>,
 }
@@ -1,7 +1,11 @@
 use super::RateLimitPlan;
+use crate::clock::Clock;
 use crate::debug::SanitizedHeaders;
 use http::Method;
 use http::StatusCode;
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
 use std::time::Duration;
 
 #[derive(Clone, Debug)]
@@ -13,12 +17,48 @@ pub struct RateLimitContext<'a> {
     pub page_index: u32,
     pub idempotent: bool,
     pub max_cooldown: Duration,
+    /// The client's time source for windows and cooldowns.
+    pub clock: &'a Arc<dyn Clock>,
     pub plan: &'a RateLimitPlan,
 }
 
 #[derive(Clone, Debug, Default)]
 pub struct RateLimitPermit;
 
+/// Resources an execution holds while it is in flight, returned by
+/// [`RateLimiter::acquire_guarded`](super::RateLimiter::acquire_guarded).
+/// The guard lives as long as the execution's response body: it is released
+/// once the body is fully read, fails, or is dropped, so values attached with
+/// [`RateLimitGuard::hold`] bound the number of executions in flight.
+#[derive(Default)]
+pub struct RateLimitGuard {
+    held: Vec<Box<dyn Any + Send + Sync>>,
+}
+
+impl RateLimitGuard {
+    /// Keep `value` alive until the guard is released.
+    #[inline]
+    pub fn hold(mut self, value: impl Any + Send + Sync) -> Self {
+        self.held.push(Box::new(value));
+        self
+    }
+}
+
+impl From<RateLimitPermit> for RateLimitGuard {
+    #[inline]
+    fn from(_: RateLimitPermit) -> Self {
+        Self::default()
+    }
+}
+
+impl fmt::Debug for RateLimitGuard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RateLimitGuard")
+            .field("held", &self.held.len())
+            .finish()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct RateLimitResponseContext<'a> {
     pub meta: RateLimitContext<'a>,
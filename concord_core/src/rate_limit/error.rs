@@ -44,6 +44,15 @@ impl RateLimitError {
     pub fn message(&self) -> &str {
         self.message.as_ref()
     }
+
+    /// A copy for another caller of the same coalesced request.
+    pub(crate) fn share(&self) -> Self {
+        Self {
+            kind: self.kind,
+            message: self.message.clone(),
+            source: self.source.as_deref().map(crate::error::share_source),
+        }
+    }
 }
 
 impl Display for RateLimitError {
@@ -1,20 +1,39 @@
 use super::limiter::RateLimitFuture;
 use super::{
     DefaultRateLimitResponsePolicy, RateLimitBucketId, RateLimitBucketUse, RateLimitContext,
-    RateLimitKey, RateLimitKeyPart, RateLimitKeyValue, RateLimitObservation, RateLimitPermit,
-    RateLimitResponseAction, RateLimitResponseContext, RateLimitResponsePolicy, RateLimitTarget,
-    RateLimitWindow, RateLimiter,
+    RateLimitGuard, RateLimitKey, RateLimitKeyPart, RateLimitKeyValue, RateLimitObservation,
+    RateLimitPermit, RateLimitResponseAction, RateLimitResponseContext, RateLimitResponsePolicy,
+    RateLimitTarget, RateLimitWindow, RateLimitWindowLearning, RateLimiter,
 };
+use crate::clock::Clock;
 use crate::error::{ApiClientError, ErrorContext};
-use governor::{DefaultDirectRateLimiter, Quota, RateLimiter as Governor};
+use governor::middleware::NoOpMiddleware;
+use governor::state::{InMemoryState, NotKeyed};
+use governor::{Quota, RateLimiter as Governor};
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
+use std::num::NonZeroU32;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::time::Instant;
+use tokio::sync::Semaphore;
 
 pub type DefaultRateLimiter = GovernorRateLimiter;
 
+type WindowLimiter = Governor<NotKeyed, InMemoryState, WindowClock, NoOpMiddleware<Instant>>;
+
+/// Feeds the client clock into governor so window refills follow it.
+#[derive(Clone)]
+struct WindowClock(Arc<dyn Clock>);
+
+impl governor::clock::Clock for WindowClock {
+    type Instant = Instant;
+
+    fn now(&self) -> Instant {
+        self.0.now()
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
 struct GovernorWindowSpec {
     id: RateLimitBucketId,
@@ -22,7 +41,14 @@ struct GovernorWindowSpec {
     window: RateLimitWindow,
 }
 
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
+struct GovernorConcurrencySpec {
+    id: RateLimitBucketId,
+    key: ResolvedRateLimitKey,
+    limit: NonZeroU32,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord)]
 struct ResolvedRateLimitKey(Vec<(Cow<'static, str>, Cow<'static, str>)>);
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
@@ -57,11 +83,15 @@ pub struct GovernorRateLimiter {
     max_window_entries: usize,
     max_cooldown_entries: usize,
     window_idle_ttl: Duration,
+    learned_windows: Mutex<HashMap<RateLimitBucketId, Vec<RateLimitWindow>>>,
+    concurrency: Mutex<HashMap<GovernorConcurrencySpec, Arc<Semaphore>>>,
+    window_learning: RateLimitWindowLearning,
+    window_ceilings: HashMap<RateLimitBucketId, Vec<RateLimitWindow>>,
 }
 
 #[derive(Clone)]
 struct GovernorWindowEntry {
-    limiter: Arc<DefaultDirectRateLimiter>,
+    limiter: Arc<WindowLimiter>,
     last_used: Instant,
 }
 
@@ -81,6 +111,7 @@ impl GovernorRateLimiter {
     const DEFAULT_MAX_WINDOW_ENTRIES: usize = 4096;
     pub const DEFAULT_MAX_COOLDOWN_ENTRIES: usize = 4096;
     const DEFAULT_WINDOW_IDLE_TTL: Duration = Duration::from_secs(15 * 60);
+    const MAX_LEARNED_WINDOWS_PER_BUCKET: usize = 8;
 
     pub fn new() -> Self {
         Self {
@@ -90,6 +121,10 @@ impl GovernorRateLimiter {
             max_window_entries: Self::DEFAULT_MAX_WINDOW_ENTRIES,
             max_cooldown_entries: Self::DEFAULT_MAX_COOLDOWN_ENTRIES,
             window_idle_ttl: Self::DEFAULT_WINDOW_IDLE_TTL,
+            learned_windows: Mutex::new(HashMap::new()),
+            concurrency: Mutex::new(HashMap::new()),
+            window_learning: RateLimitWindowLearning::default(),
+            window_ceilings: HashMap::new(),
         }
     }
 
@@ -113,16 +148,133 @@ impl GovernorRateLimiter {
         self
     }
 
+    pub fn with_window_learning(mut self, learning: RateLimitWindowLearning) -> Self {
+        self.window_learning = learning;
+        self
+    }
+
+    /// Windows that stay enforced for `bucket` whenever it has learned
+    /// windows, so a server cannot advertise a higher rate than these allow.
+    /// Without one, a bucket's configured windows are its ceiling.
+    pub fn with_window_ceiling(
+        mut self,
+        bucket: RateLimitBucketId,
+        windows: Vec<RateLimitWindow>,
+    ) -> Self {
+        self.window_ceilings.insert(bucket, windows);
+        self
+    }
+
+    /// The windows currently enforced for `bucket`: its configured windows
+    /// combined with any learned ones according to the window-learning mode.
+    pub fn effective_windows(&self, bucket: &RateLimitBucketUse) -> Vec<RateLimitWindow> {
+        let learned = self
+            .learned_windows
+            .lock()
+            .unwrap_or_else(|err| err.into_inner());
+        self.combine_windows(bucket, learned.get(&bucket.id))
+            .into_owned()
+    }
+
+    fn windows_for<'b>(
+        &self,
+        ctx: &RateLimitContext<'_>,
+        bucket: &'b RateLimitBucketUse,
+    ) -> Result<Cow<'b, [RateLimitWindow]>, ApiClientError> {
+        if self.window_learning == RateLimitWindowLearning::Ignore {
+            return Ok(Cow::Borrowed(&bucket.windows));
+        }
+        let learned = self.learned_windows.lock().map_err(|_| {
+            rate_limit_internal_error(ctx, "rate limit learned window lock poisoned")
+        })?;
+        Ok(self.combine_windows(bucket, learned.get(&bucket.id)))
+    }
+
+    fn combine_windows<'b>(
+        &self,
+        bucket: &'b RateLimitBucketUse,
+        learned: Option<&Vec<RateLimitWindow>>,
+    ) -> Cow<'b, [RateLimitWindow]> {
+        let Some(learned) = learned else {
+            return Cow::Borrowed(&bucket.windows);
+        };
+        let base = match self.window_learning {
+            RateLimitWindowLearning::Ignore => return Cow::Borrowed(&bucket.windows),
+            RateLimitWindowLearning::Tighten => tighten_windows(&bucket.windows, learned),
+            RateLimitWindowLearning::Replace => learned.clone(),
+        };
+        let ceiling = self
+            .window_ceilings
+            .get(&bucket.id)
+            .unwrap_or(&bucket.windows);
+        Cow::Owned(tighten_windows(&base, ceiling))
+    }
+
+    fn learn_windows(
+        &self,
+        ctx: &RateLimitContext<'_>,
+        reports: &[(RateLimitBucketId, Vec<RateLimitWindow>)],
+    ) -> Result<(), ApiClientError> {
+        if reports.is_empty() || self.window_learning == RateLimitWindowLearning::Ignore {
+            return Ok(());
+        }
+        let mut learned = self.learned_windows.lock().map_err(|_| {
+            rate_limit_internal_error(ctx, "rate limit learned window lock poisoned")
+        })?;
+        for (bucket, windows) in reports {
+            let windows = windows
+                .iter()
+                .filter(|window| !window.per.is_zero())
+                .take(Self::MAX_LEARNED_WINDOWS_PER_BUCKET)
+                .cloned()
+                .collect::<Vec<_>>();
+            if windows.is_empty()
+                || (!learned.contains_key(bucket) && learned.len() >= self.max_window_entries)
+            {
+                continue;
+            }
+            learned.insert(bucket.clone(), windows);
+        }
+        Ok(())
+    }
+
+    fn semaphore_for(
+        &self,
+        ctx: &RateLimitContext<'_>,
+        spec: GovernorConcurrencySpec,
+    ) -> Result<Arc<Semaphore>, ApiClientError> {
+        let mut guard = self
+            .concurrency
+            .lock()
+            .map_err(|_| rate_limit_internal_error(ctx, "rate limit concurrency lock poisoned"))?;
+        if let Some(semaphore) = guard.get(&spec) {
+            return Ok(semaphore.clone());
+        }
+        if guard.len() >= self.max_window_entries {
+            // A semaphore only the map references has no execution in flight.
+            guard.retain(|_, semaphore| Arc::strong_count(semaphore) > 1);
+            if guard.len() >= self.max_window_entries {
+                return Err(rate_limit_configuration_error(
+                    ctx,
+                    "rate-limit concurrency entry cap exceeded",
+                ));
+            }
+        }
+        let semaphore = Arc::new(Semaphore::new(spec.limit.get() as usize));
+        guard.insert(spec, semaphore.clone());
+        Ok(semaphore)
+    }
+
     fn limiter_for(
         &self,
         ctx: &RateLimitContext<'_>,
         spec: GovernorWindowSpec,
-    ) -> Result<Arc<DefaultDirectRateLimiter>, ApiClientError> {
+    ) -> Result<Arc<WindowLimiter>, ApiClientError> {
         let mut guard = self
             .windows
             .lock()
             .map_err(|_| rate_limit_internal_error(ctx, "rate limit window lock poisoned"))?;
-        let now = Instant::now();
+        let now = ctx.clock.now();
         self.prune_windows_if_needed(&mut guard, now);
         if let Some(limiter) = {
             guard.windows.get_mut(&spec).map(|existing| {
@@ -141,7 +293,10 @@ impl GovernorRateLimiter {
         }
 
         let quota = quota_for_window(ctx, &spec.window)?;
-        let limiter = Arc::new(Governor::direct(quota));
+        let limiter = Arc::new(Governor::direct_with_clock(
+            quota,
+            WindowClock(ctx.clock.clone()),
+        ));
         guard.windows.insert(
             spec,
             GovernorWindowEntry {
@@ -227,7 +382,7 @@ impl GovernorRateLimiter {
 
     async fn wait_cooldown(&self, ctx: &RateLimitContext<'_>) -> Result<(), ApiClientError> {
         loop {
-            let now = Instant::now();
+            let now = ctx.clock.now();
             let delay = {
                 let keys = cooldown_keys_for_acquire(ctx)?;
                 let mut guard = self.cooldowns.lock().map_err(|_| {
@@ -252,7 +407,7 @@ impl GovernorRateLimiter {
                     "rate-limit cooldown exceeds configured maximum",
                 ));
             }
-            tokio::time::sleep(delay).await;
+            ctx.clock.sleep(delay).await;
         }
     }
 
@@ -261,6 +416,7 @@ impl GovernorRateLimiter {
         ctx: &RateLimitResponseContext<'_>,
         mut observation: RateLimitObservation,
     ) -> Result<RateLimitResponseAction, ApiClientError> {
+        self.learn_windows(&ctx.meta, &observation.learned_windows)?;
         if !observation.limited {
             return Ok(RateLimitResponseAction::Continue);
         }
@@ -314,7 +470,7 @@ impl GovernorRateLimiter {
                 "rate-limit cooldown exceeds configured maximum",
             ));
         }
-        let now = Instant::now();
+        let now = ctx.clock.now();
         let until = now.checked_add(delay).ok_or_else(|| {
             rate_limit_configuration_error(ctx, "rate-limit cooldown duration overflowed")
         })?;
@@ -344,38 +500,103 @@ impl GovernorRateLimiter {
     }
 }
 
+/// `base` with each window of `limits` added, or lowering the maximum of a
+/// `base` window with the same period.
+fn tighten_windows(base: &[RateLimitWindow], limits: &[RateLimitWindow]) -> Vec<RateLimitWindow> {
+    let mut windows = base.to_vec();
+    for limit in limits {
+        match windows.iter_mut().find(|window| window.per == limit.per) {
+            Some(window) => window.max = window.max.min(limit.max),
+            None => windows.push(limit.clone()),
+        }
+    }
+    windows
+}
+
 fn prune_cooldowns(cooldowns: &mut HashMap<RateLimitCooldownKey, Instant>, now: Instant) {
     cooldowns.retain(|_, until| *until > now);
 }
 
 impl RateLimiter for GovernorRateLimiter {
+    /// Waits like [`RateLimiter::acquire_guarded`] but releases concurrency
+    /// slots as soon as they are taken.
     fn acquire<'a>(
         &'a self,
         ctx: RateLimitContext<'a>,
     ) -> RateLimitFuture<'a, Result<RateLimitPermit, ApiClientError>> {
+        Box::pin(async move { self.acquire_guarded(ctx).await.map(|_| RateLimitPermit) })
+    }
+
+    fn acquire_guarded<'a>(
+        &'a self,
+        ctx: RateLimitContext<'a>,
+    ) -> RateLimitFuture<'a, Result<RateLimitGuard, ApiClientError>> {
         Box::pin(async move {
             self.wait_cooldown(&ctx).await?;
 
             if ctx.plan.is_empty() {
-                return Ok(RateLimitPermit);
+                return Ok(RateLimitGuard::default());
+            }
+
+            // Slots are taken in one global order so two plans sharing
+            // concurrency buckets cannot each hold what the other waits for.
+            let mut slots = ctx
+                .plan
+                .buckets()
+                .iter()
+                .filter_map(|bucket| {
+                    let limit = bucket.concurrency?;
+                    Some(
+                        resolve_key(&ctx, &bucket.key).map(|key| GovernorConcurrencySpec {
+                            id: bucket.id.clone(),
+                            key,
+                            limit,
+                        }),
+                    )
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            slots.sort();
+            slots.dedup();
+            let mut guard = RateLimitGuard::default();
+            for spec in slots {
+                let slot = self
+                    .semaphore_for(&ctx, spec)?
+                    .acquire_owned()
+                    .await
+                    .map_err(|_| {
+                        rate_limit_internal_error(&ctx, "rate limit concurrency closed")
+                    })?;
+                guard = guard.hold(slot);
             }
 
             for bucket in ctx.plan.buckets() {
                 let key = resolve_key(&ctx, &bucket.key)?;
-                for window in &bucket.windows {
+                for window in self.windows_for(&ctx, bucket)?.iter() {
                     let spec = GovernorWindowSpec {
                         id: bucket.id.clone(),
                         key: key.clone(),
                         window: window.clone(),
                     };
                     let limiter = self.limiter_for(&ctx, spec)?;
-                    limiter.until_n_ready(bucket.cost).await.map_err(|_| {
-                        rate_limit_acquire_error(&ctx, "rate-limit cost exceeds bucket capacity")
-                    })?;
+                    loop {
+                        let decision = limiter.check_n(bucket.cost).map_err(|_| {
+                            rate_limit_acquire_error(
+                                &ctx,
+                                "rate-limit cost exceeds bucket capacity",
+                            )
+                        })?;
+                        match decision {
+                            Ok(()) => break,
+                            Err(not_until) => {
+                                let wait = not_until.wait_time_from(ctx.clock.now());
+                                ctx.clock.sleep(wait).await;
+                            }
+                        }
+                    }
                 }
             }
 
-            Ok(RateLimitPermit)
+            Ok(guard)
         })
     }
 
@@ -642,6 +863,7 @@ mod tests {
             page_index: 0,
             idempotent: true,
             max_cooldown: Duration::from_secs(60),
+            clock: crate::clock::test_system_clock(),
             plan: &PLAN,
         }
     }
@@ -659,6 +881,7 @@ mod tests {
             page_index: 0,
             idempotent: true,
             max_cooldown: Duration::from_secs(60),
+            clock: crate::clock::test_system_clock(),
             plan,
         }
     }
@@ -701,6 +924,7 @@ mod tests {
             page_index: 0,
             idempotent: true,
             max_cooldown: Duration::from_secs(60),
+            clock: crate::clock::test_system_clock(),
             plan: &PLAN,
         }
     }
@@ -1206,4 +1430,146 @@ mod tests {
         assert_eq!(guard.windows.len(), 1);
         assert!(!guard.windows.contains_key(&spec_a));
     }
+
+    fn window(max: u32, seconds: u64) -> RateLimitWindow {
+        RateLimitWindow::from_u32(max, Duration::from_secs(seconds)).expect("valid window")
+    }
+
+    fn learn(limiter: &GovernorRateLimiter, windows: Vec<RateLimitWindow>) {
+        limiter
+            .learn_windows(
+                &test_context(),
+                &[(RateLimitBucketId::new("method", "test"), windows)],
+            )
+            .expect("learned windows are stored");
+    }
+
+    #[test]
+    fn learned_windows_tighten_replace_or_are_ignored() {
+        let bucket = &test_context().plan.buckets()[0];
+
+        let limiter = GovernorRateLimiter::new();
+        assert_eq!(limiter.effective_windows(bucket), [window(10, 10)]);
+        learn(&limiter, vec![window(20, 10), window(100, 120)]);
+        assert_eq!(
+            limiter.effective_windows(bucket),
+            [window(10, 10), window(100, 120)]
+        );
+        learn(&limiter, vec![window(4, 10)]);
+        assert_eq!(limiter.effective_windows(bucket), [window(4, 10)]);
+
+        // Without a registered ceiling the configured windows bound what a
+        // server can advertise.
+        let limiter =
+            GovernorRateLimiter::new().with_window_learning(RateLimitWindowLearning::Replace);
+        learn(&limiter, vec![window(20, 10), window(100, 120)]);
+        assert_eq!(
+            limiter.effective_windows(bucket),
+            [window(10, 10), window(100, 120)]
+        );
+        learn(&limiter, vec![window(4, 10)]);
+        assert_eq!(limiter.effective_windows(bucket), [window(4, 10)]);
+
+        let limiter =
+            GovernorRateLimiter::new().with_window_learning(RateLimitWindowLearning::Ignore);
+        learn(&limiter, vec![window(1, 10)]);
+        assert_eq!(limiter.effective_windows(bucket), [window(10, 10)]);
+    }
+
+    #[test]
+    fn replaced_windows_stay_within_configured_ceilings() {
+        let bucket = &test_context().plan.buckets()[0];
+        let limiter = GovernorRateLimiter::new()
+            .with_window_learning(RateLimitWindowLearning::Replace)
+            .with_window_ceiling(
+                RateLimitBucketId::new("method", "test"),
+                vec![window(50, 10), window(500, 3600)],
+            );
+        assert_eq!(limiter.effective_windows(bucket), [window(10, 10)]);
+
+        let zero_period = RateLimitWindow::new(NonZeroU32::MIN, Duration::ZERO);
+        learn(
+            &limiter,
+            vec![window(80, 10), zero_period, window(100, 120)],
+        );
+        assert_eq!(
+            limiter.effective_windows(bucket),
+            [window(50, 10), window(100, 120), window(500, 3600)]
+        );
+    }
+
+    #[tokio::test]
+    async fn acquire_enforces_learned_windows() {
+        let limiter = GovernorRateLimiter::new();
+        learn(&limiter, vec![window(1, 60)]);
+
+        limiter
+            .acquire(test_context())
+            .await
+            .expect("first permit fits the learned window");
+        let second =
+            tokio::time::timeout(Duration::from_millis(50), limiter.acquire(test_context())).await;
+        assert!(second.is_err(), "the learned 1/60s window must block");
+    }
+
+    #[tokio::test]
+    async fn concurrency_slots_are_held_until_the_guard_is_dropped() {
+        let limiter = GovernorRateLimiter::new();
+        let plan = RateLimitPlan::from_buckets(vec![
+            RateLimitBucketUse::new(
+                "method",
+                "exports",
+                RateLimitKey::new(vec![RateLimitKeyPart::static_value("tenant", "a")]),
+            )
+            .with_concurrency(NonZeroU32::MIN),
+        ]);
+        let other_tenant = RateLimitPlan::from_buckets(vec![
+            RateLimitBucketUse::new(
+                "method",
+                "exports",
+                RateLimitKey::new(vec![RateLimitKeyPart::static_value("tenant", "b")]),
+            )
+            .with_concurrency(NonZeroU32::MIN),
+        ]);
+
+        let first = limiter
+            .acquire_guarded(hostless_context(&plan))
+            .await
+            .expect("first slot is free");
+        assert!(
+            tokio::time::timeout(
+                Duration::from_millis(20),
+                limiter.acquire_guarded(hostless_context(&plan))
+            )
+            .await
+            .is_err(),
+            "the only slot is taken"
+        );
+        limiter
+            .acquire_guarded(hostless_context(&other_tenant))
+            .await
+            .expect("other keys have their own slots");
+
+        drop(first);
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            limiter.acquire_guarded(hostless_context(&plan)),
+        )
+        .await
+        .expect("the released slot is reused")
+        .expect("acquire succeeds");
+
+        // A bare permit carries no slot, so it never blocks a later caller.
+        limiter
+            .acquire(hostless_context(&plan))
+            .await
+            .expect("slot is free");
+        tokio::time::timeout(
+            Duration::from_secs(1),
+            limiter.acquire_guarded(hostless_context(&plan)),
+        )
+        .await
+        .expect("acquire released its slot")
+        .expect("acquire succeeds");
+    }
 }
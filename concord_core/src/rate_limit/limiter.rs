@@ -1,6 +1,6 @@
 use super::{
-    RateLimitContext, RateLimitPermit, RateLimitResponseAction, RateLimitResponseContext,
-    RateLimitResponsePolicy,
+    RateLimitContext, RateLimitGuard, RateLimitPermit, RateLimitResponseAction,
+    RateLimitResponseContext, RateLimitResponsePolicy,
 };
 use crate::error::ApiClientError;
 use std::future::Future;
@@ -17,6 +17,16 @@ pub trait RateLimiter: Send + Sync + 'static {
         Box::pin(async { Ok(RateLimitPermit) })
     }
 
+    /// Acquire a permit together with anything the execution must hold while
+    /// it is in flight. The client calls this method; the default wraps
+    /// [`RateLimiter::acquire`] in an empty guard.
+    fn acquire_guarded<'a>(
+        &'a self,
+        ctx: RateLimitContext<'a>,
+    ) -> RateLimitFuture<'a, Result<RateLimitGuard, ApiClientError>> {
+        Box::pin(async move { self.acquire(ctx).await.map(RateLimitGuard::from) })
+    }
+
     fn on_response<'a>(
         &'a self,
         _ctx: RateLimitResponseContext<'a>,
@@ -10,7 +10,7 @@ mod governor_runtime;
 #[cfg(not(feature = "rate-limit-governor"))]
 mod no_governor_default;
 
-pub use context::{RateLimitContext, RateLimitPermit, RateLimitResponseContext};
+pub use context::{RateLimitContext, RateLimitGuard, RateLimitPermit, RateLimitResponseContext};
 pub use error::{RateLimitError, RateLimitErrorKind};
 #[cfg(feature = "rate-limit-governor")]
 pub use governor_runtime::{DefaultRateLimiter, GovernorRateLimiter};
@@ -19,11 +19,12 @@ pub use limiter::{NoopRateLimiter, RateLimitFuture, RateLimiter};
 pub use no_governor_default::{DefaultRateLimiter, GovernorRateLimiter};
 pub use plan::{
     RateLimitBucketId, RateLimitBucketUse, RateLimitKey, RateLimitKeyPart, RateLimitKeyValue,
-    RateLimitPlan, RateLimitSetting, RateLimitWindow,
+    RateLimitPlan, RateLimitSetting, RateLimitWindow, RateLimitWindowLearning,
 };
 #[allow(unused_imports)]
 pub use response::RateLimitTarget;
 pub use response::{
-    DefaultRateLimitResponsePolicy, RateLimitObservation, RateLimitObserver,
-    RateLimitResponseAction, RateLimitResponsePolicy, RateLimitScopeHint, parse_retry_after,
+    DefaultRateLimitResponsePolicy, IetfRateLimitHeaders, RateLimitObservation, RateLimitObserver,
+    RateLimitResponseAction, RateLimitResponsePolicy, RateLimitScopeHint, RiotRateLimitHeaders,
+    XRateLimitHeaders, parse_retry_after,
 };
@@ -152,6 +152,7 @@ mod tests {
             page_index: 0,
             idempotent: true,
             max_cooldown: Duration::from_secs(60),
+            clock: crate::clock::test_system_clock(),
             plan: &PLAN,
         }
     }
@@ -180,6 +181,7 @@ mod tests {
             page_index: 0,
             idempotent: true,
             max_cooldown: Duration::from_secs(60),
+            clock: crate::clock::test_system_clock(),
             plan: &PLAN,
         }
     }
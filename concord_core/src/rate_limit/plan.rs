@@ -73,6 +73,8 @@ pub struct RateLimitBucketUse {
     pub key: RateLimitKey,
     pub windows: Vec<RateLimitWindow>,
     pub cost: NonZeroU32,
+    /// Maximum executions of this bucket in flight per resolved key.
+    pub concurrency: Option<NonZeroU32>,
 }
 
 impl RateLimitBucketUse {
@@ -86,6 +88,7 @@ impl RateLimitBucketUse {
             key,
             windows: Vec::new(),
             cost: NonZeroU32::new(1).expect("1 is non-zero"),
+            concurrency: None,
         }
     }
 
@@ -106,9 +109,15 @@ impl RateLimitBucketUse {
         self.cost = cost;
         self
     }
+
+    #[inline]
+    pub fn with_concurrency(mut self, limit: NonZeroU32) -> Self {
+        self.concurrency = Some(limit);
+        self
+    }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct RateLimitBucketId {
     pub kind: Cow<'static, str>,
     pub name: Cow<'static, str>,
@@ -213,3 +222,18 @@ impl RateLimitWindow {
         })
     }
 }
+
+/// How [`GovernorRateLimiter`](super::GovernorRateLimiter) applies windows an
+/// observer learned from response headers.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RateLimitWindowLearning {
+    /// Keep the configured windows.
+    Ignore,
+    /// Enforce learned windows alongside the configured ones; a learned window
+    /// with the same period lowers the configured maximum but never raises it.
+    #[default]
+    Tighten,
+    /// Enforce only the learned windows, bounded by the ceiling registered
+    /// for the bucket, or by its configured windows when none is.
+    Replace,
+}
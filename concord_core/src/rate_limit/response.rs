@@ -1,4 +1,4 @@
-use super::{RateLimitBucketId, RateLimitResponseContext};
+use super::{RateLimitBucketId, RateLimitResponseContext, RateLimitWindow};
 use crate::debug::SanitizedHeaders;
 use http::StatusCode;
 use http::header::RETRY_AFTER;
@@ -69,6 +69,9 @@ pub struct RateLimitObservation {
     pub target: RateLimitTarget,
     pub scope: Option<RateLimitScopeHint>,
     pub retry_after: Option<Duration>,
+    /// Windows the server advertised for a bucket, applied according to the
+    /// limiter's [`RateLimitWindowLearning`](super::RateLimitWindowLearning).
+    pub learned_windows: Vec<(RateLimitBucketId, Vec<RateLimitWindow>)>,
 }
 
 impl RateLimitObservation {
@@ -85,6 +88,7 @@ impl RateLimitObservation {
             target: RateLimitTarget::current_plan_or_endpoint(),
             scope: Some(RateLimitScopeHint::CurrentEndpoint),
             retry_after: None,
+            learned_windows: Vec::new(),
         }
     }
 
@@ -101,6 +105,18 @@ impl RateLimitObservation {
         self
     }
 
+    /// Report the windows the server enforces for `bucket`. A `continue_`
+    /// observation may carry them too.
+    #[inline]
+    pub fn with_learned_windows(
+        mut self,
+        bucket: RateLimitBucketId,
+        windows: Vec<RateLimitWindow>,
+    ) -> Self {
+        self.learned_windows.push((bucket, windows));
+        self
+    }
+
     #[inline]
     pub fn scope_header(mut self, ctx: &RateLimitResponseContext<'_>, name: &str) -> Self {
         if let Some(value) = ctx.headers.get(name).map(|v| v.as_str().to_owned()) {
@@ -264,6 +280,258 @@ impl RateLimitResponsePolicy for DefaultRateLimitResponsePolicy {
     }
 }
 
+/// IETF `RateLimit` and `RateLimit-Policy` response headers.
+///
+/// Reads the structured-list form (`"default";r=0;t=30`), the earlier
+/// dictionary form (`limit=100, remaining=0, reset=30`), and separate
+/// `RateLimit-Remaining`/`RateLimit-Reset` headers. When a quota has no reset,
+/// the window `w` of the `RateLimit-Policy` item with the same name is used.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IetfRateLimitHeaders;
+
+impl RateLimitObserver for IetfRateLimitHeaders {
+    fn observe(&self, ctx: RateLimitResponseContext<'_>) -> RateLimitObservation {
+        let policies = header_str(&ctx, "ratelimit-policy")
+            .map(|raw| parse_structured_items(&raw))
+            .unwrap_or_default();
+        let mut items = header_str(&ctx, "ratelimit")
+            .map(|raw| parse_structured_items(&raw))
+            .unwrap_or_default();
+        if let Some(remaining) = header_str(&ctx, "ratelimit-remaining") {
+            let mut item = StructuredItem::default();
+            item.params.push(("r".to_string(), remaining));
+            if let Some(reset) = header_str(&ctx, "ratelimit-reset") {
+                item.params.push(("t".to_string(), reset));
+            }
+            items.push(item);
+        }
+        let exhausted = items
+            .iter()
+            .filter(|item| item.number(&["r", "remaining"]) == Some(0))
+            .filter_map(|item| {
+                let seconds = item.number(&["t", "reset"]).or_else(|| {
+                    policies
+                        .iter()
+                        .find(|policy| policy.name == item.name)
+                        .and_then(|policy| policy.number(&["w"]))
+                })?;
+                Some(Duration::from_secs(seconds))
+            })
+            .max();
+        quota_observation(&ctx, exhausted, RateLimitTarget::current_plan_or_endpoint())
+    }
+}
+
+/// GitHub-style `X-RateLimit-Remaining` with `X-RateLimit-Reset` (Unix
+/// seconds, or delta seconds for small values) or `X-RateLimit-Reset-After`
+/// (delta seconds).
+#[derive(Clone, Copy, Debug, Default)]
+pub struct XRateLimitHeaders;
+
+/// `X-RateLimit-Reset` values at or above this are Unix timestamps.
+const UNIX_RESET_THRESHOLD: u64 = 1_000_000_000;
+
+impl RateLimitObserver for XRateLimitHeaders {
+    fn observe(&self, ctx: RateLimitResponseContext<'_>) -> RateLimitObservation {
+        let exhausted = header_str(&ctx, "x-ratelimit-remaining")
+            .and_then(|raw| raw.trim().parse::<u64>().ok())
+            .filter(|remaining| *remaining == 0)
+            .and_then(|_| {
+                if let Some(after) = header_str(&ctx, "x-ratelimit-reset-after") {
+                    let seconds = after.trim().parse::<f64>().ok()?;
+                    return Duration::try_from_secs_f64(seconds).ok();
+                }
+                let reset = header_str(&ctx, "x-ratelimit-reset")?
+                    .trim()
+                    .parse::<u64>()
+                    .ok()?;
+                if reset < UNIX_RESET_THRESHOLD {
+                    return Some(Duration::from_secs(reset));
+                }
+                let now = SystemTime::now()
+                    .duration_since(SystemTime::UNIX_EPOCH)
+                    .ok()?
+                    .as_secs();
+                Some(Duration::from_secs(reset.saturating_sub(now)))
+            });
+        quota_observation(&ctx, exhausted, RateLimitTarget::current_plan_or_endpoint())
+    }
+}
+
+/// Riot-style `X-App-Rate-Limit`/`X-App-Rate-Limit-Count` and
+/// `X-Method-Rate-Limit`/`X-Method-Rate-Limit-Count` pairs of
+/// `limit:seconds` windows.
+///
+/// A window whose count reached its limit cools down the `application` or
+/// `method` bucket kind for the whole window. A `429` is scoped by
+/// `X-Rate-Limit-Type`. The advertised windows are reported as learned
+/// windows for the plan's buckets of the same kind.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RiotRateLimitHeaders;
+
+impl RateLimitObserver for RiotRateLimitHeaders {
+    fn observe(&self, ctx: RateLimitResponseContext<'_>) -> RateLimitObservation {
+        let exhausted = [
+            ("application", "x-app-rate-limit"),
+            ("method", "x-method-rate-limit"),
+        ]
+        .into_iter()
+        .filter_map(|(kind, name)| {
+            let limits = parse_riot_windows(&header_str(&ctx, name)?);
+            let counts = parse_riot_windows(&header_str(&ctx, &format!("{name}-count"))?);
+            let delay = counts
+                .iter()
+                .filter(|(count, window)| {
+                    limits
+                        .iter()
+                        .any(|(limit, limit_window)| limit_window == window && count >= limit)
+                })
+                .map(|(_, window)| Duration::from_secs(*window))
+                .max()?;
+            Some((kind, delay))
+        })
+        .max_by_key(|(_, delay)| *delay);
+
+        let mut observation = if ctx.is_429() {
+            let observation = RateLimitObservation::limited()
+                .scope_header(&ctx, "x-rate-limit-type")
+                .retry_after_header(&ctx);
+            match (observation.delay, exhausted) {
+                (None, Some((_, delay))) => observation.with_delay(delay),
+                _ => observation,
+            }
+        } else {
+            match exhausted {
+                Some((kind, delay)) => RateLimitObservation::limited()
+                    .with_target(RateLimitTarget::bucket_kind(
+                        kind,
+                        RateLimitTarget::current_plan_or_endpoint(),
+                    ))
+                    .with_delay(delay),
+                None => RateLimitObservation::continue_(),
+            }
+        };
+
+        // The advertised limits follow the API key's tier, so report them for
+        // every bucket of the matching kind in the current plan.
+        for (kind, name) in [
+            ("application", "x-app-rate-limit"),
+            ("method", "x-method-rate-limit"),
+        ] {
+            let Some(raw) = header_str(&ctx, name) else {
+                continue;
+            };
+            let windows = parse_riot_windows(&raw)
+                .into_iter()
+                .filter_map(|(limit, seconds)| {
+                    RateLimitWindow::from_u32(
+                        u32::try_from(limit).ok()?,
+                        Duration::from_secs(seconds),
+                    )
+                })
+                .collect::<Vec<_>>();
+            if windows.is_empty() {
+                continue;
+            }
+            for bucket in ctx.meta.plan.buckets() {
+                if bucket.id.kind == kind {
+                    observation =
+                        observation.with_learned_windows(bucket.id.clone(), windows.clone());
+                }
+            }
+        }
+        observation
+    }
+}
+
+/// A `429` keeps its `Retry-After`, falling back to the quota reset. Any other
+/// response installs a cooldown only when a quota is exhausted.
+fn quota_observation(
+    ctx: &RateLimitResponseContext<'_>,
+    exhausted: Option<Duration>,
+    target: RateLimitTarget,
+) -> RateLimitObservation {
+    if ctx.is_429() {
+        let observation = RateLimitObservation::limited()
+            .with_target(target)
+            .retry_after_header(ctx);
+        return match (observation.delay, exhausted) {
+            (None, Some(delay)) => observation.with_delay(delay),
+            _ => observation,
+        };
+    }
+    match exhausted {
+        Some(delay) if !delay.is_zero() => RateLimitObservation::limited()
+            .with_target(target)
+            .with_delay(delay),
+        _ => RateLimitObservation::continue_(),
+    }
+}
+
+fn header_str(ctx: &RateLimitResponseContext<'_>, name: &str) -> Option<String> {
+    let value = ctx.headers.get(name)?;
+    (!value.is_redacted()).then(|| value.as_str().to_owned())
+}
+
+/// One member of a structured-field list: an optional name and its
+/// parameters.
+#[derive(Debug, Default)]
+struct StructuredItem {
+    name: Option<String>,
+    params: Vec<(String, String)>,
+}
+
+impl StructuredItem {
+    fn number(&self, keys: &[&str]) -> Option<u64> {
+        self.params
+            .iter()
+            .find(|(key, _)| keys.contains(&key.as_str()))
+            .and_then(|(_, value)| value.parse().ok())
+    }
+}
+
+/// Parses `"a";r=1;t=2, "b";r=0` into named items. A dictionary whose
+/// members carry no names, such as `limit=100, remaining=0`, becomes one
+/// unnamed item.
+fn parse_structured_items(raw: &str) -> Vec<StructuredItem> {
+    let mut items = Vec::new();
+    let mut unnamed = StructuredItem::default();
+    for member in raw.split(',') {
+        let mut item = StructuredItem::default();
+        for (index, piece) in member.split(';').map(str::trim).enumerate() {
+            match piece.split_once('=') {
+                Some((key, value)) => item.params.push((
+                    key.trim().to_ascii_lowercase(),
+                    value.trim().trim_matches('"').to_string(),
+                )),
+                None if index == 0 && !piece.is_empty() => {
+                    item.name = Some(piece.trim_matches('"').to_string());
+                }
+                None => {}
+            }
+        }
+        if item.name.is_some() {
+            items.push(item);
+        } else {
+            unnamed.params.extend(item.params);
+        }
+    }
+    if !unnamed.params.is_empty() {
+        items.push(unnamed);
+    }
+    items
+}
+
+/// Parses `20:1,100:120` into `(value, window seconds)` pairs.
+fn parse_riot_windows(raw: &str) -> Vec<(u64, u64)> {
+    raw.split(',')
+        .filter_map(|pair| {
+            let (value, window) = pair.trim().split_once(':')?;
+            Some((value.trim().parse().ok()?, window.trim().parse().ok()?))
+        })
+        .collect()
+}
+
 pub fn parse_retry_after(headers: &http::HeaderMap) -> Option<Duration> {
     let raw = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
     parse_retry_after_value(raw)
@@ -332,4 +600,210 @@ mod tests {
         );
         assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
     }
+
+    fn observe_with(
+        observer: &dyn RateLimitResponsePolicy,
+        status: StatusCode,
+        pairs: &[(&'static str, &'static str)],
+    ) -> RateLimitObservation {
+        observe_in_plan(
+            &crate::rate_limit::RateLimitPlan::new(),
+            observer,
+            status,
+            pairs,
+        )
+    }
+
+    fn observe_in_plan(
+        plan: &crate::rate_limit::RateLimitPlan,
+        observer: &dyn RateLimitResponsePolicy,
+        status: StatusCode,
+        pairs: &[(&'static str, &'static str)],
+    ) -> RateLimitObservation {
+        let mut headers = HeaderMap::new();
+        for (name, value) in pairs {
+            headers.insert(*name, HeaderValue::from_static(value));
+        }
+        let ctx = RateLimitResponseContext {
+            meta: crate::rate_limit::RateLimitContext {
+                endpoint: "Quota",
+                method: &http::Method::GET,
+                url: "https://example.com/quota",
+                url_host: Some("example.com"),
+                page_index: 0,
+                idempotent: true,
+                max_cooldown: Duration::from_secs(60),
+                clock: crate::clock::test_system_clock(),
+                plan,
+            },
+            status,
+            headers: SanitizedHeaders::new(&headers),
+            max_cooldown: Duration::from_secs(60),
+        };
+        observer.observe(&ctx)
+    }
+
+    #[test]
+    fn ietf_headers_cool_down_only_exhausted_quotas() {
+        let ok = StatusCode::OK;
+        let observer = IetfRateLimitHeaders;
+        let observation = observe_with(
+            &observer,
+            ok,
+            &[("ratelimit", "\"burst\";r=4;t=1, \"daily\";r=0;t=30")],
+        );
+        assert!(observation.limited);
+        assert_eq!(observation.delay, Some(Duration::from_secs(30)));
+
+        let observation = observe_with(
+            &observer,
+            ok,
+            &[
+                ("ratelimit", "\"hourly\";r=0"),
+                ("ratelimit-policy", "\"hourly\";q=100;w=3600"),
+            ],
+        );
+        assert_eq!(observation.delay, Some(Duration::from_secs(3600)));
+
+        let observation = observe_with(
+            &observer,
+            ok,
+            &[("ratelimit", "limit=100, remaining=0, reset=7")],
+        );
+        assert_eq!(observation.delay, Some(Duration::from_secs(7)));
+
+        let observation = observe_with(
+            &observer,
+            ok,
+            &[("ratelimit-remaining", "0"), ("ratelimit-reset", "5")],
+        );
+        assert_eq!(observation.delay, Some(Duration::from_secs(5)));
+
+        assert!(!observe_with(&observer, ok, &[("ratelimit", "\"burst\";r=3;t=1")]).limited);
+        assert!(!observe_with(&observer, ok, &[]).limited);
+    }
+
+    #[test]
+    fn x_ratelimit_headers_accept_unix_and_delta_resets() {
+        let observer = XRateLimitHeaders;
+        let observation = observe_with(
+            &observer,
+            StatusCode::OK,
+            &[("x-ratelimit-remaining", "0"), ("x-ratelimit-reset", "12")],
+        );
+        assert_eq!(observation.delay, Some(Duration::from_secs(12)));
+
+        let observation = observe_with(
+            &observer,
+            StatusCode::OK,
+            &[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset-after", "1.5"),
+            ],
+        );
+        assert_eq!(observation.delay, Some(Duration::from_millis(1500)));
+
+        // A reset in the past leaves nothing to wait for.
+        let observation = observe_with(
+            &observer,
+            StatusCode::OK,
+            &[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", "1000000000"),
+            ],
+        );
+        assert!(!observation.limited);
+
+        let observation = observe_with(
+            &observer,
+            StatusCode::TOO_MANY_REQUESTS,
+            &[
+                ("x-ratelimit-remaining", "0"),
+                ("x-ratelimit-reset", "12"),
+                ("retry-after", "3"),
+            ],
+        );
+        assert_eq!(observation.delay, Some(Duration::from_secs(3)));
+    }
+
+    #[test]
+    fn riot_headers_cool_down_the_exhausted_bucket_kind() {
+        let observer = RiotRateLimitHeaders;
+        let observation = observe_with(
+            &observer,
+            StatusCode::OK,
+            &[
+                ("x-app-rate-limit", "20:1,100:120"),
+                ("x-app-rate-limit-count", "3:1,100:120"),
+                ("x-method-rate-limit", "50:10"),
+                ("x-method-rate-limit-count", "50:10"),
+            ],
+        );
+        assert_eq!(observation.delay, Some(Duration::from_secs(120)));
+        assert_eq!(
+            observation.target,
+            RateLimitTarget::bucket_kind(
+                "application",
+                RateLimitTarget::current_plan_or_endpoint()
+            )
+        );
+
+        let observation = observe_with(
+            &observer,
+            StatusCode::TOO_MANY_REQUESTS,
+            &[("x-rate-limit-type", "method"), ("retry-after", "4")],
+        );
+        assert_eq!(observation.delay, Some(Duration::from_secs(4)));
+        assert_eq!(
+            observation.scope,
+            Some(RateLimitScopeHint::BucketKind("method".to_string()))
+        );
+
+        let observation = observe_with(
+            &observer,
+            StatusCode::OK,
+            &[
+                ("x-app-rate-limit", "20:1"),
+                ("x-app-rate-limit-count", "19:1"),
+            ],
+        );
+        assert!(!observation.limited);
+    }
+
+    #[test]
+    fn riot_headers_report_learned_windows_for_matching_buckets() {
+        let plan = crate::rate_limit::RateLimitPlan::from_buckets(vec![
+            crate::rate_limit::RateLimitBucketUse::new(
+                "application",
+                "app",
+                crate::rate_limit::RateLimitKey::empty(),
+            ),
+            crate::rate_limit::RateLimitBucketUse::new(
+                "method",
+                "matches",
+                crate::rate_limit::RateLimitKey::empty(),
+            ),
+        ]);
+        let observation = observe_in_plan(
+            &plan,
+            &RiotRateLimitHeaders,
+            StatusCode::OK,
+            &[
+                ("x-app-rate-limit", "20:1,100:120"),
+                ("x-app-rate-limit-count", "1:1,1:120"),
+            ],
+        );
+
+        assert!(!observation.limited);
+        assert_eq!(
+            observation.learned_windows,
+            [(
+                RateLimitBucketId::new("application", "app"),
+                vec![
+                    RateLimitWindow::from_u32(20, Duration::from_secs(1)).expect("window"),
+                    RateLimitWindow::from_u32(100, Duration::from_secs(120)).expect("window"),
+                ]
+            )]
+        );
+    }
 }
@@ -1,6 +1,8 @@
 use http::{HeaderMap, HeaderValue};
 
-pub(crate) fn is_sensitive_name(name: &str) -> bool {
+/// Whether Concord's redaction rules treat a header, query, or field name as
+/// secret-bearing.
+pub fn is_sensitive_name(name: &str) -> bool {
     matches_ignore_ascii_case(
         name,
         &[
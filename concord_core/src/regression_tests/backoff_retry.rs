@@ -0,0 +1,174 @@
+use super::common::{
+    DeterministicHarness, MockResponse, RecordingRateLimiter, TestAuthVars, TextEndpoint, client,
+};
+use concord_core::advanced::{
+    BackoffRetryConfig, PostResponseHookContext, PreSendHookContext, RuntimeHooks,
+};
+use concord_core::prelude::ApiClientError;
+use http::{HeaderMap, HeaderValue, StatusCode};
+use std::future::Future;
+use std::num::NonZeroU32;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Records the retry count every hook observes.
+struct RetryHooks {
+    events: Arc<Mutex<Vec<String>>>,
+}
+
+impl RuntimeHooks for RetryHooks {
+    fn pre_send<'a>(
+        &'a self,
+        ctx: PreSendHookContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApiClientError>> + Send + 'a>> {
+        Box::pin(async move {
+            self.events
+                .lock()
+                .await
+                .push(format!("pre_send:{}", ctx.meta.retry));
+            Ok(())
+        })
+    }
+
+    fn post_response<'a>(
+        &'a self,
+        ctx: PostResponseHookContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        Box::pin(async move {
+            self.events.lock().await.push(format!(
+                "post_response:{}:{}",
+                ctx.meta.retry,
+                ctx.status.as_u16()
+            ));
+        })
+    }
+}
+
+fn retry_after(seconds: &'static str) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    headers.insert(http::header::RETRY_AFTER, HeaderValue::from_static(seconds));
+    headers
+}
+
+fn backoff(max_retries: u32) -> BackoffRetryConfig {
+    BackoffRetryConfig::new(
+        NonZeroU32::new(max_retries).expect("non-zero"),
+        Duration::from_millis(1),
+    )
+    .without_jitter()
+}
+
+#[tokio::test]
+async fn throttled_call_is_retried_as_visible_executions() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::TOO_MANY_REQUESTS, "slow down")
+                .with_headers(retry_after("0")),
+            MockResponse::text(StatusCode::SERVICE_UNAVAILABLE, "busy"),
+            MockResponse::text(StatusCode::OK, "done"),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config
+            .backoff_retry(backoff(2))
+            .rate_limiter(Arc::new(RecordingRateLimiter::new(events.clone())))
+            .runtime_hooks(Arc::new(RetryHooks {
+                events: events.clone(),
+            }));
+    });
+
+    let value = client.request(TextEndpoint::default()).execute().await?;
+
+    assert_eq!(value, "done");
+    assert_eq!(harness.sent_count().await, 3);
+    assert_eq!(
+        *events.lock().await,
+        [
+            "rate_acquire",
+            "pre_send:0",
+            "post_response:0:429",
+            "rate_response",
+            "rate_acquire",
+            "pre_send:1",
+            "post_response:1:503",
+            "rate_response",
+            "rate_acquire",
+            "pre_send:2",
+            "post_response:2:200",
+            "rate_response",
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn exhausted_retries_return_the_last_response() {
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::SERVICE_UNAVAILABLE, "busy"),
+            MockResponse::text(StatusCode::SERVICE_UNAVAILABLE, "still busy"),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config.backoff_retry(backoff(1));
+    });
+
+    let err = client
+        .request(TextEndpoint::default())
+        .execute()
+        .await
+        .expect_err("503 after the only retry");
+
+    assert!(
+        matches!(
+            err,
+            ApiClientError::HttpStatus { status, .. } if status == StatusCode::SERVICE_UNAVAILABLE
+        ),
+        "{err:?}"
+    );
+    assert_eq!(harness.sent_count().await, 2);
+}
+
+#[tokio::test]
+async fn other_statuses_and_retry_after_past_the_deadline_are_not_retried() {
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::BAD_GATEWAY, "gateway"),
+            MockResponse::text(StatusCode::TOO_MANY_REQUESTS, "later")
+                .with_headers(retry_after("120")),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config.backoff_retry(backoff(3).with_deadline(Duration::from_secs(5)));
+    });
+
+    let err = client
+        .request(TextEndpoint::default())
+        .execute()
+        .await
+        .expect_err("502 is returned");
+    assert!(
+        matches!(err, ApiClientError::HttpStatus { status, .. } if status == StatusCode::BAD_GATEWAY),
+        "{err:?}"
+    );
+
+    let err = client
+        .request(TextEndpoint::default())
+        .execute()
+        .await
+        .expect_err("Retry-After exceeds the deadline");
+    assert!(
+        matches!(err, ApiClientError::HttpStatus { status, .. } if status == StatusCode::TOO_MANY_REQUESTS),
+        "{err:?}"
+    );
+    assert_eq!(harness.sent_count().await, 2);
+}
@@ -0,0 +1,131 @@
+use super::common::{
+    DeterministicHarness, DeterministicOutcome, ItemsEndpoint, MockResponse, RecordingRateLimiter,
+    TestAuthVars, TextEndpoint, client,
+};
+use concord_core::advanced::CircuitBreakerConfig;
+use concord_core::prelude::{ApiClientError, ErrorCategory, PaginationTermination};
+use http::StatusCode;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+fn threshold(value: u32) -> NonZeroU32 {
+    NonZeroU32::new(value).expect("non-zero threshold")
+}
+
+async fn permits(events: &Arc<Mutex<Vec<String>>>) -> usize {
+    events
+        .lock()
+        .await
+        .iter()
+        .filter(|event| *event == "rate_acquire")
+        .count()
+}
+
+#[tokio::test]
+async fn open_origin_circuit_fails_fast_without_spending_permits() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::with_outcomes(
+        events.clone(),
+        vec![
+            DeterministicOutcome::ConnectFailure,
+            MockResponse::text(StatusCode::SERVICE_UNAVAILABLE, "down").into(),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config
+            .rate_limiter(Arc::new(RecordingRateLimiter::new(events.clone())))
+            .circuit_breaker(CircuitBreakerConfig::per_origin(
+                threshold(2),
+                Duration::from_secs(60),
+            ));
+    });
+
+    let first = client.request(TextEndpoint::default()).execute().await;
+    assert_eq!(
+        first.expect_err("connect").category(),
+        ErrorCategory::Connect
+    );
+    let second = client.request(TextEndpoint::default()).execute().await;
+    assert_eq!(
+        second.expect_err("server error").http_status(),
+        Some(StatusCode::SERVICE_UNAVAILABLE)
+    );
+
+    let error = client
+        .request(TextEndpoint::default())
+        .execute()
+        .await
+        .expect_err("circuit open");
+    let ApiClientError::CircuitOpen { retry_after, .. } = &error else {
+        panic!("unexpected error: {error:?}");
+    };
+    assert!(retry_after.is_some_and(|remaining| remaining > Duration::from_secs(59)));
+    assert_eq!(error.category(), ErrorCategory::CircuitOpen);
+
+    // The circuit covers the whole origin, including other endpoints.
+    let error = client
+        .request(ItemsEndpoint::default())
+        .paginate(PaginationTermination::hard_page_cap(2))
+        .collect()
+        .await
+        .expect_err("circuit open for the origin");
+    assert_eq!(error.category(), ErrorCategory::CircuitOpen);
+    assert_eq!(error.endpoint(), "Items");
+
+    assert_eq!(permits(&events).await, 2);
+    assert_eq!(harness.sent_count().await, 2);
+}
+
+#[tokio::test]
+async fn half_open_probe_closes_an_endpoint_circuit() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events.clone(),
+        vec![
+            MockResponse::text(StatusCode::BAD_GATEWAY, "down"),
+            MockResponse::text(StatusCode::OK, "elsewhere"),
+            MockResponse::text(StatusCode::BAD_GATEWAY, "probe failed"),
+            MockResponse::text(StatusCode::OK, "probe ok"),
+            MockResponse::text(StatusCode::OK, "closed"),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config.circuit_breaker(CircuitBreakerConfig::per_endpoint(
+            threshold(1),
+            Duration::ZERO,
+        ));
+    });
+
+    client
+        .request(TextEndpoint::default())
+        .execute()
+        .await
+        .expect_err("opens the Text circuit");
+    // Other endpoints keep their own circuit.
+    let other = TextEndpoint {
+        name: "Other",
+        ..TextEndpoint::default()
+    };
+    assert_eq!(client.request(other).execute().await?, "elsewhere");
+
+    // `open_for` is zero, so each call below is admitted as a probe.
+    client
+        .request(TextEndpoint::default())
+        .execute()
+        .await
+        .expect_err("failed probe reopens");
+    assert_eq!(
+        client.request(TextEndpoint::default()).execute().await?,
+        "probe ok"
+    );
+    assert_eq!(
+        client.request(TextEndpoint::default()).execute().await?,
+        "closed"
+    );
+    assert_eq!(harness.sent_count().await, 5);
+    Ok(())
+}
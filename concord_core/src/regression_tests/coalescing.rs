@@ -0,0 +1,160 @@
+use super::common::{
+    DeterministicHarness, GatedDeterministicHarness, MockResponse, RecordingRateLimiter,
+    RegressionClient, TestAuthVars, TestClient, TextEndpoint, client, configure_runtime,
+};
+use crate::regression_tests::test_api::ResolvedPolicy;
+use bytes::Bytes;
+use concord_core::prelude::{ApiClient, ApiClientError};
+use http::StatusCode;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+fn coalesced(path: &'static str) -> TextEndpoint {
+    TextEndpoint {
+        path,
+        policy: ResolvedPolicy {
+            coalesce: true,
+            ..ResolvedPolicy::default()
+        },
+        ..TextEndpoint::default()
+    }
+}
+
+fn gated_client(harness: &GatedDeterministicHarness) -> TestClient {
+    let inner = ApiClient::with_safe_reqwest_builder((), TestAuthVars::default(), |builder| {
+        harness.configure_application(builder)
+    })
+    .expect("deterministic coalescing client");
+    RegressionClient::from_inner(inner, None)
+}
+
+fn spawn_call(
+    client: &TestClient,
+    endpoint: TextEndpoint,
+) -> JoinHandle<Result<String, ApiClientError>> {
+    let client = client.clone();
+    tokio::spawn(async move { client.request(endpoint).execute().await })
+}
+
+/// Lets freshly spawned calls run up to their registry lookup.
+async fn settle() {
+    for _ in 0..16 {
+        tokio::task::yield_now().await;
+    }
+}
+
+#[tokio::test]
+async fn concurrent_identical_gets_share_one_execution_and_permit() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = GatedDeterministicHarness::new(
+        events.clone(),
+        vec![MockResponse::text(StatusCode::OK, "shared")],
+    );
+    let mut client = gated_client(&harness);
+    configure_runtime(
+        &mut client,
+        Some(Arc::new(RecordingRateLimiter::new(events.clone()))),
+    );
+
+    let leader = spawn_call(&client, coalesced("/text"));
+    harness.wait_for_sends(1).await;
+    let followers = [
+        spawn_call(&client, coalesced("/text")),
+        spawn_call(&client, coalesced("/text")),
+    ];
+    settle().await;
+    harness.release_all();
+
+    assert_eq!(leader.await.expect("leader task")?, "shared");
+    for follower in followers {
+        assert_eq!(follower.await.expect("follower task")?, "shared");
+    }
+    assert_eq!(harness.sent_count().await, 1);
+    let acquires = events
+        .lock()
+        .await
+        .iter()
+        .filter(|event| *event == "rate_acquire")
+        .count();
+    assert_eq!(acquires, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn followers_receive_the_leaders_error() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = GatedDeterministicHarness::new(
+        events,
+        vec![
+            MockResponse::text(StatusCode::OK, "")
+                .with_chunks(vec![Bytes::from_static(b"abc"), Bytes::from_static(b"de")]),
+        ],
+    );
+    let mut client = gated_client(&harness);
+    client.configure(|cfg| {
+        cfg.max_response_body_bytes(4);
+    });
+
+    let leader = spawn_call(&client, coalesced("/text"));
+    harness.wait_for_sends(1).await;
+    let follower = spawn_call(&client, coalesced("/text"));
+    settle().await;
+    harness.release_all();
+
+    for call in [leader, follower] {
+        let err = call.await.expect("call task").expect_err("shared failure");
+        assert!(matches!(
+            err,
+            ApiClientError::ResponseBodyLimitExceeded { limit: 4, .. }
+        ));
+    }
+    assert_eq!(harness.sent_count().await, 1);
+}
+
+#[tokio::test]
+async fn distinct_identities_and_completed_calls_are_not_shared() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events,
+        (0..4)
+            .map(|_| MockResponse::text(StatusCode::OK, "ok"))
+            .collect(),
+    );
+    let client = client(TestAuthVars::default(), harness.clone());
+
+    let (a, b, plain) = tokio::join!(
+        client.request(coalesced("/a")).execute(),
+        client.request(coalesced("/b")).execute(),
+        client.request(TextEndpoint::default()).execute(),
+    );
+    assert_eq!((a?, b?, plain?), ("ok".into(), "ok".into(), "ok".into()));
+    client.request(coalesced("/a")).execute().await?;
+
+    assert_eq!(harness.sent_count().await, 4);
+    Ok(())
+}
+
+#[tokio::test]
+async fn a_cancelled_leader_hands_execution_to_a_follower() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = GatedDeterministicHarness::new(
+        events,
+        vec![
+            MockResponse::text(StatusCode::OK, "abandoned"),
+            MockResponse::text(StatusCode::OK, "retried"),
+        ],
+    );
+    let client = gated_client(&harness);
+
+    let leader = spawn_call(&client, coalesced("/text"));
+    harness.wait_for_sends(1).await;
+    let follower = spawn_call(&client, coalesced("/text"));
+    settle().await;
+    leader.abort();
+    harness.wait_for_sends(2).await;
+    harness.release_all();
+
+    assert_eq!(follower.await.expect("follower task")?, "retried");
+    Ok(())
+}
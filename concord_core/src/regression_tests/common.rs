@@ -826,6 +826,7 @@ pub fn request_plan(
                 accept: Some(HeaderValue::from_static("text/plain")),
                 no_content: false,
                 format: crate::regression_tests::test_api::Format::Text,
+                errors: Default::default(),
             },
             pagination,
         },
@@ -476,3 +476,99 @@ fn production_constructor_has_no_executor_selector_or_post_construction_installa
     assert!(!development.contains("install_application_executor"));
     assert!(!development.contains("install_provider_executor"));
 }
+
+#[tokio::test]
+async fn request_router_answers_by_capture_and_body_digest() {
+    use sha2::{Digest, Sha256};
+
+    const ROUTED_BODY: &[u8] = b"routed-body";
+    let routed_hex = Sha256::digest(ROUTED_BODY)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    let executor = DeterministicNativeExecutor::application();
+    executor.route_requests(move |capture, digest| {
+        (capture.logical_target().path() == "/routed" && digest.to_hex() == routed_hex)
+            .then(|| text_response(b"routed"))
+    });
+    let client = client_with(&executor, TestAuthVars::default());
+    let plan = |body: &'static [u8]| {
+        let mut plan = request_plan(
+            "DeterministicRouted",
+            Method::POST,
+            "/routed",
+            Default::default(),
+            None,
+        );
+        plan.body = PreparedBody::reusable_bytes(
+            Bytes::from_static(body),
+            Some(HeaderValue::from_static("application/octet-stream")),
+        );
+        plan
+    };
+
+    let response = client
+        .execute_plan::<Text<String>>(plan(ROUTED_BODY))
+        .await
+        .expect("routed response");
+    assert_eq!(response.value(), "routed");
+    let error = client
+        .execute_plan::<Text<String>>(plan(b"other-body"))
+        .await
+        .expect_err("unrouted body");
+    assert_eq!(error.category(), ErrorCategory::RequestExecution);
+    assert_eq!(executor.unexpected_execution_count(), 1);
+    assert_eq!(executor.captures().len(), 2);
+}
+
+#[tokio::test]
+async fn injected_faults_wrap_scripts_without_consuming_them() {
+    use crate::__development::InjectedFault;
+
+    let executor = DeterministicNativeExecutor::application();
+    executor.script_response(text_response(b"scripted"));
+    let calls = Arc::new(Mutex::new(0_usize));
+    let injector_calls = calls.clone();
+    executor.inject_faults(move |_capture| {
+        let mut calls = injector_calls.lock().expect("calls lock");
+        *calls += 1;
+        match *calls {
+            1 => Some(InjectedFault::Replace(
+                ScriptedNativeResponse::bytes(StatusCode::SERVICE_UNAVAILABLE, Bytes::new())
+                    .with_latency(std::time::Duration::from_millis(20)),
+            )),
+            _ => Some(InjectedFault::Rewrite(Box::new(|response| {
+                response.truncate_body(3)
+            }))),
+        }
+    });
+    let client = client_with(&executor, TestAuthVars::default());
+    let plan = || {
+        request_plan(
+            "DeterministicFaults",
+            Method::GET,
+            "/faults",
+            Default::default(),
+            None,
+        )
+    };
+    let started = std::time::Instant::now();
+
+    let error = client
+        .execute_plan::<Text<String>>(plan())
+        .await
+        .expect_err("injected 503");
+    assert!(started.elapsed() >= std::time::Duration::from_millis(20));
+    assert!(
+        matches!(error, ApiClientError::HttpStatus { .. }),
+        "{error:?}"
+    );
+    assert_eq!(executor.remaining_scripts(), 1);
+
+    let response = client
+        .execute_plan::<Text<String>>(plan())
+        .await
+        .expect("truncated script");
+    assert_eq!(response.value(), "scr");
+    assert_eq!(executor.remaining_scripts(), 0);
+}
@@ -0,0 +1,170 @@
+use super::common::{
+    DeterministicHarness, MockResponse, TestAuthVars, TestCx, buffered_endpoint_response_terminal,
+    client, execute_buffered,
+};
+use crate::regression_tests::test_api::{
+    RegressionEndpoint, RegressionPlanContext, RegressionReusableEndpoint, RequestPlan,
+    ResolvedPolicy,
+};
+use bytes::Bytes;
+use concord_core::advanced::{CodecError, DecodeContext, ErrorBody};
+use concord_core::prelude::{ApiClient, ApiClientError};
+use http::{Method, StatusCode};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+#[derive(Debug, Eq, PartialEq)]
+struct NotFoundBody(String);
+
+#[derive(Debug, Eq, PartialEq)]
+struct FallbackBody(String);
+
+fn decode_not_found(bytes: Bytes, _ctx: DecodeContext<'_>) -> Result<ErrorBody, CodecError> {
+    String::from_utf8(bytes.to_vec())
+        .map(|text| ErrorBody::new(NotFoundBody(text)))
+        .map_err(|error| CodecError::with_source("text decode failed", error))
+}
+
+fn decode_fallback(bytes: Bytes, ctx: DecodeContext<'_>) -> Result<ErrorBody, CodecError> {
+    assert_eq!(ctx.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    String::from_utf8(bytes.to_vec())
+        .map(|text| ErrorBody::new(FallbackBody(text)))
+        .map_err(|error| CodecError::with_source("text decode failed", error))
+}
+
+#[derive(Clone, Default)]
+struct TypedErrorEndpoint;
+
+impl RegressionEndpoint<TestCx> for TypedErrorEndpoint {
+    type Response = String;
+
+    fn execute<'a>(
+        client: &'a ApiClient<TestCx>,
+        plan: RequestPlan,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Response, ApiClientError>> + Send + 'a>> {
+        execute_buffered::<_, concord_core::prelude::Text<String>>(client, plan)
+    }
+}
+
+buffered_endpoint_response_terminal!(
+    TypedErrorEndpoint,
+    TestCx,
+    concord_core::prelude::Text<String>
+);
+
+impl RegressionReusableEndpoint<TestCx> for TypedErrorEndpoint {
+    fn plan(
+        &self,
+        _ctx: &RegressionPlanContext<'_, TestCx>,
+    ) -> Result<RequestPlan, ApiClientError> {
+        let mut plan = super::common::request_plan(
+            "TypedError",
+            Method::GET,
+            "/typed-error",
+            ResolvedPolicy::default(),
+            None,
+        );
+        let errors = &mut plan.endpoint.response.errors;
+        errors.push(None, "FallbackBody", decode_fallback);
+        errors.push(
+            Some(StatusCode::NOT_FOUND),
+            "NotFoundBody",
+            decode_not_found,
+        );
+        Ok(plan)
+    }
+}
+
+async fn typed_error(response: MockResponse, limit: Option<usize>) -> ApiClientError {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(events, vec![response]);
+    let mut client = client(TestAuthVars::default(), harness);
+    if let Some(limit) = limit {
+        client.configure(|config| {
+            config.max_response_body_bytes(limit);
+        });
+    }
+    client
+        .request(TypedErrorEndpoint)
+        .await
+        .expect_err("error status must fail")
+}
+
+#[tokio::test]
+async fn error_response_exact_status_mapping_wins_over_fallback() {
+    let err = typed_error(
+        MockResponse::text(StatusCode::NOT_FOUND, "ERROR_BODY_SENTINEL"),
+        None,
+    )
+    .await;
+
+    assert_eq!(err.http_status(), Some(StatusCode::NOT_FOUND));
+    assert_eq!(
+        err.error_body::<NotFoundBody>(),
+        Some(&NotFoundBody("ERROR_BODY_SENTINEL".to_string()))
+    );
+    assert!(err.error_body::<FallbackBody>().is_none());
+    let diagnostics = format!("{err}\n{err:?}");
+    assert!(diagnostics.contains("NotFoundBody"), "{diagnostics}");
+    assert!(
+        !diagnostics.contains("ERROR_BODY_SENTINEL"),
+        "error body leaked into diagnostics: {diagnostics}"
+    );
+}
+
+#[tokio::test]
+async fn error_response_fallback_mapping_decodes_other_statuses() {
+    let err = typed_error(
+        MockResponse::text(StatusCode::INTERNAL_SERVER_ERROR, "boom"),
+        None,
+    )
+    .await;
+
+    assert_eq!(
+        err.error_body::<FallbackBody>(),
+        Some(&FallbackBody("boom".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn error_response_body_respects_response_body_limit() {
+    let err = typed_error(
+        MockResponse::text(StatusCode::NOT_FOUND, "123456789").with_content_length(Some(9)),
+        Some(4),
+    )
+    .await;
+
+    assert_eq!(err.http_status(), Some(StatusCode::NOT_FOUND));
+    assert!(err.error_body::<NotFoundBody>().is_none());
+
+    let err = typed_error(
+        MockResponse::text(StatusCode::NOT_FOUND, Bytes::new())
+            .with_content_length(None)
+            .with_chunks(vec![Bytes::from_static(b"abcd"), Bytes::from_static(b"e")]),
+        Some(4),
+    )
+    .await;
+
+    assert_eq!(err.http_status(), Some(StatusCode::NOT_FOUND));
+    assert!(err.error_body::<NotFoundBody>().is_none());
+}
+
+#[tokio::test]
+async fn error_response_decode_failure_keeps_status_error() {
+    let err = typed_error(
+        MockResponse::text(StatusCode::NOT_FOUND, Bytes::from_static(&[0xff, 0xfe])),
+        None,
+    )
+    .await;
+
+    assert!(matches!(
+        err,
+        ApiClientError::HttpStatus {
+            status: StatusCode::NOT_FOUND,
+            body: None,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,216 @@
+use super::common::{DeterministicHarness, MockResponse, TestAuthVars, TestCx, client};
+use crate::regression_tests::test_api::{
+    EndpointMeta, EndpointPlan, PreparedBody, RegressionEndpoint, RegressionPlanContext,
+    RegressionReusableEndpoint, RequestOverrides, RequestPlan, ResolvedPolicy, ResolvedRoute,
+    ResponsePlan,
+};
+use crate::regression_tests::test_api::{RawStreamResponse, ResponseEntity};
+use bytes::Bytes;
+use concord_core::advanced::{
+    CodecError, DecodeContext, Event, EventData, EventStream, Events, ResponseCodec, RuntimeConfig,
+    StreamResponse, TextContentType,
+};
+use concord_core::prelude::{ApiClient, ApiClientError};
+use http::{HeaderValue, Method, StatusCode};
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+struct Number;
+
+impl ResponseCodec for Number {
+    type Value = u32;
+    type Content = TextContentType;
+
+    fn decode(bytes: Bytes, _ctx: DecodeContext<'_>) -> Result<Self::Value, CodecError> {
+        std::str::from_utf8(&bytes)
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .ok_or_else(|| CodecError::new("number decode failed"))
+    }
+}
+
+struct EventEndpoint<D>(PhantomData<fn() -> D>);
+
+impl<D> Clone for EventEndpoint<D> {
+    fn clone(&self) -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<D: EventData> RegressionEndpoint<TestCx> for EventEndpoint<D> {
+    type Response = StreamResponse<EventStream<D>>;
+
+    fn execute<'a>(
+        client: &'a ApiClient<TestCx>,
+        plan: RequestPlan,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Response, ApiClientError>> + Send + 'a>> {
+        <RawStreamResponse<EventStream<D>> as ResponseEntity>::execute(client, plan)
+    }
+}
+
+impl<D: EventData> RegressionReusableEndpoint<TestCx> for EventEndpoint<D> {
+    fn plan(
+        &self,
+        _ctx: &RegressionPlanContext<'_, TestCx>,
+    ) -> Result<RequestPlan, ApiClientError> {
+        Ok(RequestPlan {
+            endpoint: EndpointPlan {
+                meta: EndpointMeta {
+                    name: "EventFeed",
+                    method: Method::GET,
+                    idempotent: true,
+                    facade_path: &[],
+                },
+                route: ResolvedRoute::new(http::uri::Scheme::HTTP, "example.com", "/events"),
+                policy: ResolvedPolicy::default(),
+                response: ResponsePlan {
+                    accept: Some(HeaderValue::from_static("text/event-stream")),
+                    no_content: false,
+                    format: crate::regression_tests::test_api::Format::Text,
+                    errors: Default::default(),
+                },
+                pagination: None,
+            },
+            body: PreparedBody::empty(),
+            overrides: RequestOverrides::default(),
+        })
+    }
+}
+
+fn event_response(chunks: &[&'static [u8]]) -> MockResponse {
+    let mut response = MockResponse::text(StatusCode::OK, Bytes::new())
+        .with_content_length(None)
+        .with_chunks(chunks.iter().copied().map(Bytes::from_static).collect());
+    response.headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("text/event-stream; charset=utf-8"),
+    );
+    response
+}
+
+async fn events<D: EventData>(
+    response: MockResponse,
+    configure: impl FnOnce(&mut RuntimeConfig),
+) -> Events<D> {
+    let harness = DeterministicHarness::new(Arc::new(Mutex::new(Vec::new())), vec![response]);
+    let mut client = client(TestAuthVars::default(), harness);
+    client.configure(configure);
+    client
+        .request(EventEndpoint::<D>(PhantomData))
+        .execute()
+        .await
+        .expect("event stream head succeeds")
+        .events()
+}
+
+#[tokio::test]
+async fn event_stream_yields_raw_events_across_chunks_and_discards_trailing_partial() {
+    let mut events = events::<Event>(
+        event_response(&[
+            b"retry: 1500\nid: a1\nevent: delta\ndata: hel",
+            b"lo\n\n: ping\n\ndata: second\n",
+            b"\ndata: unterminated",
+        ]),
+        |_| {},
+    )
+    .await;
+
+    assert_eq!(
+        events.next_event().await.expect("first event"),
+        Some(Event {
+            id: Some("a1".to_string()),
+            event: Some("delta".to_string()),
+            data: "hello".to_string(),
+            retry: Some(std::time::Duration::from_millis(1500)),
+        })
+    );
+    let second = events
+        .next_event()
+        .await
+        .expect("second event")
+        .expect("event");
+    assert_eq!(second.data, "second");
+    assert_eq!(second.id.as_deref(), Some("a1"));
+    assert_eq!(events.next_event().await.expect("EOF"), None);
+    assert_eq!(events.next_event().await.expect("terminal"), None);
+    assert_eq!(events.last_event_id(), Some("a1"));
+    assert_eq!(
+        events.response().headers()[http::header::CONTENT_TYPE],
+        "text/event-stream; charset=utf-8"
+    );
+}
+
+#[tokio::test]
+async fn event_stream_decodes_data_through_response_codec() {
+    let mut events = events::<Number>(
+        event_response(&[b"data: 7\n\ndata: DECODE_SENTINEL\n\ndata: 9\n\n"]),
+        |_| {},
+    )
+    .await;
+
+    assert_eq!(events.next_event().await.expect("number").unwrap().data, 7);
+    let error = events
+        .next_event()
+        .await
+        .expect_err("invalid data must fail");
+    assert!(matches!(error, ApiClientError::Decode { .. }));
+    assert!(!format!("{error} {error:?}").contains("DECODE_SENTINEL"));
+    assert_eq!(
+        events
+            .next_event()
+            .await
+            .expect("stream continues")
+            .unwrap()
+            .data,
+        9
+    );
+    assert_eq!(events.next_event().await.expect("EOF"), None);
+}
+
+#[tokio::test]
+async fn event_stream_per_event_limit_is_terminal_after_complete_events() {
+    let mut events = events::<Event>(
+        event_response(&[
+            b"data: ok\n\ndata: EVENT_LIMIT_SENTINEL",
+            b"\n\ndata: later\n\n",
+        ]),
+        |config| {
+            config.max_stream_event_bytes(16);
+        },
+    )
+    .await;
+
+    assert_eq!(events.next_event().await.expect("ok").unwrap().data, "ok");
+    let error = events
+        .next_event()
+        .await
+        .expect_err("oversized event must fail");
+    assert!(matches!(
+        error,
+        ApiClientError::StreamEventLimitExceeded { limit: 16, .. }
+    ));
+    assert!(!format!("{error:?}").contains("EVENT_LIMIT_SENTINEL"));
+    assert_eq!(events.next_event().await.expect("terminal"), None);
+}
+
+#[tokio::test]
+async fn event_stream_respects_stream_response_body_limit() {
+    let mut events = events::<Event>(
+        event_response(&[b"data: a\n\n", b"data: b\n\n"]),
+        |config| {
+            config.max_stream_response_body_bytes(12);
+        },
+    )
+    .await;
+
+    assert_eq!(events.next_event().await.expect("a").unwrap().data, "a");
+    let error = events.next_event().await.expect_err("body limit must fail");
+    assert!(matches!(
+        error,
+        ApiClientError::ResponseBodyLimitExceeded { limit: 12, .. }
+    ));
+    assert_eq!(events.next_event().await.expect("terminal"), None);
+}
@@ -0,0 +1,284 @@
+use super::common::{
+    DeterministicHarness, DeterministicOutcome, MockResponse, RecordingRateLimiter, TestAuthVars,
+    TextEndpoint, client,
+};
+use crate::regression_tests::test_api::ResolvedPolicy;
+use concord_core::advanced::{
+    Clock, ClockFuture, PostResponseHookContext, PreSendHookContext, RateLimitContext,
+    RateLimitFuture, RateLimitPermit, RateLimitResponseAction, RateLimitResponseContext,
+    RateLimiter, RequestErrorHookContext, RuntimeHooks,
+};
+use concord_core::prelude::ApiClientError;
+use http::{Method, StatusCode};
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+use tokio::sync::{Mutex, Notify};
+
+/// Holds the first response head in `post_response` until released, so it
+/// is observed after the hedge delay.
+struct HoldFirstExecution {
+    events: Arc<Mutex<Vec<String>>>,
+    calls: AtomicUsize,
+    release: Arc<Notify>,
+}
+
+impl HoldFirstExecution {
+    fn new(events: Arc<Mutex<Vec<String>>>) -> Self {
+        Self {
+            events,
+            calls: AtomicUsize::new(0),
+            release: Arc::new(Notify::new()),
+        }
+    }
+}
+
+impl RuntimeHooks for HoldFirstExecution {
+    fn pre_send<'a>(
+        &'a self,
+        _ctx: PreSendHookContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = Result<(), ApiClientError>> + Send + 'a>> {
+        Box::pin(async move {
+            self.events.lock().await.push("pre_send".to_string());
+            Ok(())
+        })
+    }
+
+    fn post_response<'a>(
+        &'a self,
+        ctx: PostResponseHookContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        let first = self.calls.fetch_add(1, Ordering::SeqCst) == 0;
+        Box::pin(async move {
+            if first {
+                self.release.notified().await;
+            }
+            self.events
+                .lock()
+                .await
+                .push(format!("post_response:{}", ctx.status.as_u16()));
+        })
+    }
+
+    fn request_error<'a>(
+        &'a self,
+        _ctx: RequestErrorHookContext<'a>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>> {
+        Box::pin(async move {
+            self.events.lock().await.push("request_error".to_string());
+        })
+    }
+}
+
+/// Makes the first permit wait for `delay`, as a saturated limiter would.
+struct SlowFirstPermit {
+    acquired: AtomicUsize,
+    delay: Duration,
+}
+
+impl RateLimiter for SlowFirstPermit {
+    fn acquire<'a>(
+        &'a self,
+        _ctx: RateLimitContext<'a>,
+    ) -> RateLimitFuture<'a, Result<RateLimitPermit, ApiClientError>> {
+        let first = self.acquired.fetch_add(1, Ordering::SeqCst) == 0;
+        Box::pin(async move {
+            if first {
+                tokio::time::sleep(self.delay).await;
+            }
+            Ok(RateLimitPermit)
+        })
+    }
+
+    fn on_response<'a>(
+        &'a self,
+        _ctx: RateLimitResponseContext<'a>,
+    ) -> RateLimitFuture<'a, Result<RateLimitResponseAction, ApiClientError>> {
+        Box::pin(async { Ok(RateLimitResponseAction::Continue) })
+    }
+}
+
+/// A clock whose sleeps never finish, so a hedge delay measured on it never
+/// elapses.
+struct FrozenClock;
+
+impl Clock for FrozenClock {
+    fn now(&self) -> std::time::Instant {
+        std::time::Instant::now()
+    }
+
+    fn sleep(&self, _duration: Duration) -> ClockFuture {
+        Box::pin(std::future::pending())
+    }
+}
+
+fn hedged(method: Method, delay: Duration) -> TextEndpoint {
+    TextEndpoint {
+        method,
+        policy: ResolvedPolicy {
+            hedge_after: Some(delay),
+            ..ResolvedPolicy::default()
+        },
+        ..TextEndpoint::default()
+    }
+}
+
+#[tokio::test]
+async fn slow_get_is_hedged_and_the_first_response_wins() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::OK, "slow"),
+            MockResponse::text(StatusCode::OK, "hedge"),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config
+            .rate_limiter(Arc::new(RecordingRateLimiter::new(events.clone())))
+            .runtime_hooks(Arc::new(HoldFirstExecution::new(events.clone())));
+    });
+
+    let value = client
+        .request(hedged(Method::GET, Duration::from_millis(10)))
+        .execute()
+        .await?;
+
+    assert_eq!(value, "hedge");
+    // Both executions took a permit, ran hooks and reached the network; the
+    // held one was dropped before its response head was observed.
+    assert_eq!(
+        *events.lock().await,
+        [
+            "rate_acquire",
+            "pre_send",
+            "rate_acquire",
+            "pre_send",
+            "post_response:200",
+            "rate_response",
+        ]
+    );
+    assert_eq!(harness.sent_count().await, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn failed_hedge_defers_to_the_slow_execution() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::with_outcomes(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::OK, "slow").into(),
+            DeterministicOutcome::ConnectFailure,
+        ],
+    );
+    let hooks = Arc::new(HoldFirstExecution::new(events.clone()));
+    let release = hooks.release.clone();
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config.runtime_hooks(hooks);
+    });
+    let releaser = tokio::spawn({
+        let harness = harness.clone();
+        async move {
+            harness.wait_for_sends(2).await;
+            release.notify_one();
+        }
+    });
+
+    let value = client
+        .request(hedged(Method::GET, Duration::from_millis(10)))
+        .execute()
+        .await?;
+
+    assert_eq!(value, "slow");
+    releaser.await.expect("releaser");
+    assert_eq!(
+        *events.lock().await,
+        ["pre_send", "pre_send", "request_error", "post_response:200"]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn fast_responses_and_unsafe_methods_are_not_hedged() -> Result<(), ApiClientError> {
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::OK, "first"),
+            MockResponse::text(StatusCode::OK, "posted"),
+        ],
+    );
+    let client = client(TestAuthVars::default(), harness.clone());
+
+    let value = client
+        .request(hedged(Method::GET, Duration::from_secs(5)))
+        .execute()
+        .await?;
+    assert_eq!(value, "first");
+    let value = client
+        .request(hedged(Method::POST, Duration::ZERO))
+        .execute()
+        .await?;
+    assert_eq!(value, "posted");
+    assert_eq!(harness.sent_count().await, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn permit_wait_does_not_count_toward_the_hedge_delay() -> Result<(), ApiClientError> {
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![MockResponse::text(StatusCode::OK, "queued")],
+    );
+    let limiter = Arc::new(SlowFirstPermit {
+        acquired: AtomicUsize::new(0),
+        delay: Duration::from_millis(50),
+    });
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config.rate_limiter(limiter.clone());
+    });
+
+    let value = client
+        .request(hedged(Method::GET, Duration::from_millis(10)))
+        .execute()
+        .await?;
+
+    assert_eq!(value, "queued");
+    assert_eq!(limiter.acquired.load(Ordering::SeqCst), 1);
+    assert_eq!(harness.sent_count().await, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn hedge_delay_waits_on_the_configured_clock() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![MockResponse::text(StatusCode::OK, "slow")],
+    );
+    let hooks = Arc::new(HoldFirstExecution::new(events.clone()));
+    let release = hooks.release.clone();
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config.runtime_hooks(hooks).clock(Arc::new(FrozenClock));
+    });
+    let releaser = tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        release.notify_one();
+    });
+
+    let value = client
+        .request(hedged(Method::GET, Duration::from_millis(1)))
+        .execute()
+        .await?;
+
+    assert_eq!(value, "slow");
+    releaser.await.expect("releaser");
+    assert_eq!(harness.sent_count().await, 1);
+    Ok(())
+}
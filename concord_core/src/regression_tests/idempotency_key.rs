@@ -0,0 +1,138 @@
+use super::common::{
+    DeterministicHarness, MockResponse, ObservationAuthVars, ObservationRuntimeHooks, TestAuthVars,
+    TextEndpoint, auth_policy, client, observation_client,
+};
+use crate::regression_tests::test_api::{AuthPlacement, ResolvedPolicy};
+use concord_core::prelude::ApiClientError;
+use http::{HeaderName, HeaderValue, Method, StatusCode};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+// `Idempotency-Key` is redacted from captured executions by its `-key`
+// suffix, so value assertions use an unredacted header name.
+const KEY: HeaderName = HeaderName::from_static("x-operation-id");
+
+fn keyed(method: Method, policy: ResolvedPolicy) -> TextEndpoint {
+    TextEndpoint {
+        method,
+        policy: ResolvedPolicy {
+            idempotency_key: Some(KEY),
+            ..policy
+        },
+        ..TextEndpoint::default()
+    }
+}
+
+#[tokio::test]
+async fn generated_key_is_reused_by_the_auth_recovery_execution() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events.clone(),
+        vec![
+            MockResponse::text(StatusCode::UNAUTHORIZED, "challenge"),
+            MockResponse::text(StatusCode::OK, "recovered"),
+        ],
+    );
+    let auth = ObservationAuthVars::bearer_replacing(
+        "initial-token",
+        "replacement-token",
+        "refresh",
+        events.clone(),
+    );
+    let mut client = observation_client(auth, &harness);
+    client.set_runtime_hooks(Arc::new(ObservationRuntimeHooks::new(events.clone())));
+
+    let response = client
+        .request(keyed(Method::POST, auth_policy(AuthPlacement::Bearer)))
+        .response()
+        .await?;
+
+    assert_eq!(response.value(), "recovered");
+    let requests = harness.requests().await;
+    assert_eq!(requests.len(), 2);
+    let first = requests[0].headers.get(&KEY).expect("generated key");
+    assert_eq!(first.len(), 36);
+    assert_eq!(requests[1].headers.get(&KEY), Some(first));
+    let events = events.lock().await.clone();
+    assert!(events.iter().any(|event| event == "hook_idempotent:true"));
+    assert!(!events.iter().any(|event| event == "hook_idempotent:false"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn each_logical_call_gets_a_fresh_key() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events,
+        vec![
+            MockResponse::text(StatusCode::OK, "one"),
+            MockResponse::text(StatusCode::OK, "two"),
+        ],
+    );
+    let client = client(TestAuthVars::default(), harness.clone());
+    let endpoint = keyed(Method::POST, ResolvedPolicy::default());
+
+    client.request(endpoint.clone()).execute().await?;
+    client.request(endpoint).execute().await?;
+
+    let requests = harness.requests().await;
+    let first = requests[0].headers.get(&KEY).expect("first key");
+    let second = requests[1].headers.get(&KEY).expect("second key");
+    assert_ne!(first, second);
+    Ok(())
+}
+
+#[tokio::test]
+async fn caller_keys_win_and_safe_methods_are_not_keyed() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events,
+        vec![
+            MockResponse::text(StatusCode::OK, "supplied"),
+            MockResponse::text(StatusCode::OK, "read"),
+            MockResponse::text(StatusCode::OK, "undeclared"),
+        ],
+    );
+    let client = client(TestAuthVars::default(), harness.clone());
+
+    client
+        .request(keyed(Method::POST, ResolvedPolicy::default()))
+        .idempotency_key(HeaderValue::from_static("order-42"))
+        .execute()
+        .await?;
+    client
+        .request(keyed(Method::GET, ResolvedPolicy::default()))
+        .execute()
+        .await?;
+    client
+        .request(TextEndpoint {
+            method: Method::POST,
+            ..TextEndpoint::default()
+        })
+        .execute()
+        .await?;
+
+    let requests = harness.requests().await;
+    assert_eq!(requests[0].headers.get(&KEY).unwrap(), "order-42");
+    assert!(requests[1].headers.get(&KEY).is_none());
+    assert!(requests[2].headers.get(&KEY).is_none());
+    Ok(())
+}
+
+#[tokio::test]
+async fn explicit_endpoint_header_is_not_replaced() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness =
+        DeterministicHarness::new(events, vec![MockResponse::text(StatusCode::OK, "explicit")]);
+    let client = client(TestAuthVars::default(), harness.clone());
+    let mut policy = ResolvedPolicy::default();
+    policy
+        .headers
+        .insert(KEY, HeaderValue::from_static("from-endpoint"));
+
+    client.request(keyed(Method::PUT, policy)).execute().await?;
+
+    let requests = harness.requests().await;
+    assert_eq!(requests[0].headers.get(&KEY).unwrap(), "from-endpoint");
+    Ok(())
+}
@@ -0,0 +1,216 @@
+use super::common::{
+    ObservationRateLimiter, ObservationRuntimeHooks, TestAuthVars, TestCx, auth_policy,
+    request_plan,
+};
+use crate::error::ErrorCategory;
+use crate::in_process::{
+    InProcessBody, InProcessError, InProcessService, configure_application_service,
+};
+use crate::prelude::{ApiClient, ApiClientError, Text};
+use crate::regression_tests::test_api::{AuthPlacement, PreparedBody};
+use bytes::Bytes;
+use http::{HeaderValue, Method, StatusCode};
+use http_body_util::BodyExt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tower_service::Service;
+
+#[derive(Clone, Copy)]
+enum Behavior {
+    Echo,
+    Fail,
+    Stall,
+}
+
+#[derive(Clone)]
+struct RecordingService {
+    behavior: Behavior,
+    seen: Arc<Mutex<Vec<String>>>,
+}
+
+impl RecordingService {
+    fn new(behavior: Behavior) -> Self {
+        Self {
+            behavior,
+            seen: Arc::default(),
+        }
+    }
+
+    fn seen(&self) -> Vec<String> {
+        self.seen.lock().expect("seen lock").clone()
+    }
+}
+
+impl Service<http::Request<InProcessBody>> for RecordingService {
+    type Response = http::Response<reqwest::Body>;
+    type Error = InProcessError;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<InProcessBody>) -> Self::Future {
+        let behavior = self.behavior;
+        let seen = self.seen.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let body = body.collect().await?.to_bytes();
+            let authorization = parts
+                .headers
+                .get(http::header::AUTHORIZATION)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("<none>");
+            seen.lock().expect("seen lock").push(format!(
+                "{} {} {authorization} {}",
+                parts.method,
+                parts.uri,
+                String::from_utf8_lossy(&body)
+            ));
+            match behavior {
+                Behavior::Echo => Ok(http::Response::builder()
+                    .status(StatusCode::OK)
+                    .header(http::header::CONTENT_TYPE, "text/plain")
+                    .body(reqwest::Body::from(body))?),
+                Behavior::Fail => Err("service rejected the request".into()),
+                Behavior::Stall => std::future::pending().await,
+            }
+        })
+    }
+}
+
+fn client_with(service: &RecordingService, auth: TestAuthVars) -> ApiClient<TestCx> {
+    ApiClient::<TestCx>::with_safe_reqwest_builder((), auth, |builder| {
+        configure_application_service(builder, InProcessService::new(service.clone()))
+    })
+    .expect("in-process client")
+}
+
+#[tokio::test]
+async fn in_process_service_receives_the_authenticated_materialized_request() {
+    let service = RecordingService::new(Behavior::Echo);
+    let mut client = client_with(
+        &service,
+        TestAuthVars {
+            token: Some("in-process-token".to_string()),
+            identity: "in-process",
+        },
+    );
+    let events = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+    let hooks = Arc::new(ObservationRuntimeHooks::default());
+    client.configure(|cfg| {
+        cfg.rate_limiter(Arc::new(ObservationRateLimiter::new(events.clone())));
+        cfg.runtime_hooks(hooks.clone());
+    });
+    let mut plan = request_plan(
+        "InProcessEcho",
+        Method::POST,
+        "/echo",
+        auth_policy(AuthPlacement::Bearer),
+        None,
+    );
+    plan.body = PreparedBody::reusable_bytes(
+        Bytes::from_static(b"ping"),
+        Some(HeaderValue::from_static("text/plain")),
+    );
+
+    let response = client
+        .execute_plan::<Text<String>>(plan)
+        .await
+        .expect("in-process response");
+
+    assert_eq!(response.value(), "ping");
+    assert_eq!(
+        service.seen(),
+        ["POST http://example.com/echo Bearer in-process-token ping"]
+    );
+    let events = events.lock().await;
+    assert!(
+        events.iter().any(|event| event == "rate_acquire"),
+        "{events:?}"
+    );
+    assert!(
+        events.iter().any(|event| event == "rate_status:200 OK"),
+        "{events:?}"
+    );
+    assert!(!hooks.events.lock().await.is_empty());
+}
+
+#[tokio::test]
+async fn in_process_response_bodies_respect_the_configured_limit() {
+    let service = RecordingService::new(Behavior::Echo);
+    let mut client = client_with(&service, TestAuthVars::default());
+    client.configure(|cfg| {
+        cfg.max_response_body_bytes(4);
+    });
+    let mut plan = request_plan(
+        "InProcessLimit",
+        Method::POST,
+        "/echo",
+        Default::default(),
+        None,
+    );
+    plan.body = PreparedBody::reusable_bytes(
+        Bytes::from_static(b"longer than four bytes"),
+        Some(HeaderValue::from_static("text/plain")),
+    );
+
+    let error = client
+        .execute_plan::<Text<String>>(plan)
+        .await
+        .expect_err("oversized in-process response");
+
+    assert!(
+        matches!(
+            error,
+            ApiClientError::ResponseBodyLimitExceeded { limit: 4, .. }
+                | ApiClientError::ResponseTooLarge { limit: 4, .. }
+        ),
+        "{error:?}"
+    );
+}
+
+#[tokio::test]
+async fn in_process_service_errors_are_opaque_request_failures() {
+    let service = RecordingService::new(Behavior::Fail);
+    let client = client_with(&service, TestAuthVars::default());
+
+    let error = client
+        .execute_plan::<Text<String>>(request_plan(
+            "InProcessFailure",
+            Method::GET,
+            "/fail",
+            Default::default(),
+            None,
+        ))
+        .await
+        .expect_err("service failure");
+
+    assert_eq!(error.category(), ErrorCategory::RequestExecution);
+    assert!(!format!("{error:?}").contains("service rejected the request"));
+    assert_eq!(service.seen().len(), 1);
+}
+
+#[tokio::test]
+async fn in_process_calls_are_bounded_by_the_request_timeout() {
+    let service = RecordingService::new(Behavior::Stall);
+    let client = client_with(&service, TestAuthVars::default());
+    let mut plan = request_plan(
+        "InProcessStall",
+        Method::GET,
+        "/stall",
+        Default::default(),
+        None,
+    );
+    plan.endpoint.policy.timeout = Some(Duration::from_millis(20));
+
+    let error = client
+        .execute_plan::<Text<String>>(plan)
+        .await
+        .expect_err("stalled service");
+
+    assert_eq!(error.category(), ErrorCategory::Timeout);
+}
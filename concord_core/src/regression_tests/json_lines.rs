@@ -0,0 +1,250 @@
+use super::common::{DeterministicHarness, MockResponse, TestAuthVars, TestCx, client};
+use crate::regression_tests::test_api::{
+    EndpointMeta, EndpointPlan, PreparedBody, RegressionEndpoint, RegressionPlanContext,
+    RegressionReusableEndpoint, RequestOverrides, RequestPlan, ResolvedPolicy, ResolvedRoute,
+    ResponsePlan,
+};
+use crate::regression_tests::test_api::{JsonLines, ResponseEntity};
+use bytes::Bytes;
+use concord_core::advanced::{
+    JsonLinesStream, RateLimitContext, RateLimitFuture, RateLimitGuard, RateLimiter, RuntimeConfig,
+};
+use concord_core::prelude::{ApiClient, ApiClientError};
+use http::{HeaderValue, Method, StatusCode};
+use serde::Deserialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::sync::Mutex;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Row {
+    id: u32,
+}
+
+#[derive(Clone)]
+struct RowsEndpoint;
+
+impl RegressionEndpoint<TestCx> for RowsEndpoint {
+    type Response = JsonLinesStream<Row>;
+
+    fn execute<'a>(
+        client: &'a ApiClient<TestCx>,
+        plan: RequestPlan,
+    ) -> Pin<Box<dyn Future<Output = Result<Self::Response, ApiClientError>> + Send + 'a>> {
+        <JsonLines<Row> as ResponseEntity>::execute(client, plan)
+    }
+}
+
+impl RegressionReusableEndpoint<TestCx> for RowsEndpoint {
+    fn plan(
+        &self,
+        _ctx: &RegressionPlanContext<'_, TestCx>,
+    ) -> Result<RequestPlan, ApiClientError> {
+        Ok(RequestPlan {
+            endpoint: EndpointPlan {
+                meta: EndpointMeta {
+                    name: "ExportRows",
+                    method: Method::GET,
+                    idempotent: true,
+                    facade_path: &[],
+                },
+                route: ResolvedRoute::new(http::uri::Scheme::HTTP, "example.com", "/rows"),
+                policy: ResolvedPolicy::default(),
+                response: ResponsePlan {
+                    accept: Some(HeaderValue::from_static("application/x-ndjson")),
+                    no_content: false,
+                    format: crate::regression_tests::test_api::Format::Binary,
+                    errors: Default::default(),
+                },
+                pagination: None,
+            },
+            body: PreparedBody::empty(),
+            overrides: RequestOverrides::default(),
+        })
+    }
+}
+
+fn rows_response(chunks: &[&'static [u8]]) -> MockResponse {
+    let mut response = MockResponse::text(StatusCode::OK, Bytes::new())
+        .with_content_length(None)
+        .with_chunks(chunks.iter().copied().map(Bytes::from_static).collect());
+    response.headers.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson"),
+    );
+    response
+}
+
+async fn rows(
+    response: MockResponse,
+    configure: impl FnOnce(&mut RuntimeConfig),
+) -> JsonLinesStream<Row> {
+    let harness = DeterministicHarness::new(Arc::new(Mutex::new(Vec::new())), vec![response]);
+    let mut client = client(TestAuthVars::default(), harness);
+    client.configure(configure);
+    client
+        .request(RowsEndpoint)
+        .execute()
+        .await
+        .expect("json lines head succeeds")
+}
+
+#[tokio::test]
+async fn json_lines_decode_items_across_chunks_including_unterminated_tail() {
+    let mut rows = rows(
+        rows_response(&[b"{\"id\":1}\n{\"i", b"d\":2}\r\n\n", b"{\"id\":3}"]),
+        |_| {},
+    )
+    .await;
+
+    assert_eq!(rows.next_item().await.expect("first"), Some(Row { id: 1 }));
+    assert_eq!(rows.next_item().await.expect("second"), Some(Row { id: 2 }));
+    assert_eq!(rows.next_item().await.expect("tail"), Some(Row { id: 3 }));
+    assert_eq!(rows.next_item().await.expect("EOF"), None);
+    assert_eq!(rows.next_item().await.expect("terminal"), None);
+    assert_eq!(
+        rows.response().headers()[http::header::CONTENT_TYPE],
+        "application/x-ndjson"
+    );
+}
+
+#[tokio::test]
+async fn json_lines_accept_jsonl_media_type_aliases() {
+    for media_type in [
+        "application/jsonl",
+        "application/x-jsonlines; charset=utf-8",
+    ] {
+        let mut response = rows_response(&[b"{\"id\":1}\n"]);
+        response.headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static(media_type),
+        );
+        let mut rows = rows(response, |_| {}).await;
+
+        assert_eq!(rows.next_item().await.expect("first"), Some(Row { id: 1 }));
+        assert_eq!(rows.next_item().await.expect("EOF"), None);
+    }
+}
+
+#[tokio::test]
+async fn json_lines_decode_error_is_sanitized_and_consumes_one_line() {
+    let mut rows = rows(
+        rows_response(&[b"{\"id\":1}\n{\"id\":\"DECODE_SENTINEL\"}\n{\"id\":3}\n"]),
+        |_| {},
+    )
+    .await;
+
+    assert_eq!(rows.next_item().await.expect("first"), Some(Row { id: 1 }));
+    let error = rows.next_item().await.expect_err("invalid line must fail");
+    assert!(matches!(error, ApiClientError::Decode { .. }));
+    assert!(!format!("{error} {error:?}").contains("DECODE_SENTINEL"));
+    assert_eq!(
+        rows.next_item().await.expect("stream continues"),
+        Some(Row { id: 3 })
+    );
+    assert_eq!(rows.next_item().await.expect("EOF"), None);
+}
+
+#[tokio::test]
+async fn json_lines_per_line_limit_is_terminal_after_complete_lines() {
+    let mut rows = rows(
+        rows_response(&[
+            b"{\"id\":1}\n{\"id\":2,\"pad\":\"LINE_LIMIT_SENTINEL",
+            b"\"}\n{\"id\":3}\n",
+        ]),
+        |config| {
+            config.max_stream_line_bytes(16);
+        },
+    )
+    .await;
+
+    assert_eq!(rows.next_item().await.expect("first"), Some(Row { id: 1 }));
+    let error = rows
+        .next_item()
+        .await
+        .expect_err("oversized line must fail");
+    assert!(matches!(
+        error,
+        ApiClientError::StreamLineLimitExceeded { limit: 16, .. }
+    ));
+    assert!(!format!("{error:?}").contains("LINE_LIMIT_SENTINEL"));
+    assert_eq!(rows.next_item().await.expect("terminal"), None);
+}
+
+#[tokio::test]
+async fn json_lines_respects_stream_response_body_limit() {
+    let mut rows = rows(
+        rows_response(&[b"{\"id\":1}\n", b"{\"id\":2}\n"]),
+        |config| {
+            config.max_stream_response_body_bytes(12);
+        },
+    )
+    .await;
+
+    assert_eq!(rows.next_item().await.expect("first"), Some(Row { id: 1 }));
+    let error = rows.next_item().await.expect_err("body limit must fail");
+    assert!(matches!(
+        error,
+        ApiClientError::ResponseBodyLimitExceeded { limit: 12, .. }
+    ));
+    assert_eq!(rows.next_item().await.expect("terminal"), None);
+}
+
+/// Counts guards that are still held.
+struct InFlightLimiter(Arc<AtomicUsize>);
+
+struct InFlightGuard(Arc<AtomicUsize>);
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl RateLimiter for InFlightLimiter {
+    fn acquire_guarded<'a>(
+        &'a self,
+        _ctx: RateLimitContext<'a>,
+    ) -> RateLimitFuture<'a, Result<RateLimitGuard, ApiClientError>> {
+        self.0.fetch_add(1, Ordering::SeqCst);
+        let guard = InFlightGuard(self.0.clone());
+        Box::pin(async move { Ok(RateLimitGuard::default().hold(guard)) })
+    }
+}
+
+#[tokio::test]
+async fn json_lines_stream_holds_the_rate_limit_permit_until_eof() {
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let limiter = Arc::new(InFlightLimiter(in_flight.clone()));
+    let mut stream = rows(
+        rows_response(&[b"{\"id\":1}\n", b"{\"id\":2}\n"]),
+        |config| {
+            config.rate_limiter(limiter);
+        },
+    )
+    .await;
+
+    assert_eq!(in_flight.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        stream.next_item().await.expect("first"),
+        Some(Row { id: 1 })
+    );
+    assert_eq!(in_flight.load(Ordering::SeqCst), 1);
+    assert_eq!(
+        stream.next_item().await.expect("second"),
+        Some(Row { id: 2 })
+    );
+    assert_eq!(stream.next_item().await.expect("EOF"), None);
+    assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+
+    let limiter = Arc::new(InFlightLimiter(in_flight.clone()));
+    let stream = rows(rows_response(&[b"{\"id\":1}\n"]), |config| {
+        config.rate_limiter(limiter);
+    })
+    .await;
+    assert_eq!(in_flight.load(Ordering::SeqCst), 1);
+    drop(stream);
+    assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+}
@@ -0,0 +1,102 @@
+use super::common::{
+    DeterministicHarness, ItemsEndpoint, MockResponse, ObservationAuthVars, TestAuthVars,
+    TextEndpoint, auth_policy, client, observation_client,
+};
+use crate::regression_tests::test_api::AuthPlacement;
+use concord_core::advanced::InMemoryMetricsRecorder;
+use concord_core::prelude::{ApiClientError, PaginationTermination};
+use http::{Method, StatusCode};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+#[tokio::test]
+async fn auth_recovery_counts_both_executions_under_one_key() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events.clone(),
+        vec![
+            MockResponse::text(StatusCode::UNAUTHORIZED, "challenge"),
+            MockResponse::text(StatusCode::OK, "recovered"),
+        ],
+    );
+    let auth = ObservationAuthVars::bearer_replacing(
+        "initial-token",
+        "replacement-token",
+        "refresh",
+        events,
+    );
+    let recorder = Arc::new(InMemoryMetricsRecorder::new());
+    let mut client = observation_client(auth, &harness);
+    client.configure(|config| {
+        config.metrics_recorder(recorder.clone());
+    });
+
+    let value = client
+        .request(TextEndpoint {
+            method: Method::POST,
+            policy: auth_policy(AuthPlacement::Bearer),
+            ..TextEndpoint::default()
+        })
+        .execute()
+        .await?;
+
+    assert_eq!(value, "recovered");
+    let metrics = recorder
+        .endpoint("Text", &Method::POST)
+        .expect("recorded metrics");
+    assert_eq!(metrics.statuses, BTreeMap::from([(200, 1), (401, 1)]));
+    assert_eq!(metrics.auth_recoveries, 1);
+    assert_eq!(metrics.rate_limit_wait.count, 2);
+    assert_eq!(metrics.time_to_headers.count, 2);
+    assert_eq!(metrics.body_read.count, 1);
+    assert_eq!(metrics.request_errors, 0);
+    assert_eq!(recorder.snapshot().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn every_page_and_error_status_is_measured() -> Result<(), ApiClientError> {
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::OK, "a,b"),
+            MockResponse::text(StatusCode::OK, "c"),
+            MockResponse::text(StatusCode::INTERNAL_SERVER_ERROR, "boom"),
+        ],
+    );
+    let recorder = Arc::new(InMemoryMetricsRecorder::new());
+    let mut client = client(TestAuthVars::default(), harness);
+    client.configure(|config| {
+        config.metrics_recorder(recorder.clone());
+    });
+
+    let items = client
+        .request(ItemsEndpoint::default())
+        .paginate(PaginationTermination::hard_page_cap(4))
+        .collect()
+        .await?;
+    assert_eq!(items.len(), 3);
+    let error = client
+        .request(TextEndpoint::default())
+        .execute()
+        .await
+        .expect_err("server error");
+    assert!(
+        matches!(error, ApiClientError::HttpStatus { .. }),
+        "{error:?}"
+    );
+
+    let pages = recorder
+        .endpoint("Items", &Method::GET)
+        .expect("recorded pages");
+    assert_eq!(pages.statuses, BTreeMap::from([(200, 2)]));
+    assert_eq!(pages.time_to_headers.count, 2);
+    assert_eq!(pages.body_read.count, 2);
+    let failed = recorder
+        .endpoint("Text", &Method::GET)
+        .expect("recorded failure");
+    assert_eq!(failed.statuses, BTreeMap::from([(500, 1)]));
+    assert_eq!(failed.auth_recoveries, 0);
+    Ok(())
+}
@@ -1,9 +1,21 @@
 #![allow(dead_code, unused_imports)]
 
+mod backoff_retry;
+mod circuit_breaker;
+mod coalescing;
 mod common;
 pub(crate) use common::{deterministic_mock, request_plan};
 #[cfg(any(test, feature = "dangerous-dev-tools"))]
 mod deterministic_executor;
+mod error_responses;
+mod event_stream;
+mod hedging;
+mod idempotency_key;
+#[cfg(feature = "dangerous-in-process")]
+mod in_process;
+#[cfg(feature = "json")]
+mod json_lines;
+mod metrics_recorder;
 mod native_runtime;
 mod output_model;
 mod pagination;
@@ -13,9 +25,12 @@ mod redaction_matrix;
 mod request_entities;
 mod request_error;
 mod response_body_limit;
+mod response_cache;
 mod retry_modes;
 mod runtime_config;
 mod runtime_order;
 mod safe_response_url;
 pub(crate) mod test_api;
 mod tls_preflight;
+#[cfg(feature = "tracing")]
+mod tracing_spans;
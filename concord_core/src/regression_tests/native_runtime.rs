@@ -18,10 +18,10 @@ use crate::regression_tests::test_api::{
 };
 use bytes::Bytes;
 use concord_core::advanced::{
-    AuthError, AuthFuture, BodyError, BodyErrorKind, CredentialContext, CredentialId,
-    CredentialProvider, CredentialProviderState, InvalidateReason, OctetStream, RateLimitContext,
-    RateLimitFuture, RateLimitPermit, RateLimitResponseAction, RateLimitResponseContext,
-    RateLimiter, StreamBody, StreamBodyError, StreamResponse,
+    AuthError, AuthFuture, BodyError, BodyErrorKind, CodecError, CredentialContext, CredentialId,
+    CredentialProvider, CredentialProviderState, DecodeContext, ErrorBody, InvalidateReason,
+    OctetStream, RateLimitContext, RateLimitFuture, RateLimitPermit, RateLimitResponseAction,
+    RateLimitResponseContext, RateLimiter, StreamBody, StreamBodyError, StreamResponse,
 };
 #[cfg(feature = "multipart")]
 use concord_core::advanced::{ErrorContext, MultipartBody};
@@ -519,6 +519,64 @@ async fn non_rebuildable_challenged_body_returns_original_status_without_recover
     }
 }
 
+#[derive(Debug, Eq, PartialEq)]
+struct ChallengeBody(String);
+
+fn decode_challenge(bytes: Bytes, _ctx: DecodeContext<'_>) -> Result<ErrorBody, CodecError> {
+    String::from_utf8(bytes.to_vec())
+        .map(|text| ErrorBody::new(ChallengeBody(text)))
+        .map_err(|error| CodecError::with_source("text decode failed", error))
+}
+
+#[tokio::test]
+async fn non_rebuildable_challenged_status_decodes_its_typed_error_body() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::from_replies(
+        events.clone(),
+        [ScriptedReply::status(StatusCode::UNAUTHORIZED)
+            .with_header(
+                http::header::CONTENT_TYPE,
+                HeaderValue::from_static("text/plain"),
+            )
+            .with_body(Bytes::from_static(b"token expired"))],
+    );
+    let capture = harness.clone();
+    let client = observation_client(
+        ObservationAuthVars::bearer("AUTH_ONE_SHOT_SENTINEL", "refresh", events.clone()),
+        &harness,
+    );
+    let mut plan = native_stream_plan(
+        "OneShotChallenge",
+        StreamBody::from_bytes(Bytes::from_static(b"one-shot")),
+    );
+    plan.endpoint.policy = auth_policy(crate::regression_tests::test_api::AuthPlacement::Bearer);
+    plan.endpoint.response.errors.push(
+        Some(StatusCode::UNAUTHORIZED),
+        "ChallengeBody",
+        decode_challenge,
+    );
+
+    let error = client
+        .execute_plan::<concord_core::prelude::Text<String>>(plan)
+        .await
+        .expect_err("one-shot body cannot perform authentication recovery");
+
+    assert_eq!(error.http_status(), Some(StatusCode::UNAUTHORIZED));
+    assert_eq!(
+        error.error_body::<ChallengeBody>(),
+        Some(&ChallengeBody("token expired".to_string()))
+    );
+    assert_eq!(capture.sent_count().await, 1);
+    let events = events.lock().await;
+    assert!(!events.iter().any(|event| event == "auth_retry"));
+    assert!(
+        events
+            .iter()
+            .any(|event| event == "auth_invalidation:identity_match=true:applied=true"),
+        "{events:?}"
+    );
+}
+
 #[tokio::test]
 async fn non_rebuildable_challenged_stream_returns_status_after_invalidation() {
     let events = Arc::new(Mutex::new(Vec::new()));
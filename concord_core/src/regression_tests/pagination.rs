@@ -1898,6 +1898,164 @@ async fn loop_detection_still_default_enabled() {
     assert_eq!(sent.sent_count().await, 1);
 }
 
+async fn next_from<S: futures_core::Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+    std::future::poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+}
+
+#[tokio::test]
+async fn pages_stream_fetches_next_page_only_when_polled() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events,
+        vec![
+            MockResponse::text(StatusCode::OK, "a,b"),
+            MockResponse::text(StatusCode::OK, "c,d"),
+            MockResponse::text(StatusCode::OK, "e"),
+        ],
+    );
+    let sent = harness.clone();
+    let client = client(TestAuthVars::default(), harness);
+
+    let endpoint = ItemsEndpoint {
+        policy: Default::default(),
+        start: 0,
+        count: 2,
+        pagination: PaginationVariant::OffsetLimit {
+            offset: 0,
+            limit: 2,
+        },
+        ..Default::default()
+    };
+
+    let mut pages = client
+        .request(endpoint)
+        .paginate(PaginationTermination::hard_page_cap(10))
+        .pages();
+    assert_eq!(sent.sent_count().await, 0);
+
+    let first = next_from(&mut pages).await.expect("first page")?;
+    assert_eq!(first, vec!["a", "b"]);
+    assert_eq!(sent.sent_count().await, 1);
+
+    let second = next_from(&mut pages).await.expect("second page")?;
+    assert_eq!(second, vec!["c", "d"]);
+    assert_eq!(sent.sent_count().await, 2);
+
+    let third = next_from(&mut pages).await.expect("short final page")?;
+    assert_eq!(third, vec!["e"]);
+    assert!(next_from(&mut pages).await.is_none());
+    assert!(next_from(&mut pages).await.is_none());
+    assert_eq!(sent.sent_count().await, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn items_stream_truncates_take_items_like_collect() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events,
+        vec![
+            MockResponse::text(StatusCode::OK, numbered_items(0, 20)),
+            MockResponse::text(StatusCode::OK, numbered_items(20, 20)),
+        ],
+    );
+    let sent = harness.clone();
+    let client = client(TestAuthVars::default(), harness);
+
+    let endpoint = ItemsEndpoint {
+        policy: Default::default(),
+        start: 0,
+        count: 20,
+        pagination: PaginationVariant::OffsetLimit {
+            offset: 0,
+            limit: 20,
+        },
+        ..Default::default()
+    };
+
+    let mut items = client
+        .request(endpoint)
+        .paginate(PaginationTermination::take_items(25))
+        .items();
+    let mut out = Vec::new();
+    while let Some(item) = next_from(&mut items).await {
+        out.push(item?);
+        if out.len() == 20 {
+            assert_eq!(sent.sent_count().await, 1);
+        }
+    }
+
+    assert_eq!(out.len(), 25);
+    assert_eq!(out.last().map(String::as_str), Some("item-24"));
+    assert_eq!(sent.sent_count().await, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn pages_stream_yields_page_before_hard_page_cap_error() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness =
+        DeterministicHarness::new(events, vec![MockResponse::text(StatusCode::OK, "a,b")]);
+    let sent = harness.clone();
+    let client = client(TestAuthVars::default(), harness);
+
+    let endpoint = ItemsEndpoint {
+        policy: Default::default(),
+        start: 0,
+        count: 2,
+        pagination: PaginationVariant::OffsetLimit {
+            offset: 0,
+            limit: 2,
+        },
+        ..Default::default()
+    };
+
+    let mut pages = client
+        .request(endpoint)
+        .paginate(PaginationTermination::hard_page_cap(1))
+        .pages();
+
+    let first = next_from(&mut pages)
+        .await
+        .expect("first page")
+        .expect("first page decodes");
+    assert_eq!(first, vec!["a", "b"]);
+    let err = next_from(&mut pages)
+        .await
+        .expect("cap error")
+        .expect_err("hard page cap should fail before fetching page 2");
+    assert_eq!(
+        err.pagination_error_kind(),
+        Some(concord_core::error::PaginationErrorKind::PageLimitExceeded)
+    );
+    assert!(next_from(&mut pages).await.is_none());
+    assert_eq!(sent.sent_count().await, 1);
+}
+
+#[tokio::test]
+async fn pages_stream_reports_invalid_termination_without_harness() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(events, Vec::new());
+    let sent = harness.clone();
+    let client = client(TestAuthVars::default(), harness);
+
+    let mut pages = client
+        .request(ItemsEndpoint::default())
+        .paginate(PaginationTermination::hard_item_cap(0))
+        .pages();
+
+    let err = next_from(&mut pages)
+        .await
+        .expect("termination error")
+        .expect_err("hard item cap zero must be rejected");
+    assert_eq!(
+        err.pagination_error_kind(),
+        Some(concord_core::error::PaginationErrorKind::InvalidSize)
+    );
+    assert!(next_from(&mut pages).await.is_none());
+    assert_eq!(sent.sent_count().await, 0);
+}
+
 fn pagination_sentinels() -> RedactionSentinels {
     RedactionSentinels::new(
         "PAGINATION_AUTH_SENTINEL_PR16",
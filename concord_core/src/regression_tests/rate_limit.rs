@@ -64,6 +64,7 @@ impl RegressionReusableEndpoint<TestCx> for ObservationFailureEndpoint {
                     accept: Some(HeaderValue::from_static("application/json")),
                     no_content: false,
                     format: crate::regression_tests::test_api::Format::Text,
+                    errors: Default::default(),
                 },
                 pagination: None,
             },
@@ -112,6 +113,7 @@ impl RegressionReusableEndpoint<TestCx> for HostlessEndpoint {
                     accept: Some(HeaderValue::from_static("text/plain")),
                     no_content: false,
                     format: crate::regression_tests::test_api::Format::Text,
+                    errors: Default::default(),
                 },
                 pagination: None,
             },
@@ -818,6 +820,7 @@ async fn rate_limit_response_huge_delay_is_capped_before_storage() {
             page_index: 0,
             idempotent: true,
             max_cooldown: Duration::from_secs(1),
+            clock: crate::clock::test_system_clock(),
             plan: &plan,
         },
         status: StatusCode::TOO_MANY_REQUESTS,
@@ -1293,3 +1296,34 @@ async fn no_default_rate_limit_explicit_noop_limiter_opt_out_succeeds() -> Resul
     assert_eq!(sent.sent_count().await, 1);
     Ok(())
 }
+
+#[tokio::test]
+async fn exhausted_x_ratelimit_quota_delays_the_next_call() -> Result<(), ApiClientError> {
+    let mut quota = http::HeaderMap::new();
+    quota.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+    quota.insert("x-ratelimit-reset-after", HeaderValue::from_static("0.05"));
+    let rate_limiter = Arc::new(
+        concord_core::advanced::GovernorRateLimiter::new()
+            .with_response_policy(Arc::new(concord_core::advanced::XRateLimitHeaders)),
+    );
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::OK, "last").with_headers(quota),
+            MockResponse::text(StatusCode::OK, "next"),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    configure_runtime(&mut client, Some(rate_limiter));
+
+    let first = client.request(TextEndpoint::default()).response().await?;
+    assert_eq!(first.value(), "last");
+
+    let started = std::time::Instant::now();
+    let next = client.request(TextEndpoint::default()).response().await?;
+
+    assert_eq!(next.value(), "next");
+    assert!(started.elapsed() >= Duration::from_millis(40));
+    assert_eq!(harness.sent_count().await, 2);
+    Ok(())
+}
@@ -74,6 +74,7 @@ impl RegressionReusableEndpoint<TestCx> for ByteBodyEndpoint {
                     accept: Some(HeaderValue::from_static("text/plain")),
                     no_content: false,
                     format: crate::regression_tests::test_api::Format::Text,
+                    errors: Default::default(),
                 },
                 pagination: None,
             },
@@ -119,6 +120,7 @@ impl RegressionReusableEndpoint<TestCx> for StreamEndpoint {
                     accept: Some(HeaderValue::from_static("application/octet-stream")),
                     no_content: false,
                     format: crate::regression_tests::test_api::Format::Binary,
+                    errors: Default::default(),
                 },
                 pagination: None,
             },
@@ -0,0 +1,221 @@
+use super::common::{
+    DeterministicHarness, MockResponse, ObservationAuthVars, TestAuthVars, TextEndpoint,
+    auth_policy, client, observation_client,
+};
+use crate::regression_tests::test_api::{AuthPlacement, ResolvedPolicy};
+use concord_core::advanced::{CachePolicy, CacheStatus, InMemoryResponseCache};
+use concord_core::prelude::ApiClientError;
+use http::header::{CACHE_CONTROL, ETAG, IF_NONE_MATCH};
+use http::{HeaderMap, HeaderValue, Method, StatusCode};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+fn cached_endpoint(max_age: Duration) -> TextEndpoint {
+    TextEndpoint {
+        policy: ResolvedPolicy {
+            cache: Some(CachePolicy::max_age(max_age)),
+            ..ResolvedPolicy::default()
+        },
+        ..TextEndpoint::default()
+    }
+}
+
+fn text_with(
+    status: StatusCode,
+    body: &'static str,
+    headers: &[(&'static str, &'static str)],
+) -> MockResponse {
+    let mut map = HeaderMap::new();
+    map.insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("text/plain"),
+    );
+    for (name, value) in headers {
+        map.insert(*name, HeaderValue::from_static(value));
+    }
+    MockResponse::text(status, body).with_headers(map)
+}
+
+#[tokio::test]
+async fn fresh_entries_are_served_without_a_network_round_trip() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events,
+        vec![text_with(
+            StatusCode::OK,
+            "cached",
+            &[("cache-control", "max-age=60")],
+        )],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.set_response_cache(Some(Arc::new(InMemoryResponseCache::new(8))));
+    let endpoint = cached_endpoint(Duration::from_secs(1));
+
+    let first = client.request(endpoint.clone()).response().await?;
+    let second = client.request(endpoint).response().await?;
+
+    assert_eq!(first.cache, CacheStatus::Miss);
+    assert_eq!(second.cache, CacheStatus::Hit);
+    assert_eq!(second.value, "cached");
+    assert_eq!(second.status, StatusCode::OK);
+    assert_eq!(harness.requests().await.len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn stale_entries_revalidate_and_decode_the_stored_body_on_304() -> Result<(), ApiClientError>
+{
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events,
+        vec![
+            text_with(
+                StatusCode::OK,
+                "versioned",
+                &[("cache-control", "no-cache"), ("etag", "\"v1\"")],
+            ),
+            text_with(
+                StatusCode::NOT_MODIFIED,
+                "",
+                &[("cache-control", "no-cache")],
+            ),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.set_response_cache(Some(Arc::new(InMemoryResponseCache::new(8))));
+    let endpoint = cached_endpoint(Duration::from_secs(60));
+
+    let first = client.request(endpoint.clone()).response().await?;
+    let second = client.request(endpoint).response().await?;
+
+    assert_eq!(first.cache, CacheStatus::Miss);
+    assert_eq!(second.cache, CacheStatus::Revalidated);
+    assert_eq!(second.status, StatusCode::OK);
+    assert_eq!(second.value, "versioned");
+    assert_eq!(second.headers.get(ETAG).unwrap(), "\"v1\"");
+    assert_eq!(second.headers.get(CACHE_CONTROL).unwrap(), "no-cache");
+
+    let requests = harness.requests().await;
+    assert_eq!(requests.len(), 2);
+    assert!(requests[0].headers.get(IF_NONE_MATCH).is_none());
+    assert_eq!(requests[1].headers.get(IF_NONE_MATCH).unwrap(), "\"v1\"");
+    Ok(())
+}
+
+#[tokio::test]
+async fn uncached_endpoints_and_no_store_responses_bypass_storage() -> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events,
+        vec![
+            MockResponse::text(StatusCode::OK, "plain"),
+            text_with(StatusCode::OK, "secret", &[("cache-control", "no-store")]),
+            text_with(StatusCode::OK, "again", &[]),
+        ],
+    );
+    let cache = Arc::new(InMemoryResponseCache::new(8));
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.set_response_cache(Some(cache.clone()));
+
+    let plain = client.request(TextEndpoint::default()).response().await?;
+    assert_eq!(plain.cache, CacheStatus::Bypass);
+    assert!(cache.is_empty());
+
+    let endpoint = cached_endpoint(Duration::from_secs(60));
+    let no_store = client.request(endpoint.clone()).response().await?;
+    assert_eq!(no_store.cache, CacheStatus::Miss);
+    assert!(cache.is_empty());
+
+    let again = client.request(endpoint).response().await?;
+    assert_eq!(again.cache, CacheStatus::Miss);
+    assert_eq!(again.value, "again");
+    assert_eq!(cache.len(), 1);
+    assert_eq!(harness.requests().await.len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn unsafe_methods_and_clients_without_a_backend_bypass_the_cache()
+-> Result<(), ApiClientError> {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events,
+        vec![
+            text_with(StatusCode::OK, "posted", &[("cache-control", "max-age=60")]),
+            text_with(StatusCode::OK, "one", &[("cache-control", "max-age=60")]),
+            text_with(StatusCode::OK, "two", &[("cache-control", "max-age=60")]),
+        ],
+    );
+    let cache = Arc::new(InMemoryResponseCache::new(8));
+    let mut cached_client = client(TestAuthVars::default(), harness.clone());
+    cached_client.set_response_cache(Some(cache.clone()));
+    let post = TextEndpoint {
+        method: Method::POST,
+        ..cached_endpoint(Duration::from_secs(60))
+    };
+    let posted = cached_client.request(post).response().await?;
+    assert_eq!(posted.cache, CacheStatus::Bypass);
+    assert!(cache.is_empty());
+
+    let plain_client = client(TestAuthVars::default(), harness.clone());
+    let endpoint = cached_endpoint(Duration::from_secs(60));
+    let one = plain_client.request(endpoint.clone()).response().await?;
+    let two = plain_client.request(endpoint).response().await?;
+    assert_eq!(one.cache, CacheStatus::Bypass);
+    assert_eq!(two.value, "two");
+    assert_eq!(harness.requests().await.len(), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn authenticated_endpoints_are_cached_only_when_marked_shared() -> Result<(), ApiClientError>
+{
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events.clone(),
+        vec![
+            text_with(
+                StatusCode::OK,
+                "private-one",
+                &[("cache-control", "max-age=60")],
+            ),
+            text_with(
+                StatusCode::OK,
+                "private-two",
+                &[("cache-control", "max-age=60")],
+            ),
+            text_with(StatusCode::OK, "shared", &[("cache-control", "max-age=60")]),
+        ],
+    );
+    let cache = Arc::new(InMemoryResponseCache::new(8));
+    let mut client = observation_client(
+        ObservationAuthVars::bearer("token", "refresh", events),
+        &harness,
+    );
+    client.set_response_cache(Some(cache.clone()));
+    let authenticated = |policy: CachePolicy| TextEndpoint {
+        policy: ResolvedPolicy {
+            cache: Some(policy),
+            ..auth_policy(AuthPlacement::Bearer)
+        },
+        ..TextEndpoint::default()
+    };
+    let private = authenticated(CachePolicy::max_age(Duration::from_secs(60)));
+
+    let one = client.request(private.clone()).response().await?;
+    let two = client.request(private).response().await?;
+    assert_eq!(one.cache, CacheStatus::Bypass);
+    assert_eq!(two.cache, CacheStatus::Bypass);
+    assert_eq!(two.value, "private-two");
+    assert!(cache.is_empty());
+
+    let shared = authenticated(CachePolicy::max_age(Duration::from_secs(60)).shared());
+    let stored = client.request(shared.clone()).response().await?;
+    let hit = client.request(shared).response().await?;
+    assert_eq!(stored.cache, CacheStatus::Miss);
+    assert_eq!(hit.cache, CacheStatus::Hit);
+    assert_eq!(hit.value, "shared");
+    assert_eq!(harness.requests().await.len(), 3);
+    Ok(())
+}
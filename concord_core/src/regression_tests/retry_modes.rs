@@ -44,6 +44,7 @@ fn disabled_retry_plan() -> RequestPlan {
                 accept: Some(HeaderValue::from_static("text/plain")),
                 no_content: false,
                 format: crate::codec::Format::Text,
+                errors: Default::default(),
             },
             pagination: None,
         },
@@ -373,6 +373,7 @@ impl RegressionReusableEndpoint<TestCx> for QueryStreamEndpoint {
                     accept: Some(HeaderValue::from_static("application/octet-stream")),
                     no_content: false,
                     format: crate::regression_tests::test_api::Format::Binary,
+                    errors: Default::default(),
                 },
                 pagination: None,
             },
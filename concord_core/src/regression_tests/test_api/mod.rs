@@ -15,6 +15,8 @@ pub(crate) use crate::endpoint::{
     EndpointMeta, EndpointPlan, PaginationMarker, RequestOverrides, RequestPlan, RequestPlanView,
     ResolvedRoute, ResponsePlan,
 };
+#[cfg(feature = "json")]
+pub(crate) use crate::io::JsonLines;
 #[cfg(feature = "multipart")]
 pub(crate) use crate::io::MultipartRequest;
 pub(crate) use crate::io::{
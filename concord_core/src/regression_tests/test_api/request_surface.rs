@@ -17,10 +17,12 @@ use std::num::NonZeroUsize;
 use std::pin::Pin;
 use std::time::Duration;
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct RequestOptions {
     debug_level: Option<DebugLevel>,
     timeout_override: TimeoutOverride,
+    idempotency_key: Option<http::HeaderValue>,
+    traceparent: Option<http::HeaderValue>,
 }
 
 impl Default for RequestOptions {
@@ -28,6 +30,8 @@ impl Default for RequestOptions {
         Self {
             debug_level: None,
             timeout_override: TimeoutOverride::Inherit,
+            idempotency_key: None,
+            traceparent: None,
         }
     }
 }
@@ -41,6 +45,9 @@ impl RequestOptions {
         };
         plan.overrides.debug_level = self.debug_level;
         plan.overrides.page_index = page_index;
+        if page_index == 0 {
+            plan.overrides.idempotency_key = self.idempotency_key.clone();
+        }
     }
 }
 
@@ -69,10 +76,22 @@ impl<'a, Cx: ClientContext, E: RegressionIntoPlan<Cx>> PendingRequest<'a, Cx, E>
         self
     }
 
+    pub fn idempotency_key(mut self, key: http::HeaderValue) -> Self {
+        self.options.idempotency_key = Some(key);
+        self
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn traceparent(mut self, parent: http::HeaderValue) -> Self {
+        self.options.traceparent = Some(parent);
+        self
+    }
+
     pub async fn execute(self) -> Result<E::Response, ApiClientError> {
         let client = self.client;
         let plan = self.request_plan()?;
-        E::execute(client, plan).await
+        let trace = plan.overrides.trace.clone();
+        trace.run(E::execute(client, plan)).await
     }
 
     pub async fn response(self) -> Result<DecodedResponse<E::Response>, ApiClientError>
@@ -81,7 +100,8 @@ impl<'a, Cx: ClientContext, E: RegressionIntoPlan<Cx>> PendingRequest<'a, Cx, E>
     {
         let client = self.client;
         let plan = self.request_plan()?;
-        E::execute_response(client, plan).await
+        let trace = plan.overrides.trace.clone();
+        trace.run(E::execute_response(client, plan)).await
     }
 
     #[cfg(feature = "dangerous-raw-response")]
@@ -90,7 +110,8 @@ impl<'a, Cx: ClientContext, E: RegressionIntoPlan<Cx>> PendingRequest<'a, Cx, E>
     ) -> Result<crate::dangerous::BuiltResponse, ApiClientError> {
         let client = self.client;
         let plan = self.request_plan()?;
-        client.execute_plan_raw(plan).await
+        let trace = plan.overrides.trace.clone();
+        trace.run(client.execute_plan_raw(plan)).await
     }
 
     fn request_plan(self) -> Result<crate::endpoint::RequestPlan, ApiClientError> {
@@ -104,6 +125,11 @@ impl<'a, Cx: ClientContext, E: RegressionIntoPlan<Cx>> PendingRequest<'a, Cx, E>
             auth_vars: client.auth_vars(),
         };
         let mut plan = endpoint.into_plan(&context)?;
+        plan.overrides.trace = crate::telemetry::CallTrace::open(
+            &plan.endpoint.meta,
+            client.runtime_state().propagate_traceparent(),
+            options.traceparent.as_ref(),
+        );
         options.apply_to(&mut plan, 0);
         Ok(plan)
     }
@@ -133,7 +159,8 @@ where
     pub async fn execute_stream(self) -> Result<StreamResponse<M>, ApiClientError> {
         let client = self.client;
         let plan = self.request_plan()?;
-        E::execute(client, plan).await
+        let trace = plan.overrides.trace.clone();
+        trace.run(E::execute(client, plan)).await
     }
 }
 
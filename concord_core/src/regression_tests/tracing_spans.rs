@@ -0,0 +1,351 @@
+use super::common::{
+    DeterministicHarness, ItemsEndpoint, MockResponse, ObservationAuthVars, TestAuthVars,
+    TextEndpoint, auth_policy, client, observation_client,
+};
+use crate::regression_tests::test_api::{AuthPlacement, ResolvedPolicy};
+use concord_core::prelude::{ApiClientError, PaginationTermination};
+use http::{HeaderName, HeaderValue, Method, StatusCode};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex as StdMutex};
+use tokio::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::subscriber::Interest;
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(Clone, Debug)]
+struct RecordedSpan {
+    id: u64,
+    name: &'static str,
+    parent: Option<u64>,
+    fields: BTreeMap<&'static str, String>,
+}
+
+impl RecordedSpan {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields.get(name).map(String::as_str)
+    }
+}
+
+/// Records every span with its fields and contextual parent.
+#[derive(Clone, Default)]
+struct SpanRecorder {
+    spans: Arc<StdMutex<Vec<RecordedSpan>>>,
+    entered: Arc<StdMutex<Vec<u64>>>,
+}
+
+impl SpanRecorder {
+    fn named(&self, name: &str) -> Vec<RecordedSpan> {
+        self.spans
+            .lock()
+            .expect("span recorder")
+            .iter()
+            .filter(|span| span.name == name)
+            .cloned()
+            .collect()
+    }
+}
+
+struct FieldRecorder<'a>(&'a mut BTreeMap<&'static str, String>);
+
+impl Visit for FieldRecorder<'_> {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name(), value.to_string());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name(), format!("{value:?}"));
+    }
+}
+
+impl Subscriber for SpanRecorder {
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        Interest::sometimes()
+    }
+
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, attrs: &Attributes<'_>) -> Id {
+        let parent = attrs.parent().map(Id::into_u64).or_else(|| {
+            attrs
+                .is_contextual()
+                .then(|| self.entered.lock().expect("entered").last().copied())
+                .flatten()
+        });
+        let mut spans = self.spans.lock().expect("span recorder");
+        let id = spans.len() as u64 + 1;
+        let mut span = RecordedSpan {
+            id,
+            name: attrs.metadata().name(),
+            parent,
+            fields: BTreeMap::new(),
+        };
+        attrs.record(&mut FieldRecorder(&mut span.fields));
+        spans.push(span);
+        Id::from_u64(id)
+    }
+
+    fn record(&self, id: &Id, values: &Record<'_>) {
+        let mut spans = self.spans.lock().expect("span recorder");
+        let span = &mut spans[id.into_u64() as usize - 1];
+        values.record(&mut FieldRecorder(&mut span.fields));
+    }
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, _: &Event<'_>) {}
+
+    fn enter(&self, id: &Id) {
+        self.entered.lock().expect("entered").push(id.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.entered.lock().expect("entered").pop();
+    }
+}
+
+#[tokio::test]
+async fn auth_recovery_opens_a_second_execution_under_the_same_call() -> Result<(), ApiClientError>
+{
+    let recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let harness = DeterministicHarness::new(
+        events.clone(),
+        vec![
+            MockResponse::text(StatusCode::UNAUTHORIZED, "challenge"),
+            MockResponse::text(StatusCode::OK, "recovered"),
+        ],
+    );
+    let auth = ObservationAuthVars::bearer_replacing(
+        "initial-token",
+        "replacement-token",
+        "refresh",
+        events,
+    );
+    let client = observation_client(auth, &harness);
+
+    let value = client
+        .request(TextEndpoint {
+            method: Method::POST,
+            policy: auth_policy(AuthPlacement::Bearer),
+            ..TextEndpoint::default()
+        })
+        .execute()
+        .await?;
+
+    assert_eq!(value, "recovered");
+    let calls = recorder.named("concord.call");
+    assert_eq!(calls.len(), 1);
+    let call = &calls[0];
+    assert_eq!(call.field("endpoint"), Some("Text"));
+    assert_eq!(call.field("http.request.method"), Some("POST"));
+    assert_eq!(call.field("error.type"), None);
+
+    let executions = recorder.named("concord.execution");
+    let kinds: Vec<_> = executions
+        .iter()
+        .map(|span| span.field("execution").unwrap_or_default())
+        .collect();
+    assert_eq!(kinds, ["initial", "auth_recovery"]);
+    assert!(executions.iter().all(|span| span.parent == Some(call.id)));
+    assert_eq!(
+        executions[0].field("http.response.status_code"),
+        Some("401")
+    );
+    assert_eq!(
+        executions[1].field("http.response.status_code"),
+        Some("200")
+    );
+
+    let credential_ops: Vec<_> = recorder
+        .named("concord.credential")
+        .into_iter()
+        .map(|span| {
+            let parent = executions
+                .iter()
+                .find(|execution| Some(execution.id) == span.parent)
+                .and_then(|execution| execution.field("execution"))
+                .unwrap_or("<detached>")
+                .to_string();
+            (
+                span.field("operation").unwrap_or_default().to_string(),
+                parent,
+            )
+        })
+        .collect();
+    assert_eq!(
+        credential_ops,
+        [
+            ("acquire".to_string(), "initial".to_string()),
+            ("invalidate".to_string(), "auth_recovery".to_string()),
+            ("acquire".to_string(), "auth_recovery".to_string()),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn each_page_is_an_execution_of_one_pagination_call() -> Result<(), ApiClientError> {
+    let recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::OK, "a,b"),
+            MockResponse::text(StatusCode::OK, "c"),
+        ],
+    );
+    let client = client(TestAuthVars::default(), harness);
+
+    let items = client
+        .request(ItemsEndpoint::default())
+        .paginate(PaginationTermination::hard_page_cap(4))
+        .collect()
+        .await?;
+
+    assert_eq!(items.len(), 3);
+    let calls = recorder.named("concord.call");
+    assert_eq!(calls.len(), 1);
+    let executions = recorder.named("concord.execution");
+    let pages: Vec<_> = executions
+        .iter()
+        .map(|span| span.field("page_index").unwrap_or_default())
+        .collect();
+    assert_eq!(pages, ["0", "1"]);
+    assert!(
+        executions
+            .iter()
+            .all(|span| span.parent == Some(calls[0].id)
+                && span.field("execution") == Some("initial"))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn recorded_fields_are_redacted_and_traceparent_is_opt_in() -> Result<(), ApiClientError> {
+    let recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::OK, "traced"),
+            MockResponse::text(StatusCode::OK, "untraced"),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    let mut policy = ResolvedPolicy::default();
+    policy.headers.insert(
+        HeaderName::from_static("x-session-id"),
+        HeaderValue::from_static("LEAK_SENTINEL_HEADER"),
+    );
+    policy.query.push((
+        "client_secret".to_string(),
+        "LEAK_SENTINEL_QUERY".to_string(),
+    ));
+    let endpoint = TextEndpoint {
+        policy,
+        ..TextEndpoint::default()
+    };
+
+    client.configure(|config| {
+        config.propagate_traceparent(true);
+    });
+    client.request(endpoint.clone()).execute().await?;
+    client.configure(|config| {
+        config.propagate_traceparent(false);
+    });
+    client.request(endpoint).execute().await?;
+
+    let executions = recorder.named("concord.execution");
+    let url = executions[0].field("url.full").expect("recorded url");
+    assert!(url.contains("client_secret=<redacted>"), "{url}");
+    let headers = executions[0]
+        .field("http.request.headers")
+        .expect("recorded headers");
+    assert!(headers.contains("x-session-id"), "{headers}");
+    for span in recorder.spans.lock().expect("span recorder").iter() {
+        for value in span.fields.values() {
+            assert!(!value.contains("LEAK_SENTINEL"), "{value}");
+        }
+    }
+
+    let requests = harness.requests().await;
+    let traceparent = requests[0]
+        .headers
+        .get("traceparent")
+        .and_then(|value| value.to_str().ok())
+        .expect("propagated traceparent");
+    let trace_id = recorder.named("concord.call")[0]
+        .field("trace_id")
+        .expect("recorded trace id")
+        .to_string();
+    let span_id = executions[0].field("span_id").expect("recorded span id");
+    assert_eq!(traceparent, format!("00-{trace_id}-{span_id}-01"));
+    assert_eq!(trace_id.len(), 32);
+    assert_eq!(span_id.len(), 16);
+    assert!(requests[1].headers.get("traceparent").is_none());
+    assert_eq!(executions[1].field("span_id"), None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn caller_traceparent_continues_the_callers_trace() -> Result<(), ApiClientError> {
+    let recorder = SpanRecorder::default();
+    let _guard = tracing::subscriber::set_default(recorder.clone());
+    let harness = DeterministicHarness::new(
+        Arc::new(Mutex::new(Vec::new())),
+        vec![
+            MockResponse::text(StatusCode::OK, "continued"),
+            MockResponse::text(StatusCode::OK, "restarted"),
+        ],
+    );
+    let mut client = client(TestAuthVars::default(), harness.clone());
+    client.configure(|config| {
+        config.propagate_traceparent(true);
+    });
+
+    let parent_trace = "4bf92f3577b34da6a3ce929d0e0e4736";
+    client
+        .request(TextEndpoint::default())
+        .traceparent(HeaderValue::from_static(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-00",
+        ))
+        .execute()
+        .await?;
+    client
+        .request(TextEndpoint::default())
+        .traceparent(HeaderValue::from_static("not-a-traceparent"))
+        .execute()
+        .await?;
+
+    let calls = recorder.named("concord.call");
+    let executions = recorder.named("concord.execution");
+    let requests = harness.requests().await;
+    let header = |index: usize| {
+        requests[index]
+            .headers
+            .get("traceparent")
+            .and_then(|value| value.to_str().ok())
+            .expect("propagated traceparent")
+            .to_string()
+    };
+
+    // The parent's trace id and unsampled flag carry over; the parent id is
+    // this execution's own.
+    assert_eq!(calls[0].field("trace_id"), Some(parent_trace));
+    assert_eq!(calls[0].field("parent_span_id"), Some("00f067aa0ba902b7"));
+    let span_id = executions[0].field("span_id").expect("recorded span id");
+    assert_eq!(header(0), format!("00-{parent_trace}-{span_id}-00"));
+
+    // A malformed parent falls back to a new, sampled trace.
+    let trace_id = calls[1].field("trace_id").expect("recorded trace id");
+    assert_ne!(trace_id, parent_trace);
+    assert_eq!(calls[1].field("parent_span_id"), None);
+    let span_id = executions[1].field("span_id").expect("recorded span id");
+    assert_eq!(header(1), format!("00-{trace_id}-{span_id}-01"));
+    Ok(())
+}
@@ -2,7 +2,7 @@ use crate::client::{ApiClient, ClientContext};
 use crate::debug::DebugLevel;
 use crate::endpoint::{
     GeneratedEndpoint, GeneratedIntoPreparedCall, GeneratedPaginatedEndpoint,
-    GeneratedResponseTerminalEndpoint, GeneratedReusableEndpoint,
+    GeneratedResponseTerminalEndpoint, GeneratedReusableEndpoint, RequestIdentity,
 };
 use crate::error::{ApiClientError, ErrorContext, PaginationErrorKind};
 use crate::pagination::{
@@ -10,26 +10,34 @@ use crate::pagination::{
     PaginationTermination, ProgressKey,
 };
 use crate::stream_response::StreamResponse;
+use crate::telemetry::CallTrace;
 use crate::timeout::TimeoutOverride;
 use crate::transport::DecodedResponse;
-use std::cmp::Ordering;
-use std::collections::HashSet;
+use futures_core::Stream;
+use std::collections::{HashSet, VecDeque};
 use std::future::{Future, IntoFuture};
 use std::num::NonZeroUsize;
 use std::pin::Pin;
+use std::task::{Context, Poll};
 use std::time::Duration;
 
 /// Options runtime partagées entre requête simple et pagination.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Debug)]
 pub(crate) struct RequestOptions {
     pub debug_level: Option<DebugLevel>,
     pub timeout_override: TimeoutOverride,
+    pub idempotency_key: Option<http::HeaderValue>,
+    pub traceparent: Option<http::HeaderValue>,
+    pub trace: CallTrace,
 }
 impl Default for RequestOptions {
     fn default() -> Self {
         Self {
             debug_level: None,
             timeout_override: TimeoutOverride::Inherit,
+            idempotency_key: None,
+            traceparent: None,
+            trace: CallTrace::default(),
         }
     }
 }
@@ -43,6 +51,12 @@ impl RequestOptions {
         };
         plan.overrides.debug_level = self.debug_level;
         plan.overrides.page_index = page_index;
+        plan.overrides.trace = self.trace.clone();
+        // A caller-supplied key names one logical call; later pages get
+        // their own keys.
+        if page_index == 0 {
+            plan.overrides.idempotency_key = self.idempotency_key.clone();
+        }
     }
 }
 
@@ -92,10 +106,34 @@ impl<'a, Cx: ClientContext, E: GeneratedIntoPreparedCall<Cx>> PendingRequest<'a,
         self
     }
 
+    /// Use `key` as this call's idempotency key instead of a generated one.
+    ///
+    /// It is sent in the endpoint's `idempotency_key` header, or in
+    /// `Idempotency-Key` when the endpoint declares none, and is reused by
+    /// the authentication-recovery execution and any transport resend.
+    #[inline]
+    pub fn idempotency_key(mut self, key: http::HeaderValue) -> Self {
+        self.opts.idempotency_key = Some(key);
+        self
+    }
+
+    /// Continue the caller's trace: with `propagate_traceparent` enabled,
+    /// every execution of this call sends `parent`'s trace id and sampled
+    /// flag with its own parent id. A malformed `parent` is ignored and the
+    /// call starts a new trace with a random id, as it does without one.
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub fn traceparent(mut self, parent: http::HeaderValue) -> Self {
+        self.opts.traceparent = Some(parent);
+        self
+    }
+
     #[inline]
     pub async fn execute(self) -> Result<E::Response, ApiClientError> {
         let client = self.client;
-        self.prepared_call()?.execute(client).await
+        let call = self.prepared_call()?;
+        let trace = call.plan().overrides.trace.clone();
+        trace.run(call.execute(client)).await
     }
 
     pub async fn execute_and_store_manual<F>(self, slot: F) -> Result<(), ApiClientError>
@@ -115,7 +153,8 @@ impl<'a, Cx: ClientContext, E: GeneratedIntoPreparedCall<Cx>> PendingRequest<'a,
             endpoint: plan.endpoint.meta.name,
             method: plan.endpoint.meta.method.clone(),
         };
-        let value = call.execute(client).await?;
+        let trace = plan.overrides.trace.clone();
+        let value = trace.run(call.execute(client)).await?;
         let auth_state = client
             .try_auth_state()
             .map_err(|source| ApiClientError::Auth {
@@ -123,7 +162,7 @@ impl<'a, Cx: ClientContext, E: GeneratedIntoPreparedCall<Cx>> PendingRequest<'a,
                 source,
             })?;
         slot(auth_state.as_ref())
-            .set_manual(value)
+            .set_manual(value, client.credential_store(), client.clock())
             .await
             .map_err(|source| ApiClientError::Auth { ctx, source })?;
         Ok(())
@@ -136,7 +175,9 @@ impl<'a, Cx: ClientContext, E: GeneratedIntoPreparedCall<Cx>> PendingRequest<'a,
         E: GeneratedResponseTerminalEndpoint<Cx>,
     {
         let client = self.client;
-        self.prepared_call()?.execute_with_meta(client).await
+        let call = self.prepared_call()?;
+        let trace = call.plan().overrides.trace.clone();
+        trace.run(call.execute_with_meta(client)).await
     }
 
     #[cfg(feature = "dangerous-raw-response")]
@@ -145,15 +186,31 @@ impl<'a, Cx: ClientContext, E: GeneratedIntoPreparedCall<Cx>> PendingRequest<'a,
     ) -> Result<crate::dangerous::BuiltResponse, ApiClientError> {
         let client = self.client;
         let call = self.prepared_call()?;
-        client.execute_plan_raw(call.into_plan()).await
+        let trace = call.plan().overrides.trace.clone();
+        trace.run(client.execute_plan_raw(call.into_plan())).await
     }
 
     fn prepared_call(
         self,
     ) -> Result<crate::__private::GeneratedPreparedCall<Cx, E::Response>, ApiClientError> {
-        let Self { client, ep, opts } = self;
+        let Self {
+            client,
+            ep,
+            mut opts,
+        } = self;
+        #[cfg(any(test, feature = "dangerous-dev-tools"))]
+        let endpoint_matches = client.development_endpoint_matches(&ep);
         let plan_ctx = client.plan_context();
         let mut plan = ep.into_plan(&plan_ctx)?;
+        #[cfg(any(test, feature = "dangerous-dev-tools"))]
+        {
+            plan.plan_mut().overrides.endpoint_matches = endpoint_matches;
+        }
+        opts.trace = CallTrace::open(
+            &plan.plan().endpoint.meta,
+            client.runtime_state().propagate_traceparent(),
+            opts.traceparent.as_ref(),
+        );
         opts.apply_to(plan.plan_mut(), 0);
         Ok(plan)
     }
@@ -177,7 +234,9 @@ where
     #[inline]
     pub async fn execute_stream(self) -> Result<StreamResponse<M>, ApiClientError> {
         let client = self.client;
-        self.prepared_call()?.execute(client).await
+        let call = self.prepared_call()?;
+        let trace = call.plan().overrides.trace.clone();
+        trace.run(call.execute(client)).await
     }
 }
 
@@ -223,13 +282,255 @@ impl<'a, Cx: ClientContext, E: GeneratedReusableEndpoint<Cx>> PaginatedRequest<'
         E: GeneratedPaginatedEndpoint<Cx>,
         E::Response: PageItems,
     {
-        // This intentionally has a dedicated loop instead of delegating to a
-        // higher-level callback surface: collection can enforce hard item caps
-        // from the actual `into_items()` length, while page-level processing
-        // cannot. Keep pagination ordering changes in sync with other
-        // pagination execution paths.
-        let caps = self.caps;
-        let pending = self.pending;
+        // Collection, `pages()`, and `items()` all drive the same
+        // `PaginationDriver`, so loop detection, request-identity progress
+        // checks, and termination caps stay identical across surfaces.
+        // Collection applies the `TakeItems` truncation reported by the driver
+        // from the actual `into_items()` length.
+        let Some(mut driver) = PaginationDriver::start(self.pending, self.caps)? else {
+            return Ok(Vec::new());
+        };
+        let mut out: Vec<<E::Response as PageItems>::Item> = Vec::new();
+        while let Some(page) = driver.next_page().await? {
+            page.extend_items(&mut out);
+        }
+        Ok(out)
+    }
+
+    /// Stream decoded pages lazily.
+    ///
+    /// The next page is requested only when the stream is polled after the
+    /// previous page was yielded. `TakeItems` stops after the page that
+    /// reaches the item limit but yields that page whole; use
+    /// [`PaginatedRequest::items`] for exact item truncation. A hard-cap or
+    /// pagination error is yielded once as the final stream element.
+    pub fn pages(self) -> PageStream<'a, Cx, E>
+    where
+        Cx: 'a,
+        E: GeneratedPaginatedEndpoint<Cx> + 'a,
+        E::Response: PageItems,
+    {
+        PageStream::new(PaginationDriver::start(self.pending, self.caps))
+    }
+
+    /// Stream page items lazily, fetching the next page only after every item
+    /// of the current page was yielded.
+    pub fn items(self) -> ItemStream<'a, Cx, E>
+    where
+        Cx: 'a,
+        E: GeneratedPaginatedEndpoint<Cx> + 'a,
+        E::Response: PageItems,
+    {
+        ItemStream {
+            pages: self.pages(),
+            buffered: VecDeque::new(),
+        }
+    }
+}
+
+type PageFetchFuture<'a, Cx, E> = Pin<
+    Box<
+        dyn Future<
+                Output = (
+                    PaginationDriver<'a, Cx, E>,
+                    PageFetchResult<<E as GeneratedEndpoint<Cx>>::Response>,
+                ),
+            > + Send
+            + 'a,
+    >,
+>;
+
+type PageFetchResult<Page> = Result<Option<PaginatedPage<Page>>, ApiClientError>;
+
+enum PageStreamState<'a, Cx, E>
+where
+    Cx: ClientContext,
+    E: GeneratedPaginatedEndpoint<Cx>,
+    E::Response: PageItems,
+{
+    Idle(Box<PaginationDriver<'a, Cx, E>>),
+    Fetching(PageFetchFuture<'a, Cx, E>),
+    Failed(ApiClientError),
+    Done,
+}
+
+/// Lazy [`Stream`] of decoded pages returned by [`PaginatedRequest::pages`].
+pub struct PageStream<'a, Cx, E>
+where
+    Cx: ClientContext,
+    E: GeneratedPaginatedEndpoint<Cx>,
+    E::Response: PageItems,
+{
+    state: PageStreamState<'a, Cx, E>,
+}
+
+impl<'a, Cx, E> PageStream<'a, Cx, E>
+where
+    Cx: ClientContext + 'a,
+    E: GeneratedPaginatedEndpoint<Cx> + 'a,
+    E::Response: PageItems,
+{
+    fn new(start: Result<Option<PaginationDriver<'a, Cx, E>>, ApiClientError>) -> Self {
+        let state = match start {
+            Ok(Some(driver)) => PageStreamState::Idle(Box::new(driver)),
+            Ok(None) => PageStreamState::Done,
+            Err(err) => PageStreamState::Failed(err),
+        };
+        Self { state }
+    }
+
+    fn poll_page(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<PaginatedPage<E::Response>, ApiClientError>>> {
+        loop {
+            match std::mem::replace(&mut self.state, PageStreamState::Done) {
+                PageStreamState::Idle(mut driver) => {
+                    self.state = PageStreamState::Fetching(Box::pin(async move {
+                        let result = driver.next_page().await;
+                        (*driver, result)
+                    }));
+                }
+                PageStreamState::Fetching(mut fetch) => match fetch.as_mut().poll(cx) {
+                    Poll::Pending => {
+                        self.state = PageStreamState::Fetching(fetch);
+                        return Poll::Pending;
+                    }
+                    Poll::Ready((driver, Ok(Some(page)))) => {
+                        self.state = PageStreamState::Idle(Box::new(driver));
+                        return Poll::Ready(Some(Ok(page)));
+                    }
+                    Poll::Ready((_, Ok(None))) => return Poll::Ready(None),
+                    Poll::Ready((_, Err(err))) => return Poll::Ready(Some(Err(err))),
+                },
+                PageStreamState::Failed(err) => return Poll::Ready(Some(Err(err))),
+                PageStreamState::Done => return Poll::Ready(None),
+            }
+        }
+    }
+}
+
+impl<'a, Cx, E> Stream for PageStream<'a, Cx, E>
+where
+    Cx: ClientContext + 'a,
+    E: GeneratedPaginatedEndpoint<Cx> + 'a,
+    E::Response: PageItems,
+{
+    type Item = Result<E::Response, ApiClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut()
+            .poll_page(cx)
+            .map(|page| page.map(|page| page.map(|page| page.page)))
+    }
+}
+
+/// Lazy [`Stream`] of page items returned by [`PaginatedRequest::items`].
+pub struct ItemStream<'a, Cx, E>
+where
+    Cx: ClientContext,
+    E: GeneratedPaginatedEndpoint<Cx>,
+    E::Response: PageItems,
+{
+    pages: PageStream<'a, Cx, E>,
+    buffered: VecDeque<<E::Response as PageItems>::Item>,
+}
+
+// Buffered items are never pinned; only the boxed page fetch is polled.
+impl<'a, Cx, E> Unpin for ItemStream<'a, Cx, E>
+where
+    Cx: ClientContext,
+    E: GeneratedPaginatedEndpoint<Cx>,
+    E::Response: PageItems,
+{
+}
+
+impl<'a, Cx, E> Stream for ItemStream<'a, Cx, E>
+where
+    Cx: ClientContext + 'a,
+    E: GeneratedPaginatedEndpoint<Cx> + 'a,
+    E::Response: PageItems,
+{
+    type Item = Result<<E::Response as PageItems>::Item, ApiClientError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(item) = this.buffered.pop_front() {
+                return Poll::Ready(Some(Ok(item)));
+            }
+            match this.pages.poll_page(cx) {
+                Poll::Ready(Some(Ok(page))) => this.buffered.extend(page.into_items()),
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// One decoded page plus the item truncation required by `TakeItems`.
+struct PaginatedPage<Page> {
+    page: Page,
+    take_items: Option<usize>,
+}
+
+impl<Page: PageItems> PaginatedPage<Page> {
+    fn into_items(self) -> Vec<Page::Item> {
+        let mut items = self.page.into_items();
+        if let Some(take) = self.take_items {
+            items.truncate(take);
+        }
+        items
+    }
+
+    fn extend_items(self, out: &mut Vec<Page::Item>) {
+        out.extend(self.into_items());
+    }
+}
+
+enum PaginationDriverState {
+    Fetch,
+    Fail(ApiClientError),
+    Done,
+}
+
+/// Resumable pagination loop shared by `collect()` and the stream adapters.
+///
+/// Each `next_page` call performs at most one visible page execution. Errors
+/// detected after a page was decoded, such as an exceeded hard page cap, are
+/// deferred so that the decoded page is still delivered before the error.
+struct PaginationDriver<'a, Cx, E>
+where
+    Cx: ClientContext,
+    E: GeneratedPaginatedEndpoint<Cx>,
+    E::Response: PageItems,
+{
+    pending: PendingRequest<'a, Cx, E>,
+    runtime: Box<dyn PaginationRuntime<E, E::Response>>,
+    caps: PaginationCaps,
+    ctx: ErrorContext,
+    seen: Option<HashSet<ProgressKey>>,
+    progress_state: PaginationRunState,
+    items_count: usize,
+    page_index: u32,
+    state: PaginationDriverState,
+}
+
+impl<'a, Cx, E> PaginationDriver<'a, Cx, E>
+where
+    Cx: ClientContext + 'a,
+    E: GeneratedPaginatedEndpoint<Cx> + 'a,
+    E::Response: PageItems,
+{
+    /// Validate the termination and initialize the pagination runtime.
+    ///
+    /// Returns `None` when the termination selects zero pages or items, in
+    /// which case no page request is ever sent.
+    fn start(
+        mut pending: PendingRequest<'a, Cx, E>,
+        caps: PaginationCaps,
+    ) -> Result<Option<Self>, ApiClientError> {
         let first_call = pending.ep.plan(&pending.client.plan_context())?;
         let first_plan = first_call.plan();
         let ctx = crate::error::ErrorContext {
@@ -241,56 +542,76 @@ impl<'a, Cx: ClientContext, E: GeneratedReusableEndpoint<Cx>> PaginatedRequest<'
             caps.termination,
             PaginationTermination::TakePages(0) | PaginationTermination::TakeItems(0)
         ) {
-            return Ok(Vec::new());
+            return Ok(None);
         }
-        if let Some(runtime) = pending.ep.pagination_runtime() {
-            return collect_with_pagination_runtime(pending, runtime, caps, ctx).await;
-        }
-        if first_plan.endpoint.pagination.is_some() {
+        let Some(mut runtime) = pending.ep.pagination_runtime() else {
+            if first_plan.endpoint.pagination.is_some() {
+                return Err(ApiClientError::pagination(
+                    ctx,
+                    PaginationErrorKind::UnsupportedPagination,
+                    "pagination requires runtime support",
+                ));
+            }
             return Err(ApiClientError::pagination(
                 ctx,
                 PaginationErrorKind::UnsupportedPagination,
-                "pagination requires runtime support",
+                "endpoint is not paginated",
             ));
-        }
-        Err(ApiClientError::pagination(
+        };
+        runtime.init(
+            &pending.ep,
+            PageApply {
+                endpoint: ctx.endpoint,
+                page_index: 0,
+                ctx: &ctx,
+            },
+        )?;
+        pending.opts.trace = CallTrace::open(
+            &first_plan.endpoint.meta,
+            pending.client.runtime_state().propagate_traceparent(),
+            pending.opts.traceparent.as_ref(),
+        );
+        let seen = if caps.detect_loops {
+            Some(HashSet::new())
+        } else {
+            None
+        };
+        Ok(Some(Self {
+            pending,
+            runtime,
+            caps,
             ctx,
-            PaginationErrorKind::UnsupportedPagination,
-            "endpoint is not paginated",
-        ))
+            seen,
+            progress_state: PaginationRunState::default(),
+            items_count: 0,
+            page_index: 0,
+            state: PaginationDriverState::Fetch,
+        }))
     }
-}
-async fn collect_with_pagination_runtime<'a, Cx, E>(
-    mut pending: PendingRequest<'a, Cx, E>,
-    mut runtime: Box<dyn PaginationRuntime<E, E::Response>>,
-    caps: PaginationCaps,
-    ctx: ErrorContext,
-) -> Result<Vec<<E::Response as PageItems>::Item>, ApiClientError>
-where
-    Cx: ClientContext + 'a,
-    E: GeneratedPaginatedEndpoint<Cx> + 'a,
-    E::Response: PageItems,
-{
-    let page_apply_ctx = PageApply {
-        endpoint: ctx.endpoint,
-        page_index: 0,
-        ctx: &ctx,
-    };
-    let mut out: Vec<<E::Response as PageItems>::Item> = Vec::new();
-    let mut seen: Option<HashSet<ProgressKey>> = if caps.detect_loops {
-        Some(HashSet::new())
-    } else {
-        None
-    };
-    let mut progress_state = PaginationRunState::default();
-    let mut items_count: usize = 0;
-    let mut page_index: u32 = 0;
 
-    runtime.init(&pending.ep, page_apply_ctx)?;
+    async fn next_page(&mut self) -> Result<Option<PaginatedPage<E::Response>>, ApiClientError> {
+        match std::mem::replace(&mut self.state, PaginationDriverState::Done) {
+            PaginationDriverState::Fetch => {}
+            PaginationDriverState::Fail(err) => return Err(err),
+            PaginationDriverState::Done => return Ok(None),
+        }
+        // Any error or stop leaves the driver in `Done`; only a successful
+        // page records its follow-up state.
+        let trace = self.pending.opts.trace.clone();
+        let Some((page, next)) = trace.run(self.fetch_page()).await? else {
+            return Ok(None);
+        };
+        self.state = next;
+        Ok(Some(page))
+    }
 
-    loop {
-        if let Some(seen) = seen.as_mut()
-            && let Some(k) = runtime.progress_key()
+    async fn fetch_page(
+        &mut self,
+    ) -> Result<Option<(PaginatedPage<E::Response>, PaginationDriverState)>, ApiClientError> {
+        let ctx = &self.ctx;
+        let page_index = self.page_index;
+        if let Some(seen) = self.seen.as_mut()
+            && let Some(k) = self.runtime.progress_key()
             && !seen.insert(k.clone())
         {
             return Err(ApiClientError::pagination(
@@ -304,40 +625,44 @@ where
             ));
         }
 
-        runtime.apply(
-            &mut pending.ep,
+        self.runtime.apply(
+            &mut self.pending.ep,
             PageApply {
                 endpoint: ctx.endpoint,
                 page_index: page_index as u64,
-                ctx: &ctx,
+                ctx,
             },
         )?;
-        let expected_items = runtime.expected_items_per_page();
-        let mut call = pending.ep.plan(&pending.client.plan_context())?;
-        pending.opts.apply_to(call.plan_mut(), page_index);
-        let request_identity = pagination_request_identity(call.plan());
-        progress_state.ensure_progress(request_identity, &ctx, page_index)?;
-        let page = call.execute(pending.client).await?;
+        let expected_items = self.runtime.expected_items_per_page();
+        let mut call = self.pending.ep.plan(&self.pending.client.plan_context())?;
+        self.pending.opts.apply_to(call.plan_mut(), page_index);
+        #[cfg(any(test, feature = "dangerous-dev-tools"))]
+        {
+            call.plan_mut().overrides.endpoint_matches = self
+                .pending
+                .client
+                .development_endpoint_matches(&self.pending.ep);
+        }
+        let request_identity = RequestIdentity::of(&call.plan().endpoint);
+        self.progress_state
+            .ensure_progress(request_identity, ctx, page_index)?;
+        let page = call.execute(self.pending.client).await?;
         let page_len = page.item_count();
-        let pre_advance = pre_advance_decision(
-            caps.termination,
-            items_count,
-            page_len,
-            expected_items,
-            &ctx,
-        )?;
+        let termination = self.caps.termination;
+        let pre_advance =
+            pre_advance_decision(termination, self.items_count, page_len, expected_items, ctx)?;
         if let (PaginationTermination::HardItemCap(max_items), Some(new_total)) =
-            (caps.termination, pre_advance.hard_item_cap_exceeded)
+            (termination, pre_advance.hard_item_cap_exceeded)
         {
-            return Err(hard_item_cap_error(&ctx, max_items, new_total, page_index));
+            return Err(hard_item_cap_error(ctx, max_items, new_total, page_index));
         }
         let control_ctrl = if pre_advance.common_stop || pre_advance.take_items_done {
             Control::Stop
         } else {
-            runtime
+            self.runtime
                 .advance(
-                    &mut pending.ep,
-                    &ctx,
+                    &mut self.pending.ep,
+                    ctx,
                     &page,
                     PageAdvance {
                         endpoint: ctx.endpoint,
@@ -347,89 +672,84 @@ where
                 )?
                 .into()
         };
-        let items = <E::Response as PageItems>::into_items(page);
         if page_len == 0 {
-            return Ok(out);
+            return Ok(None);
         }
-        let common_stop = common_content_stop(page_len, expected_items);
-        match caps.termination {
+        let items_overflow = || {
+            ApiClientError::pagination(ctx.clone(), PaginationErrorKind::Overflow, "items overflow")
+        };
+        match termination {
             PaginationTermination::HardItemCap(max_items) => {
-                let new_total = items_count.checked_add(page_len).ok_or_else(|| {
-                    ApiClientError::pagination(
-                        ctx.clone(),
-                        PaginationErrorKind::Overflow,
-                        "items overflow",
-                    )
-                })?;
+                let new_total = self
+                    .items_count
+                    .checked_add(page_len)
+                    .ok_or_else(items_overflow)?;
                 if new_total > max_items {
-                    return Err(hard_item_cap_error(&ctx, max_items, new_total, page_index));
+                    return Err(hard_item_cap_error(ctx, max_items, new_total, page_index));
                 }
-                items_count = new_total;
-                out.extend(items);
+                self.items_count = new_total;
             }
             PaginationTermination::TakeItems(max_items) => {
-                let remaining = max_items.checked_sub(items_count).ok_or_else(|| {
-                    ApiClientError::pagination(
-                        ctx.clone(),
-                        PaginationErrorKind::Overflow,
-                        "items overflow",
-                    )
-                })?;
+                let remaining = max_items
+                    .checked_sub(self.items_count)
+                    .ok_or_else(items_overflow)?;
                 if page_len >= remaining {
-                    out.extend(items.into_iter().take(remaining));
-                    return Ok(out);
+                    let page = PaginatedPage {
+                        page,
+                        take_items: Some(remaining),
+                    };
+                    return Ok(Some((page, PaginationDriverState::Done)));
                 }
-                items_count = items_count.checked_add(page_len).ok_or_else(|| {
-                    ApiClientError::pagination(
-                        ctx.clone(),
-                        PaginationErrorKind::Overflow,
-                        "items overflow",
-                    )
-                })?;
-                out.extend(items);
+                self.items_count = self
+                    .items_count
+                    .checked_add(page_len)
+                    .ok_or_else(items_overflow)?;
             }
             _ => {
-                items_count = items_count.checked_add(page_len).ok_or_else(|| {
-                    ApiClientError::pagination(
-                        ctx.clone(),
-                        PaginationErrorKind::Overflow,
-                        "items overflow",
-                    )
-                })?;
-                out.extend(items);
+                self.items_count = self
+                    .items_count
+                    .checked_add(page_len)
+                    .ok_or_else(items_overflow)?;
             }
         }
-        if common_stop {
-            return Ok(out);
+        let page = PaginatedPage {
+            page,
+            take_items: None,
+        };
+        if common_content_stop(page_len, expected_items) {
+            return Ok(Some((page, PaginationDriverState::Done)));
         }
         let fetched_pages = page_index as usize + 1;
-        match control_ctrl {
-            Control::Continue => match caps.termination {
+        let next = match control_ctrl {
+            Control::Continue => match termination {
                 PaginationTermination::HardPageCap(max_pages) if fetched_pages >= max_pages => {
-                    return Err(ApiClientError::pagination_limit(
-                        ctx,
+                    PaginationDriverState::Fail(ApiClientError::pagination_limit(
+                        ctx.clone(),
                         PaginationErrorKind::PageLimitExceeded,
                         format!(
                             "pagination hard page cap exceeded (max={} seen_items={} page_index={})",
-                            max_pages, items_count, fetched_pages
+                            max_pages, self.items_count, fetched_pages
                         ),
-                    ));
+                    ))
                 }
                 PaginationTermination::TakePages(max_pages) if fetched_pages >= max_pages => {
-                    return Ok(out);
-                }
-                _ => {
-                    page_index = page_index.checked_add(1).ok_or_else(|| {
-                        ApiClientError::pagination(
-                            ctx.clone(),
-                            PaginationErrorKind::Overflow,
-                            "page index overflow",
-                        )
-                    })?;
+                    PaginationDriverState::Done
                 }
+                _ => match page_index.checked_add(1) {
+                    Some(next_index) => {
+                        self.page_index = next_index;
+                        PaginationDriverState::Fetch
+                    }
+                    None => PaginationDriverState::Fail(ApiClientError::pagination(
+                        ctx.clone(),
+                        PaginationErrorKind::Overflow,
+                        "page index overflow",
+                    )),
+                },
             },
-            Control::Stop => return Ok(out),
-        }
+            Control::Stop => PaginationDriverState::Done,
+        };
+        Ok(Some((page, next)))
     }
 }
 
@@ -510,13 +830,13 @@ fn common_content_stop(item_count: usize, expected_items: Option<NonZeroUsize>)
 
 #[derive(Default)]
 struct PaginationRunState {
-    seen_request_identities: HashSet<PaginationRequestIdentity>,
+    seen_request_identities: HashSet<RequestIdentity>,
 }
 
 impl PaginationRunState {
     fn ensure_progress(
         &mut self,
-        current_identity: PaginationRequestIdentity,
+        current_identity: RequestIdentity,
         ctx: &crate::error::ErrorContext,
         page_index: u32,
     ) -> Result<(), ApiClientError> {
@@ -533,50 +853,3 @@ impl PaginationRunState {
         Ok(())
     }
 }
-
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-struct PaginationRequestIdentity {
-    endpoint: &'static str,
-    method: http::Method,
-    scheme: http::uri::Scheme,
-    host: String,
-    path: String,
-    query: Vec<(String, String)>,
-    headers: Vec<PaginationRequestHeader>,
-}
-
-#[derive(Clone, Debug, Eq, PartialEq, Hash)]
-struct PaginationRequestHeader {
-    name: String,
-    value: Vec<u8>,
-}
-
-fn pagination_request_identity(plan: &crate::endpoint::RequestPlan) -> PaginationRequestIdentity {
-    let mut headers: Vec<_> = plan
-        .endpoint
-        .policy
-        .headers
-        .iter()
-        .map(|(name, value)| PaginationRequestHeader {
-            name: name.as_str().to_string(),
-            value: value.as_bytes().to_vec(),
-        })
-        .collect();
-    headers.sort_unstable_by(|a, b| {
-        let name_order = a.name.cmp(&b.name);
-        if name_order == Ordering::Equal {
-            a.value.cmp(&b.value)
-        } else {
-            name_order
-        }
-    });
-    PaginationRequestIdentity {
-        endpoint: plan.endpoint.meta.name,
-        method: plan.endpoint.meta.method.clone(),
-        scheme: plan.endpoint.route.scheme.clone(),
-        host: plan.endpoint.route.host.clone(),
-        path: plan.endpoint.route.path.clone(),
-        query: plan.endpoint.policy.query.clone(),
-        headers,
-    }
-}
@@ -1,4 +1,10 @@
+use crate::auth::CredentialStore;
+use crate::backoff_retry::{BackoffRetry, BackoffRetryConfig};
+use crate::cache::ResponseCache;
+use crate::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use crate::clock::{Clock, SystemClock};
 use crate::debug::{DebugLevel, DebugSink, StderrDebugSink};
+use crate::metrics::{MetricsRecorder, NoopMetricsRecorder};
 use crate::rate_limit::{DefaultRateLimiter, RateLimiter};
 use crate::runtime_hooks::{NoopRuntimeHooks, RuntimeHooks};
 use std::sync::Arc;
@@ -23,12 +29,21 @@ impl Default for DebugConfig {
 pub struct RuntimeConfig {
     pub(crate) hooks: Arc<dyn RuntimeHooks>,
     pub(crate) rate_limiter: Arc<dyn RateLimiter>,
+    pub(crate) clock: Arc<dyn Clock>,
+    pub(crate) metrics: Arc<dyn MetricsRecorder>,
+    pub(crate) credential_store: Option<Arc<dyn CredentialStore>>,
+    pub(crate) response_cache: Option<Arc<dyn ResponseCache>>,
+    pub(crate) circuit_breaker: Option<Arc<CircuitBreaker>>,
+    pub(crate) backoff_retry: Option<Arc<BackoffRetry>>,
     pub(crate) max_rate_limit_cooldown: Duration,
     pub(crate) pagination_detect_loops: bool,
     pub(crate) debug: DebugConfig,
     pub(crate) max_response_body_bytes: Option<usize>,
     pub(crate) max_request_body_bytes: Option<usize>,
     pub(crate) max_stream_response_body_bytes: Option<usize>,
+    pub(crate) max_stream_event_bytes: Option<usize>,
+    pub(crate) max_stream_line_bytes: Option<usize>,
+    pub(crate) propagate_traceparent: bool,
 }
 
 impl Default for RuntimeConfig {
@@ -36,12 +51,21 @@ impl Default for RuntimeConfig {
         Self {
             hooks: Arc::new(NoopRuntimeHooks),
             rate_limiter: Arc::new(DefaultRateLimiter::new()),
+            clock: Arc::new(SystemClock),
+            metrics: Arc::new(NoopMetricsRecorder),
+            credential_store: None,
+            response_cache: None,
+            circuit_breaker: None,
+            backoff_retry: None,
             max_rate_limit_cooldown: Duration::from_secs(60),
             pagination_detect_loops: true,
             debug: DebugConfig::default(),
             max_response_body_bytes: Some(16 * 1024 * 1024),
             max_request_body_bytes: Some(16 * 1024 * 1024),
             max_stream_response_body_bytes: Some(16 * 1024 * 1024),
+            max_stream_event_bytes: Some(1024 * 1024),
+            max_stream_line_bytes: Some(1024 * 1024),
+            propagate_traceparent: false,
         }
     }
 }
@@ -76,6 +100,65 @@ impl RuntimeConfig {
         self
     }
 
+    /// Time source for rate-limit windows and cooldowns and for credential
+    /// expiry and refresh decisions.
+    #[inline]
+    pub fn clock(&mut self, clock: Arc<dyn Clock>) -> &mut Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Receives per-endpoint timings and status counts for every visible
+    /// execution.
+    #[inline]
+    pub fn metrics_recorder(&mut self, recorder: Arc<dyn MetricsRecorder>) -> &mut Self {
+        self.metrics = recorder;
+        self
+    }
+
+    /// Persists acquired credentials across clients and process restarts.
+    #[inline]
+    pub fn credential_store(&mut self, store: Arc<dyn CredentialStore>) -> &mut Self {
+        self.credential_store = Some(store);
+        self
+    }
+
+    /// Stores responses for endpoints that opt in with a `cache` policy.
+    #[inline]
+    pub fn response_cache(&mut self, cache: Arc<dyn ResponseCache>) -> &mut Self {
+        self.response_cache = Some(cache);
+        self
+    }
+
+    /// Fails executions fast while their circuit is open. Replacing the
+    /// configuration starts every circuit closed; configuring other fields
+    /// keeps the current circuit state.
+    #[inline]
+    pub fn circuit_breaker(&mut self, config: CircuitBreakerConfig) -> &mut Self {
+        self.circuit_breaker = Some(CircuitBreaker::new(config));
+        self
+    }
+
+    #[inline]
+    pub fn no_circuit_breaker(&mut self) -> &mut Self {
+        self.circuit_breaker = None;
+        self
+    }
+
+    /// Retries `429` and `503` responses as new visible executions after a
+    /// backoff delay. Replacing the configuration resets the retry budget.
+    #[inline]
+    pub fn backoff_retry(&mut self, config: BackoffRetryConfig) -> &mut Self {
+        self.backoff_retry = Some(Arc::new(BackoffRetry::new(config)));
+        self
+    }
+
+    #[inline]
+    pub fn no_backoff_retry(&mut self) -> &mut Self {
+        self.backoff_retry = None;
+        self
+    }
+
     #[inline]
     pub fn max_rate_limit_cooldown(&mut self, max_delay: Duration) -> &mut Self {
         self.max_rate_limit_cooldown = max_delay;
@@ -128,6 +211,48 @@ impl RuntimeConfig {
         self
     }
 
+    /// Sets the per-event limit for `text/event-stream` responses, counted
+    /// over the raw lines of one event including its blank-line terminator.
+    #[inline]
+    pub fn max_stream_event_bytes(&mut self, bytes: usize) -> &mut Self {
+        self.max_stream_event_bytes = Some(bytes);
+        self
+    }
+
+    #[inline]
+    pub fn no_stream_event_limit(&mut self) -> &mut Self {
+        self.max_stream_event_bytes = None;
+        self
+    }
+
+    /// Sets the per-line limit for JSON Lines responses, counted over one
+    /// line excluding its terminator.
+    #[inline]
+    pub fn max_stream_line_bytes(&mut self, bytes: usize) -> &mut Self {
+        self.max_stream_line_bytes = Some(bytes);
+        self
+    }
+
+    #[inline]
+    pub fn no_stream_line_limit(&mut self) -> &mut Self {
+        self.max_stream_line_bytes = None;
+        self
+    }
+
+    /// Sends a W3C `traceparent` header on every visible execution of a
+    /// call. Executions of one call share a trace id; each gets its own
+    /// parent id, recorded as `span_id` on its `concord.execution` span. A
+    /// `traceparent` the endpoint already sets is left untouched.
+    ///
+    /// The trace id comes from the call's `traceparent` option; calls
+    /// without a valid one start a new trace with a random id.
+    #[cfg(feature = "tracing")]
+    #[inline]
+    pub fn propagate_traceparent(&mut self, enabled: bool) -> &mut Self {
+        self.propagate_traceparent = enabled;
+        self
+    }
+
     #[inline]
     pub fn debug_level_value(&self) -> DebugLevel {
         self.debug.level
@@ -147,9 +272,17 @@ mod tests {
         assert_eq!(cfg.max_response_body_bytes, Some(16 * 1024 * 1024));
         assert_eq!(cfg.max_request_body_bytes, Some(16 * 1024 * 1024));
         assert_eq!(cfg.max_stream_response_body_bytes, Some(16 * 1024 * 1024));
+        assert_eq!(cfg.max_stream_event_bytes, Some(1024 * 1024));
+        assert_eq!(cfg.max_stream_line_bytes, Some(1024 * 1024));
         assert_eq!(cfg.max_rate_limit_cooldown, Duration::from_secs(60));
+        assert!(!cfg.propagate_traceparent);
         assert_eq!(Arc::strong_count(&cfg.hooks), 1);
         assert_eq!(Arc::strong_count(&cfg.rate_limiter), 1);
+        assert_eq!(Arc::strong_count(&cfg.metrics), 1);
+        assert!(cfg.credential_store.is_none());
+        assert!(cfg.response_cache.is_none());
+        assert!(cfg.circuit_breaker.is_none());
+        assert!(cfg.backoff_retry.is_none());
         assert_eq!(Arc::strong_count(&cfg.debug.sink), 1);
     }
 }
@@ -14,6 +14,8 @@ pub struct HookMeta<'a> {
     pub url: &'a str,
     pub page_index: u32,
     pub idempotent: bool,
+    /// Backoff retries of this call before this execution.
+    pub retry: u32,
 }
 
 #[derive(Clone)]
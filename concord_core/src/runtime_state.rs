@@ -1,3 +1,9 @@
+use crate::auth::CredentialStore;
+use crate::backoff_retry::BackoffRetry;
+use crate::cache::ResponseCache;
+use crate::circuit_breaker::CircuitBreaker;
+use crate::clock::Clock;
+use crate::metrics::MetricsRecorder;
 use crate::rate_limit::RateLimiter;
 use crate::runtime::RuntimeConfig;
 use crate::runtime_hooks::RuntimeHooks;
@@ -8,10 +14,19 @@ use std::time::Duration;
 pub struct ClientRuntimeState {
     hooks: Arc<dyn RuntimeHooks>,
     rate_limiter: Arc<dyn RateLimiter>,
+    clock: Arc<dyn Clock>,
+    metrics: Arc<dyn MetricsRecorder>,
+    credential_store: Option<Arc<dyn CredentialStore>>,
+    response_cache: Option<Arc<dyn ResponseCache>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    backoff_retry: Option<Arc<BackoffRetry>>,
     max_rate_limit_cooldown: Duration,
     max_response_body_bytes: Option<usize>,
     max_request_body_bytes: Option<usize>,
     max_stream_response_body_bytes: Option<usize>,
+    max_stream_event_bytes: Option<usize>,
+    max_stream_line_bytes: Option<usize>,
+    propagate_traceparent: bool,
 }
 
 impl Default for ClientRuntimeState {
@@ -26,10 +41,19 @@ impl ClientRuntimeState {
         Self {
             hooks: config.hooks,
             rate_limiter: config.rate_limiter,
+            clock: config.clock,
+            metrics: config.metrics,
+            credential_store: config.credential_store,
+            response_cache: config.response_cache,
+            circuit_breaker: config.circuit_breaker,
+            backoff_retry: config.backoff_retry,
             max_rate_limit_cooldown: config.max_rate_limit_cooldown,
             max_response_body_bytes: config.max_response_body_bytes,
             max_request_body_bytes: config.max_request_body_bytes,
             max_stream_response_body_bytes: config.max_stream_response_body_bytes,
+            max_stream_event_bytes: config.max_stream_event_bytes,
+            max_stream_line_bytes: config.max_stream_line_bytes,
+            propagate_traceparent: config.propagate_traceparent,
         }
     }
 
@@ -73,6 +97,31 @@ impl ClientRuntimeState {
         self.max_stream_response_body_bytes
     }
 
+    #[inline]
+    pub fn max_stream_event_bytes(&self) -> Option<usize> {
+        self.max_stream_event_bytes
+    }
+
+    #[inline]
+    pub fn max_stream_line_bytes(&self) -> Option<usize> {
+        self.max_stream_line_bytes
+    }
+
+    #[inline]
+    pub(crate) fn circuit_breaker(&self) -> Option<&Arc<CircuitBreaker>> {
+        self.circuit_breaker.as_ref()
+    }
+
+    #[inline]
+    pub(crate) fn backoff_retry(&self) -> Option<&Arc<BackoffRetry>> {
+        self.backoff_retry.as_ref()
+    }
+
+    #[inline]
+    pub fn propagate_traceparent(&self) -> bool {
+        self.propagate_traceparent
+    }
+
     #[inline]
     pub fn rate_limiter(&self) -> &Arc<dyn RateLimiter> {
         &self.rate_limiter
@@ -82,6 +131,46 @@ impl ClientRuntimeState {
     pub fn set_rate_limiter(&mut self, rate_limiter: Arc<dyn RateLimiter>) {
         self.rate_limiter = rate_limiter;
     }
+
+    #[inline]
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
+
+    #[inline]
+    pub fn set_clock(&mut self, clock: Arc<dyn Clock>) {
+        self.clock = clock;
+    }
+
+    #[inline]
+    pub fn metrics_recorder(&self) -> &Arc<dyn MetricsRecorder> {
+        &self.metrics
+    }
+
+    #[inline]
+    pub fn set_metrics_recorder(&mut self, recorder: Arc<dyn MetricsRecorder>) {
+        self.metrics = recorder;
+    }
+
+    #[inline]
+    pub fn credential_store(&self) -> Option<&Arc<dyn CredentialStore>> {
+        self.credential_store.as_ref()
+    }
+
+    #[inline]
+    pub fn set_credential_store(&mut self, credential_store: Option<Arc<dyn CredentialStore>>) {
+        self.credential_store = credential_store;
+    }
+
+    #[inline]
+    pub fn response_cache(&self) -> Option<&Arc<dyn ResponseCache>> {
+        self.response_cache.as_ref()
+    }
+
+    #[inline]
+    pub fn set_response_cache(&mut self, response_cache: Option<Arc<dyn ResponseCache>>) {
+        self.response_cache = response_cache;
+    }
 }
 
 impl From<RuntimeConfig> for ClientRuntimeState {
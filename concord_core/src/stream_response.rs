@@ -17,12 +17,22 @@ use std::pin::Pin;
 use std::task::{Context, Poll};
 use tokio::io::AsyncWriteExt;
 
+mod event_stream;
+#[cfg(feature = "json")]
+mod json_lines;
+
+pub use event_stream::{Event, EventData, EventStream, Events};
+#[cfg(feature = "json")]
+pub use json_lines::JsonLinesStream;
+
 /// A streaming response façade over the runtime's native response body.
 ///
 /// [`StreamResponse::next_chunk`] and [`StreamResponse::write_to_file`] are
 /// data-only conveniences over the native Reqwest response stream.
 pub struct StreamResponse<M> {
     resp: ExecutionResponse,
+    event_limit: Option<usize>,
+    line_limit: Option<usize>,
     _media: PhantomData<fn() -> M>,
 }
 
@@ -30,10 +40,22 @@ impl<M> StreamResponse<M> {
     pub(crate) fn new(resp: ExecutionResponse) -> Self {
         Self {
             resp,
+            event_limit: None,
+            line_limit: None,
             _media: PhantomData,
         }
     }
 
+    pub(crate) fn with_event_limit(mut self, limit: Option<usize>) -> Self {
+        self.event_limit = limit;
+        self
+    }
+
+    pub(crate) fn with_line_limit(mut self, limit: Option<usize>) -> Self {
+        self.line_limit = limit;
+        self
+    }
+
     pub fn meta(&self) -> &crate::execution_meta::RequestExecutionMeta {
         &self.resp.context.meta
     }
@@ -267,9 +289,11 @@ mod tests {
                     method: http::Method::GET,
                     idempotent: true,
                     page_index: 0,
+                    retry: 0,
                 },
                 logical_url: url::Url::parse("http://example.invalid/stream").expect("request URL"),
                 rate_limit: RateLimitPlan::new(),
+                cache: crate::cache::CacheStatus::Bypass,
             },
             error_mapper,
             limit,
@@ -0,0 +1,383 @@
+use super::StreamResponse;
+use crate::codec::{CodecError, ContentType, DecodeContext, ResponseCodec};
+use crate::error::ApiClientError;
+use bytes::Bytes;
+use http::header::CONTENT_TYPE;
+use std::collections::VecDeque;
+use std::fmt;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// `text/event-stream` media marker for Server-Sent Events responses.
+///
+/// `D` selects how each event's `data` field is exposed: [`Event`] keeps the
+/// raw text, while a [`ResponseCodec`] such as `Json<T>` decodes it.
+pub struct EventStream<D = Event>(PhantomData<fn() -> D>);
+
+impl<D> fmt::Debug for EventStream<D> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("EventStream")
+    }
+}
+
+impl<D: EventData> ContentType for EventStream<D> {
+    const CONTENT_TYPE: &'static str = "text/event-stream";
+}
+
+/// One dispatched Server-Sent Events record.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Event<T = String> {
+    /// Last event ID in effect at dispatch. It persists across events until
+    /// the server sends another `id` field.
+    pub id: Option<String>,
+    /// The `event` field, or `None` for the default `message` type.
+    pub event: Option<String>,
+    /// The joined `data` lines, decoded through the stream's [`EventData`].
+    pub data: T,
+    /// The `retry` field sent with this event, if any.
+    pub retry: Option<Duration>,
+}
+
+/// Decodes the joined `data` field of each event.
+pub trait EventData: Send + Sync + 'static {
+    type Value: Send + 'static;
+
+    fn decode(data: String, ctx: DecodeContext<'_>) -> Result<Self::Value, CodecError>;
+}
+
+impl EventData for Event {
+    type Value = String;
+
+    fn decode(data: String, _ctx: DecodeContext<'_>) -> Result<Self::Value, CodecError> {
+        Ok(data)
+    }
+}
+
+impl<C: ResponseCodec> EventData for C {
+    type Value = C::Value;
+
+    fn decode(data: String, ctx: DecodeContext<'_>) -> Result<Self::Value, CodecError> {
+        C::decode(Bytes::from(data), ctx)
+    }
+}
+
+impl<D: EventData> StreamResponse<EventStream<D>> {
+    /// Parses the remaining body as Server-Sent Events.
+    pub fn events(self) -> Events<D> {
+        Events {
+            response: self,
+            parser: EventParser::default(),
+            overflow: None,
+            done: false,
+        }
+    }
+}
+
+/// Incremental Server-Sent Events reader over a [`StreamResponse`].
+///
+/// The stream response body limit still bounds the whole body, and the
+/// client's `max_stream_event_bytes` bounds each event.
+pub struct Events<D = Event> {
+    response: StreamResponse<EventStream<D>>,
+    parser: EventParser,
+    overflow: Option<usize>,
+    done: bool,
+}
+
+impl<D: EventData> Events<D> {
+    pub fn response(&self) -> &StreamResponse<EventStream<D>> {
+        &self.response
+    }
+
+    /// Returns the last event ID seen so far, for reconnect requests.
+    pub fn last_event_id(&self) -> Option<&str> {
+        self.parser.last_event_id.as_deref()
+    }
+
+    /// Returns the most recent `retry` reconnection time sent by the server.
+    pub fn retry(&self) -> Option<Duration> {
+        self.parser.reconnect
+    }
+
+    /// Returns the next dispatched event.
+    ///
+    /// A trailing event without its blank-line terminator is discarded at EOF.
+    /// Body errors and limit errors permanently terminate the reader; a
+    /// `data` decode error consumes only that event.
+    pub async fn next_event(&mut self) -> Result<Option<Event<D::Value>>, ApiClientError> {
+        loop {
+            if let Some(event) = self.parser.ready.pop_front() {
+                return self.decode(event).map(Some);
+            }
+            if let Some(limit) = self.overflow.take() {
+                return Err(ApiClientError::StreamEventLimitExceeded {
+                    ctx: self.response.error_context(),
+                    limit,
+                });
+            }
+            if self.done {
+                return Ok(None);
+            }
+            let chunk = match self.response.next_chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => {
+                    self.done = true;
+                    continue;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Err(err);
+                }
+            };
+            if let Err(EventLimitExceeded(limit)) =
+                self.parser.feed(&chunk, self.response.event_limit)
+            {
+                self.done = true;
+                self.overflow = Some(limit);
+            }
+        }
+    }
+
+    fn decode(&self, event: Event) -> Result<Event<D::Value>, ApiClientError> {
+        let ctx = self.response.error_context();
+        let status = self.response.status();
+        let content_type = self
+            .response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok());
+        let data = D::decode(
+            event.data,
+            DecodeContext::new(ctx.endpoint, &ctx.method, status, content_type),
+        )
+        .map_err(|_| {
+            ApiClientError::response_body_decode_error(ctx.clone(), status, content_type)
+        })?;
+        Ok(Event {
+            id: event.id,
+            event: event.event,
+            data,
+            retry: event.retry,
+        })
+    }
+}
+
+impl<D> fmt::Debug for Events<D>
+where
+    EventStream<D>: ContentType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Events")
+            .field("response", &self.response)
+            .field("pending", &self.parser.ready.len())
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+struct EventLimitExceeded(usize);
+
+/// Line-oriented `text/event-stream` parser following the WHATWG framing
+/// rules: CRLF, LF and CR terminators, `:` comments, one optional space after
+/// the field colon, and a leading BOM on the first line.
+#[derive(Default)]
+struct EventParser {
+    line: Vec<u8>,
+    skip_lf: bool,
+    started: bool,
+    event_bytes: usize,
+    data: String,
+    event: Option<String>,
+    pending_retry: Option<Duration>,
+    last_event_id: Option<String>,
+    reconnect: Option<Duration>,
+    ready: VecDeque<Event>,
+}
+
+impl EventParser {
+    fn feed(&mut self, mut chunk: &[u8], limit: Option<usize>) -> Result<(), EventLimitExceeded> {
+        if !chunk.is_empty() && std::mem::take(&mut self.skip_lf) && chunk[0] == b'\n' {
+            chunk = &chunk[1..];
+        }
+        while let Some(end) = chunk.iter().position(|b| matches!(b, b'\n' | b'\r')) {
+            let mut consumed = end + 1;
+            if chunk[end] == b'\r' {
+                match chunk.get(consumed) {
+                    Some(b'\n') => consumed += 1,
+                    Some(_) => {}
+                    None => self.skip_lf = true,
+                }
+            }
+            self.line.extend_from_slice(&chunk[..end]);
+            self.event_bytes += self.line.len() + consumed - end;
+            Self::check(self.event_bytes, limit)?;
+            let line = std::mem::take(&mut self.line);
+            self.process_line(&line);
+            chunk = &chunk[consumed..];
+        }
+        self.line.extend_from_slice(chunk);
+        Self::check(self.event_bytes + self.line.len(), limit)
+    }
+
+    fn check(observed: usize, limit: Option<usize>) -> Result<(), EventLimitExceeded> {
+        match limit {
+            Some(limit) if observed > limit => Err(EventLimitExceeded(limit)),
+            _ => Ok(()),
+        }
+    }
+
+    fn process_line(&mut self, line: &[u8]) {
+        let line = String::from_utf8_lossy(line);
+        let mut line = line.as_ref();
+        if !std::mem::replace(&mut self.started, true) {
+            line = line.strip_prefix('\u{feff}').unwrap_or(line);
+        }
+        if line.is_empty() {
+            self.dispatch();
+            return;
+        }
+        if line.starts_with(':') {
+            return;
+        }
+        let (field, value) = match line.split_once(':') {
+            Some((field, value)) => (field, value.strip_prefix(' ').unwrap_or(value)),
+            None => (line, ""),
+        };
+        match field {
+            "event" => self.event = Some(value.to_string()),
+            "data" => {
+                self.data.push_str(value);
+                self.data.push('\n');
+            }
+            "id" if !value.contains('\0') => {
+                self.last_event_id = (!value.is_empty()).then(|| value.to_string());
+            }
+            "retry" if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) => {
+                if let Ok(millis) = value.parse::<u64>() {
+                    let retry = Duration::from_millis(millis);
+                    self.pending_retry = Some(retry);
+                    self.reconnect = Some(retry);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn dispatch(&mut self) {
+        self.event_bytes = 0;
+        let event = self.event.take().filter(|event| !event.is_empty());
+        let retry = self.pending_retry.take();
+        if self.data.is_empty() {
+            return;
+        }
+        let mut data = std::mem::take(&mut self.data);
+        data.pop();
+        self.ready.push_back(Event {
+            id: self.last_event_id.clone(),
+            event,
+            data,
+            retry,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(chunks: &[&[u8]], limit: Option<usize>) -> (Vec<Event>, bool) {
+        let mut parser = EventParser::default();
+        let mut overflow = false;
+        for chunk in chunks {
+            if parser.feed(chunk, limit).is_err() {
+                overflow = true;
+                break;
+            }
+        }
+        (parser.ready.into_iter().collect(), overflow)
+    }
+
+    fn event(id: Option<&str>, event: Option<&str>, data: &str) -> Event {
+        Event {
+            id: id.map(str::to_string),
+            event: event.map(str::to_string),
+            data: data.to_string(),
+            retry: None,
+        }
+    }
+
+    #[test]
+    fn event_stream_parses_fields_comments_and_multiline_data() {
+        let (events, overflow) = parse(
+            &[b"\xef\xbb\xbfid: 1\nevent: delta\n: keep-alive\ndata: a\ndata:b\n\ndata\n\n"],
+            None,
+        );
+
+        assert!(!overflow);
+        assert_eq!(
+            events,
+            vec![
+                event(Some("1"), Some("delta"), "a\nb"),
+                event(Some("1"), None, ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn event_stream_handles_mixed_terminators_split_across_chunks() {
+        let (events, _) = parse(
+            &[
+                b"data: one\r",
+                b"\n\r",
+                b"\ndata: two\r\rdata: th",
+                b"ree\n\n",
+            ],
+            None,
+        );
+
+        assert_eq!(
+            events,
+            vec![
+                event(None, None, "one"),
+                event(None, None, "two"),
+                event(None, None, "three"),
+            ]
+        );
+    }
+
+    #[test]
+    fn event_stream_tracks_ids_retry_and_discards_blocks_without_data() {
+        let mut parser = EventParser::default();
+        let fed = parser.feed(
+            b"retry: 2500\nid: 7\n\nretry: nope\ndata: x\n\nid\ndata: y\nretry: 10\n\n",
+            None,
+        );
+        assert!(fed.is_ok());
+
+        assert_eq!(parser.reconnect, Some(Duration::from_millis(10)));
+        assert_eq!(parser.last_event_id, None);
+        let events: Vec<_> = parser.ready.into_iter().collect();
+        assert_eq!(events[0], event(Some("7"), None, "x"));
+        assert_eq!(
+            events[1],
+            Event {
+                retry: Some(Duration::from_millis(10)),
+                ..event(None, None, "y")
+            }
+        );
+    }
+
+    #[test]
+    fn event_stream_limit_counts_one_event_including_partial_lines() {
+        let (events, overflow) = parse(&[b"data: 1234\n\ndata: 5678\n\n"], Some(12));
+        assert!(!overflow);
+        assert_eq!(events.len(), 2);
+
+        let (events, overflow) = parse(&[b"data: 1234\n\ndata: 12", b"345678"], Some(12));
+        assert!(overflow);
+        assert_eq!(events, vec![event(None, None, "1234")]);
+
+        let (_, overflow) = parse(&[b": comment that never ends"], Some(8));
+        assert!(overflow);
+    }
+}
@@ -0,0 +1,199 @@
+use super::StreamResponse;
+use crate::codec::ContentType;
+use crate::error::ApiClientError;
+use crate::media::JsonLinesContentType;
+use http::header::CONTENT_TYPE;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::fmt;
+use std::marker::PhantomData;
+
+/// Incremental newline-delimited JSON reader over a [`StreamResponse`].
+///
+/// The stream response body limit still bounds the whole body, and the
+/// client's `max_stream_line_bytes` bounds each line.
+pub struct JsonLinesStream<T> {
+    response: StreamResponse<JsonLinesContentType>,
+    splitter: LineSplitter,
+    overflow: Option<usize>,
+    done: bool,
+    _item: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> JsonLinesStream<T> {
+    pub(crate) fn new(response: StreamResponse<JsonLinesContentType>) -> Self {
+        Self {
+            response,
+            splitter: LineSplitter::default(),
+            overflow: None,
+            done: false,
+            _item: PhantomData,
+        }
+    }
+
+    pub fn response(&self) -> &StreamResponse<JsonLinesContentType> {
+        &self.response
+    }
+
+    /// Returns the next decoded line.
+    ///
+    /// Blank lines are skipped and a final line without a trailing newline is
+    /// still decoded at EOF. Body errors and limit errors permanently terminate
+    /// the reader; a decode error consumes only that line.
+    pub async fn next_item(&mut self) -> Result<Option<T>, ApiClientError> {
+        loop {
+            if let Some(line) = self.splitter.ready.pop_front() {
+                return self.decode(&line).map(Some);
+            }
+            if let Some(limit) = self.overflow.take() {
+                return Err(ApiClientError::StreamLineLimitExceeded {
+                    ctx: self.response.error_context(),
+                    limit,
+                });
+            }
+            if self.done {
+                return Ok(None);
+            }
+            let chunk = match self.response.next_chunk().await {
+                Ok(Some(chunk)) => chunk,
+                Ok(None) => {
+                    self.done = true;
+                    self.splitter.finish();
+                    continue;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Err(err);
+                }
+            };
+            if let Err(LineLimitExceeded(limit)) =
+                self.splitter.feed(&chunk, self.response.line_limit)
+            {
+                self.done = true;
+                self.overflow = Some(limit);
+            }
+        }
+    }
+
+    fn decode(&self, line: &[u8]) -> Result<T, ApiClientError> {
+        serde_json::from_slice(line).map_err(|_| {
+            let content_type = self
+                .response
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok());
+            ApiClientError::response_body_decode_error(
+                self.response.error_context(),
+                self.response.status(),
+                content_type,
+            )
+        })
+    }
+}
+
+impl<T> fmt::Debug for JsonLinesStream<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JsonLinesStream")
+            .field("media", &JsonLinesContentType::CONTENT_TYPE)
+            .field("status", &self.response.status())
+            .field("pending", &self.splitter.ready.len())
+            .field("done", &self.done)
+            .finish_non_exhaustive()
+    }
+}
+
+struct LineLimitExceeded(usize);
+
+/// Splits a byte stream on `\n`, dropping an optional `\r` before it and any
+/// whitespace-only lines. The limit applies to one line without terminators.
+#[derive(Default)]
+struct LineSplitter {
+    partial: Vec<u8>,
+    ready: VecDeque<Vec<u8>>,
+}
+
+impl LineSplitter {
+    fn feed(&mut self, mut chunk: &[u8], limit: Option<usize>) -> Result<(), LineLimitExceeded> {
+        while let Some(end) = memchr::memchr(b'\n', chunk) {
+            self.partial.extend_from_slice(&chunk[..end]);
+            let line = std::mem::take(&mut self.partial);
+            self.push(line, limit)?;
+            chunk = &chunk[end + 1..];
+        }
+        self.partial.extend_from_slice(chunk);
+        Self::check(trim_cr(&self.partial), limit)
+    }
+
+    fn finish(&mut self) {
+        let line = std::mem::take(&mut self.partial);
+        let _ = self.push(line, None);
+    }
+
+    fn push(&mut self, mut line: Vec<u8>, limit: Option<usize>) -> Result<(), LineLimitExceeded> {
+        line.truncate(trim_cr(&line).len());
+        Self::check(&line, limit)?;
+        if !line.iter().all(u8::is_ascii_whitespace) {
+            self.ready.push_back(line);
+        }
+        Ok(())
+    }
+
+    fn check(line: &[u8], limit: Option<usize>) -> Result<(), LineLimitExceeded> {
+        match limit {
+            Some(limit) if line.len() > limit => Err(LineLimitExceeded(limit)),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn trim_cr(line: &[u8]) -> &[u8] {
+    line.strip_suffix(b"\r").unwrap_or(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(chunks: &[&[u8]], limit: Option<usize>) -> (Vec<Vec<u8>>, bool) {
+        let mut splitter = LineSplitter::default();
+        for chunk in chunks {
+            if splitter.feed(chunk, limit).is_err() {
+                return (splitter.ready.into_iter().collect(), true);
+            }
+        }
+        splitter.finish();
+        (splitter.ready.into_iter().collect(), false)
+    }
+
+    #[test]
+    fn json_lines_split_across_chunks_and_skip_blank_lines() {
+        let (lines, overflow) = split(
+            &[b"{\"a\":1}\r\n\n  \r\n{\"a\"", b":2}\n", b"{\"a\":3}"],
+            None,
+        );
+
+        assert!(!overflow);
+        assert_eq!(
+            lines,
+            vec![
+                b"{\"a\":1}".to_vec(),
+                b"{\"a\":2}".to_vec(),
+                b"{\"a\":3}".to_vec(),
+            ]
+        );
+    }
+
+    #[test]
+    fn json_lines_limit_counts_one_line_including_partial_data() {
+        let (lines, overflow) = split(&[b"1234\r\n5678\n"], Some(4));
+        assert!(!overflow);
+        assert_eq!(lines.len(), 2);
+
+        let (lines, overflow) = split(&[b"1234\n12", b"345"], Some(4));
+        assert!(overflow);
+        assert_eq!(lines, vec![b"1234".to_vec()]);
+
+        let (_, overflow) = split(&[b"1234\r"], Some(4));
+        assert!(!overflow);
+    }
+}
@@ -0,0 +1,359 @@
+//! `tracing` spans for logical calls, visible executions, and
+//! credential-provider operations.
+//!
+//! A logical call (one `execute`, `response`, or pagination run) opens a
+//! `concord.call` span. Each visible execution below it opens a
+//! `concord.execution` span, tagged `initial`, `auth_recovery`, or
+//! `backoff_retry` and carrying its page index and retry count, and each
+//! credential-provider `acquire`, `refresh`, or `invalidate` opens a
+//! `concord.credential` span under the execution that needed it. URLs are the redacted debug form and headers pass through
+//! [`SanitizedHeaders`](crate::debug::SanitizedHeaders), so a recorded field
+//! never carries a value that debug output would hide.
+//!
+//! Without the `tracing` feature every type here is empty and every method is
+//! a no-op.
+
+use crate::endpoint::EndpointMeta;
+use crate::error::ApiClientError;
+use crate::execution_meta::RequestExecutionMeta;
+use http::{HeaderMap, StatusCode};
+use std::fmt;
+use std::future::Future;
+
+#[cfg(feature = "tracing")]
+use tracing::Instrument as _;
+#[cfg(feature = "tracing")]
+use tracing::field::{Empty, debug, display};
+
+/// Which visible execution of a logical call a span describes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum ExecutionKind {
+    Initial,
+    AuthRecovery,
+    BackoffRetry,
+}
+
+impl ExecutionKind {
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Initial => "initial",
+            Self::AuthRecovery => "auth_recovery",
+            Self::BackoffRetry => "backoff_retry",
+        }
+    }
+}
+
+/// Span of one logical call, shared by every page and execution it drives.
+///
+/// When `traceparent` propagation is enabled the call also owns a W3C trace
+/// id and trace flags, reused by every execution of the call. They come from
+/// the caller's parent `traceparent` when one is given and valid; otherwise
+/// the call starts a new, sampled trace with a random id.
+#[derive(Clone, Debug)]
+pub(crate) struct CallTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    trace_id: Option<[u8; 16]>,
+    #[cfg(feature = "tracing")]
+    trace_flags: u8,
+}
+
+#[cfg_attr(not(feature = "tracing"), allow(clippy::derivable_impls))]
+impl Default for CallTrace {
+    fn default() -> Self {
+        Self {
+            #[cfg(feature = "tracing")]
+            span: tracing::Span::none(),
+            #[cfg(feature = "tracing")]
+            trace_id: None,
+            #[cfg(feature = "tracing")]
+            trace_flags: SAMPLED,
+        }
+    }
+}
+
+impl CallTrace {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn open(
+        meta: &EndpointMeta,
+        propagate: bool,
+        parent: Option<&http::HeaderValue>,
+    ) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::info_span!(
+                "concord.call",
+                endpoint = meta.name,
+                http.request.method = %meta.method,
+                otel.kind = "client",
+                trace_id = Empty,
+                parent_span_id = Empty,
+                "error.type" = Empty,
+                otel.status_code = Empty,
+            );
+            if !propagate {
+                return Self {
+                    span,
+                    ..Self::default()
+                };
+            }
+            let (trace_id, trace_flags) = match parent.and_then(ParentTrace::parse) {
+                Some(parent) => {
+                    span.record("parent_span_id", display(Hex(&parent.span_id)));
+                    (Some(parent.trace_id), parent.flags & SAMPLED)
+                }
+                None => (random_id::<16>(), SAMPLED),
+            };
+            if let Some(id) = trace_id.as_ref() {
+                span.record("trace_id", display(Hex(id)));
+            }
+            Self {
+                span,
+                trace_id,
+                trace_flags,
+            }
+        }
+        #[cfg(not(feature = "tracing"))]
+        Self {}
+    }
+
+    /// Drive `call` inside this span, recording the error category of a
+    /// failed call.
+    pub(crate) async fn run<T, F>(&self, call: F) -> Result<T, ApiClientError>
+    where
+        F: Future<Output = Result<T, ApiClientError>>,
+    {
+        #[cfg(feature = "tracing")]
+        {
+            let result = call.instrument(self.span.clone()).await;
+            if let Err(error) = &result {
+                record_error(&self.span, error);
+            }
+            result
+        }
+        #[cfg(not(feature = "tracing"))]
+        call.await
+    }
+}
+
+/// Span of one visible execution.
+pub(crate) struct ExecutionTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+    #[cfg(feature = "tracing")]
+    traceparent: Option<http::HeaderValue>,
+}
+
+impl ExecutionTrace {
+    /// Open an execution span as a child of the current (call) span.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn open(meta: &RequestExecutionMeta, kind: ExecutionKind, call: &CallTrace) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            let span = tracing::info_span!(
+                "concord.execution",
+                endpoint = meta.endpoint,
+                http.request.method = %meta.method,
+                idempotent = meta.idempotent,
+                page_index = meta.page_index,
+                retry = meta.retry,
+                execution = kind.as_str(),
+                span_id = Empty,
+                url.full = Empty,
+                http.request.headers = Empty,
+                http.response.status_code = Empty,
+                http.response.headers = Empty,
+                "error.type" = Empty,
+                otel.status_code = Empty,
+            );
+            let traceparent = call.trace_id.as_ref().and_then(|trace_id| {
+                let span_id = random_id::<8>()?;
+                span.record("span_id", display(Hex(&span_id)));
+                let value = format!(
+                    "00-{}-{}-{:02x}",
+                    Hex(trace_id),
+                    Hex(&span_id),
+                    call.trace_flags
+                );
+                http::HeaderValue::from_str(&value).ok()
+            });
+            Self { span, traceparent }
+        }
+        #[cfg(not(feature = "tracing"))]
+        Self {}
+    }
+
+    /// Drive one step of this execution inside its span.
+    pub(crate) async fn run<F: Future>(&self, step: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        {
+            step.instrument(self.span.clone()).await
+        }
+        #[cfg(not(feature = "tracing"))]
+        step.await
+    }
+
+    /// Add the W3C `traceparent` header unless the request already has one.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn inject_traceparent(&self, headers: &mut HeaderMap) {
+        #[cfg(feature = "tracing")]
+        if let Some(value) = self.traceparent.as_ref()
+            && !headers.contains_key(TRACEPARENT)
+        {
+            headers.insert(TRACEPARENT, value.clone());
+        }
+    }
+
+    /// Record the redacted URL and headers about to be sent.
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_request(&self, safe_url: &str, headers: &HeaderMap) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span.record("url.full", safe_url);
+            self.span.record(
+                "http.request.headers",
+                debug(crate::debug::SanitizedHeaders::new(headers)),
+            );
+        }
+    }
+
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_response(&self, status: StatusCode, headers: &HeaderMap) {
+        #[cfg(feature = "tracing")]
+        {
+            self.span
+                .record("http.response.status_code", status.as_u16());
+            self.span.record(
+                "http.response.headers",
+                debug(crate::debug::SanitizedHeaders::new(headers)),
+            );
+        }
+    }
+
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn record_error(&self, error: &ApiClientError) {
+        #[cfg(feature = "tracing")]
+        record_error(&self.span, error);
+    }
+}
+
+/// Span of one credential-provider operation.
+pub(crate) struct CredentialTrace {
+    #[cfg(feature = "tracing")]
+    span: tracing::Span,
+}
+
+impl CredentialTrace {
+    #[cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+    pub(crate) fn open(
+        credential: &crate::auth::CredentialId,
+        operation: &'static str,
+        reason: &dyn fmt::Debug,
+    ) -> Self {
+        #[cfg(feature = "tracing")]
+        {
+            Self {
+                span: tracing::info_span!(
+                    "concord.credential",
+                    credential = %credential,
+                    operation,
+                    reason = ?reason,
+                ),
+            }
+        }
+        #[cfg(not(feature = "tracing"))]
+        Self {}
+    }
+
+    pub(crate) async fn run<F: Future>(self, operation: F) -> F::Output {
+        #[cfg(feature = "tracing")]
+        {
+            operation.instrument(self.span).await
+        }
+        #[cfg(not(feature = "tracing"))]
+        operation.await
+    }
+}
+
+#[cfg(feature = "tracing")]
+const TRACEPARENT: http::HeaderName = http::HeaderName::from_static("traceparent");
+
+/// The `sampled` trace flag, the only one defined by `traceparent` version 00.
+#[cfg(feature = "tracing")]
+const SAMPLED: u8 = 0x01;
+
+/// The trace context carried by a caller-supplied `traceparent`.
+#[cfg(feature = "tracing")]
+struct ParentTrace {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    flags: u8,
+}
+
+#[cfg(feature = "tracing")]
+impl ParentTrace {
+    /// Parse a W3C `traceparent` value. Unknown versions are read by their
+    /// version-00 prefix; version `ff`, all-zero ids, and malformed values
+    /// are rejected.
+    fn parse(value: &http::HeaderValue) -> Option<Self> {
+        let mut parts = value.to_str().ok()?.split('-');
+        let version = parse_hex::<1>(parts.next()?)?;
+        let trace_id = parse_hex::<16>(parts.next()?)?;
+        let span_id = parse_hex::<8>(parts.next()?)?;
+        let [flags] = parse_hex::<1>(parts.next()?)?;
+        let valid = version != [0xff]
+            && (version != [0x00] || parts.next().is_none())
+            && trace_id.iter().any(|byte| *byte != 0)
+            && span_id.iter().any(|byte| *byte != 0);
+        valid.then_some(Self {
+            trace_id,
+            span_id,
+            flags,
+        })
+    }
+}
+
+/// Decode exactly `N` bytes of lowercase hex.
+#[cfg(feature = "tracing")]
+fn parse_hex<const N: usize>(text: &str) -> Option<[u8; N]> {
+    let text = text.as_bytes();
+    if text.len() != N * 2 {
+        return None;
+    }
+    let digit = |byte: u8| match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        _ => None,
+    };
+    let mut out = [0_u8; N];
+    for (slot, pair) in out.iter_mut().zip(text.chunks_exact(2)) {
+        *slot = digit(pair[0])? << 4 | digit(pair[1])?;
+    }
+    Some(out)
+}
+
+#[cfg(feature = "tracing")]
+fn record_error(span: &tracing::Span, error: &ApiClientError) {
+    span.record("error.type", debug(error.category()));
+    span.record("otel.status_code", "ERROR");
+}
+
+/// A random, non-zero W3C trace or span id.
+#[cfg(feature = "tracing")]
+fn random_id<const N: usize>() -> Option<[u8; N]> {
+    let mut id = [0_u8; N];
+    getrandom::fill(&mut id).ok()?;
+    id.iter().any(|byte| *byte != 0).then_some(id)
+}
+
+#[cfg(feature = "tracing")]
+struct Hex<'a>(&'a [u8]);
+
+#[cfg(feature = "tracing")]
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
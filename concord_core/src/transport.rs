@@ -26,6 +26,8 @@ pub(crate) struct RequestExecutionContext {
     pub(crate) auth_query_keys: Vec<String>,
     #[cfg(any(test, feature = "dangerous-dev-tools"))]
     pub(crate) protected_header_names: Vec<http::HeaderName>,
+    #[cfg(any(test, feature = "dangerous-dev-tools"))]
+    pub(crate) endpoint_matches: Vec<usize>,
 }
 
 pub(crate) struct BuiltRequest {
@@ -63,6 +65,20 @@ impl BuiltRequest {
     pub(crate) fn context(&self) -> &RequestExecutionContext {
         &self.context
     }
+
+    /// An identical request for a second visible execution, unless the body
+    /// is a stream that cannot be replayed.
+    pub(crate) fn try_clone(&self) -> Option<Self> {
+        Some(Self {
+            message: self.message.try_clone()?,
+            context: RequestExecutionContext {
+                body_errors: Default::default(),
+                ..self.context.clone()
+            },
+            auth_plan: self.auth_plan.clone(),
+            rate_limit: self.rate_limit.clone(),
+        })
+    }
 }
 
 /// Feature-gated buffered raw-response escape hatch.
@@ -81,6 +97,7 @@ pub(crate) struct ResponseContext {
     pub(crate) meta: crate::execution_meta::RequestExecutionMeta,
     pub(crate) logical_url: Url,
     pub(crate) rate_limit: RateLimitPlan,
+    pub(crate) cache: crate::cache::CacheStatus,
 }
 
 pub(crate) struct ExecutionResponse {
@@ -142,6 +159,8 @@ pub(crate) struct BoundedResponseStream {
     seen: u64,
     terminal: bool,
     error_mapper: NativeResponseErrorMapper,
+    /// Released at EOF, on a body error, or when the stream is dropped.
+    rate_limit_guard: Option<crate::rate_limit::RateLimitGuard>,
 }
 
 impl BoundedResponseStream {
@@ -165,9 +184,21 @@ impl BoundedResponseStream {
             seen: 0,
             terminal: false,
             error_mapper,
+            rate_limit_guard: None,
+        }
+    }
+
+    pub(crate) fn hold_rate_limit_guard(&mut self, guard: crate::rate_limit::RateLimitGuard) {
+        if !self.terminal {
+            self.rate_limit_guard = Some(guard);
         }
     }
 
+    fn terminate(&mut self) {
+        self.terminal = true;
+        self.rate_limit_guard = None;
+    }
+
     fn set_limit(&mut self, limit: u64) {
         self.limit = Some(limit);
     }
@@ -247,11 +278,11 @@ impl Body for BoundedResponseStream {
         match Pin::new(&mut this.body).poll_frame(cx) {
             Poll::Pending => Poll::Pending,
             Poll::Ready(None) => {
-                this.terminal = true;
+                this.terminate();
                 Poll::Ready(None)
             }
             Poll::Ready(Some(Err(error))) => {
-                this.terminal = true;
+                this.terminate();
                 Poll::Ready(Some(Err(this.error_mapper.map_body_error(error))))
             }
             Poll::Ready(Some(Ok(frame))) => {
@@ -260,7 +291,7 @@ impl Body for BoundedResponseStream {
                     if let Some(limit) = this.limit
                         && actual > limit
                     {
-                        this.terminal = true;
+                        this.terminate();
                         return Poll::Ready(Some(Err(crate::body::BodyError::limit_exceeded(
                             limit, actual,
                         ))));
@@ -351,6 +382,7 @@ impl BuiltResponse {
                 meta,
                 logical_url: request_url,
                 rate_limit,
+                cache: crate::cache::CacheStatus::Bypass,
             },
         )
     }
@@ -389,6 +421,16 @@ impl BuiltResponse {
         &self.context.rate_limit
     }
 
+    /// Whether the response cache produced or confirmed this response.
+    pub fn cache_status(&self) -> crate::cache::CacheStatus {
+        self.context.cache
+    }
+
+    pub(crate) fn with_cache_status(mut self, cache: crate::cache::CacheStatus) -> Self {
+        self.context.cache = cache;
+        self
+    }
+
     pub fn into_body(self) -> Bytes {
         self.message.into_body()
     }
@@ -396,6 +438,12 @@ impl BuiltResponse {
     pub(crate) fn into_parts(self) -> (http::Response<Bytes>, ResponseContext) {
         (self.message, self.context)
     }
+
+    /// A copy for another caller of the same coalesced request. The body is
+    /// reference-counted, so only the head is cloned.
+    pub(crate) fn duplicate(&self) -> Self {
+        Self::new(self.message.clone(), self.context.clone())
+    }
 }
 
 #[derive(Clone)]
@@ -405,6 +453,8 @@ pub struct DecodedResponse<T> {
     pub status: StatusCode,
     pub headers: HeaderMap,
     pub value: T,
+    /// Whether the response cache produced or confirmed this response.
+    pub cache: crate::cache::CacheStatus,
 }
 
 impl<T: fmt::Debug> fmt::Debug for DecodedResponse<T> {
@@ -421,6 +471,7 @@ impl<T: fmt::Debug> fmt::Debug for DecodedResponse<T> {
                 &crate::debug::SanitizedHeaders::new(&self.headers),
             )
             .field("value", &self.value)
+            .field("cache", &self.cache)
             .finish()
     }
 }
@@ -824,6 +875,8 @@ pub(crate) struct ManagedReqwestClient {
     provider: ManagedProviderReqwestClient,
     #[cfg(any(test, feature = "dangerous-dev-tools"))]
     development_executor: Option<crate::development_executor::DeterministicNativeExecutor>,
+    #[cfg(feature = "dangerous-in-process")]
+    in_process: Option<crate::in_process::InProcessService>,
 }
 
 /// The separately managed Reqwest authority used only for credential-provider
@@ -835,6 +888,8 @@ pub(crate) struct ManagedProviderReqwestClient {
     configured_proxies: Vec<SafeProxy>,
     #[cfg(any(test, feature = "dangerous-dev-tools"))]
     development_executor: Option<crate::development_executor::DeterministicNativeExecutor>,
+    #[cfg(feature = "dangerous-in-process")]
+    in_process: Option<crate::in_process::InProcessService>,
 }
 
 /// HTTPS capability carried by each concrete managed Reqwest client.
@@ -963,6 +1018,10 @@ pub struct SafeReqwestBuilder {
         Option<crate::development_executor::DeterministicNativeExecutor>,
     #[cfg(any(test, feature = "dangerous-dev-tools"))]
     development_provider_executor: Option<crate::development_executor::DeterministicNativeExecutor>,
+    #[cfg(feature = "dangerous-in-process")]
+    in_process_application_service: Option<crate::in_process::InProcessService>,
+    #[cfg(feature = "dangerous-in-process")]
+    in_process_provider_service: Option<crate::in_process::InProcessService>,
 }
 
 impl SafeReqwestBuilder {
@@ -980,9 +1039,31 @@ impl SafeReqwestBuilder {
             development_application_executor: None,
             #[cfg(any(test, feature = "dangerous-dev-tools"))]
             development_provider_executor: None,
+            #[cfg(feature = "dangerous-in-process")]
+            in_process_application_service: None,
+            #[cfg(feature = "dangerous-in-process")]
+            in_process_provider_service: None,
         }
     }
 
+    #[cfg(feature = "dangerous-in-process")]
+    pub(crate) fn with_in_process_application_service(
+        mut self,
+        service: crate::in_process::InProcessService,
+    ) -> Self {
+        self.in_process_application_service = Some(service);
+        self
+    }
+
+    #[cfg(feature = "dangerous-in-process")]
+    pub(crate) fn with_in_process_provider_service(
+        mut self,
+        service: crate::in_process::InProcessService,
+    ) -> Self {
+        self.in_process_provider_service = Some(service);
+        self
+    }
+
     #[cfg(any(test, feature = "dangerous-dev-tools"))]
     pub(crate) fn with_development_application_executor(
         mut self,
@@ -1242,6 +1323,10 @@ impl ManagedReqwestClient {
         let development_application_executor = configured.development_application_executor.clone();
         #[cfg(any(test, feature = "dangerous-dev-tools"))]
         let development_provider_executor = configured.development_provider_executor.clone();
+        #[cfg(feature = "dangerous-in-process")]
+        let in_process_application_service = configured.in_process_application_service.clone();
+        #[cfg(feature = "dangerous-in-process")]
+        let in_process_provider_service = configured.in_process_provider_service.clone();
         let configured_proxies = configured.configured_proxies.clone();
         let provider_proxies = configured.configured_proxies;
         let provider_retry = configured.provider_retry_mode.resolve();
@@ -1281,9 +1366,13 @@ impl ManagedReqwestClient {
                 configured_proxies: provider_proxies,
                 #[cfg(any(test, feature = "dangerous-dev-tools"))]
                 development_executor: development_provider_executor,
+                #[cfg(feature = "dangerous-in-process")]
+                in_process: in_process_provider_service,
             },
             #[cfg(any(test, feature = "dangerous-dev-tools"))]
             development_executor: development_application_executor,
+            #[cfg(feature = "dangerous-in-process")]
+            in_process: in_process_application_service,
         })
     }
 
@@ -1319,9 +1408,20 @@ impl ManagedReqwestClient {
         if let Some(executor) = &self.development_executor {
             return executor.execute_native(request, context).await;
         }
+        #[cfg(feature = "dangerous-in-process")]
+        if let Some(service) = &self.in_process {
+            return service.execute_native(request, context).await;
+        }
         execute_managed(&self.client, &self.configured_proxies, request, context).await
     }
 
+    #[cfg(any(test, feature = "dangerous-dev-tools"))]
+    pub(crate) fn development_endpoint_matches(&self, endpoint: &dyn std::any::Any) -> Vec<usize> {
+        self.development_executor
+            .as_ref()
+            .map_or_else(Vec::new, |executor| executor.endpoint_matches(endpoint))
+    }
+
     pub(crate) fn provider(&self) -> &ManagedProviderReqwestClient {
         &self.provider
     }
@@ -1357,6 +1457,10 @@ impl ManagedProviderReqwestClient {
         if let Some(executor) = &self.development_executor {
             return executor.execute_native(request, context).await;
         }
+        #[cfg(feature = "dangerous-in-process")]
+        if let Some(service) = &self.in_process {
+            return service.execute_native(request, context).await;
+        }
         execute_managed(&self.client, &self.configured_proxies, request, context).await
     }
 
@@ -1669,12 +1773,14 @@ mod reqwest_transport_tests {
                 method: Method::POST,
                 idempotent: false,
                 page_index: 0,
+                retry: 0,
             },
             logical_url,
             timeout: Some(Duration::from_secs(2)),
             body_errors: crate::body::RequestBodyErrorSlot::default(),
             auth_query_keys: Vec::new(),
             protected_header_names: Vec::new(),
+            endpoint_matches: Vec::new(),
         };
         let mut response = managed
             .execute(request, Some(&context))
@@ -1727,12 +1833,14 @@ mod reqwest_transport_tests {
                 method: Method::GET,
                 idempotent: true,
                 page_index: 0,
+                retry: 0,
             },
             logical_url,
             timeout: None,
             body_errors: crate::body::RequestBodyErrorSlot::default(),
             auth_query_keys: Vec::new(),
             protected_header_names: Vec::new(),
+            endpoint_matches: Vec::new(),
         };
         let result = managed.execute(request, Some(&context)).await;
         let response = match result {
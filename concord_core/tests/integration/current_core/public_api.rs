@@ -49,6 +49,7 @@ fn final_public_surface_compiles() {
     uses_type::<advanced::Jpeg>();
     uses_type::<advanced::RateLimitContext<'static>>();
     uses_type::<advanced::RateLimitPermit>();
+    uses_type::<advanced::RateLimitGuard>();
     uses_type::<advanced::RateLimitResponseContext<'static>>();
     uses_type::<advanced::RateLimitResponseAction>();
     uses_type::<advanced::AuthError>();
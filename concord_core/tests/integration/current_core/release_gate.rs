@@ -310,12 +310,17 @@ fn deterministic_native_executor_remains_a_private_feature_gated_reqwest_seam()
         .find(|line| line.starts_with("default ="))
         .expect("default features");
     assert!(!defaults.contains("dangerous-dev-tools"));
+    assert!(!defaults.contains("dangerous-in-process"));
     for forbidden_dependency in ["hyper =", "hyper-util", "tower ="] {
         assert!(
             !manifest.contains(forbidden_dependency),
             "executor seam added forbidden dependency {forbidden_dependency}"
         );
     }
+    // The in-process transport may depend on the `Service` trait only, and only
+    // behind its explicit non-default feature.
+    assert!(manifest.contains("tower-service = { workspace = true, optional = true }"));
+    assert!(manifest.contains("dangerous-in-process = [\"dep:tower-service\"]"));
 
     let implementation = read_repo_file("concord_core/src/development_executor.rs");
     let capture = implementation
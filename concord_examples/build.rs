@@ -0,0 +1,13 @@
+use std::path::PathBuf;
+
+fn main() {
+    let out = PathBuf::from(std::env::var("OUT_DIR").expect("cargo sets OUT_DIR"));
+    println!("cargo::rerun-if-changed=openapi/petstore.yaml");
+    let generated = concord_openapi::Generator::new("PetstoreApi")
+        .generate_path("openapi/petstore.yaml")
+        .unwrap_or_else(|err| panic!("{err}"));
+    for diagnostic in generated.diagnostics() {
+        println!("cargo::warning={diagnostic}");
+    }
+    std::fs::write(out.join("petstore.rs"), generated.source()).expect("write petstore.rs");
+}
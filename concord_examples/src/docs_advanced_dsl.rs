@@ -100,3 +100,5 @@ api! {
     profile query_authenticated
     -> Json<AdvancedUser>
 }
+
+pub use self::docs_advanced_dsl_api::DocsAdvancedDslApi;
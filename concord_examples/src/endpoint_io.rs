@@ -1,6 +1,6 @@
 use bytes::Bytes;
-use concord_core::advanced::{MultipartBody, OctetStream, StreamBody, StreamResponse};
-use concord_core::prelude::{Json, Text};
+use concord_core::advanced::{Event, MultipartBody, OctetStream, StreamBody, StreamResponse};
+use concord_core::prelude::{Form, Json, Text};
 use concord_macros::api;
 use serde::{Deserialize, Serialize};
 
@@ -9,15 +9,42 @@ pub struct UploadResult {
     pub id: u64,
 }
 
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct Delta {
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+pub struct TokenRequest {
+    pub grant_type: &'static str,
+    pub refresh_token: String,
+    pub scope: Vec<String>,
+    pub client_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct ApiError {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+pub struct NotFound {
+    pub resource: String,
+}
+
 api! {
     client EndpointIoApi {
         base "https://api.example.com"
+        default {
+            on_error Json<ApiError>
+        }
     }
 
     GET JsonResponse
         as json_response
         path ["json"]
         -> Json<UploadResult>
+        on_error 404 Json<NotFound>
 
     GET TextResponse
         as text_response
@@ -44,6 +71,26 @@ api! {
         path ["stream", "download"]
         -> Stream<OctetStream>
 
+    GET LiveFeed
+        as live_feed
+        path ["events", "live"]
+        -> Sse<Event>
+
+    POST Complete
+        as complete
+        path ["events", "complete"]
+        -> Sse<Json<Delta>>
+
+    GET ExportUploads
+        as export_uploads
+        path ["uploads", "export"]
+        -> JsonLines<UploadResult>
+
+    POST RefreshToken(body: Form<TokenRequest>)
+        as refresh_token
+        path ["oauth", "token"]
+        -> Json<UploadResult>
+
     POST UploadMultipart(body: Multipart<()>)
         as upload_multipart
         path ["multipart", "upload"]
@@ -58,6 +105,15 @@ pub async fn json_example(
     api.json_response().execute().await
 }
 
+/// Classify a failed call through the typed `on_error` bodies.
+pub fn describe_error(err: &concord_core::prelude::ApiClientError) -> Option<String> {
+    if let Some(missing) = err.error_body::<NotFound>() {
+        return Some(format!("missing {}", missing.resource));
+    }
+    err.error_body::<ApiError>()
+        .map(|error| format!("api error {}", error.code))
+}
+
 pub async fn text_example(
     api: EndpointIoApi,
 ) -> Result<String, concord_core::prelude::ApiClientError> {
@@ -84,6 +140,48 @@ pub async fn stream_examples(
     request.execute().await
 }
 
+pub async fn sse_examples(
+    api: EndpointIoApi,
+) -> Result<(Vec<String>, String), concord_core::prelude::ApiClientError> {
+    let mut feed = api.live_feed().execute_stream().await?.events();
+    let mut kinds = Vec::new();
+    while let Some(event) = feed.next_event().await? {
+        kinds.push(event.event.unwrap_or_else(|| "message".to_string()));
+    }
+
+    let mut completion = api.complete().execute_stream().await?.events();
+    let mut text = String::new();
+    while let Some(event) = completion.next_event().await? {
+        text.push_str(&event.data.text);
+    }
+    Ok((kinds, text))
+}
+
+pub async fn json_lines_example(
+    api: EndpointIoApi,
+) -> Result<Vec<u64>, concord_core::prelude::ApiClientError> {
+    let mut uploads = api.export_uploads().execute().await?;
+    let mut ids = Vec::new();
+    while let Some(upload) = uploads.next_item().await? {
+        ids.push(upload.id);
+    }
+    Ok(ids)
+}
+
+pub async fn form_example(
+    api: EndpointIoApi,
+    refresh_token: String,
+) -> Result<UploadResult, concord_core::prelude::ApiClientError> {
+    api.refresh_token(TokenRequest {
+        grant_type: "refresh_token",
+        refresh_token,
+        scope: vec!["read".to_string(), "write".to_string()],
+        client_id: None,
+    })
+    .execute()
+    .await
+}
+
 pub async fn multipart_form_data_example(
     api: EndpointIoApi,
 ) -> Result<UploadResult, concord_core::prelude::ApiClientError> {
@@ -10,6 +10,7 @@ pub mod docs_dsl;
 pub mod endpoint_io;
 pub mod explicit_endpoint;
 pub mod minimal;
+pub mod openapi_petstore;
 pub mod pagination;
 pub mod policy_stack;
 pub mod riot;
@@ -0,0 +1,8 @@
+//! A client generated at build time from `openapi/petstore.yaml` by
+//! `concord_openapi`; see `build.rs`.
+
+include!(concat!(env!("OUT_DIR"), "/petstore.rs"));
+
+pub async fn petstore_call_example(api: PetstoreApi) -> Result<models::Pet, ApiClientError> {
+    api.pets().show_pet_by_id(42).await
+}
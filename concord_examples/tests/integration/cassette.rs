@@ -0,0 +1,97 @@
+use concord_examples::minimal::{MinimalApi, User};
+use concord_test_support::{Cassette, CassetteRecorder, RequestMatching};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+const SECRET: &str = "CASSETTE_INTEGRATION_SECRET";
+
+/// Answers every connection with one JSON user derived from the request path.
+async fn local_user_server() -> url::Url {
+    let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+    let address = listener.local_addr().expect("local address");
+    tokio::spawn(async move {
+        while let Ok((mut socket, _)) = listener.accept().await {
+            let mut request = Vec::new();
+            let mut buffer = [0_u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") {
+                let read = socket.read(&mut buffer).await.expect("read request");
+                if read == 0 {
+                    break;
+                }
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let head = String::from_utf8_lossy(&request);
+            let id = head
+                .split_whitespace()
+                .nth(1)
+                .and_then(|path| path.rsplit('/').next())
+                .unwrap_or("0")
+                .to_owned();
+            let body = format!(r#"{{"id":{id},"name":"user-{id}","session_token":"{SECRET}"}}"#);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\nset-cookie: sid={SECRET}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket
+                .write_all(response.as_bytes())
+                .await
+                .expect("write response");
+        }
+    });
+    url::Url::parse(&format!("http://{address}")).expect("upstream url")
+}
+
+#[tokio::test]
+async fn recorded_cassette_replays_a_generated_client_without_secrets() {
+    let recorder = CassetteRecorder::new(local_user_server().await);
+    let api = MinimalApi::new_with_safe_reqwest_builder(|builder| recorder.configure_both(builder))
+        .expect("recording client");
+    let recorded = api.users().get_user(42).await.expect("recorded user");
+    assert_eq!(recorded.name, "user-42");
+
+    let path = std::env::temp_dir().join(format!(
+        "concord-cassette-{}-{}.yaml",
+        std::process::id(),
+        line!()
+    ));
+    recorder.save(&path).expect("save cassette");
+    let contents = std::fs::read_to_string(&path).expect("read cassette");
+    assert!(!contents.contains(SECRET), "{contents}");
+    let cassette = Cassette::load(&path).expect("load cassette");
+    std::fs::remove_file(&path).expect("remove cassette");
+    assert_eq!(cassette, recorder.cassette());
+
+    let (transport, handle) = cassette.replay(RequestMatching::new().body_hash(true));
+    let api =
+        MinimalApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("replay client");
+    let replayed = api.users().get_user(42).await.expect("replayed user");
+
+    assert_eq!(
+        replayed,
+        User {
+            id: 42,
+            name: "user-42".to_string()
+        }
+    );
+    handle.assert_recorded_len(1);
+    handle.finish();
+}
+
+#[tokio::test]
+#[should_panic(expected = "unexpected deterministic executions")]
+async fn replay_rejects_requests_outside_the_cassette() {
+    let cassette = Cassette::new();
+    let (transport, handle) = cassette.replay(RequestMatching::default());
+    let api =
+        MinimalApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("replay client");
+
+    api.users()
+        .get_user(7)
+        .await
+        .expect_err("no recorded interaction matches");
+
+    handle.assert_recorded_len(1);
+    handle.finish();
+}
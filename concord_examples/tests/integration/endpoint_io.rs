@@ -1,6 +1,138 @@
-use concord_examples::endpoint_io::EndpointIoApi;
+use bytes::Bytes;
+use concord_examples::endpoint_io::{
+    EndpointIoApi, UploadResult, describe_error, form_example, json_lines_example, sse_examples,
+};
+use concord_test_support::{ScriptedReply, assert_execution, deterministic_mock};
+use http::{HeaderValue, StatusCode, header::CONTENT_TYPE};
 
 #[test]
 fn endpoint_io_examples_compile() {
     let _ = std::any::type_name::<EndpointIoApi>();
 }
+
+fn json_error(status: StatusCode, body: &'static str) -> ScriptedReply {
+    ScriptedReply::status(status)
+        .with_header(CONTENT_TYPE, HeaderValue::from_static("application/json"))
+        .with_body(Bytes::from_static(body.as_bytes()))
+}
+
+#[tokio::test]
+async fn endpoint_io_on_error_decodes_status_specific_and_fallback_bodies() {
+    let (transport, handle) = deterministic_mock()
+        .reply(json_error(
+            StatusCode::NOT_FOUND,
+            r#"{"resource":"upload"}"#,
+        ))
+        .reply(json_error(
+            StatusCode::SERVICE_UNAVAILABLE,
+            r#"{"code":"maintenance"}"#,
+        ))
+        .reply(json_error(StatusCode::BAD_REQUEST, "not json"))
+        .build();
+    let api =
+        EndpointIoApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+
+    let missing = api.json_response().await.expect_err("404 must fail");
+    assert_eq!(missing.http_status(), Some(StatusCode::NOT_FOUND));
+    assert_eq!(describe_error(&missing).as_deref(), Some("missing upload"));
+    assert!(!format!("{missing:?}").contains("upload\""));
+
+    let unavailable = api.text_response().await.expect_err("503 must fail");
+    assert_eq!(
+        describe_error(&unavailable).as_deref(),
+        Some("api error maintenance")
+    );
+
+    let undecodable = api.json_response().await.expect_err("400 must fail");
+    assert_eq!(undecodable.http_status(), Some(StatusCode::BAD_REQUEST));
+    assert_eq!(describe_error(&undecodable), None);
+
+    assert_eq!(handle.recorded().len(), 3);
+    handle.finish();
+}
+
+#[tokio::test]
+async fn endpoint_io_form_body_is_url_encoded() {
+    let expected = "grant_type=refresh_token&refresh_token=a+b%26c&scope=read&scope=write";
+    let (transport, handle) = deterministic_mock()
+        .reply(
+            ScriptedReply::ok_json(Bytes::from_static(br#"{"id":7}"#))
+                .expect_body(Bytes::from_static(expected.as_bytes())),
+        )
+        .build();
+    let api =
+        EndpointIoApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+
+    let result = form_example(api, "a b&c".to_string()).await.unwrap();
+
+    assert_eq!(result, UploadResult { id: 7 });
+    let recorded = handle.recorded();
+    assert_eq!(recorded.len(), 1);
+    assert_execution(&recorded[0])
+        .path("/oauth/token")
+        .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+        .body_present();
+    assert_eq!(recorded[0].known_body_length, Some(expected.len() as u64));
+    handle.finish();
+}
+
+fn event_stream(body: &'static str) -> ScriptedReply {
+    ScriptedReply::status(StatusCode::OK)
+        .with_header(CONTENT_TYPE, HeaderValue::from_static("text/event-stream"))
+        .with_body(Bytes::from_static(body.as_bytes()))
+}
+
+#[tokio::test]
+async fn endpoint_io_sse_yields_raw_and_decoded_events() {
+    let (transport, handle) = deterministic_mock()
+        .reply(event_stream(
+            "event: goal\ndata: 1-0\n\n: keep-alive\n\ndata: kickoff\n\n",
+        ))
+        .reply(event_stream(
+            "data: {\"text\":\"Hel\"}\n\ndata: {\"text\":\"lo\"}\n\n",
+        ))
+        .build();
+    let api =
+        EndpointIoApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+
+    let (kinds, text) = sse_examples(api).await.unwrap();
+
+    assert_eq!(kinds, vec!["goal".to_string(), "message".to_string()]);
+    assert_eq!(text, "Hello");
+    let recorded = handle.recorded();
+    assert_eq!(recorded.len(), 2);
+    assert_execution(&recorded[0])
+        .path("/events/live")
+        .header(http::header::ACCEPT, "text/event-stream");
+    handle.finish();
+}
+
+#[tokio::test]
+async fn endpoint_io_json_lines_decodes_one_item_per_line() {
+    let (transport, handle) = deterministic_mock()
+        .reply(
+            ScriptedReply::status(StatusCode::OK)
+                .with_header(
+                    CONTENT_TYPE,
+                    HeaderValue::from_static("application/x-ndjson"),
+                )
+                .with_body(Bytes::from_static(
+                    b"{\"id\":1}\n\n{\"id\":2}\r\n{\"id\":3}",
+                )),
+        )
+        .build();
+    let api =
+        EndpointIoApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+
+    assert_eq!(json_lines_example(api).await.unwrap(), vec![1, 2, 3]);
+    let recorded = handle.recorded();
+    assert_eq!(recorded.len(), 1);
+    assert_execution(&recorded[0])
+        .path("/uploads/export")
+        .header(http::header::ACCEPT, "application/x-ndjson");
+    handle.finish();
+}
@@ -0,0 +1,69 @@
+use concord_examples::minimal::{MinimalApi, User};
+use concord_test_support::{
+    FaultInjection, InjectedFaultKind, ScriptedReply, deterministic_mock, json_bytes,
+};
+use std::time::Duration;
+
+fn user_reply() -> ScriptedReply {
+    ScriptedReply::ok_json(json_bytes(&User {
+        id: 1,
+        name: "Ada".to_string(),
+    }))
+}
+
+async fn outcomes(faults: &FaultInjection, calls: usize) -> Vec<bool> {
+    let (transport, handle) = faults.wrap(deterministic_mock().repeating(user_reply()).build());
+    let api =
+        MinimalApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+    let mut outcomes = Vec::new();
+    for _ in 0..calls {
+        outcomes.push(api.users().get_user(1).await.is_ok());
+    }
+    handle.finish();
+    outcomes
+}
+
+#[tokio::test(start_paused = true)]
+async fn seeded_server_errors_reproduce_the_same_run() {
+    let first = FaultInjection::seeded(24).server_errors(0.4);
+    let second = FaultInjection::seeded(24).server_errors(0.4);
+
+    let first_outcomes = outcomes(&first, 32).await;
+    assert_eq!(first_outcomes, outcomes(&second, 32).await);
+    assert_eq!(first.injected(), second.injected());
+    let failures = first_outcomes.iter().filter(|ok| !**ok).count();
+    assert!(failures > 0 && failures < 32, "{first_outcomes:?}");
+    assert_eq!(failures, first.injected().len());
+}
+
+#[tokio::test(start_paused = true)]
+async fn latency_and_slow_drip_advance_only_the_paused_clock() {
+    let faults = FaultInjection::seeded(1)
+        .latency(1.0, Duration::from_secs(30), Duration::from_secs(30))
+        .slow_drip(1.0, 4, Duration::from_secs(1));
+    let started = tokio::time::Instant::now();
+
+    assert_eq!(outcomes(&faults, 1).await, [true]);
+
+    // 30s of head latency plus one second before each 4-byte body piece.
+    assert!(started.elapsed() >= Duration::from_secs(35));
+    assert_eq!(
+        faults.injected(),
+        [
+            InjectedFaultKind::Latency(Duration::from_secs(30)),
+            InjectedFaultKind::SlowDrip,
+        ]
+    );
+}
+
+#[tokio::test(start_paused = true)]
+async fn broken_bodies_surface_as_request_errors() {
+    let reset = FaultInjection::seeded(3).reset_mid_body(1.0);
+    let truncated = FaultInjection::seeded(3).truncate_body(1.0);
+
+    assert_eq!(outcomes(&reset, 2).await, [false, false]);
+    assert_eq!(outcomes(&truncated, 2).await, [false, false]);
+    assert_eq!(reset.injected(), [InjectedFaultKind::ResetMidBody; 2]);
+    assert_eq!(truncated.injected(), [InjectedFaultKind::TruncatedBody; 2]);
+}
@@ -0,0 +1,60 @@
+use concord_core::in_process::{
+    InProcessBody, InProcessError, InProcessService, configure_application_service,
+};
+use concord_examples::minimal::{MinimalApi, User};
+use http::StatusCode;
+use std::future::{Ready, ready};
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use tower_service::Service;
+
+/// A tiny stand-in for an axum router serving `GET /users/{id}`.
+#[derive(Clone, Default)]
+struct UsersRouter {
+    paths: Arc<Mutex<Vec<String>>>,
+}
+
+impl Service<http::Request<InProcessBody>> for UsersRouter {
+    type Response = http::Response<String>;
+    type Error = InProcessError;
+    type Future = Ready<Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<InProcessBody>) -> Self::Future {
+        let path = request.uri().path().to_string();
+        self.paths.lock().unwrap().push(path.clone());
+        let response = match path.strip_prefix("/users/").map(str::parse::<u64>) {
+            Some(Ok(id)) => http::Response::builder()
+                .header(http::header::CONTENT_TYPE, "application/json")
+                .body(format!(r#"{{"id":{id},"name":"user-{id}"}}"#)),
+            _ => http::Response::builder()
+                .status(StatusCode::NOT_FOUND)
+                .body(String::new()),
+        };
+        ready(response.map_err(Into::into))
+    }
+}
+
+#[tokio::test]
+async fn generated_client_executes_against_an_in_process_service() {
+    let router = UsersRouter::default();
+    let service = InProcessService::new(router.clone());
+    let api = MinimalApi::new_with_safe_reqwest_builder(|builder| {
+        configure_application_service(builder, service)
+    })
+    .expect("in-process client");
+
+    let user = api.users().get_user(42).await.expect("in-process user");
+
+    assert_eq!(
+        user,
+        User {
+            id: 42,
+            name: "user-42".to_string()
+        }
+    );
+    assert_eq!(*router.paths.lock().unwrap(), ["/users/42"]);
+}
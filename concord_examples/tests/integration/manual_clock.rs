@@ -0,0 +1,166 @@
+use bytes::Bytes;
+use concord_core::advanced::BackoffRetryConfig;
+use concord_examples::docs_advanced_dsl::DocsAdvancedDslApi;
+use concord_examples::policy_stack::PolicyApi;
+use concord_test_support::{ManualClock, ScriptedReply, deterministic_mock};
+use http::header::{AUTHORIZATION, RETRY_AFTER};
+use http::{HeaderValue, StatusCode};
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn text_reply() -> ScriptedReply {
+    ScriptedReply::ok_text(Bytes::from_static(b"ok"))
+}
+
+fn token_reply(token: &str) -> ScriptedReply {
+    ScriptedReply::ok_json(Bytes::from(format!(
+        r#"{{"access_token":"{token}","token_type":"bearer","expires_in":120}}"#
+    )))
+    .provider()
+}
+
+#[tokio::test]
+async fn rate_limit_windows_refill_when_the_clock_advances() {
+    let clock = ManualClock::new();
+    let (transport, handle) = deterministic_mock().repeating(text_reply()).build();
+    let mut api =
+        PolicyApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+    api.configure_mut(|cfg| {
+        cfg.clock(Arc::new(clock.clone()));
+    });
+
+    // The `app` bucket allows a burst of 100 per second.
+    for _ in 0..100 {
+        api.text().execute().await.expect("within the burst");
+    }
+    let (limited, ()) = tokio::join!(api.text().execute(), async {
+        clock.wait_for_sleepers(1).await;
+        assert_eq!(handle.recorded_len(), 100);
+        clock.advance(Duration::from_millis(10));
+    });
+
+    assert_eq!(limited.expect("refilled cell"), "ok");
+    handle.assert_recorded_len(101);
+    handle.finish();
+}
+
+#[tokio::test]
+async fn retry_after_cooldowns_expire_when_the_clock_advances() {
+    let clock = ManualClock::new();
+    let (transport, handle) = deterministic_mock()
+        .reply(
+            ScriptedReply::status(StatusCode::TOO_MANY_REQUESTS)
+                .with_header(RETRY_AFTER, HeaderValue::from_static("30")),
+        )
+        .reply(text_reply())
+        .build();
+    let mut api =
+        PolicyApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+    api.configure_mut(|cfg| {
+        cfg.clock(Arc::new(clock.clone()));
+    });
+
+    let limited = api.rate_limited().execute().await.expect_err("429");
+    assert_eq!(limited.http_status(), Some(StatusCode::TOO_MANY_REQUESTS));
+
+    let (next, ()) = tokio::join!(api.rate_limited().execute(), async {
+        clock.wait_for_sleepers(1).await;
+        clock.advance(Duration::from_secs(29));
+        tokio::task::yield_now().await;
+        assert_eq!(clock.sleepers(), 1, "the cooldown has not expired yet");
+        assert_eq!(handle.recorded_len(), 1);
+        clock.advance(Duration::from_secs(1));
+    });
+
+    assert_eq!(next.expect("cooldown expired"), "ok");
+    handle.assert_recorded_len(2);
+    handle.finish();
+}
+
+#[tokio::test]
+async fn access_tokens_refresh_once_the_clock_reaches_the_refresh_skew() {
+    let clock = ManualClock::new();
+    let (transport, handle) = deterministic_mock()
+        .reply(token_reply("first"))
+        .reply(
+            ScriptedReply::ok_json(Bytes::from_static(b"[]"))
+                .expect_header(AUTHORIZATION, "Bearer first"),
+        )
+        .reply(
+            ScriptedReply::ok_json(Bytes::from_static(b"[]"))
+                .expect_header(AUTHORIZATION, "Bearer first"),
+        )
+        .reply(token_reply("second"))
+        .reply(
+            ScriptedReply::ok_json(Bytes::from_static(b"[]"))
+                .expect_header(AUTHORIZATION, "Bearer second"),
+        )
+        .build();
+    let mut api = DocsAdvancedDslApi::new_with_safe_reqwest_builder(
+        "user".to_string(),
+        "password".to_string(),
+        "query-key".to_string(),
+        "client-id".to_string(),
+        "client-secret".to_string(),
+        |builder| transport.configure_both(builder),
+    )
+    .expect("mock client");
+    api.configure_mut(|cfg| {
+        cfg.clock(Arc::new(clock.clone()));
+    });
+    let list_users = || {
+        api.tenants("acme".to_string())
+            .list_users("req".to_string())
+    };
+
+    list_users().execute().await.expect("initial token");
+    // Still valid for 120s and outside the default 60s refresh skew.
+    clock.advance(Duration::from_secs(59));
+    list_users().execute().await.expect("cached token");
+    handle.assert_recorded_len(3);
+
+    clock.advance(Duration::from_secs(2));
+    list_users().execute().await.expect("refreshed token");
+
+    handle.assert_recorded_len(5);
+    handle.finish();
+}
+
+#[tokio::test]
+async fn backoff_retries_wait_on_the_clock_and_cap_retry_after() {
+    let clock = ManualClock::new();
+    let (transport, handle) = deterministic_mock()
+        .reply(
+            ScriptedReply::status(StatusCode::TOO_MANY_REQUESTS)
+                .with_header(RETRY_AFTER, HeaderValue::from_static("86400")),
+        )
+        .reply(text_reply())
+        .build();
+    let mut api =
+        PolicyApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+    api.configure_mut(|cfg| {
+        cfg.clock(Arc::new(clock.clone()))
+            .max_rate_limit_cooldown(Duration::from_secs(10))
+            .backoff_retry(BackoffRetryConfig::new(
+                NonZeroU32::MIN,
+                Duration::from_millis(100),
+            ));
+    });
+
+    let (retried, ()) = tokio::join!(api.rate_limited().execute(), async {
+        clock.wait_for_sleepers(1).await;
+        assert_eq!(handle.recorded_len(), 1);
+        clock.advance(Duration::from_secs(9));
+        tokio::task::yield_now().await;
+        assert_eq!(handle.recorded_len(), 1, "the capped delay has not passed");
+        clock.advance(Duration::from_secs(1));
+    });
+
+    assert_eq!(retried.expect("retried after the capped delay"), "ok");
+    handle.assert_recorded_len(2);
+    handle.finish();
+}
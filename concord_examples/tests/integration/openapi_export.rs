@@ -0,0 +1,89 @@
+use concord_examples::openapi_petstore::PetstoreApi;
+use concord_examples::pagination::PaginationApi;
+use serde_json::{Value, json};
+
+fn operation<'a>(document: &'a Value, path: &str, method: &str) -> &'a Value {
+    document["paths"][path]
+        .get(method)
+        .unwrap_or_else(|| panic!("missing {method} {path}"))
+}
+
+fn param<'a>(operation: &'a Value, location: &str, name: &str) -> &'a Value {
+    operation["parameters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .find(|param| param["in"] == location && param["name"] == name)
+        .unwrap_or_else(|| panic!("missing {location} parameter `{name}`"))
+}
+
+#[test]
+fn petstore_export_describes_routes_parameters_auth_and_media_types() {
+    let document = PetstoreApi::openapi().into_value();
+
+    assert_eq!(document["openapi"], "3.1.0");
+    assert_eq!(document["info"]["title"], "PetstoreApi");
+    assert_eq!(
+        document["servers"],
+        json!([{ "url": "https://petstore.example.com" }])
+    );
+    assert_eq!(
+        document["components"]["securitySchemes"]["api_key_"],
+        json!({ "type": "apiKey", "in": "header", "name": "X-API-Key" })
+    );
+
+    let list = operation(&document, "/v1/pets", "get");
+    assert_eq!(list["operationId"], "pets.ListPets");
+    assert_eq!(list["tags"], json!(["pets"]));
+    assert_eq!(list["security"], json!([{ "api_key_": [] }]));
+    assert_eq!(param(list, "query", "limit")["required"], false);
+    assert_eq!(
+        param(list, "query", "tag")["schema"],
+        json!({ "type": "array", "items": { "type": "string" } })
+    );
+    assert!(list["responses"]["2XX"]["content"]["application/json"].is_object());
+    assert!(list["responses"]["default"]["content"]["application/json"].is_object());
+
+    let create = operation(&document, "/v1/pets", "post");
+    assert_eq!(create["requestBody"]["required"], true);
+    assert!(create["requestBody"]["content"]["application/json"].is_object());
+
+    let show = operation(&document, "/v1/pets/{pet_id}", "get");
+    assert_eq!(param(show, "path", "pet_id")["required"], true);
+    assert_eq!(param(show, "header", "X-Request-ID")["required"], false);
+    assert!(show["responses"]["404"].is_object());
+
+    let delete = operation(&document, "/v1/pets/{pet_id}", "delete");
+    assert_eq!(
+        delete["responses"]["2XX"],
+        json!({ "description": "Successful response." })
+    );
+
+    let photo = operation(&document, "/v1/pets/{pet_id}/photo.{format}", "get");
+    assert_eq!(param(photo, "path", "format")["required"], true);
+    assert!(photo["responses"]["2XX"]["content"]["application/octet-stream"].is_object());
+
+    let health = operation(&document, "/v1/health", "get");
+    assert!(health.get("security").is_none());
+    assert!(health["responses"]["2XX"]["content"]["text/plain; charset=utf-8"].is_object());
+}
+
+#[test]
+fn pagination_export_names_the_controller_and_advanced_parameters() {
+    let document = PaginationApi::openapi().into_value();
+
+    let cursor = operation(&document, "/cursor-items", "get");
+    assert_eq!(
+        cursor["x-concord-pagination"],
+        json!({
+            "controller": "CursorPagination<String>",
+            "parameters": ["cursor", "count"],
+        })
+    );
+    assert_eq!(param(cursor, "query", "cursor")["required"], false);
+    // Defaulted parameters are always sent but need not be supplied.
+    assert_eq!(param(cursor, "query", "count")["required"], false);
+
+    let text = PaginationApi::openapi().to_json_pretty();
+    assert!(text.contains("\"/offset-items\""));
+}
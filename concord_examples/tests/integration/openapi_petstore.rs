@@ -0,0 +1,84 @@
+use bytes::Bytes;
+use concord_examples::openapi_petstore::{PetsListPetsRequestExt, PetstoreApi, models};
+use concord_test_support::{ScriptedReply, assert_execution, deterministic_mock};
+use http::{HeaderValue, StatusCode};
+
+#[tokio::test]
+async fn generated_petstore_client_binds_routes_queries_and_auth() {
+    let (transport, handle) = deterministic_mock()
+        .reply(ScriptedReply::ok_json(Bytes::from_static(
+            br#"[{"id":1,"name":"Rex","status":"available","category":{"id":3,"displayName":null}}]"#,
+        )))
+        .reply(ScriptedReply::ok_text(Bytes::from_static(b"ok")))
+        .build();
+    let api = PetstoreApi::new_with_safe_reqwest_builder("pet-key".to_string(), |builder| {
+        transport.configure_both(builder)
+    })
+    .expect("mock client");
+
+    let pets = api
+        .pets()
+        .list_pets()
+        .limit(2)
+        .status(models::ListPetsStatus::Available)
+        .tag(vec!["dog".to_string(), "small".to_string()])
+        .await
+        .unwrap();
+    assert_eq!(pets[0].name, "Rex");
+    assert_eq!(pets[0].status, Some(models::PetStatus::Available));
+    assert_eq!(
+        pets[0].category,
+        Some(models::NewPetCategory {
+            display_name: None,
+            id: 3
+        })
+    );
+    assert_eq!(api.health().health().await.unwrap(), "ok");
+
+    let recorded = handle.recorded();
+    assert_execution(&recorded[0])
+        .host("petstore.example.com")
+        .path("/v1/pets")
+        .query_has("limit", "2")
+        .query_has("status", "available")
+        .query_values("tag", &["dog", "small"])
+        .protected_header("X-API-Key");
+    assert_execution(&recorded[1])
+        .path("/v1/health")
+        .header_absent("X-API-Key");
+    handle.finish();
+}
+
+#[tokio::test]
+async fn generated_petstore_client_decodes_typed_error_bodies() {
+    let not_found = ScriptedReply::status(StatusCode::NOT_FOUND)
+        .with_header(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        )
+        .with_body(Bytes::from_static(br#"{"resource":"pet"}"#));
+    let (transport, handle) = deterministic_mock()
+        .reply(not_found)
+        .reply(ScriptedReply::status(StatusCode::NO_CONTENT))
+        .build();
+    let api = PetstoreApi::new_with_safe_reqwest_builder("pet-key".to_string(), |builder| {
+        transport.configure_both(builder)
+    })
+    .expect("mock client");
+
+    let err = api.pets().show_pet_by_id(7).await.unwrap_err();
+    assert_eq!(
+        err.error_body::<models::NotFound>(),
+        Some(&models::NotFound {
+            resource: "pet".to_string()
+        })
+    );
+    api.pets().delete_pet(7).await.unwrap();
+
+    let recorded = handle.recorded();
+    assert_execution(&recorded[0]).path("/v1/pets/7");
+    assert_execution(&recorded[1])
+        .method(http::Method::DELETE)
+        .path("/v1/pets/7");
+    handle.finish();
+}
@@ -3,7 +3,9 @@ use concord_core::prelude::PaginationTermination;
 use concord_examples::custom_pagination::{CustomPaginationApi, Item as CustomPaginationItem};
 use concord_examples::pagination::{Item, PaginationApi, PaginationAuthApi};
 use concord_test_support::{ScriptedReply, assert_execution, deterministic_mock};
+use futures_core::Stream;
 use http::StatusCode;
+use std::pin::Pin;
 
 #[tokio::test]
 async fn offset_pagination_collects_items_and_preserves_query_shape() {
@@ -111,6 +113,41 @@ async fn session_header_pagination_preserves_offset_and_items() {
     handle.finish();
 }
 
+#[tokio::test]
+async fn offset_pagination_item_stream_fetches_pages_on_demand() {
+    let (transport, handle) = deterministic_mock()
+        .reply(json_reply(r#"[{"id":1},{"id":2}]"#))
+        .reply(json_reply(r#"[{"id":3}]"#))
+        .build();
+    let api =
+        PaginationApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+
+    let mut items = api
+        .list_offset()
+        .paginate(PaginationTermination::hard_page_cap(10))
+        .items();
+    let mut streamed = Vec::new();
+    while let Some(item) = next_item(&mut items).await {
+        streamed.push(item.expect("offset pagination item succeeds"));
+        if streamed.len() == 2 {
+            assert_eq!(handle.recorded_len(), 1);
+        }
+    }
+
+    assert_eq!(ids(&streamed), vec![1, 2, 3]);
+    let recorded = handle.recorded();
+    assert_eq!(recorded.len(), 2);
+    assert_execution(&recorded[1])
+        .path("/offset-items")
+        .query_has("start", "2");
+    handle.finish();
+}
+
+async fn next_item<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+    std::future::poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+}
+
 fn ids(items: &[Item]) -> Vec<u64> {
     items.iter().map(|item| item.id).collect()
 }
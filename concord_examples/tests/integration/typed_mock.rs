@@ -0,0 +1,77 @@
+use concord_examples::minimal::{MinimalApi, User, endpoints};
+use concord_test_support::{ScriptedReply, TypedMockApi};
+use http::StatusCode;
+
+fn user(id: u64, name: &str) -> User {
+    User {
+        id,
+        name: name.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn typed_expectations_match_endpoint_values_in_any_order() {
+    let (transport, handle) = MinimalApi::mock()
+        .expect::<endpoints::users::GetUser>()
+        .with(|ep| *ep.id == 42)
+        .reply_json(&user(42, "Ada"))
+        .expect::<endpoints::users::GetUser>()
+        .with(|ep| *ep.id == 7)
+        .with_plan(|plan| plan.logical_url.path() == "/users/7")
+        .reply_json(&user(7, "Grace"))
+        .build();
+    let api =
+        MinimalApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+
+    assert_eq!(api.users().get_user(7).await.unwrap(), user(7, "Grace"));
+    assert_eq!(api.users().get_user(42).await.unwrap(), user(42, "Ada"));
+
+    let recorded = handle.recorded();
+    assert_eq!(recorded[0].endpoint.as_deref(), Some("users::GetUser"));
+    assert_eq!(recorded[1].logical_url.path(), "/users/42");
+    handle.finish();
+}
+
+#[tokio::test]
+async fn typed_expectations_are_consumed_once_each() {
+    let (transport, handle) = MinimalApi::mock()
+        .expect::<endpoints::users::GetUser>()
+        .reply(ScriptedReply::status(StatusCode::NOT_FOUND))
+        .expect::<endpoints::users::GetUser>()
+        .reply_json(&user(1, "Ada"))
+        .build();
+    let api =
+        MinimalApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+
+    api.users()
+        .get_user(1)
+        .await
+        .expect_err("first expectation answers 404");
+    assert_eq!(api.users().get_user(1).await.unwrap(), user(1, "Ada"));
+
+    handle.assert_recorded_len(2);
+    handle.finish();
+}
+
+#[tokio::test]
+#[should_panic(expected = "unexpected deterministic executions")]
+async fn typed_expectations_reject_unmatched_endpoint_values() {
+    let (transport, handle) = MinimalApi::mock()
+        .expect::<endpoints::users::GetUser>()
+        .with(|ep| *ep.id == 42)
+        .reply_json(&user(42, "Ada"))
+        .build();
+    let api =
+        MinimalApi::new_with_safe_reqwest_builder(|builder| transport.configure_both(builder))
+            .expect("mock client");
+
+    api.users()
+        .get_user(7)
+        .await
+        .expect_err("no expectation accepts id 7");
+    assert_eq!(api.users().get_user(42).await.unwrap(), user(42, "Ada"));
+
+    handle.finish();
+}
@@ -1,12 +1,19 @@
 mod integration {
     mod auth_session;
+    mod cassette;
     mod custom_codec;
     mod custom_cursor_pagination;
     mod custom_pagination;
     mod endpoint_io;
+    mod fault_injection;
+    mod in_process;
     mod live_smoke_entrypoints;
+    mod manual_clock;
     mod minimal;
+    mod openapi_export;
+    mod openapi_petstore;
     mod pagination;
     mod policy_stack;
     mod riot_large;
+    mod typed_mock;
 }
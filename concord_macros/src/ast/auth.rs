@@ -27,6 +27,13 @@ pub enum AuthCredentialKind {
         client_secret: SecretRef,
         scope: Option<LitStr>,
     },
+    OAuth2RefreshToken {
+        token_url: LitStr,
+        client_id: SecretRef,
+        client_secret: Option<SecretRef>,
+        refresh_token: SecretRef,
+        scope: Option<LitStr>,
+    },
     Endpoint {
         endpoint: Path,
     },
@@ -3,6 +3,68 @@ pub struct PolicyBlocks {
     pub headers: Option<PolicyBlock>,
     pub query: Option<PolicyBlock>,
     pub timeout: Option<Expr>,
+    pub error_responses: Vec<ErrorResponseSpec>,
+    pub cache: Option<CacheSpec>,
+    pub idempotency_key: Option<IdempotencyKeySpec>,
+    pub coalesce: Option<CoalesceSpec>,
+    pub hedge: Option<HedgeSpec>,
+}
+
+/// `cache max_age 5m [shared]` or `cache off`
+#[derive(Debug)]
+pub enum CacheSpec {
+    MaxAge {
+        lit: LitInt,
+        unit: CacheDurationUnit,
+        shared: bool,
+    },
+    Off,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum CacheDurationUnit {
+    Seconds,
+    Minutes,
+    Hours,
+}
+
+/// `idempotency_key header "Idempotency-Key"` or `idempotency_key off`
+#[derive(Debug)]
+pub enum IdempotencyKeySpec {
+    Header(LitStr),
+    Off,
+}
+
+/// `coalesce` or `coalesce off`
+#[derive(Debug)]
+pub struct CoalesceSpec {
+    pub span: Span,
+    pub enabled: bool,
+}
+
+/// `hedge after 50ms` or `hedge off`
+#[derive(Debug)]
+pub enum HedgeSpec {
+    After {
+        span: Span,
+        lit: LitInt,
+        unit: HedgeDelayUnit,
+    },
+    Off,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum HedgeDelayUnit {
+    Millis,
+    Seconds,
+}
+
+/// `on_error Json<ApiError>` or `on_error 404 Json<NotFound>`
+#[derive(Debug)]
+pub struct ErrorResponseSpec {
+    pub span: Span,
+    pub status: Option<LitInt>,
+    pub io: RawIoSpec,
 }
 
 #[derive(Debug)]
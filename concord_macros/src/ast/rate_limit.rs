@@ -29,6 +29,7 @@ pub struct RateLimitBucketSpec {
     pub kind: Ident,
     pub key: Vec<RateLimitKeySpec>,
     pub cost: Option<LitInt>,
+    pub concurrency: Option<LitInt>,
     pub windows: Vec<RateLimitWindowSpec>,
 }
 
@@ -126,7 +126,8 @@ fn emit_client_auth_state_init(resolved_api: &ResolvedApi, auth_state_ty: &Ident
         let name_lit = LitStr::new(&name.to_string(), name.span());
         let provider = emit_auth_provider_init(&client_ns, c);
         match &c.kind {
-            AuthCredentialKindIr::OAuth2ClientCredentials { .. } => quote! {
+            AuthCredentialKindIr::OAuth2ClientCredentials { .. }
+            | AuthCredentialKindIr::OAuth2RefreshToken { .. } => quote! {
                 #name: ::std::sync::Arc::new(::concord_core::__private::GeneratedCredentialBinding::new_result(
                     ::concord_core::__private::CredentialId::new(#client_ns, #name_lit),
                     #provider,
@@ -176,6 +177,9 @@ fn emit_auth_provider_ty(kind: &AuthCredentialKindIr) -> TokenStream2 {
         AuthCredentialKindIr::OAuth2ClientCredentials { .. } => {
             quote! { ::concord_core::__private::OAuth2ClientCredentialsProvider }
         }
+        AuthCredentialKindIr::OAuth2RefreshToken { .. } => {
+            quote! { ::concord_core::__private::OAuth2RefreshTokenProvider }
+        }
         AuthCredentialKindIr::Endpoint { output_ty, .. } => {
             quote! { ::concord_core::__private::GeneratedManualCredentialProvider<#output_ty> }
         }
@@ -236,6 +240,31 @@ fn emit_auth_provider_init(client_ns: &LitStr, credential: &AuthCredentialIr) ->
                 provider
             }
         }
+        AuthCredentialKindIr::OAuth2RefreshToken {
+            token_url,
+            client_id,
+            client_secret,
+            refresh_token,
+            scope,
+        } => {
+            let authorize_name = emit_helpers::ident(&format!("authorize_auth_{name}"), name.span());
+            let hint = LitStr::new(&format!("client.{authorize_name}(...)"), Span::call_site());
+            let client_secret = client_secret.as_ref().map(|client_secret| {
+                quote! { .client_secret(auth.#client_secret.clone()) }
+            });
+            let scope = scope.as_ref().map(|scope| quote! { .scope(#scope) });
+            quote! {
+                ::concord_core::__private::OAuth2RefreshTokenProvider::from_validated_token_url(
+                    #credential_id,
+                    #token_url,
+                    auth.#client_id.clone(),
+                    ::core::convert::Into::<
+                        ::core::option::Option<::concord_core::prelude::SecretString>,
+                    >::into(auth.#refresh_token.clone()),
+                )
+                .map(|provider| provider #client_secret #scope .with_missing_hint(#hint))
+            }
+        }
         AuthCredentialKindIr::Endpoint { .. } => {
             let acquire_name = emit_helpers::ident(&format!("acquire_auth_{name}"), name.span());
             let hint = LitStr::new(&format!("client.{acquire_name}(...)"), Span::call_site());
@@ -248,29 +277,41 @@ fn emit_auth_provider_init(client_ns: &LitStr, credential: &AuthCredentialIr) ->
 }
 
 fn auth_credential_secret_names(resolved_api: &ResolvedApi) -> (std::collections::BTreeSet<String>, bool) {
-    let mut out = std::collections::BTreeSet::new();
-    for c in &resolved_api.client_auth_credentials {
-        match &c.kind {
-            AuthCredentialKindIr::ApiKey { secret }
-            | AuthCredentialKindIr::StaticBearer { secret } => {
-                out.insert(secret.to_string());
-            }
-            AuthCredentialKindIr::Basic { username, password } => {
-                out.insert(username.to_string());
-                out.insert(password.to_string());
-            }
-            AuthCredentialKindIr::OAuth2ClientCredentials {
-                client_id,
-                client_secret,
-                ..
-            } => {
-                out.insert(client_id.to_string());
-                out.insert(client_secret.to_string());
-            }
-            AuthCredentialKindIr::Endpoint { .. } => {}
+    let out = resolved_api
+        .client_auth_credentials
+        .iter()
+        .flat_map(|c| auth_credential_var_names(&c.kind))
+        .collect();
+    (out, false)
+}
+
+/// Auth vars a built-in credential's provider is built from. Endpoint-backed
+/// credentials read none.
+fn auth_credential_var_names(kind: &AuthCredentialKindIr) -> Vec<String> {
+    match kind {
+        AuthCredentialKindIr::ApiKey { secret } | AuthCredentialKindIr::StaticBearer { secret } => {
+            vec![secret.to_string()]
+        }
+        AuthCredentialKindIr::Basic { username, password } => {
+            vec![username.to_string(), password.to_string()]
         }
+        AuthCredentialKindIr::OAuth2ClientCredentials {
+            client_id,
+            client_secret,
+            ..
+        } => vec![client_id.to_string(), client_secret.to_string()],
+        AuthCredentialKindIr::OAuth2RefreshToken {
+            client_id,
+            client_secret,
+            refresh_token,
+            ..
+        } => std::iter::once(client_id)
+            .chain(client_secret)
+            .chain(std::iter::once(refresh_token))
+            .map(ToString::to_string)
+            .collect(),
+        AuthCredentialKindIr::Endpoint { .. } => Vec::new(),
     }
-    (out, false)
 }
 
 fn emit_client_auth_binding_fn(resolved_api: &ResolvedApi) -> TokenStream2 {
@@ -304,7 +345,8 @@ fn emit_client_auth_binding_fn(resolved_api: &ResolvedApi) -> TokenStream2 {
                 quote! { ::concord_core::__private::AuthPreparationMode::RequestLocal },
                 quote! { ::concord_core::__private::AuthChallengeMode::Refresh },
             ),
-            AuthCredentialKindIr::OAuth2ClientCredentials { .. } => (
+            AuthCredentialKindIr::OAuth2ClientCredentials { .. }
+            | AuthCredentialKindIr::OAuth2RefreshToken { .. } => (
                 quote! { secret_binding },
                 quote! { ::concord_core::__private::AuthPreparationMode::PerExecution },
                 quote! { ::concord_core::__private::AuthChallengeMode::Refresh },
@@ -407,6 +449,10 @@ fn emit_policy_fn_base(policy: &PolicyBlocksResolved) -> TokenStream2 {
     if let Some(rate_limit) = emit_rate_limit_op(&policy.rate_limit, PolicyEmitCtx::ClientBase) {
         ops.push(rate_limit);
     }
+    ops.extend(emit_cache_op(&policy.cache));
+    ops.extend(emit_idempotency_key_op(&policy.idempotency_key));
+    ops.extend(emit_coalesce_op(policy.coalesce));
+    ops.extend(emit_hedge_op(policy.hedge));
 
     quote! {
         let mut policy = ::concord_core::advanced::ClientPolicyBuilder::new();
@@ -24,10 +24,19 @@ fn emit_endpoint_def(
         .collect();
 
     let mut fields_ts = Vec::new();
+    let mut view_fields_ts = Vec::new();
+    let mut view_init_ts = Vec::new();
     let mut setters_ts = Vec::new();
     for v in &ep.vars {
         let f = &v.rust;
         let ty = &v.ty;
+        if v.optional {
+            view_fields_ts.push(quote! { pub #f: ::core::option::Option<&'a #ty> });
+            view_init_ts.push(quote! { #f: self.#f.as_ref() });
+        } else {
+            view_fields_ts.push(quote! { pub #f: &'a #ty });
+            view_init_ts.push(quote! { #f: &self.#f });
+        }
         if v.optional {
             fields_ts.push(quote! { pub(crate) #f: ::core::option::Option<#ty> });
             if let Some(setter) = facade_setter_for_var(facade, &facade_setters_by_field, f) {
@@ -89,7 +98,10 @@ fn emit_endpoint_def(
     let mut struct_fields: Vec<TokenStream2> = fields_ts;
     if let Some(body_ty) = &body_inner_ty {
         struct_fields.push(quote! { pub(crate) body: #body_ty });
+        view_fields_ts.push(quote! { pub body: &'a #body_ty });
+        view_init_ts.push(quote! { body: &self.body });
     }
+    let fields_view_ty = endpoint_fields_view_ident(ep);
     let mut fn_args: Vec<TokenStream2> = required_vars
         .iter()
         .map(|v| {
@@ -229,6 +241,24 @@ fn emit_endpoint_def(
             #( #setters_ts )*
         }
 
+        #[doc(hidden)]
+        pub struct #fields_view_ty<'a> {
+            #( #view_fields_ts, )*
+            _endpoint: ::core::marker::PhantomData<&'a #ty_name>,
+        }
+
+        impl ::concord_core::__private::GeneratedEndpointFields for #ty_name {
+            type Fields<'a> = #fields_view_ty<'a>;
+
+            #[inline]
+            fn fields(&self) -> #fields_view_ty<'_> {
+                #fields_view_ty {
+                    #( #view_init_ts, )*
+                    _endpoint: ::core::marker::PhantomData,
+                }
+            }
+        }
+
         #paginate_binding_impl
 
         impl ::concord_core::__private::GeneratedEndpoint<super::#cx_ty> for #ty_name {
@@ -633,11 +663,20 @@ fn endpoint_response_plan_tokens(
     cx_ty: &Ident,
 ) -> TokenStream2 {
     let response_entity_adapter_ty = endpoint_response_adapter_ty(ep, ty_name);
+    let error_responses = ep.error_responses.iter().map(|mapping| {
+        let marker = &mapping.codec.marker;
+        let status = match mapping.status {
+            Some(status) => quote! { ::core::option::Option::Some(#status) },
+            None => quote! { ::core::option::Option::None },
+        };
+        quote! { .on_error::<#marker>(#status, &ctx_err)? }
+    });
     quote! {
         let __response_preparation = ::concord_core::__private::prepare_generated_response::<
             super::#cx_ty,
             #response_entity_adapter_ty,
-        >(ctx_err.clone())?;
+        >(ctx_err.clone())?
+        #( #error_responses )*;
         let __response_accept = __response_preparation.accept().cloned();
         let __response_no_content = __response_preparation.is_no_content();
     }
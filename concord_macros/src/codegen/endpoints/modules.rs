@@ -19,6 +19,11 @@ fn endpoint_descriptor_ident(ep: &ResolvedEndpoint) -> Ident {
     )
 }
 
+fn endpoint_fields_view_ident(ep: &ResolvedEndpoint) -> Ident {
+    let internal = endpoint_internal_ident(ep);
+    emit_helpers::ident(&format!("{internal}Fields"), ep.name.span())
+}
+
 fn endpoint_pending_ext_trait_ident(ep: &ResolvedEndpoint) -> Ident {
     emit_helpers::ident(
         &crate::model::facade::generated_endpoint_request_ext_trait_type_name(ep),
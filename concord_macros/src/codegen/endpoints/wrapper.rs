@@ -181,14 +181,21 @@ fn emit_client_wrapper(
     });
 
     let (credential_secret_names, has_custom_credentials) = auth_credential_secret_names(resolved_api);
-    let preserve_manual_slots = resolved_api.client_auth_credentials.iter().filter_map(|credential| {
-        if matches!(credential.kind, AuthCredentialKindIr::Endpoint { .. }) {
-            let name = &credential.name;
-            Some(quote! {
+    // Slots keep their cached credential and provider state, such as a rotated
+    // refresh token, unless the auth var that changed is one they read.
+    let preserve_unchanged_slots = resolved_api.client_auth_credentials.iter().map(|credential| {
+        let name = &credential.name;
+        let vars = auth_credential_var_names(&credential.kind);
+        if vars.is_empty() {
+            quote! {
                 __new_auth_state.#name = __old_auth_state.#name.clone();
-            })
+            }
         } else {
-            None
+            quote! {
+                if !matches!(__changed, #( #vars )|*) {
+                    __new_auth_state.#name = __old_auth_state.#name.clone();
+                }
+            }
         }
     });
     let rebuild_auth_state_method = if resolved_api.client_auth_credentials.is_empty() {
@@ -196,8 +203,9 @@ fn emit_client_wrapper(
     } else {
         quote! {
             #[inline]
-            fn __concord_rebuild_auth_state_preserving_manual(
+            fn __concord_rebuild_auth_state(
                 &mut self,
+                __changed: &str,
             ) -> ::core::result::Result<(), ::concord_core::prelude::AuthError> {
                 let __old_auth_state = self.inner.try_auth_state()?;
                 let mut __new_auth_state =
@@ -205,7 +213,7 @@ fn emit_client_wrapper(
                         self.inner.vars(),
                         self.inner.auth_vars(),
                     );
-                #( #preserve_manual_slots )*
+                #( #preserve_unchanged_slots )*
                 self.inner.try_set_auth_state(__new_auth_state)
             }
         }
@@ -230,8 +238,8 @@ fn emit_client_wrapper(
         };
         let f = &v.rust;
         let set_name = setter.set_name.clone();
-        let rebuild_auth_state =
-            has_custom_credentials || credential_secret_names.contains(&f.to_string());
+        let changed = f.to_string();
+        let rebuild_auth_state = has_custom_credentials || credential_secret_names.contains(&changed);
         if v.optional {
             let clear_name = setter.clear_name.clone();
             if rebuild_auth_state {
@@ -244,7 +252,7 @@ fn emit_client_wrapper(
                             )?;
                             __g.#f = ::core::option::Option::Some(v.into());
                         }
-                        self.__concord_rebuild_auth_state_preserving_manual()?;
+                        self.__concord_rebuild_auth_state(#changed)?;
                         ::core::result::Result::Ok(self)
                     }
                     #[inline]
@@ -255,7 +263,7 @@ fn emit_client_wrapper(
                             )?;
                             __g.#f = ::core::option::Option::None;
                         }
-                        self.__concord_rebuild_auth_state_preserving_manual()?;
+                        self.__concord_rebuild_auth_state(#changed)?;
                         ::core::result::Result::Ok(self)
                     }
                 }
@@ -290,7 +298,7 @@ fn emit_client_wrapper(
                             )?;
                             __g.#f = v.into();
                         }
-                        self.__concord_rebuild_auth_state_preserving_manual()?;
+                        self.__concord_rebuild_auth_state(#changed)?;
                         ::core::result::Result::Ok(self)
                     }
                 }
@@ -346,13 +354,19 @@ fn emit_client_wrapper(
                 value: #output_ty,
             ) -> ::core::result::Result<(), ::concord_core::prelude::AuthError> {
                 let __auth_state = self.inner.try_auth_state()?;
-                __auth_state.#name.set_manual(value).await
+                __auth_state
+                    .#name
+                    .set_manual(value, self.inner.credential_store(), self.inner.clock())
+                    .await
             }
 
             #[inline]
             pub async fn #clear_name(&self) -> ::core::result::Result<(), ::concord_core::prelude::AuthError> {
                 let __auth_state = self.inner.try_auth_state()?;
-                __auth_state.#name.clear_manual().await
+                __auth_state
+                    .#name
+                    .clear_manual(self.inner.credential_store())
+                    .await
             }
 
             #[inline]
@@ -362,6 +376,79 @@ fn emit_client_wrapper(
             }
         })
     });
+    let authorize_methods = facade_ir.authorize_methods.iter().map(|method| {
+        let name = &method.credential;
+        let authorize_name = &method.authorize_name;
+        let Some(AuthCredentialKindIr::OAuth2RefreshToken {
+            token_url,
+            client_id,
+            client_secret,
+            refresh_token,
+            ..
+        }) = resolved_api
+            .client_auth_credentials
+            .iter()
+            .find(|credential| credential.name == *name)
+            .map(|credential| &credential.kind)
+        else {
+            return emit_helpers::compile_error_tokens(
+                "FacadeIr authorize helper must target an oauth2_refresh credential",
+                name.span(),
+            );
+        };
+        let client_secret = client_secret.as_ref().map(|client_secret| {
+            quote! { .client_secret(__auth.#client_secret.clone()) }
+        });
+        let changed = refresh_token.to_string();
+        let doc = LitStr::new(
+            &format!(
+                "Exchange an OAuth2 authorization code for the `{name}` credential, store the returned refresh token, and seed the credential with the access token."
+            ),
+            name.span(),
+        );
+        quote! {
+            #[doc = #doc]
+            pub async fn #authorize_name(
+                &mut self,
+                code: impl Into<::concord_core::prelude::SecretString>,
+                redirect_uri: impl Into<::std::string::String>,
+                pkce: &::concord_core::advanced::OAuth2Pkce,
+            ) -> ::core::result::Result<(), ::concord_core::prelude::AuthError> {
+                let __grant = {
+                    let __auth = ::concord_core::__private::generated_auth_read(
+                        self.inner.auth_vars(),
+                    )?;
+                    ::concord_core::__private::OAuth2AuthorizationCode::from_validated_token_url(
+                        #token_url,
+                        __auth.#client_id.clone(),
+                        code,
+                        redirect_uri,
+                        pkce,
+                    )?
+                    #client_secret
+                };
+                let __token = self.inner.exchange_oauth2_authorization_code(&__grant).await?;
+                let ::core::option::Option::Some(__refresh_token) = __token.refresh_token.clone() else {
+                    return ::core::result::Result::Err(::concord_core::prelude::AuthError::new(
+                        ::concord_core::advanced::AuthErrorKind::AcquireFailed,
+                        "oauth2 authorization response did not include a refresh token",
+                    ));
+                };
+                {
+                    let mut __g = ::concord_core::__private::generated_auth_write(
+                        self.inner.auth_vars(),
+                    )?;
+                    __g.#refresh_token = ::core::convert::Into::into(__refresh_token);
+                }
+                self.__concord_rebuild_auth_state(#changed)?;
+                let __auth_state = self.inner.try_auth_state()?;
+                __auth_state
+                    .#name
+                    .set_manual(__token, self.inner.credential_store(), self.inner.clock())
+                    .await
+            }
+        }
+    });
     let credential_pending_methods = resolved_api.client_auth_credentials.iter().filter_map(|credential| {
         let name = &credential.name;
         let AuthCredentialKindIr::Endpoint { target, .. } = &credential.kind else {
@@ -415,6 +502,9 @@ fn emit_client_wrapper(
         pub struct #client_ty {
             inner: ::concord_core::prelude::ApiClient<#cx_ty>,
         }
+        impl ::concord_core::__private::GeneratedClient for #client_ty {
+            type Cx = #cx_ty;
+        }
         impl #client_ty {
             #[doc = "Create a client backed by Concord's managed Reqwest client."]
             #[inline]
@@ -571,6 +661,7 @@ fn emit_client_wrapper(
             #( #var_setters )*
             #( #auth_setters )*
             #( #credential_lifecycle_methods )*
+            #( #authorize_methods )*
             #auth_facade_methods
 
             #[doc = "Return the current debug level."]
@@ -677,7 +768,11 @@ fn emit_auth_facade(resolved_api: &ResolvedApi, client_ty: &Ident) -> (TokenStre
                             source,
                         }
                     })?;
-                    __auth_state.#name.set_manual(value).await.map_err(|source| {
+                    __auth_state
+                        .#name
+                        .set_manual(value, self.client.inner.credential_store(), self.client.inner.clock())
+                        .await
+                        .map_err(|source| {
                         ::concord_core::prelude::ApiClientError::Auth {
                             ctx: ::concord_core::__private::ErrorContext {
                                 endpoint: #endpoint_name_lit,
@@ -694,13 +789,19 @@ fn emit_auth_facade(resolved_api: &ResolvedApi, client_ty: &Ident) -> (TokenStre
                     value: #output_ty,
                 ) -> ::core::result::Result<(), ::concord_core::prelude::AuthError> {
                     let __auth_state = self.client.inner.try_auth_state()?;
-                    __auth_state.#name.set_manual(value).await
+                    __auth_state
+                        .#name
+                        .set_manual(value, self.client.inner.credential_store(), self.client.inner.clock())
+                        .await
                 }
 
                 #[inline]
                 pub async fn clear(&self) -> ::core::result::Result<(), ::concord_core::prelude::AuthError> {
                     let __auth_state = self.client.inner.try_auth_state()?;
-                    __auth_state.#name.clear_manual().await
+                    __auth_state
+                        .#name
+                        .clear_manual(self.client.inner.credential_store())
+                        .await
                 }
 
                 #[inline]
@@ -81,6 +81,7 @@ fn emit_resolved(resolved_api: ResolvedApi, facade_ir: &FacadeIr) -> TokenStream
     let internal_mod = emit_internal(&resolved_api, &vars_ty, &auth_vars_ty, &cx_ty);
     let endpoints_mod = emit_endpoints(&resolved_api, facade_ir, &cx_ty);
     let api_descriptor = emit_api_descriptor(&resolved_api);
+    let openapi = emit_openapi(&resolved_api);
     let acquire_trait_imports =
         resolved_api
             .client_auth_credentials
@@ -125,6 +126,7 @@ fn emit_resolved(resolved_api: ResolvedApi, facade_ir: &FacadeIr) -> TokenStream
             #cx_struct
 
             #client_wrapper
+            #openapi
 
             #endpoints_mod
             #api_descriptor
@@ -184,6 +186,7 @@ fn emit_fixed_origin(origin: &FixedOriginIr) -> TokenStream2 {
 include!("client.rs");
 include!("endpoints/mod.rs");
 include!("policy/mod.rs");
+include!("openapi.rs");
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,572 @@
+// OpenAPI export: translates resolved routes, policy, auth, I/O, and
+// pagination into builder calls inside `Client::openapi()`. The item is wrapped
+// in `__openapi_item!`, which drops it unless `concord_core/openapi` is on.
+
+fn emit_openapi(api: &ResolvedApi) -> TokenStream2 {
+    let client_ty = &api.client_name;
+    let title = LitStr::new(&client_ty.to_string(), client_ty.span());
+    let schemes = OpenApiSchemes::collect(api);
+
+    let servers = api
+        .endpoints
+        .iter()
+        .map(|ep| openapi_server(api, ep))
+        .collect::<Vec<_>>();
+    let shared_server = servers
+        .first()
+        .filter(|first| servers.iter().all(|server| server == *first));
+    let top_server = shared_server.map(|server| server.emit(quote! { __doc }));
+
+    let scheme_decls = schemes.entries.iter().map(|entry| {
+        let name = LitStr::new(&entry.name, Span::call_site());
+        let scheme = &entry.scheme;
+        quote! {
+            __doc.security_scheme(#name, #scheme);
+        }
+    });
+    let operations = api.endpoints.iter().zip(&servers).map(|(ep, server)| {
+        let server = shared_server.is_none().then_some(server);
+        emit_openapi_operation(api, ep, &schemes, server)
+    });
+
+    quote! {
+        ::concord_core::__openapi_item! {
+            impl #client_ty {
+                #[doc = "OpenAPI 3.1 document describing this client's endpoints."]
+                #[doc = ""]
+                #[doc = "Generated when `concord_core` is built with the `openapi` feature; with `schemars`, types implementing `JsonSchema` contribute their schemas."]
+                pub fn openapi() -> ::concord_core::prelude::OpenApiDocument {
+                    let mut __doc = ::concord_core::__private::OpenApiBuilder::new(
+                        #title,
+                        ::core::env!("CARGO_PKG_VERSION"),
+                    );
+                    #top_server
+                    #( #scheme_decls )*
+                    #( #operations )*
+                    __doc.finish()
+                }
+            }
+        }
+    }
+}
+
+fn emit_openapi_operation(
+    api: &ResolvedApi,
+    ep: &ResolvedEndpoint,
+    schemes: &OpenApiSchemes,
+    server: Option<&OpenApiServer>,
+) -> TokenStream2 {
+    let method = LitStr::new(&ep.method.to_string(), ep.method.span());
+    let mut path = String::new();
+    let mut path_fields = Vec::new();
+    for piece in ep.scope_path_pieces.iter().chain(&ep.route_pieces) {
+        path.push('/');
+        match piece {
+            PathPiece::Static(segment) => path.push_str(segment),
+            PathPiece::CxVar { field, .. } => {
+                openapi_push_placeholder(&mut path, field);
+                path_fields.push((FmtVarSource::Cx, field));
+            }
+            PathPiece::EpVar { field } => {
+                openapi_push_placeholder(&mut path, field);
+                path_fields.push((FmtVarSource::Ep, field));
+            }
+            PathPiece::Fmt(fmt) => {
+                for piece in &fmt.pieces {
+                    match piece {
+                        FmtResolvedPiece::Lit(lit) => path.push_str(&lit.value()),
+                        FmtResolvedPiece::Var { source, field, .. } => {
+                            openapi_push_placeholder(&mut path, field);
+                            path_fields.push((*source, field));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if path.is_empty() {
+        path.push('/');
+    }
+    let path = LitStr::new(&path, ep.name.span());
+    let operation_id = ep
+        .scope_modules
+        .iter()
+        .map(ToString::to_string)
+        .chain([ep.name.to_string()])
+        .collect::<Vec<_>>()
+        .join(".");
+    let operation_id = LitStr::new(&operation_id, ep.name.span());
+
+    let mut calls = Vec::new();
+    if let Some(tag) = ep.scope_modules.first() {
+        let tag = LitStr::new(&tag.to_string(), tag.span());
+        calls.push(quote! { __op.tag(#tag); });
+    }
+    if let Some(server) = server {
+        calls.push(server.emit(quote! { __op }));
+    }
+
+    let mut seen_path_fields = std::collections::BTreeSet::new();
+    for (source, field) in path_fields {
+        if !seen_path_fields.insert(field.to_string()) {
+            continue;
+        }
+        let name = LitStr::new(&field.to_string(), field.span());
+        let location = openapi_location(OpenApiLocation::Path);
+        match openapi_var(api, ep, source, field) {
+            Some(var) => {
+                let ty = &var.ty;
+                let schema = openapi_schema(quote!(#ty));
+                calls.push(quote! { __op.param(#location, #name, true, false, #schema); });
+            }
+            None => calls.push(quote! { __op.string_param(#location, #name, true); }),
+        }
+    }
+
+    let params = openapi_policy_params(api, ep);
+    for param in &params {
+        let name = LitStr::new(&param.name, Span::call_site());
+        let location = openapi_location(param.location);
+        calls.push(match &param.value {
+            OpenApiParamValue::Typed {
+                ty,
+                required,
+                repeated,
+            } => {
+                let schema = openapi_schema(quote!(#ty));
+                quote! { __op.param(#location, #name, #required, #repeated, #schema); }
+            }
+            OpenApiParamValue::Text { required } => {
+                quote! { __op.string_param(#location, #name, #required); }
+            }
+            OpenApiParamValue::Constant(value) => {
+                let value = LitStr::new(value, Span::call_site());
+                quote! { __op.constant_param(#location, #name, #value); }
+            }
+        });
+    }
+
+    calls.push(match &ep.io.request_body {
+        ResolvedRequestBodyIo::None => quote! {},
+        ResolvedRequestBodyIo::BufferedCodec(io) => {
+            let marker = &io.marker;
+            let schema = openapi_schema(
+                quote!(<#marker as ::concord_core::advanced::BodyCodec>::Value),
+            );
+            quote! { __op.request_body::<#marker>(#schema); }
+        }
+        ResolvedRequestBodyIo::RawStream { media_ty } => {
+            quote! { __op.stream_request::<#media_ty>(); }
+        }
+        ResolvedRequestBodyIo::Multipart { .. } => quote! { __op.multipart_request(); },
+    });
+    calls.push(match &ep.io.response_body {
+        ResolvedResponseBodyIo::BufferedCodec(io) => {
+            let marker = &io.marker;
+            let schema = openapi_schema(
+                quote!(<#marker as ::concord_core::advanced::ResponseCodec>::Value),
+            );
+            quote! { __op.success::<#marker>(#schema); }
+        }
+        ResolvedResponseBodyIo::BufferedBytes => quote! { __op.success_bytes(); },
+        ResolvedResponseBodyIo::NoContent => quote! { __op.success_no_content(); },
+        ResolvedResponseBodyIo::RawStream { media_ty } => {
+            quote! { __op.success_stream::<#media_ty>(); }
+        }
+        ResolvedResponseBodyIo::JsonLines { value_ty } => {
+            let schema = openapi_schema(quote!(#value_ty));
+            quote! { __op.success_json_lines(#schema); }
+        }
+    });
+    for error in &ep.error_responses {
+        let marker = &error.codec.marker;
+        let status = match error.status {
+            Some(status) => quote! { ::core::option::Option::Some(#status) },
+            None => quote! { ::core::option::Option::None },
+        };
+        let schema = openapi_schema(quote!(
+            <#marker as ::concord_core::advanced::ResponseCodec>::Value
+        ));
+        calls.push(quote! { __op.error::<#marker>(#status, #schema); });
+    }
+
+    for requirement in &ep.policy.auth {
+        let Some(entry) = schemes.entry(&requirement.credential, &requirement.placement) else {
+            continue;
+        };
+        let name = LitStr::new(&entry.name, Span::call_site());
+        let scopes = entry
+            .scopes
+            .iter()
+            .map(|scope| LitStr::new(scope, Span::call_site()));
+        calls.push(quote! { __op.security(#name, &[ #( #scopes ),* ]); });
+    }
+
+    if let Some(paginate) = &ep.paginate {
+        let controller_ty = &paginate.controller_ty;
+        let controller = quote!(#controller_ty).to_string().replace(' ', "");
+        let controller = LitStr::new(&controller, ep.name.span());
+        let bound = paginate
+            .bindings
+            .iter()
+            .map(|binding| binding.endpoint_rust_field.to_string())
+            .collect::<std::collections::BTreeSet<_>>();
+        let parameters = params
+            .iter()
+            .filter(|param| param.field.as_ref().is_some_and(|field| bound.contains(field)))
+            .map(|param| LitStr::new(&param.name, Span::call_site()));
+        calls.push(quote! { __op.pagination(#controller, &[ #( #parameters ),* ]); });
+    }
+
+    quote! {
+        __doc.operation(#method, #path, #operation_id, |__op| {
+            #( #calls )*
+        });
+    }
+}
+
+/// Schema source for a concrete type; falls back to the opaque description
+/// when the type has no `JsonSchema`, so `schemars` adds no bounds.
+fn openapi_schema(ty: TokenStream2) -> TokenStream2 {
+    quote! { ::concord_core::__openapi_schema!(#ty) }
+}
+
+fn openapi_push_placeholder(out: &mut String, field: &Ident) {
+    out.push('{');
+    out.push_str(&field.to_string());
+    out.push('}');
+}
+
+fn openapi_var<'a>(
+    api: &'a ResolvedApi,
+    ep: &'a ResolvedEndpoint,
+    source: FmtVarSource,
+    field: &Ident,
+) -> Option<&'a VarInfo> {
+    let vars = match source {
+        FmtVarSource::Cx => &api.client_vars,
+        FmtVarSource::Ep => &ep.vars,
+    };
+    vars.iter().find(|var| var.rust == *field)
+}
+
+/// Server URL for one endpoint: its fixed origin, or a template whose
+/// variables are the client and endpoint fields feeding host labels.
+#[derive(Debug, PartialEq)]
+struct OpenApiServer {
+    url: String,
+    variables: Vec<String>,
+}
+
+impl OpenApiServer {
+    fn emit(&self, target: TokenStream2) -> TokenStream2 {
+        let url = LitStr::new(&self.url, Span::call_site());
+        let variables = self
+            .variables
+            .iter()
+            .map(|variable| LitStr::new(variable, Span::call_site()));
+        quote! { #target.server(#url, &[ #( #variables ),* ]); }
+    }
+}
+
+fn openapi_server(api: &ResolvedApi, ep: &ResolvedEndpoint) -> OpenApiServer {
+    let scheme = match api.scheme {
+        crate::model::Scheme::Http => "http",
+        crate::model::Scheme::Https => "https",
+    };
+    if let EndpointOriginIr::Fixed(origin) = &ep.descriptor.origin {
+        let scheme = match origin.scheme {
+            OriginSchemeIr::Http => "http",
+            OriginSchemeIr::Https => "https",
+        };
+        return OpenApiServer {
+            url: format!("{scheme}://{}", origin.authority),
+            variables: Vec::new(),
+        };
+    }
+    let mut labels = Vec::new();
+    let mut variables = Vec::new();
+    let placeholder = |field: &Ident, variables: &mut Vec<String>| {
+        let field = field.to_string();
+        if !variables.contains(&field) {
+            variables.push(field.clone());
+        }
+        format!("{{{field}}}")
+    };
+    for piece in &ep.prefix_pieces {
+        labels.push(match piece {
+            PrefixPiece::Static(label) => label.clone(),
+            PrefixPiece::CxVar { field, .. } | PrefixPiece::EpVar { field } => {
+                placeholder(field, &mut variables)
+            }
+            PrefixPiece::Fmt(fmt) => fmt
+                .pieces
+                .iter()
+                .map(|piece| match piece {
+                    FmtResolvedPiece::Lit(lit) => lit.value(),
+                    FmtResolvedPiece::Var { field, .. } => placeholder(field, &mut variables),
+                })
+                .collect(),
+        });
+    }
+    labels.push(api.domain.value());
+    OpenApiServer {
+        url: format!("{scheme}://{}", labels.join(".")),
+        variables,
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum OpenApiLocation {
+    Path,
+    Query,
+    Header,
+}
+
+fn openapi_location(location: OpenApiLocation) -> TokenStream2 {
+    match location {
+        OpenApiLocation::Path => {
+            quote! { ::concord_core::__private::OpenApiParamLocation::Path }
+        }
+        OpenApiLocation::Query => {
+            quote! { ::concord_core::__private::OpenApiParamLocation::Query }
+        }
+        OpenApiLocation::Header => {
+            quote! { ::concord_core::__private::OpenApiParamLocation::Header }
+        }
+    }
+}
+
+struct OpenApiParam {
+    location: OpenApiLocation,
+    name: String,
+    /// Endpoint field the value comes from, for pagination bindings.
+    field: Option<String>,
+    value: OpenApiParamValue,
+}
+
+#[allow(clippy::large_enum_variant)]
+enum OpenApiParamValue {
+    Typed {
+        ty: syn::Type,
+        required: bool,
+        repeated: bool,
+    },
+    Text {
+        required: bool,
+    },
+    Constant(String),
+}
+
+/// Query and header parameters after client, scope, and endpoint policy are
+/// applied in order, so later sets and removals win.
+fn openapi_policy_params(api: &ResolvedApi, ep: &ResolvedEndpoint) -> Vec<OpenApiParam> {
+    let mut params: Vec<OpenApiParam> = Vec::new();
+    let blocks = std::iter::once(&api.client_policy)
+        .chain(&ep.policy.scopes)
+        .chain([&ep.policy.endpoint]);
+    for block in blocks {
+        for (kind, location, ops) in [
+            (PolicyKeyKind::Query, OpenApiLocation::Query, &block.query),
+            (PolicyKeyKind::Header, OpenApiLocation::Header, &block.headers),
+        ] {
+            for op in ops {
+                let key = match op {
+                    PolicyOp::Remove { key } | PolicyOp::Set { key, .. } => key,
+                };
+                let (name, _, _) = emit_key_string(key, kind);
+                let same = |param: &OpenApiParam| {
+                    param.location == location
+                        && match location {
+                            OpenApiLocation::Header => param.name.eq_ignore_ascii_case(&name),
+                            _ => param.name == name,
+                        }
+                };
+                params.retain(|param| !same(param));
+                let PolicyOp::Set {
+                    value, cardinality, ..
+                } = op
+                else {
+                    continue;
+                };
+                // OpenAPI describes these headers through media types and
+                // security schemes instead of parameters.
+                if location == OpenApiLocation::Header
+                    && ["accept", "content-type", "authorization"]
+                        .iter()
+                        .any(|reserved| name.eq_ignore_ascii_case(reserved))
+                {
+                    continue;
+                }
+                let repeated = matches!(
+                    cardinality,
+                    QueryValueCardinality::Vector | QueryValueCardinality::OptionalVector
+                );
+                let typed = |source, field: &Ident, required: bool| {
+                    let field_name = matches!(source, FmtVarSource::Ep).then(|| field.to_string());
+                    match openapi_var(api, ep, source, field) {
+                        Some(var) => (
+                            field_name,
+                            OpenApiParamValue::Typed {
+                                ty: var.ty.clone(),
+                                required: required && !var.optional && var.default.is_none(),
+                                repeated,
+                            },
+                        ),
+                        None => (field_name, OpenApiParamValue::Text { required }),
+                    }
+                };
+                let (field, value) = match value {
+                    PolicySetValue::Value(PublicValueKind::LitStr(lit)) => {
+                        (None, OpenApiParamValue::Constant(lit.value()))
+                    }
+                    PolicySetValue::Value(PublicValueKind::CxField(field)) => {
+                        typed(FmtVarSource::Cx, field, true)
+                    }
+                    PolicySetValue::Value(PublicValueKind::EpField(field)) => {
+                        typed(FmtVarSource::Ep, field, true)
+                    }
+                    PolicySetValue::OptionalCxField(field) => {
+                        typed(FmtVarSource::Cx, field, false)
+                    }
+                    PolicySetValue::OptionalEpField(field) => {
+                        typed(FmtVarSource::Ep, field, false)
+                    }
+                    PolicySetValue::Value(PublicValueKind::OtherExpr(_)) => {
+                        (None, OpenApiParamValue::Text { required: true })
+                    }
+                    PolicySetValue::Value(PublicValueKind::Fmt(fmt)) => {
+                        let skippable = fmt.require_all
+                            && fmt.pieces.iter().any(|piece| {
+                                matches!(piece, FmtResolvedPiece::Var { optional: true, .. })
+                            });
+                        (
+                            None,
+                            OpenApiParamValue::Text {
+                                required: !skippable,
+                            },
+                        )
+                    }
+                };
+                params.push(OpenApiParam {
+                    location,
+                    name: name.clone(),
+                    field,
+                    value,
+                });
+            }
+        }
+    }
+    params
+}
+
+/// Security schemes keyed by credential and placement. A credential placed one
+/// way keeps its own name; further placements get numbered suffixes.
+struct OpenApiSchemes {
+    entries: Vec<OpenApiSchemeEntry>,
+}
+
+struct OpenApiSchemeEntry {
+    credential: String,
+    placement: AuthPlacementIr,
+    name: String,
+    scheme: TokenStream2,
+    scopes: Vec<String>,
+}
+
+impl OpenApiSchemes {
+    fn collect(api: &ResolvedApi) -> Self {
+        let mut entries: Vec<OpenApiSchemeEntry> = Vec::new();
+        for requirement in api.endpoints.iter().flat_map(|ep| &ep.policy.auth) {
+            let credential = requirement.credential.to_string();
+            if entries.iter().any(|entry| {
+                entry.credential == credential && entry.placement == requirement.placement
+            }) {
+                continue;
+            }
+            let kind = api
+                .client_auth_credentials
+                .iter()
+                .find(|declared| declared.name == requirement.credential)
+                .map(|declared| &declared.kind);
+            let (scheme, scopes) = openapi_security_scheme(&requirement.placement, kind);
+            let placements = entries
+                .iter()
+                .filter(|entry| entry.credential == credential)
+                .count();
+            let name = match placements {
+                0 => credential.clone(),
+                n => format!("{credential}_{}", n + 1),
+            };
+            entries.push(OpenApiSchemeEntry {
+                credential,
+                placement: requirement.placement.clone(),
+                name,
+                scheme,
+                scopes,
+            });
+        }
+        Self { entries }
+    }
+
+    fn entry(&self, credential: &Ident, placement: &AuthPlacementIr) -> Option<&OpenApiSchemeEntry> {
+        self.entries
+            .iter()
+            .find(|entry| *credential == entry.credential && entry.placement == *placement)
+    }
+}
+
+fn openapi_security_scheme(
+    placement: &AuthPlacementIr,
+    kind: Option<&AuthCredentialKindIr>,
+) -> (TokenStream2, Vec<String>) {
+    let scheme = quote! { ::concord_core::__private::OpenApiSecurityScheme };
+    match placement {
+        AuthPlacementIr::Header { name } => {
+            let location = openapi_location(OpenApiLocation::Header);
+            (
+                quote! { #scheme::ApiKey { location: #location, name: #name } },
+                Vec::new(),
+            )
+        }
+        AuthPlacementIr::Query { key } => {
+            let location = openapi_location(OpenApiLocation::Query);
+            (
+                quote! { #scheme::ApiKey { location: #location, name: #key } },
+                Vec::new(),
+            )
+        }
+        AuthPlacementIr::Basic => (quote! { #scheme::Basic }, Vec::new()),
+        AuthPlacementIr::Bearer => match kind {
+            Some(AuthCredentialKindIr::OAuth2ClientCredentials {
+                token_url, scope, ..
+            }) => {
+                let scopes = scope
+                    .iter()
+                    .flat_map(|scope| {
+                        scope
+                            .value()
+                            .split_whitespace()
+                            .map(str::to_string)
+                            .collect::<Vec<_>>()
+                    })
+                    .collect();
+                let scope = match scope {
+                    Some(scope) => quote! { ::core::option::Option::Some(#scope) },
+                    None => quote! { ::core::option::Option::None },
+                };
+                (
+                    quote! {
+                        #scheme::OAuth2ClientCredentials { token_url: #token_url, scope: #scope }
+                    },
+                    scopes,
+                )
+            }
+            Some(AuthCredentialKindIr::OAuth2RefreshToken { token_url, .. }) => (
+                quote! { #scheme::OAuth2RefreshToken { token_url: #token_url } },
+                Vec::new(),
+            ),
+            _ => (quote! { #scheme::Bearer }, Vec::new()),
+        },
+    }
+}
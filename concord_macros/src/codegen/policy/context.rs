@@ -24,8 +24,57 @@ fn emit_policy_apply_fn(policy: &PolicyBlocksResolved, ctx: PolicyEmitCtx) -> To
     if let Some(rate_limit) = emit_rate_limit_op(&policy.rate_limit, ctx) {
         ops.push(rate_limit);
     }
+    ops.extend(emit_cache_op(&policy.cache));
+    ops.extend(emit_idempotency_key_op(&policy.idempotency_key));
+    ops.extend(emit_coalesce_op(policy.coalesce));
+    ops.extend(emit_hedge_op(policy.hedge));
     quote! { #( #ops )* }
 }
 
+fn emit_cache_op(cache: &Option<CacheResolved>) -> Option<TokenStream2> {
+    Some(match cache.as_ref()? {
+        CacheResolved::Off => quote! {
+            policy.clear_cache();
+        },
+        CacheResolved::MaxAge { secs, shared } => {
+            let shared = shared.then(|| quote! { .shared() });
+            quote! {
+                policy.set_cache(::concord_core::advanced::CachePolicy::max_age(
+                    ::std::time::Duration::from_secs(#secs),
+                )#shared);
+            }
+        }
+    })
+}
+
+fn emit_coalesce_op(coalesce: Option<bool>) -> Option<TokenStream2> {
+    let coalesce = coalesce?;
+    Some(quote! {
+        policy.set_coalesce(#coalesce);
+    })
+}
+
+fn emit_hedge_op(hedge: Option<HedgeResolved>) -> Option<TokenStream2> {
+    Some(match hedge? {
+        HedgeResolved::Off => quote! {
+            policy.clear_hedge();
+        },
+        HedgeResolved::AfterMillis(millis) => quote! {
+            policy.set_hedge(::std::time::Duration::from_millis(#millis));
+        },
+    })
+}
+
+fn emit_idempotency_key_op(key: &Option<IdempotencyKeyResolved>) -> Option<TokenStream2> {
+    Some(match key.as_ref()? {
+        IdempotencyKeyResolved::Off => quote! {
+            policy.clear_idempotency_key();
+        },
+        IdempotencyKeyResolved::Header(name) => quote! {
+            policy.set_idempotency_key(::http::header::HeaderName::from_static(#name));
+        },
+    })
+}
+
 
 
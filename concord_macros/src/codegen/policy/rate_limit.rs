@@ -28,6 +28,17 @@ fn emit_rate_limit_plan(plan: &RateLimitPlanResolved, ctx: PolicyEmitCtx) -> Tok
         let name = LitStr::new(&bucket.name, Span::call_site());
         let key = emit_rate_limit_key(&bucket.key, ctx);
         let cost = bucket.cost;
+        let concurrency = bucket.concurrency.map(|limit| {
+            quote! {
+                .with_concurrency(::std::num::NonZeroU32::new(#limit).ok_or_else(|| {
+                    ::concord_core::prelude::ApiClientError::rate_limit(
+                        ctx.clone(),
+                        ::concord_core::prelude::RateLimitErrorKind::InvalidConfiguration,
+                        "validated rate-limit concurrent limit was zero",
+                    )
+                })?)
+            }
+        });
         let windows = bucket.windows.iter().map(|window| {
                 let max = window.max;
                 let per_secs = window.per_secs;
@@ -54,6 +65,7 @@ fn emit_rate_limit_plan(plan: &RateLimitPlanResolved, ctx: PolicyEmitCtx) -> Tok
                     )
                 })?)
                 .with_windows(::std::vec![ #( #windows ),* ])
+                #concurrency
         }
     });
     quote! {
@@ -153,6 +153,44 @@ fn generated_oauth2_client_credentials_provider_is_typed() {
     assert!(out.contains("AuthPreparationMode::PerExecution"));
 }
 
+#[test]
+fn generated_oauth2_refresh_provider_and_authorize_helper() {
+    let out = expanded(quote! {
+        client OAuthRefreshApi {
+            base "https://example.com"
+            secret client_id: String
+            secret client_secret: String
+            secret refresh_token?: String
+
+            credential session = oauth2_refresh {
+                token_url: "https://auth.example.com/oauth/token",
+                client_id: secret.client_id,
+                client_secret: secret.client_secret,
+                refresh_token: secret.refresh_token,
+                scope: "read:me",
+            }
+        }
+
+        GET OAuthMe
+            path ["oauth-me"]
+            auth bearer session
+            -> Json<String>
+    });
+
+    assert_contains_all(
+        &out,
+        &[
+            "::concord_core::__private::OAuth2RefreshTokenProvider::from_validated_token_url",
+            ".client_secret(auth.client_secret.clone())",
+            ".scope(\"read:me\")",
+            ".with_missing_hint(\"client.authorize_auth_session(...)\")",
+            "pub async fn authorize_auth_session",
+            "exchange_oauth2_authorization_code",
+            "__auth_state.session.set_manual(__token, self.inner.credential_store(), self.inner.clock())",
+        ],
+    );
+}
+
 #[test]
 fn generated_auth_session_contains_auth_state_and_acquire_sugar() {
     let out = expanded(quote! {
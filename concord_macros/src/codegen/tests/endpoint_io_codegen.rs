@@ -42,6 +42,53 @@ fn emit_uses_stream_request_and_response_codegen() {
     );
 }
 
+#[test]
+fn emit_lowers_sse_response_to_event_stream_media() {
+    let expanded = expanded(quote! {
+        api! {
+            client SseCodegen {
+                base "https://example.com"
+            }
+
+            GET Feed
+                path ["feed"]
+                -> Sse<Json<Delta>>
+        }
+    });
+
+    assert_contains_all(
+        &expanded,
+        &[
+            "StreamResponse<::concord_core::advanced::EventStream<Json<Delta>>>",
+            "GeneratedRawStreamResponse<::concord_core::advanced::EventStream<Json<Delta>>>",
+        ],
+    );
+}
+
+#[test]
+fn emit_lowers_json_lines_response_to_json_lines_stream() {
+    let expanded = expanded(quote! {
+        api! {
+            client JsonLinesCodegen {
+                base "https://example.com"
+            }
+
+            GET Export
+                path ["export"]
+                -> JsonLines<Row>
+        }
+    });
+
+    assert_contains_all(
+        &expanded,
+        &[
+            "::concord_core::advanced::JsonLinesStream<Row>",
+            "GeneratedJsonLinesResponse<Row>",
+        ],
+    );
+    assert!(!expanded.contains("GeneratedResponseTerminalEndpoint"));
+}
+
 #[test]
 fn emit_uses_buffered_request_entity_codegen() {
     let expanded = expanded(quote! {
@@ -140,3 +187,28 @@ fn emit_uses_multipart_request_codegen() {
         ],
     );
 }
+
+#[test]
+fn emit_attaches_resolved_error_responses_to_response_preparation() {
+    let expanded = expanded(quote! {
+        api! {
+            client ErrorCodegen {
+                base "https://example.com"
+                default { on_error Json<ApiError> }
+            }
+
+            GET GetUser(id: u64)
+                path [id]
+                -> Json<User>
+                on_error 404 Json<NotFound>
+        }
+    });
+
+    assert_contains_all(
+        &expanded,
+        &[
+            ".on_error::<Json<ApiError>>(::core::option::Option::None,&ctx_err)?",
+            ".on_error::<Json<NotFound>>(::core::option::Option::Some(404u16),&ctx_err)?",
+        ],
+    );
+}
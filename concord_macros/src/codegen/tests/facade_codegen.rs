@@ -392,6 +392,68 @@ fn generated_scope_default_capture_is_applied_without_public_endpoint_setter() {
     assert_generated_doc_attrs_do_not_contain(&out, "`en_US`");
 }
 
+#[test]
+fn generated_endpoint_fields_stay_private_to_the_client_crate() {
+    let out = expanded(quote! {
+        client PrivateFields {
+            base "https://example.com"
+        }
+
+        POST Rename(id: u64, note?: String, body: Json<String>)
+            as rename
+            path ["items", id]
+            query { note }
+            -> Json<String>
+    });
+
+    assert_contains_all(
+        &out,
+        &[
+            "pub (crate) id : u64",
+            "pub (crate) note : :: core :: option :: Option < String >",
+            "pub (crate) body : String",
+        ],
+    );
+    assert_not_contains_all(
+        &out,
+        &[
+            "pub id : u64",
+            "pub note : :: core :: option :: Option < String >",
+            "pub body : String",
+            "non_exhaustive",
+        ],
+    );
+}
+
+#[test]
+fn generated_endpoint_fields_are_readable_through_a_borrowed_view() {
+    let out = expanded(quote! {
+        client FieldView {
+            base "https://example.com"
+        }
+
+        POST Rename(id: u64, note?: String, body: Json<String>)
+            as rename
+            path ["items", id]
+            query { note }
+            -> Json<String>
+    });
+
+    assert_contains_all(
+        &out,
+        &[
+            "impl :: concord_core :: __private :: GeneratedEndpointFields for EpRename",
+            "type Fields < 'a > = EpRename",
+            "pub id : & 'a u64",
+            "pub note : :: core :: option :: Option < & 'a String >",
+            "pub body : & 'a String",
+            "id : & self . id",
+            "note : self . note . as_ref ()",
+            "body : & self . body",
+        ],
+    );
+}
+
 #[test]
 fn generated_client_construction_contains_current_api_only() {
     let out = expanded(quote! {
@@ -412,6 +474,7 @@ fn generated_client_construction_contains_current_api_only() {
         &out,
         &[
             "pub struct ConstructApi",
+            "impl :: concord_core :: __private :: GeneratedClient for ConstructApi { type Cx = ConstructApiCx ; }",
             "pub fn new ( tenant : String , api_key : String ) -> Self",
             "pub fn new_with_safe_reqwest_builder ( tenant : String , api_key : String , configure : impl FnOnce (:: concord_core :: advanced :: SafeReqwestBuilder) -> :: concord_core :: advanced :: SafeReqwestBuilder , ) -> :: core :: result :: Result < Self , :: concord_core :: advanced :: ReqwestClientBuildError >",
             "pub fn new_with_safe_reqwest_builder_fallible ( tenant : String , api_key : String , configure : impl FnOnce (:: concord_core :: advanced :: SafeReqwestBuilder,) -> :: core :: result :: Result < :: concord_core :: advanced :: SafeReqwestBuilder , :: concord_core :: advanced :: ReqwestClientBuildError , > , ) -> :: core :: result :: Result < Self , :: concord_core :: advanced :: ReqwestClientBuildError >",
@@ -15,6 +15,8 @@ mod generated_docs;
 #[cfg(test)]
 mod helpers;
 #[cfg(test)]
+mod openapi_codegen;
+#[cfg(test)]
 mod output_model_codegen;
 #[cfg(test)]
 mod pagination_codegen;
@@ -0,0 +1,81 @@
+use super::helpers::*;
+use quote::quote;
+
+#[test]
+fn openapi_export_is_gated_and_built_from_resolved_routes_and_policy() {
+    let out = expanded(quote! {
+        client ExportApi {
+            base "https://example.com"
+            var tenant: String
+            secret key: String
+            credential api = api_key(secret.key)
+            default {
+                headers { "X-Client" = "concord" }
+            }
+        }
+        scope users {
+            host [vars.tenant]
+            path ["users"]
+
+            GET Get(id: u64, tags?: Vec<String>)
+                path [id]
+                query { tags }
+                headers { "Accept" = "application/json" }
+                auth header "X-Api-Key" = api
+                -> Json<String>
+                on_error 404 Json<String>
+        }
+    });
+
+    assert_contains_all(
+        &out,
+        &[
+            ":: concord_core :: __openapi_item ! { impl ExportApi {",
+            "pub fn openapi () -> :: concord_core :: prelude :: OpenApiDocument",
+            "__doc . server (\"https://{tenant}.example.com\" , & [\"tenant\"]) ;",
+            "__doc . security_scheme (\"api\" , :: concord_core :: __private :: OpenApiSecurityScheme :: ApiKey { location : :: concord_core :: __private :: OpenApiParamLocation :: Header , name : \"X-Api-Key\" }) ;",
+            "__doc . operation (\"GET\" , \"/users/{id}\" , \"users.Get\" , | __op |",
+            "__op . tag (\"users\") ;",
+            "__op . param (:: concord_core :: __private :: OpenApiParamLocation :: Path , \"id\" , true , false , :: concord_core :: __openapi_schema ! (u64)) ;",
+            "__op . constant_param (:: concord_core :: __private :: OpenApiParamLocation :: Header , \"X-Client\" , \"concord\") ;",
+            "__op . param (:: concord_core :: __private :: OpenApiParamLocation :: Query , \"tags\" , false , true , :: concord_core :: __openapi_schema ! (Vec < String >)) ;",
+            "__op . success :: < Json < String > > (:: concord_core :: __openapi_schema ! (< Json < String > as :: concord_core :: advanced :: ResponseCodec > :: Value)) ;",
+            "__op . error :: < Json < String > > (:: core :: option :: Option :: Some (404u16) , :: concord_core :: __openapi_schema ! (< Json < String > as :: concord_core :: advanced :: ResponseCodec > :: Value)) ;",
+            "__op . security (\"api\" , & []) ;",
+        ],
+    );
+    // Content negotiation headers are described by media types instead.
+    assert_not_contains_all(&out, &["\"Accept\" , \"application/json\""]);
+}
+
+#[test]
+fn openapi_export_names_each_credential_placement_and_oauth_scopes() {
+    let out = expanded(quote! {
+        client SchemesApi {
+            base "https://example.com"
+            secret id: String
+            secret secret_value: String
+            credential token = oauth2_client {
+                token_url: "https://auth.example.com/token",
+                client_id: secret.id,
+                client_secret: secret.secret_value,
+                scope: "read write",
+            }
+        }
+        GET Header -> Json<String>
+            auth bearer token
+        GET Query -> Json<String>
+            auth query "access_token" = token
+    });
+
+    assert_contains_all(
+        &out,
+        &[
+            "__doc . server (\"https://example.com\" , & []) ;",
+            "__doc . security_scheme (\"token\" , :: concord_core :: __private :: OpenApiSecurityScheme :: OAuth2ClientCredentials { token_url : \"https://auth.example.com/token\" , scope : :: core :: option :: Option :: Some (\"read write\") })",
+            "__doc . security_scheme (\"token_2\" ,",
+            "__op . security (\"token\" , & [\"read\" , \"write\"]) ;",
+            "__op . security (\"token_2\" , & []) ;",
+        ],
+    );
+}
@@ -178,3 +178,94 @@ fn rustdoc_profile_label_dedup_does_not_affect_policy() {
         3
     );
 }
+
+#[test]
+fn cache_clauses_lower_to_policy_builder_calls() {
+    let out = expanded(quote! {
+        client CacheApi {
+            base "https://example.com"
+            default {
+                cache max_age 5m
+            }
+        }
+
+        GET Versions path ["versions"] -> Json<Vec<String>>;
+        GET Live path ["live"] cache off -> Json<String>;
+        GET Catalog path ["catalog"] cache max_age 1h shared -> Json<String>;
+    });
+
+    assert_contains_all(
+        &out,
+        &[
+            "policy.set_cache(::concord_core::advanced::CachePolicy::max_age(::std::time::Duration::from_secs(300u64),));",
+            "policy.set_cache(::concord_core::advanced::CachePolicy::max_age(::std::time::Duration::from_secs(3600u64),).shared());",
+            "policy.clear_cache();",
+        ],
+    );
+}
+
+#[test]
+fn idempotency_key_clauses_lower_to_policy_builder_calls() {
+    let out = expanded(quote! {
+        client PaymentsApi {
+            base "https://example.com"
+            default {
+                idempotency_key header "Idempotency-Key"
+            }
+        }
+
+        POST Charge path ["charges"] -> Json<String>;
+        POST Ping path ["ping"] idempotency_key off -> Json<String>;
+    });
+
+    assert_contains_all(
+        &out,
+        &[
+            "policy.set_idempotency_key(::http::header::HeaderName::from_static(\"idempotency-key\"))",
+            "policy.clear_idempotency_key();",
+        ],
+    );
+}
+
+#[test]
+fn coalesce_clauses_lower_to_policy_builder_calls() {
+    let out = expanded(quote! {
+        client CatalogApi {
+            base "https://example.com"
+            default {
+                coalesce
+            }
+        }
+
+        GET Item path ["item"] -> Json<String>;
+        GET Fresh path ["fresh"] coalesce off -> Json<String>;
+    });
+
+    assert_contains_all(
+        &out,
+        &["policy.set_coalesce(true);", "policy.set_coalesce(false);"],
+    );
+}
+
+#[test]
+fn hedge_clauses_lower_to_policy_builder_calls() {
+    let out = expanded(quote! {
+        client CatalogApi {
+            base "https://example.com"
+            default {
+                hedge after 50ms
+            }
+        }
+
+        GET Item path ["item"] -> Json<String>;
+        GET Fresh path ["fresh"] hedge off -> Json<String>;
+    });
+
+    assert_contains_all(
+        &out,
+        &[
+            "policy.set_hedge(::std::time::Duration::from_millis(50u64));",
+            "policy.clear_hedge();",
+        ],
+    );
+}
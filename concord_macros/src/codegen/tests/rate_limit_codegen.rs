@@ -71,3 +71,33 @@ fn generated_rate_limit_materializes_resolved_policy() {
         ],
     );
 }
+
+#[test]
+fn generated_rate_limit_emits_concurrent_limit() {
+    let out = expanded(quote! {
+        client SnapshotConcurrency {
+            base "https://example.com"
+
+            rate_limit exports {
+                bucket method by [endpoint] {
+                    concurrent 4
+                    10 / 1s
+                }
+            }
+        }
+
+        GET Export
+            as export
+            path ["export"]
+            rate_limit exports
+            -> Json<()>;
+    });
+
+    assert_contains_all(
+        &out,
+        &[
+            ". with_concurrency (:: std :: num :: NonZeroU32 :: new (4u32)",
+            "validated rate-limit concurrent limit was zero",
+        ],
+    );
+}
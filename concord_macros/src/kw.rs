@@ -22,6 +22,7 @@ syn::custom_keyword!(bucket);
 syn::custom_keyword!(by);
 syn::custom_keyword!(key);
 syn::custom_keyword!(cost);
+syn::custom_keyword!(concurrent);
 syn::custom_keyword!(header);
 syn::custom_keyword!(bearer);
 syn::custom_keyword!(basic);
@@ -29,6 +30,14 @@ syn::custom_keyword!(api_key);
 syn::custom_keyword!(access_token);
 syn::custom_keyword!(endpoint);
 syn::custom_keyword!(observe);
+syn::custom_keyword!(on_error);
+syn::custom_keyword!(cache);
+syn::custom_keyword!(max_age);
+syn::custom_keyword!(shared);
+syn::custom_keyword!(idempotency_key);
+syn::custom_keyword!(coalesce);
+syn::custom_keyword!(hedge);
+syn::custom_keyword!(after);
 
 syn::custom_keyword!(prefix);
 syn::custom_keyword!(path);
@@ -12,6 +12,7 @@ pub(crate) struct FacadeIr {
     pub client_setters: Vec<FacadeSetter>,
     pub auth_setters: Vec<FacadeSetter>,
     pub credential_methods: Vec<FacadeCredentialMethods>,
+    pub authorize_methods: Vec<FacadeAuthorizeMethod>,
     pub scopes: Vec<FacadeScope>,
     pub endpoints: Vec<FacadeEndpoint>,
     pub docs: Vec<FacadeDoc>,
@@ -68,6 +69,14 @@ pub(crate) struct FacadeCredentialMethods {
     pub pending_method: Ident,
 }
 
+/// Authorization-code exchange helper for an `oauth2_refresh` credential.
+#[derive(Debug)]
+#[allow(dead_code)]
+pub(crate) struct FacadeAuthorizeMethod {
+    pub credential: Ident,
+    pub authorize_name: Ident,
+}
+
 #[derive(Debug)]
 #[allow(dead_code)]
 pub(crate) struct FacadeArg {
@@ -191,6 +200,7 @@ pub(crate) fn build_facade_ir(resolved_api: &ResolvedApi) -> FacadeIr {
         ),
         auth_setters: facade_client_setters(&resolved_api.client_auth_vars, &BTreeMap::new()),
         credential_methods: facade_credential_methods(&resolved_api.client_auth_credentials),
+        authorize_methods: facade_authorize_methods(&resolved_api.client_auth_credentials),
         scopes,
         endpoints,
         docs: Vec::new(),
@@ -220,6 +230,25 @@ fn facade_credential_methods(credentials: &[AuthCredentialIr]) -> Vec<FacadeCred
         .collect()
 }
 
+fn facade_authorize_methods(credentials: &[AuthCredentialIr]) -> Vec<FacadeAuthorizeMethod> {
+    credentials
+        .iter()
+        .filter(|credential| {
+            matches!(
+                credential.kind,
+                AuthCredentialKindIr::OAuth2RefreshToken { .. }
+            )
+        })
+        .map(|credential| {
+            let name = &credential.name;
+            FacadeAuthorizeMethod {
+                credential: name.clone(),
+                authorize_name: emit_helpers::ident(&format!("authorize_auth_{name}"), name.span()),
+            }
+        })
+        .collect()
+}
+
 fn facade_client_setters(
     vars: &[VarInfo],
     query_cardinalities: &BTreeMap<String, QueryValueCardinality>,
@@ -681,6 +710,57 @@ fn facade_endpoint_doc_texts(resolved_api: &ResolvedApi, ep: &ResolvedEndpoint)
         );
     }
 
+    if let Some(CacheResolved::MaxAge { secs, shared }) =
+        effective_cache(ep, &resolved_api.client_policy)
+    {
+        let mut lines = vec![format!(
+            "default max_age {secs}s when the client has a response cache"
+        )];
+        if shared {
+            lines.push("shared across credentials".to_string());
+        }
+        push_section(&mut docs, "Cache:", lines);
+    }
+
+    if let Some(IdempotencyKeyResolved::Header(name)) =
+        effective_idempotency_key(ep, &resolved_api.client_policy)
+        && !matches!(
+            ep.method.to_string().as_str(),
+            "GET" | "HEAD" | "OPTIONS" | "TRACE"
+        )
+    {
+        push_section(
+            &mut docs,
+            "Idempotency:",
+            vec![format!(
+                "generated `{name}` key per call, reused on recovery"
+            )],
+        );
+    }
+
+    if effective_coalesce(ep, &resolved_api.client_policy)
+        && matches!(ep.method.to_string().as_str(), "GET" | "HEAD")
+    {
+        push_section(
+            &mut docs,
+            "Coalescing:",
+            vec!["concurrent identical calls share one execution".to_string()],
+        );
+    }
+
+    if let Some(HedgeResolved::AfterMillis(millis)) =
+        effective_hedge(ep, &resolved_api.client_policy)
+        && ep.method == "GET"
+    {
+        push_section(
+            &mut docs,
+            "Hedging:",
+            vec![format!(
+                "second execution after {millis}ms without a response; the first response wins"
+            )],
+        );
+    }
+
     push_section(
         &mut docs,
         "Retry:",
@@ -836,9 +916,13 @@ fn rate_limit_doc_lines(plan: &RateLimitPlanResolved) -> Vec<String> {
             .map(|window| format!("{} / {}s", window.max, window.per_secs))
             .collect::<Vec<_>>()
             .join(", ");
+        let concurrency = bucket
+            .concurrency
+            .map(|limit| format!(" concurrent {limit}"))
+            .unwrap_or_default();
         lines.push(format!(
-            "bucket `{}` key [{}] cost {} windows [{}]",
-            bucket.kind, key, bucket.cost, windows
+            "bucket `{}` key [{}] cost {} windows [{}]{}",
+            bucket.kind, key, bucket.cost, windows, concurrency
         ));
     }
     lines
@@ -927,6 +1011,67 @@ fn effective_timeout(
     timeout
 }
 
+fn effective_cache(
+    ep: &ResolvedEndpoint,
+    client_policy: &PolicyBlocksResolved,
+) -> Option<CacheResolved> {
+    let mut cache = client_policy.cache;
+    for scope in &ep.policy.scopes {
+        if scope.cache.is_some() {
+            cache = scope.cache;
+        }
+    }
+    if ep.policy.endpoint.cache.is_some() {
+        cache = ep.policy.endpoint.cache;
+    }
+    cache
+}
+
+fn effective_idempotency_key(
+    ep: &ResolvedEndpoint,
+    client_policy: &PolicyBlocksResolved,
+) -> Option<IdempotencyKeyResolved> {
+    let mut key = client_policy.idempotency_key.clone();
+    for scope in &ep.policy.scopes {
+        if scope.idempotency_key.is_some() {
+            key = scope.idempotency_key.clone();
+        }
+    }
+    if ep.policy.endpoint.idempotency_key.is_some() {
+        key = ep.policy.endpoint.idempotency_key.clone();
+    }
+    key
+}
+
+fn effective_coalesce(ep: &ResolvedEndpoint, client_policy: &PolicyBlocksResolved) -> bool {
+    let mut coalesce = client_policy.coalesce;
+    for scope in &ep.policy.scopes {
+        if scope.coalesce.is_some() {
+            coalesce = scope.coalesce;
+        }
+    }
+    if ep.policy.endpoint.coalesce.is_some() {
+        coalesce = ep.policy.endpoint.coalesce;
+    }
+    coalesce.unwrap_or(false)
+}
+
+fn effective_hedge(
+    ep: &ResolvedEndpoint,
+    client_policy: &PolicyBlocksResolved,
+) -> Option<HedgeResolved> {
+    let mut hedge = client_policy.hedge;
+    for scope in &ep.policy.scopes {
+        if scope.hedge.is_some() {
+            hedge = scope.hedge;
+        }
+    }
+    if ep.policy.endpoint.hedge.is_some() {
+        hedge = ep.policy.endpoint.hedge;
+    }
+    hedge
+}
+
 fn doc_public_value(value: &PublicValueKind) -> String {
     match value {
         PublicValueKind::LitStr(lit) => lit.value(),
@@ -48,6 +48,9 @@ fn parse_auth_credential_after_keyword(
             "oauth2_client" => {
                 parse_oauth2_client_credentials(input, kind_name.span())?.into()
             }
+            "oauth2_refresh" => {
+                parse_oauth2_refresh_token(input, kind_name.span())?.into()
+            }
             "endpoint" => {
                 let endpoint = parse_auth_endpoint_ref(input)?;
                 AuthCredentialKind::Endpoint { endpoint }
@@ -55,7 +58,7 @@ fn parse_auth_credential_after_keyword(
             _ => {
                 return Err(syn::Error::new(
                     kind_name.span(),
-                    "unknown auth credential kind; expected api_key(...), bearer(...), basic(...), oauth2_client { ... }, or endpoint path",
+                    "unknown auth credential kind; expected api_key(...), bearer(...), basic(...), oauth2_client { ... }, oauth2_refresh { ... }, or endpoint path",
                 ));
             }
         };
@@ -159,6 +162,85 @@ impl From<OAuth2ClientCredentialsFields> for AuthCredentialKind {
     }
 }
 
+struct OAuth2RefreshTokenFields {
+    token_url: LitStr,
+    client_id: SecretRef,
+    client_secret: Option<SecretRef>,
+    refresh_token: SecretRef,
+    scope: Option<LitStr>,
+}
+
+fn parse_oauth2_refresh_token(
+    input: ParseStream<'_>,
+    span: Span,
+) -> Result<OAuth2RefreshTokenFields> {
+    let content;
+    braced!(content in input);
+
+    let mut token_url: Option<LitStr> = None;
+    let mut client_id: Option<SecretRef> = None;
+    let mut client_secret: Option<SecretRef> = None;
+    let mut refresh_token: Option<SecretRef> = None;
+    let mut scope: Option<LitStr> = None;
+
+    while !content.is_empty() {
+        let key: Ident = content.parse()?;
+        content.parse::<Token![:]>()?;
+        match key.to_string().as_str() {
+            "token_url" => set_once_lit(&mut token_url, key.span(), content.parse()?)?,
+            "client_id" => {
+                set_once_secret_ref(&mut client_id, key.span(), parse_secret_ref(&content)?)?
+            }
+            "client_secret" => {
+                set_once_secret_ref(&mut client_secret, key.span(), parse_secret_ref(&content)?)?
+            }
+            "refresh_token" => {
+                set_once_secret_ref(&mut refresh_token, key.span(), parse_secret_ref(&content)?)?
+            }
+            "scope" => set_once_lit(&mut scope, key.span(), content.parse()?)?,
+            _ => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "unknown OAuth2RefreshToken field; expected token_url, client_id, client_secret, refresh_token, or scope",
+                ));
+            }
+        }
+
+        if content.peek(Token![,]) {
+            content.parse::<Token![,]>()?;
+        } else if !content.is_empty() {
+            let tt: TokenTree = content.parse()?;
+            return Err(syn::Error::new(
+                tt.span(),
+                "expected `,` between OAuth2RefreshToken fields",
+            ));
+        }
+    }
+
+    Ok(OAuth2RefreshTokenFields {
+        token_url: token_url
+            .ok_or_else(|| syn::Error::new(span, "OAuth2RefreshToken missing `token_url`"))?,
+        client_id: client_id
+            .ok_or_else(|| syn::Error::new(span, "OAuth2RefreshToken missing `client_id`"))?,
+        client_secret,
+        refresh_token: refresh_token
+            .ok_or_else(|| syn::Error::new(span, "OAuth2RefreshToken missing `refresh_token`"))?,
+        scope,
+    })
+}
+
+impl From<OAuth2RefreshTokenFields> for AuthCredentialKind {
+    fn from(v: OAuth2RefreshTokenFields) -> Self {
+        AuthCredentialKind::OAuth2RefreshToken {
+            token_url: v.token_url,
+            client_id: v.client_id,
+            client_secret: v.client_secret,
+            refresh_token: v.refresh_token,
+            scope: v.scope,
+        }
+    }
+}
+
 fn set_once_lit(out: &mut Option<LitStr>, span: Span, value: LitStr) -> Result<()> {
     if out.is_some() {
         return Err(syn::Error::new(span, "duplicate auth field"));
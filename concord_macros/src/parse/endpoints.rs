@@ -55,12 +55,46 @@ impl EndpointBlockParts {
         if let Some(query) = other.policy.query {
             merge_policy_block(&mut self.policy.query, query);
         }
+        self.policy.error_responses.extend(other.policy.error_responses);
         if other.policy.timeout.is_some() {
             if self.policy.timeout.is_some() {
                 return Err(syn::Error::new(name.span(), "duplicate timeout policy in endpoint"));
             }
             self.policy.timeout = other.policy.timeout;
         }
+        if other.policy.cache.is_some() {
+            if self.policy.cache.is_some() {
+                return Err(syn::Error::new(name.span(), "duplicate cache policy in endpoint"));
+            }
+            self.policy.cache = other.policy.cache;
+        }
+        if other.policy.idempotency_key.is_some() {
+            if self.policy.idempotency_key.is_some() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "duplicate idempotency_key policy in endpoint",
+                ));
+            }
+            self.policy.idempotency_key = other.policy.idempotency_key;
+        }
+        if other.policy.coalesce.is_some() {
+            if self.policy.coalesce.is_some() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "duplicate coalesce policy in endpoint",
+                ));
+            }
+            self.policy.coalesce = other.policy.coalesce;
+        }
+        if other.policy.hedge.is_some() {
+            if self.policy.hedge.is_some() {
+                return Err(syn::Error::new(
+                    name.span(),
+                    "duplicate hedge policy in endpoint",
+                ));
+            }
+            self.policy.hedge = other.policy.hedge;
+        }
         self.auth_uses.extend(other.auth_uses);
         self.profile_uses.extend(other.profile_uses);
         if other.rate_limit.is_some() {
@@ -80,6 +114,21 @@ impl EndpointBlockParts {
     }
 }
 
+fn parse_error_response_spec(input: ParseStream<'_>) -> Result<ErrorResponseSpec> {
+    let on_error: kw::on_error = input.parse()?;
+    let status = if input.peek(LitInt) {
+        Some(input.parse::<LitInt>()?)
+    } else {
+        None
+    };
+    let io: RawIoSpec = input.parse()?;
+    Ok(ErrorResponseSpec {
+        span: on_error.span,
+        status,
+        io,
+    })
+}
+
 fn parse_endpoint_response_spec(input: ParseStream<'_>) -> Result<RawResponseIo> {
     input.parse::<Token![->]>()?;
     let response: RawIoSpec = input.parse()?;
@@ -170,6 +219,19 @@ fn parse_endpoint_inline_parts(input: ParseStream<'_>, name: &Ident) -> Result<E
             }
             let t = parse_expr_until_comma_or_endpoint_arrow(input)?;
             parts.policy.timeout = Some(t);
+        } else if input.peek(kw::on_error) {
+            parts
+                .policy
+                .error_responses
+                .push(parse_error_response_spec(input)?);
+        } else if input.peek(kw::cache) {
+            parse_cache_spec(input, &mut parts.policy.cache)?;
+        } else if input.peek(kw::idempotency_key) {
+            parse_idempotency_key_spec(input, &mut parts.policy.idempotency_key)?;
+        } else if input.peek(kw::coalesce) {
+            parse_coalesce_spec(input, &mut parts.policy.coalesce)?;
+        } else if input.peek(kw::hedge) {
+            parse_hedge_spec(input, &mut parts.policy.hedge)?;
         } else if input.peek(kw::profile) {
             parts.profile_uses.push(parse_profile_use_spec(input)?);
         } else if input.peek(kw::auth) {
@@ -100,6 +100,23 @@ impl Parse for RawScopeTaggedScope {
                 let t = content.parse::<Expr>()?;
                 policy.timeout = Some(t);
                 let _ = content.parse::<Option<Token![,]>>()?;
+            } else if content.peek(kw::on_error) {
+                policy
+                    .error_responses
+                    .push(parse_error_response_spec(&content)?);
+                let _ = content.parse::<Option<Token![,]>>()?;
+            } else if content.peek(kw::cache) {
+                parse_cache_spec(&content, &mut policy.cache)?;
+                let _ = content.parse::<Option<Token![,]>>()?;
+            } else if content.peek(kw::idempotency_key) {
+                parse_idempotency_key_spec(&content, &mut policy.idempotency_key)?;
+                let _ = content.parse::<Option<Token![,]>>()?;
+            } else if content.peek(kw::coalesce) {
+                parse_coalesce_spec(&content, &mut policy.coalesce)?;
+                let _ = content.parse::<Option<Token![,]>>()?;
+            } else if content.peek(kw::hedge) {
+                parse_hedge_spec(&content, &mut policy.hedge)?;
+                let _ = content.parse::<Option<Token![,]>>()?;
             } else if content.peek(kw::profile) {
                 profile_uses.push(parse_profile_use_spec(&content)?);
                 let _ = content.parse::<Option<Token![,]>>()?;
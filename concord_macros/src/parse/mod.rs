@@ -445,6 +445,18 @@ fn parse_client_default_block(
                 input.parse::<Token![:]>()?;
             }
             policy.timeout = Some(input.parse::<Expr>()?);
+        } else if input.peek(kw::on_error) {
+            policy
+                .error_responses
+                .push(parse_error_response_spec(input)?);
+        } else if input.peek(kw::cache) {
+            parse_cache_spec(input, &mut policy.cache)?;
+        } else if input.peek(kw::idempotency_key) {
+            parse_idempotency_key_spec(input, &mut policy.idempotency_key)?;
+        } else if input.peek(kw::coalesce) {
+            parse_coalesce_spec(input, &mut policy.coalesce)?;
+        } else if input.peek(kw::hedge) {
+            parse_hedge_spec(input, &mut policy.hedge)?;
         } else if input.peek(kw::auth) {
             input.parse::<kw::auth>()?;
             auth_uses.push(parse_auth_use_decl_after_auth_keyword(input)?);
@@ -408,6 +408,123 @@ fn parse_fmt_spec(input: ParseStream<'_>) -> Result<FmtSpec> {
     })
 }
 
+/// `cache max_age 5m [shared]` or `cache off`.
+fn parse_cache_spec(input: ParseStream<'_>, slot: &mut Option<CacheSpec>) -> Result<()> {
+    let cache: kw::cache = input.parse()?;
+    if slot.is_some() {
+        return Err(syn::Error::new(cache.span, "duplicate cache policy"));
+    }
+    if input.peek(kw::off) {
+        input.parse::<kw::off>()?;
+        *slot = Some(CacheSpec::Off);
+        return Ok(());
+    }
+    if !input.peek(kw::max_age) {
+        return Err(syn::Error::new(
+            input.span(),
+            "expected `cache max_age 5m` or `cache off`",
+        ));
+    }
+    input.parse::<kw::max_age>()?;
+    let lit: LitInt = input.parse()?;
+    let unit = match lit.suffix() {
+        "s" => CacheDurationUnit::Seconds,
+        "m" => CacheDurationUnit::Minutes,
+        "h" => CacheDurationUnit::Hours,
+        _ => {
+            return Err(syn::Error::new(
+                lit.span(),
+                "cache max_age must use `s`, `m`, or `h`, e.g. `cache max_age 5m`",
+            ));
+        }
+    };
+    let shared = input.peek(kw::shared);
+    if shared {
+        input.parse::<kw::shared>()?;
+    }
+    *slot = Some(CacheSpec::MaxAge { lit, unit, shared });
+    Ok(())
+}
+
+/// `idempotency_key header "Idempotency-Key"` or `idempotency_key off`.
+fn parse_idempotency_key_spec(
+    input: ParseStream<'_>,
+    slot: &mut Option<IdempotencyKeySpec>,
+) -> Result<()> {
+    let kw: kw::idempotency_key = input.parse()?;
+    if slot.is_some() {
+        return Err(syn::Error::new(kw.span, "duplicate idempotency_key policy"));
+    }
+    if input.peek(kw::off) {
+        input.parse::<kw::off>()?;
+        *slot = Some(IdempotencyKeySpec::Off);
+        return Ok(());
+    }
+    if !input.peek(kw::header) || !input.peek2(LitStr) {
+        return Err(syn::Error::new(
+            input.span(),
+            "expected `idempotency_key header \"Idempotency-Key\"` or `idempotency_key off`",
+        ));
+    }
+    input.parse::<kw::header>()?;
+    *slot = Some(IdempotencyKeySpec::Header(input.parse()?));
+    Ok(())
+}
+
+/// `coalesce` or `coalesce off`.
+fn parse_coalesce_spec(input: ParseStream<'_>, slot: &mut Option<CoalesceSpec>) -> Result<()> {
+    let kw: kw::coalesce = input.parse()?;
+    if slot.is_some() {
+        return Err(syn::Error::new(kw.span, "duplicate coalesce policy"));
+    }
+    let enabled = !input.peek(kw::off);
+    if !enabled {
+        input.parse::<kw::off>()?;
+    }
+    *slot = Some(CoalesceSpec {
+        span: kw.span,
+        enabled,
+    });
+    Ok(())
+}
+
+/// `hedge after 50ms` or `hedge off`.
+fn parse_hedge_spec(input: ParseStream<'_>, slot: &mut Option<HedgeSpec>) -> Result<()> {
+    let kw: kw::hedge = input.parse()?;
+    if slot.is_some() {
+        return Err(syn::Error::new(kw.span, "duplicate hedge policy"));
+    }
+    if input.peek(kw::off) {
+        input.parse::<kw::off>()?;
+        *slot = Some(HedgeSpec::Off);
+        return Ok(());
+    }
+    if !input.peek(kw::after) {
+        return Err(syn::Error::new(
+            input.span(),
+            "expected `hedge after 50ms` or `hedge off`",
+        ));
+    }
+    input.parse::<kw::after>()?;
+    let lit: LitInt = input.parse()?;
+    let unit = match lit.suffix() {
+        "ms" => HedgeDelayUnit::Millis,
+        "s" => HedgeDelayUnit::Seconds,
+        _ => {
+            return Err(syn::Error::new(
+                lit.span(),
+                "hedge delay must use `ms` or `s`, e.g. `hedge after 50ms`",
+            ));
+        }
+    };
+    *slot = Some(HedgeSpec::After {
+        span: kw.span,
+        lit,
+        unit,
+    });
+    Ok(())
+}
+
 fn parse_policy_value(input: syn::parse::ParseStream<'_>) -> Result<PolicyValue> {
     if input.peek(kw::fmt) {
         return Ok(PolicyValue::Fmt(parse_fmt_spec(input)?));
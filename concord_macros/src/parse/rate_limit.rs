@@ -125,6 +125,7 @@ fn parse_rate_limit_bucket(input: ParseStream<'_>) -> Result<RateLimitBucketSpec
     braced!(content in input);
 
     let mut cost = None;
+    let mut concurrency = None;
     let mut windows = Vec::new();
     while !content.is_empty() {
         if content.peek(kw::cost) {
@@ -136,6 +137,15 @@ fn parse_rate_limit_bucket(input: ParseStream<'_>) -> Result<RateLimitBucketSpec
             }
             content.parse::<kw::cost>()?;
             cost = Some(content.parse::<LitInt>()?);
+        } else if content.peek(kw::concurrent) {
+            if concurrency.is_some() {
+                return Err(syn::Error::new(
+                    content.span(),
+                    "duplicate rate_limit bucket concurrent limit",
+                ));
+            }
+            content.parse::<kw::concurrent>()?;
+            concurrency = Some(content.parse::<LitInt>()?);
         } else if content.peek(LitInt) {
             let max: LitInt = content.parse()?;
             content.parse::<Token![/]>()?;
@@ -156,7 +166,7 @@ fn parse_rate_limit_bucket(input: ParseStream<'_>) -> Result<RateLimitBucketSpec
             let tt: TokenTree = content.parse()?;
             return Err(syn::Error::new(
                 tt.span(),
-                "unexpected token in rate_limit bucket; expected `cost` or `500 / 10s` shorthand",
+                "unexpected token in rate_limit bucket; expected `cost`, `concurrent`, or `500 / 10s` shorthand",
             ));
         }
         let _ = content.parse::<Option<Token![,]>>()?;
@@ -166,6 +176,7 @@ fn parse_rate_limit_bucket(input: ParseStream<'_>) -> Result<RateLimitBucketSpec
         kind,
         key,
         cost,
+        concurrency,
         windows,
     })
 }
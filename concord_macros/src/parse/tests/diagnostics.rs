@@ -273,3 +273,133 @@ fn nested_scope_depth_over_limit_fails_closed_without_panic() {
     );
     assert!(!err.to_string().contains("LEAK_SENTINEL_DEPTH_PING"));
 }
+
+#[test]
+fn malformed_cache_clauses_fail() {
+    for (source, expected) in [
+        (
+            r#"
+            api! {
+                client Api { base "https://example.com" }
+                GET Ping cache max_age 10ms -> Json<()>;
+            }
+            "#,
+            "`s`, `m`, or `h`",
+        ),
+        (
+            r#"
+            api! {
+                client Api { base "https://example.com" }
+                GET Ping cache forever -> Json<()>;
+            }
+            "#,
+            "expected `cache max_age 5m` or `cache off`",
+        ),
+        (
+            r#"
+            api! {
+                client Api { base "https://example.com" }
+                GET Ping cache off cache max_age 1m -> Json<()>;
+            }
+            "#,
+            "duplicate cache policy",
+        ),
+    ] {
+        let err = parse_err(source);
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[test]
+fn malformed_idempotency_key_clauses_fail() {
+    for (source, expected) in [
+        (
+            r#"
+            api! {
+                client Api { base "https://example.com" }
+                POST Charge idempotency_key "Idempotency-Key" -> Json<()>;
+            }
+            "#,
+            "expected `idempotency_key header",
+        ),
+        (
+            r#"
+            api! {
+                client Api { base "https://example.com" }
+                POST Charge idempotency_key off idempotency_key header "Idempotency-Key" -> Json<()>;
+            }
+            "#,
+            "duplicate idempotency_key policy",
+        ),
+    ] {
+        let err = parse_err(source);
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
+
+#[test]
+fn duplicate_coalesce_clauses_fail() {
+    let err = parse_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            GET Item coalesce coalesce off -> Json<()>;
+        }
+        "#,
+    );
+    assert!(
+        err.to_string().contains("duplicate coalesce policy"),
+        "{err}"
+    );
+}
+
+#[test]
+fn hedge_clause_requires_a_millisecond_or_second_delay() {
+    let err = parse_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            GET Item hedge after 5m -> Json<()>;
+        }
+        "#,
+    );
+    assert!(
+        err.to_string().contains("hedge delay must use `ms` or `s`"),
+        "{err}"
+    );
+
+    let err = parse_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            GET Item hedge after 50ms hedge off -> Json<()>;
+        }
+        "#,
+    );
+    assert!(err.to_string().contains("duplicate hedge policy"), "{err}");
+}
+
+#[test]
+fn rate_limit_bucket_rejects_duplicate_concurrent_limit() {
+    let err = parse_err(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+
+                rate_limit exports {
+                    bucket method by [endpoint] {
+                        concurrent 4
+                        concurrent 2
+                    }
+                }
+            }
+        }
+        "#,
+    );
+
+    assert!(
+        err.to_string()
+            .contains("duplicate rate_limit bucket concurrent limit")
+    );
+}
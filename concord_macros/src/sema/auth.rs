@@ -58,6 +58,27 @@ pub(super) fn analyze_auth_credentials(
                     scope: scope.clone(),
                 }
             }
+            AuthCredentialKind::OAuth2RefreshToken {
+                token_url,
+                client_id,
+                client_secret,
+                refresh_token,
+                scope,
+            } => {
+                validate_oauth2_token_url(token_url)?;
+                validate_required_secret(client_id, auth_vars)?;
+                if let Some(client_secret) = client_secret {
+                    validate_required_secret(client_secret, auth_vars)?;
+                }
+                validate_known_secret(refresh_token, auth_vars)?;
+                AuthCredentialKindIr::OAuth2RefreshToken {
+                    token_url: token_url.clone(),
+                    client_id: client_id.ident.clone(),
+                    client_secret: client_secret.as_ref().map(|secret| secret.ident.clone()),
+                    refresh_token: refresh_token.ident.clone(),
+                    scope: scope.clone(),
+                }
+            }
             AuthCredentialKind::Endpoint { endpoint } => {
                 let target = endpoint_target_from_path(endpoint)?;
                 let output_ty = endpoint_outputs.get(&target.key()).ok_or_else(|| {
@@ -125,16 +146,7 @@ pub(super) fn validate_required_secret(
     secret: &SecretRef,
     auth_vars: &BTreeMap<String, VarInfo>,
 ) -> Result<()> {
-    let Some(info) = auth_vars.get(&secret.ident.to_string()) else {
-        return Err(syn::Error::new(
-            secret.ident.span(),
-            format!(
-                "unknown secret `secret.{}` in auth credential",
-                secret.ident
-            ),
-        ));
-    };
-    if info.optional {
+    if validate_known_secret(secret, auth_vars)? {
         return Err(syn::Error::new(
             secret.ident.span(),
             format!(
@@ -146,6 +158,26 @@ pub(super) fn validate_required_secret(
     Ok(())
 }
 
+/// Returns whether the referenced secret is optional. An optional refresh
+/// token starts the credential in the missing state.
+fn validate_known_secret(
+    secret: &SecretRef,
+    auth_vars: &BTreeMap<String, VarInfo>,
+) -> Result<bool> {
+    auth_vars
+        .get(&secret.ident.to_string())
+        .map(|info| info.optional)
+        .ok_or_else(|| {
+            syn::Error::new(
+                secret.ident.span(),
+                format!(
+                    "unknown secret `secret.{}` in auth credential",
+                    secret.ident
+                ),
+            )
+        })
+}
+
 pub(super) fn resolve_auth_requirements(
     uses: &[NormAuthUse],
     credentials: &BTreeMap<String, AuthCredentialIr>,
@@ -376,7 +408,8 @@ pub(super) fn validate_auth_usage_fit(u: &AuthUseKind, cred: &AuthCredentialIr)
     let shape = match &cred.kind {
         AuthCredentialKindIr::ApiKey { .. } => AuthMaterialShapeIr::SecretValue,
         AuthCredentialKindIr::StaticBearer { .. }
-        | AuthCredentialKindIr::OAuth2ClientCredentials { .. } => AuthMaterialShapeIr::AccessToken,
+        | AuthCredentialKindIr::OAuth2ClientCredentials { .. }
+        | AuthCredentialKindIr::OAuth2RefreshToken { .. } => AuthMaterialShapeIr::AccessToken,
         AuthCredentialKindIr::Basic { .. } => AuthMaterialShapeIr::Basic,
         AuthCredentialKindIr::Endpoint { material_shape, .. } => *material_shape,
     };
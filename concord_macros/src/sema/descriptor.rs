@@ -266,5 +266,8 @@ fn response_format_descriptor(response: &ResolvedResponseBodyIo) -> ResponseForm
         ResolvedResponseBodyIo::RawStream { media_ty } => ResponseFormatDescriptorIr::Streaming {
             media: quote::quote!(#media_ty).to_string(),
         },
+        ResolvedResponseBodyIo::JsonLines { value_ty } => ResponseFormatDescriptorIr::Streaming {
+            media: format!("JsonLines<{}>", quote::quote!(#value_ty)),
+        },
     }
 }
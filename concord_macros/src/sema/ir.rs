@@ -63,6 +63,7 @@ pub struct LayerIr {
     pub policy: PolicyBlocksResolved,
     pub auth: Vec<AuthUsePlanIr>,
     pub rate_limit_key_bindings: Vec<RateLimitKeyBindingResolved>,
+    pub error_responses: Vec<ErrorResponseIr>,
     pub profile_names: Vec<String>,
     pub decls: Vec<VarInfo>, // endpoint vars declared by this layer
 }
@@ -93,6 +94,9 @@ pub struct ResolvedEndpoint {
     /// semantic metadata consumed by facade documentation construction.
     pub query_cardinalities: std::collections::BTreeMap<String, QueryValueCardinality>,
     pub io: ResolvedHttpEndpointIo,
+    /// Effective `on_error` mappings after client, scope, and endpoint
+    /// inheritance. Inner sites replace outer mappings for the same status.
+    pub error_responses: Vec<ErrorResponseIr>,
 
     pub policy: ResolvedPolicySpec,
     pub profile_doc: ProfileDocMeta,
@@ -138,6 +142,10 @@ pub enum ResponseFormatDescriptorIr {
 pub struct ResolvedHttpEndpointIo {
     pub request_entity: RequestEntityPlanIr,
     pub response_entity: ResponseEntityPlanIr,
+    /// Body classifications kept for the OpenAPI export, which needs codec
+    /// markers and value types rather than runtime adapters.
+    pub request_body: ResolvedRequestBodyIo,
+    pub response_body: ResolvedResponseBodyIo,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -177,6 +185,14 @@ pub struct ResponseEntityPlanIr {
     pub capabilities: ResponseIoCapabilities,
 }
 
+/// One typed error-response mapping. `status: None` is the fallback used for
+/// error statuses without an exact mapping.
+#[derive(Debug, Clone)]
+pub struct ErrorResponseIr {
+    pub status: Option<u16>,
+    pub codec: BufferedCodecIo,
+}
+
 #[derive(Debug, Clone)]
 pub struct BufferedCodecIo {
     pub marker: Type,
@@ -204,6 +220,7 @@ pub enum ResolvedResponseBodyIo {
     BufferedBytes,
     NoContent,
     RawStream { media_ty: Type },
+    JsonLines { value_ty: Type },
 }
 
 #[derive(Debug)]
@@ -285,6 +302,13 @@ pub enum AuthCredentialKindIr {
         client_secret: Ident,
         scope: Option<LitStr>,
     },
+    OAuth2RefreshToken {
+        token_url: LitStr,
+        client_id: Ident,
+        client_secret: Option<Ident>,
+        refresh_token: Ident,
+        scope: Option<LitStr>,
+    },
     Endpoint {
         target: EndpointTargetIr,
         output_ty: Type,
@@ -384,6 +408,29 @@ pub struct PolicyBlocksResolved {
     pub query: Vec<PolicyOp>,
     pub timeout: Option<PublicValueKind>,
     pub rate_limit: Option<RateLimitResolved>,
+    pub cache: Option<CacheResolved>,
+    pub idempotency_key: Option<IdempotencyKeyResolved>,
+    pub coalesce: Option<bool>,
+    pub hedge: Option<HedgeResolved>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheResolved {
+    MaxAge { secs: u64, shared: bool },
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HedgeResolved {
+    AfterMillis(u64),
+    Off,
+}
+
+/// Lower-cased header name, validated at expansion time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyKeyResolved {
+    Header(String),
+    Off,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -412,6 +459,7 @@ pub struct RateLimitBucketResolved {
     pub name: String,
     pub key: Vec<RateLimitKeyResolved>,
     pub cost: u32,
+    pub concurrency: Option<u32>,
     pub windows: Vec<RateLimitWindowResolved>,
 }
 
@@ -433,6 +481,7 @@ pub(crate) struct RateLimitBucketTemplate {
     pub name: String,
     pub key: Vec<RateLimitKeyTemplate>,
     pub cost: u32,
+    pub concurrency: Option<u32>,
     pub windows: Vec<RateLimitWindowResolved>,
 }
 
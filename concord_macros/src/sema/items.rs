@@ -8,6 +8,7 @@ pub(super) struct WalkItemsCtx<'a> {
     pub(super) auth_credentials: &'a BTreeMap<String, AuthCredentialIr>,
     pub(super) client_auth: &'a [AuthUsePlanIr],
     pub(super) client_default_profile_names: &'a [String],
+    pub(super) client_error_responses: &'a [ErrorResponseIr],
     pub(super) rate_limit_profiles: &'a BTreeMap<String, RateLimitPlanTemplate>,
     pub(super) profiles: &'a BTreeMap<String, ProfileResolved>,
     pub(super) layers: &'a mut Vec<LayerIr>,
@@ -22,6 +23,7 @@ pub(super) struct EndpointAnalysisCtx<'a> {
     auth_credentials: &'a BTreeMap<String, AuthCredentialIr>,
     client_auth: &'a [AuthUsePlanIr],
     client_default_profile_names: &'a [String],
+    client_error_responses: &'a [ErrorResponseIr],
     rate_limit_profiles: &'a BTreeMap<String, RateLimitPlanTemplate>,
     profiles: &'a BTreeMap<String, ProfileResolved>,
     layers: &'a [LayerIr],
@@ -105,6 +107,7 @@ pub(super) fn walk_items(
                     ctx.auth_credentials,
                     AuthUseProvenanceIr::Scope(id),
                 )?;
+                let error_responses = resolve_error_responses(&ld.policy.error_responses)?;
 
                 ctx.layers.push(LayerIr {
                     scope_name: ld.scope_name.clone(),
@@ -114,6 +117,7 @@ pub(super) fn walk_items(
                     policy,
                     auth,
                     rate_limit_key_bindings: key_bindings,
+                    error_responses,
                     profile_names,
                     decls,
                 });
@@ -131,6 +135,7 @@ pub(super) fn walk_items(
                     auth_credentials: ctx.auth_credentials,
                     client_auth: ctx.client_auth,
                     client_default_profile_names: ctx.client_default_profile_names,
+                    client_error_responses: ctx.client_error_responses,
                     rate_limit_profiles: ctx.rate_limit_profiles,
                     profiles: ctx.profiles,
                     layers: ctx.layers.as_slice(),
@@ -363,6 +368,19 @@ pub(super) fn response_entity_plan_ir(
                 is_no_content: false,
             },
         },
+        ResolvedResponseBodyIo::JsonLines { value_ty } => ResponseEntityPlanIr {
+            adapter_ty: syn::parse_quote!(::concord_core::__private::GeneratedJsonLinesResponse<#value_ty>),
+            public_output_ty: response_public_output_ty(response_io),
+            doc: IoDocIr {
+                summary: "Newline-delimited JSON response body.".to_string(),
+                facade_summary: Some(format!("Response: JsonLines<{}>", quote::quote!(#value_ty))),
+            },
+            capabilities: ResponseIoCapabilities {
+                supports_pagination: false,
+                is_streaming: true,
+                is_no_content: false,
+            },
+        },
     }
 }
 
@@ -374,6 +392,9 @@ pub(super) fn response_public_output_ty(response_io: &ResolvedResponseBodyIo) ->
         ResolvedResponseBodyIo::RawStream { media_ty } => {
             syn::parse_quote!(::concord_core::advanced::StreamResponse<#media_ty>)
         }
+        ResolvedResponseBodyIo::JsonLines { value_ty } => {
+            syn::parse_quote!(::concord_core::advanced::JsonLinesStream<#value_ty>)
+        }
     }
 }
 
@@ -744,6 +765,24 @@ pub(super) fn analyze_endpoint(
             "unsupported endpoint method",
         ));
     }
+    if let Some(coalesce) = &ed.policy.coalesce
+        && coalesce.enabled
+        && !matches!(method_name.as_str(), "GET" | "HEAD")
+    {
+        return Err(syn::Error::new(
+            coalesce.span,
+            "coalesce applies only to GET and HEAD endpoints",
+        ));
+    }
+
+    if let Some(HedgeSpec::After { span, .. }) = &ed.policy.hedge
+        && method_name != "GET"
+    {
+        return Err(syn::Error::new(
+            *span,
+            "hedge applies only to GET endpoints",
+        ));
+    }
 
     let request_io = classify_request_io(ed.body.as_ref())?;
     let request_entity = request_entity_plan_ir(&request_io);
@@ -762,6 +801,15 @@ pub(super) fn analyze_endpoint(
         ));
     }
 
+    let mut error_responses = ctx.client_error_responses.to_vec();
+    for &lid in ancestry {
+        merge_error_responses(&mut error_responses, &ctx.layers[lid].error_responses);
+    }
+    merge_error_responses(
+        &mut error_responses,
+        &resolve_error_responses(&ed.policy.error_responses)?,
+    );
+
     // 4) Resolve paginate, if any.
     if request_entity.capabilities.has_body && ed.paginate.is_some() {
         return Err(syn::Error::new(
@@ -813,7 +861,10 @@ pub(super) fn analyze_endpoint(
         io: ResolvedHttpEndpointIo {
             request_entity,
             response_entity,
+            request_body: request_io,
+            response_body: response_io,
         },
+        error_responses,
 
         policy: ResolvedPolicySpec {
             scopes: scope_policies,
@@ -878,6 +929,12 @@ pub(super) fn classify_request_io(spec: Option<&RawIoSpec>) -> Result<ResolvedRe
         EndpointIoClassification::Multipart { value_ty } => {
             ResolvedRequestBodyIo::Multipart { value_ty }
         }
+        EndpointIoClassification::JsonLines { .. } => {
+            return Err(syn::Error::new_spanned(
+                spec.marker.clone(),
+                "`JsonLines` is only valid as an endpoint response",
+            ));
+        }
     })
 }
 
@@ -890,6 +947,9 @@ pub(super) fn classify_http_response_io(spec: &RawResponseIo) -> Result<Resolved
         EndpointIoClassification::RawStream { media_ty } => {
             ResolvedResponseBodyIo::RawStream { media_ty }
         }
+        EndpointIoClassification::JsonLines { value_ty } => {
+            ResolvedResponseBodyIo::JsonLines { value_ty }
+        }
         EndpointIoClassification::Multipart { .. } => {
             return Err(syn::Error::new_spanned(
                 spec.marker.clone(),
@@ -899,12 +959,60 @@ pub(super) fn classify_http_response_io(spec: &RawResponseIo) -> Result<Resolved
     })
 }
 
+pub(super) fn resolve_error_responses(specs: &[ErrorResponseSpec]) -> Result<Vec<ErrorResponseIr>> {
+    let mut out: Vec<ErrorResponseIr> = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let status = match &spec.status {
+            None => None,
+            Some(lit) => {
+                let status = lit
+                    .base10_parse::<u16>()
+                    .ok()
+                    .filter(|s| (400..=599).contains(s));
+                let Some(status) = status else {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "`on_error` status must be a 4xx or 5xx status code",
+                    ));
+                };
+                Some(status)
+            }
+        };
+        let codec = match classify_endpoint_io(&spec.io, EndpointIoPosition::Response)? {
+            EndpointIoClassification::BufferedCodec(io) => io,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    spec.io.marker.clone(),
+                    "`on_error` expects a buffered codec such as `Json<T>`",
+                ));
+            }
+        };
+        if out.iter().any(|existing| existing.status == status) {
+            let message = match status {
+                Some(status) => format!("duplicate `on_error` mapping for status {status}"),
+                None => "duplicate fallback `on_error` mapping".to_string(),
+            };
+            return Err(syn::Error::new(spec.span, message));
+        }
+        out.push(ErrorResponseIr { status, codec });
+    }
+    Ok(out)
+}
+
+pub(super) fn merge_error_responses(out: &mut Vec<ErrorResponseIr>, site: &[ErrorResponseIr]) {
+    for mapping in site {
+        out.retain(|existing| existing.status != mapping.status);
+        out.push(mapping.clone());
+    }
+}
+
 #[allow(clippy::large_enum_variant)]
 pub(super) enum EndpointIoClassification {
     BufferedCodec(BufferedCodecIo),
     BufferedBytes,
     NoContent,
     RawStream { media_ty: Type },
+    JsonLines { value_ty: Type },
     Multipart { value_ty: Type },
 }
 
@@ -953,6 +1061,35 @@ pub(super) fn classify_endpoint_io(
                 media_ty: spec.args[0].clone(),
             })
         }
+        "Sse" => {
+            if arg_count != 1 {
+                return Err(syn::Error::new_spanned(
+                    spec.marker.clone(),
+                    "reserved endpoint I/O family `Sse` expects exactly one type argument",
+                ));
+            }
+            if matches!(position, EndpointIoPosition::Request) {
+                return Err(syn::Error::new_spanned(
+                    spec.marker.clone(),
+                    "`Sse` is only valid as an endpoint response",
+                ));
+            }
+            let data_ty = &spec.args[0];
+            Ok(EndpointIoClassification::RawStream {
+                media_ty: syn::parse_quote!(::concord_core::advanced::EventStream<#data_ty>),
+            })
+        }
+        "JsonLines" => {
+            if arg_count != 1 {
+                return Err(syn::Error::new_spanned(
+                    spec.marker.clone(),
+                    "reserved endpoint I/O family `JsonLines` expects exactly one type argument",
+                ));
+            }
+            Ok(EndpointIoClassification::JsonLines {
+                value_ty: spec.args[0].clone(),
+            })
+        }
         "Multipart" => {
             if arg_count != 1 {
                 return Err(syn::Error::new_spanned(
@@ -6,11 +6,12 @@
 //! parser structures.
 
 use crate::ast::{
-    AuthCredentialKind, AuthCredentials, AuthUseKind, FmtPiece, FmtSpec, KeySpec, PaginateSpec,
-    PolicyBlock, PolicyBlocks, PolicyStmt, PolicyValue, ProfileDef, ProfileUseSpec, ProfilesBlock,
-    RateLimitDurationUnit, RateLimitKeyBindingSpec, RateLimitKeySpec, RateLimitPlanSpec,
-    RateLimitProfilesBlock, RateLimitSpec, RawIoSpec, RawResponseIo, RefScope, RouteAtom,
-    SecretRef,
+    AuthCredentialKind, AuthCredentials, AuthUseKind, CacheDurationUnit, CacheSpec,
+    ErrorResponseSpec, FmtPiece, FmtSpec, HedgeDelayUnit, HedgeSpec, IdempotencyKeySpec, KeySpec,
+    PaginateSpec, PolicyBlock, PolicyBlocks, PolicyStmt, PolicyValue, ProfileDef, ProfileUseSpec,
+    ProfilesBlock, RateLimitDurationUnit, RateLimitKeyBindingSpec, RateLimitKeySpec,
+    RateLimitPlanSpec, RateLimitProfilesBlock, RateLimitSpec, RawIoSpec, RawResponseIo, RefScope,
+    RouteAtom, SecretRef,
 };
 use crate::emit_helpers;
 use crate::model::facade::{
@@ -162,6 +163,7 @@ fn resolve(norm: NormApiTree) -> Result<ResolvedApiPipeline> {
     )?;
     client_policy.rate_limit =
         merge_rate_limit_resolved(default_profile_rate_limit, explicit_default_rate_limit);
+    let client_error_responses = resolve_error_responses(&norm.client.policy.error_responses)?;
 
     // walk layers/endpoints
     let mut layers: Vec<LayerIr> = Vec::new();
@@ -176,6 +178,7 @@ fn resolve(norm: NormApiTree) -> Result<ResolvedApiPipeline> {
         auth_credentials: &auth_credential_map,
         client_auth: &client_auth,
         client_default_profile_names: &client_default_profile_names,
+        client_error_responses: &client_error_responses,
         rate_limit_profiles: &rate_limit_profiles,
         profiles: &profiles,
         layers: &mut layers,
@@ -201,7 +204,8 @@ fn resolve(norm: NormApiTree) -> Result<ResolvedApiPipeline> {
             .client
             .rate_limit
             .as_ref()
-            .and_then(|block| block.response_policy.clone()),
+            .and_then(|block| block.response_policy.as_ref())
+            .map(resolve_rate_limit_observer),
         descriptor,
         endpoints,
     };
@@ -286,6 +290,7 @@ fn validate_client_method_namespace(
         "with_api_headers",
         "request",
         "auth_state",
+        "openapi",
     ] {
         ns.reserve(name, "generated client method", api.client_name.span());
     }
@@ -344,6 +349,14 @@ fn validate_client_method_namespace(
             );
         }
     }
+    for method in &facade_ir.authorize_methods {
+        ns.add(
+            errors,
+            method.authorize_name.to_string(),
+            method.authorize_name.span(),
+            "oauth2 authorization-code helper",
+        );
+    }
 
     for scope in facade_ir
         .scopes
@@ -197,10 +197,95 @@ pub(super) fn resolve_policy_blocks(
         )?;
         out.timeout = Some(public_value_from_value_kind(timeout, lowered.span())?);
     }
+    if let Some(cache) = &policy.cache {
+        out.cache = Some(resolve_cache_spec(cache)?);
+    }
+    if let Some(idempotency_key) = &policy.idempotency_key {
+        out.idempotency_key = Some(resolve_idempotency_key_spec(idempotency_key)?);
+    }
+    out.coalesce = policy.coalesce.as_ref().map(|spec| spec.enabled);
+    if let Some(hedge) = &policy.hedge {
+        out.hedge = Some(resolve_hedge_spec(hedge)?);
+    }
 
     Ok(out)
 }
 
+fn resolve_cache_spec(spec: &CacheSpec) -> Result<CacheResolved> {
+    let (lit, unit, shared) = match spec {
+        CacheSpec::Off => return Ok(CacheResolved::Off),
+        CacheSpec::MaxAge { lit, unit, shared } => (lit, unit, *shared),
+    };
+    let amount = lit.base10_parse::<u64>()?;
+    if amount == 0 {
+        return Err(syn::Error::new(
+            lit.span(),
+            "cache max_age must be greater than zero; use `cache off` to disable caching",
+        ));
+    }
+    let multiplier = match unit {
+        CacheDurationUnit::Seconds => 1,
+        CacheDurationUnit::Minutes => 60,
+        CacheDurationUnit::Hours => 60 * 60,
+    };
+    amount
+        .checked_mul(multiplier)
+        .map(|secs| CacheResolved::MaxAge { secs, shared })
+        .ok_or_else(|| syn::Error::new(lit.span(), "cache max_age is too large"))
+}
+
+fn resolve_hedge_spec(spec: &HedgeSpec) -> Result<HedgeResolved> {
+    let (lit, unit) = match spec {
+        HedgeSpec::Off => return Ok(HedgeResolved::Off),
+        HedgeSpec::After { lit, unit, .. } => (lit, unit),
+    };
+    let amount = lit.base10_parse::<u64>()?;
+    if amount == 0 {
+        return Err(syn::Error::new(
+            lit.span(),
+            "hedge delay must be greater than zero; use `hedge off` to disable hedging",
+        ));
+    }
+    let multiplier = match unit {
+        HedgeDelayUnit::Millis => 1,
+        HedgeDelayUnit::Seconds => 1000,
+    };
+    amount
+        .checked_mul(multiplier)
+        .map(HedgeResolved::AfterMillis)
+        .ok_or_else(|| syn::Error::new(lit.span(), "hedge delay is too large"))
+}
+
+fn resolve_idempotency_key_spec(spec: &IdempotencyKeySpec) -> Result<IdempotencyKeyResolved> {
+    let lit = match spec {
+        IdempotencyKeySpec::Off => return Ok(IdempotencyKeyResolved::Off),
+        IdempotencyKeySpec::Header(lit) => lit,
+    };
+    let name = http::HeaderName::from_bytes(lit.value().as_bytes()).map_err(|_| {
+        syn::Error::new(
+            lit.span(),
+            "idempotency_key header is not a valid header name",
+        )
+    })?;
+    if matches!(
+        name.as_str(),
+        "authorization"
+            | "proxy-authorization"
+            | "cookie"
+            | "host"
+            | "content-type"
+            | "content-length"
+            | "transfer-encoding"
+            | "user-agent"
+    ) {
+        return Err(syn::Error::new(
+            lit.span(),
+            "idempotency_key header is owned by authentication or the HTTP transport",
+        ));
+    }
+    Ok(IdempotencyKeyResolved::Header(name.as_str().to_string()))
+}
+
 pub(super) fn resolve_policy_block(
     blk: &PolicyBlock,
     kind: PolicyKeyKind,
@@ -24,6 +24,23 @@ pub(super) fn resolve_rate_limit_profiles(
     )
 }
 
+/// Built-in header observers are named by a bare lower-case identifier;
+/// any other path names a user observer type.
+pub(super) fn resolve_rate_limit_observer(path: &syn::Path) -> syn::Path {
+    let builtin = path.get_ident().and_then(|ident| {
+        Some(match ident.to_string().as_str() {
+            "ietf_headers" => quote::format_ident!("IetfRateLimitHeaders", span = ident.span()),
+            "x_ratelimit_headers" => quote::format_ident!("XRateLimitHeaders", span = ident.span()),
+            "riot_headers" => quote::format_ident!("RiotRateLimitHeaders", span = ident.span()),
+            _ => return None,
+        })
+    });
+    match builtin {
+        Some(ty) => syn::parse_quote!(::concord_core::advanced::#ty),
+        None => path.clone(),
+    }
+}
+
 pub(super) fn resolve_client_rate_limit(
     block: Option<&RateLimitProfilesBlock>,
     profiles: &BTreeMap<String, RateLimitPlanTemplate>,
@@ -105,12 +122,26 @@ pub(super) fn resolve_rate_limit_plan_spec(
     const NANOS_PER_SECOND: u128 = 1_000_000_000;
     let mut out = RateLimitPlanTemplate::default();
     for (idx, bucket) in plan.buckets.iter().enumerate() {
-        if bucket.windows.is_empty() {
+        if bucket.windows.is_empty() && bucket.concurrency.is_none() {
             return Err(syn::Error::new(
                 bucket.kind.span(),
-                "rate_limit bucket must contain at least one `limit`",
+                "rate_limit bucket must contain at least one `limit` or a `concurrent` limit",
             ));
         }
+        let concurrency = bucket
+            .concurrency
+            .as_ref()
+            .map(|lit| {
+                let limit = lit.base10_parse::<u32>()?;
+                if limit == 0 {
+                    return Err(syn::Error::new(
+                        lit.span(),
+                        "rate_limit concurrent limit must be greater than zero",
+                    ));
+                }
+                Ok(limit)
+            })
+            .transpose()?;
         let cost = if let Some(cost_lit) = &bucket.cost {
             let cost = cost_lit.base10_parse::<u32>()?;
             if cost == 0 {
@@ -173,6 +204,7 @@ pub(super) fn resolve_rate_limit_plan_spec(
             name: format!("{default_bucket_name}_{idx}"),
             key: bucket.key.iter().map(resolve_rate_limit_key_spec).collect(),
             cost,
+            concurrency,
             windows,
         });
     }
@@ -227,6 +259,7 @@ pub(super) fn materialize_rate_limit_plan(
                 .map(|key| materialize_rate_limit_key(key, visible_keys, endpoint_vars, ctx))
                 .collect::<Result<Vec<_>>>()?,
             cost: bucket.cost,
+            concurrency: bucket.concurrency,
             windows: bucket.windows,
         });
     }
@@ -130,6 +130,68 @@ fn auth_credentials_reject_optional_secret_material() {
     );
 }
 
+#[test]
+fn auth_credentials_oauth2_refresh_accepts_optional_refresh_token() {
+    let api = analyze_ok(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+                secret client_id: String
+                secret refresh_token?: String
+                credential session = oauth2_refresh {
+                    token_url: "https://auth.example.com/oauth/token",
+                    client_id: secret.client_id,
+                    refresh_token: secret.refresh_token,
+                }
+            }
+        }
+        "#,
+    );
+
+    match &credential_by_name(&api, "session").kind {
+        AuthCredentialKindIr::OAuth2RefreshToken {
+            client_id,
+            client_secret,
+            refresh_token,
+            scope,
+            ..
+        } => {
+            assert_eq!(client_id.to_string(), "client_id");
+            assert!(client_secret.is_none());
+            assert_eq!(refresh_token.to_string(), "refresh_token");
+            assert!(scope.is_none());
+        }
+        other => panic!("expected oauth2 refresh credential, got {other:?}"),
+    }
+}
+
+#[test]
+fn auth_credentials_oauth2_refresh_rejects_optional_client_secret() {
+    let err = analyze_err(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+                secret client_id: String
+                secret client_secret?: String
+                secret refresh_token: String
+                credential session = oauth2_refresh {
+                    token_url: "https://auth.example.com/oauth/token",
+                    client_id: secret.client_id,
+                    client_secret: secret.client_secret,
+                    refresh_token: secret.refresh_token,
+                }
+            }
+        }
+        "#,
+    );
+    assert_error_contains(
+        &err,
+        "auth credential secret `secret.client_secret` must be required",
+    );
+}
+
 #[test]
 fn auth_credentials_reject_unsafe_oauth2_token_urls() {
     for (token_url, expected) in [
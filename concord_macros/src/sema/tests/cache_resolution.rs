@@ -0,0 +1,63 @@
+use super::helpers::{analyze_err, analyze_ok, client_policy, endpoint_policy, scope_policy};
+use crate::sema::CacheResolved;
+
+#[test]
+fn cache_resolution_lowers_client_scope_endpoint_clauses() {
+    let api = analyze_ok(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+                default {
+                    cache max_age 30s
+                }
+            }
+
+            scope versions {
+                path ["versions"]
+                cache max_age 2h
+
+                GET Latest path ["latest"] cache off -> Json<String>;
+                GET All cache max_age 5m shared -> Json<Vec<String>>;
+            }
+        }
+        "#,
+    );
+
+    assert_eq!(
+        client_policy(&api).cache,
+        Some(CacheResolved::MaxAge {
+            secs: 30,
+            shared: false
+        })
+    );
+    let latest = endpoint_policy(&api, "Latest");
+    assert_eq!(
+        scope_policy(latest, 0).cache,
+        Some(CacheResolved::MaxAge {
+            secs: 2 * 60 * 60,
+            shared: false
+        })
+    );
+    assert_eq!(latest.endpoint.cache, Some(CacheResolved::Off));
+    assert_eq!(
+        endpoint_policy(&api, "All").endpoint.cache,
+        Some(CacheResolved::MaxAge {
+            secs: 5 * 60,
+            shared: true
+        })
+    );
+}
+
+#[test]
+fn cache_max_age_rejects_zero() {
+    let zero = analyze_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            GET Ping cache max_age 0s -> Json<()>;
+        }
+        "#,
+    );
+    assert!(zero.to_string().contains("use `cache off`"), "{zero}");
+}
@@ -0,0 +1,48 @@
+use super::helpers::{analyze_err, analyze_ok, client_policy, endpoint_policy, scope_policy};
+
+#[test]
+fn coalesce_lowers_client_scope_endpoint_clauses() {
+    let api = analyze_ok(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+                default {
+                    coalesce
+                }
+            }
+
+            scope catalog {
+                path ["catalog"]
+                coalesce off
+
+                GET Item path ["item"] coalesce -> Json<String>;
+                GET List -> Json<String>;
+            }
+        }
+        "#,
+    );
+
+    assert_eq!(client_policy(&api).coalesce, Some(true));
+    let item = endpoint_policy(&api, "Item");
+    assert_eq!(scope_policy(item, 0).coalesce, Some(false));
+    assert_eq!(item.endpoint.coalesce, Some(true));
+    assert_eq!(endpoint_policy(&api, "List").endpoint.coalesce, None);
+}
+
+#[test]
+fn coalesce_is_rejected_on_unsafe_endpoints() {
+    let err = analyze_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            POST Charge coalesce -> Json<()>;
+        }
+        "#,
+    );
+    assert!(
+        err.to_string()
+            .contains("coalesce applies only to GET and HEAD endpoints"),
+        "{err}"
+    );
+}
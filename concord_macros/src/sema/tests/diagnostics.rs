@@ -47,6 +47,25 @@ fn generated_public_name_collisions_are_rejected() {
     assert_error_contains(&err, "generated client method");
 }
 
+#[test]
+fn openapi_export_name_is_reserved_on_the_client() {
+    let err = analyze_err(
+        r#"
+        client Reserved {
+            base "https://example.com"
+        }
+
+        GET Spec
+            as openapi
+            path ["openapi.json"]
+            -> Json<String>
+        "#,
+    );
+
+    assert_error_contains(&err, "openapi");
+    assert_error_contains(&err, "generated client method");
+}
+
 #[test]
 fn synthetic_raw_scope_tree_hits_depth_limit_during_normalization() {
     let mut raw = super::helpers::parse_raw(
@@ -0,0 +1,114 @@
+use super::helpers::{analyze_err, analyze_ok, endpoint_by_name};
+
+fn mappings(api: &crate::sema::ResolvedApi, endpoint: &str) -> Vec<(Option<u16>, String)> {
+    endpoint_by_name(api, endpoint)
+        .error_responses
+        .iter()
+        .map(|mapping| {
+            let marker = &mapping.codec.marker;
+            (mapping.status, quote::quote!(#marker).to_string())
+        })
+        .collect()
+}
+
+#[test]
+fn error_responses_inherit_client_scope_endpoint_with_inner_override() {
+    let api = analyze_ok(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+                default {
+                    on_error Json<ApiError>
+                    on_error 429 Json<Throttled>
+                }
+            }
+
+            scope users {
+                path ["users"]
+                on_error 404 Json<UserMissing>
+
+                GET GetUser(id: u64) path [id] -> Json<User> on_error Json<UserError>;
+                GET ListUsers -> Json<Vec<User>>;
+            }
+
+            GET Ping -> Json<()>;
+        }
+        "#,
+    );
+
+    assert_eq!(
+        mappings(&api, "Ping"),
+        vec![
+            (None, "Json < ApiError >".to_string()),
+            (Some(429), "Json < Throttled >".to_string()),
+        ]
+    );
+    assert_eq!(
+        mappings(&api, "ListUsers"),
+        vec![
+            (None, "Json < ApiError >".to_string()),
+            (Some(429), "Json < Throttled >".to_string()),
+            (Some(404), "Json < UserMissing >".to_string()),
+        ]
+    );
+    assert_eq!(
+        mappings(&api, "GetUser"),
+        vec![
+            (Some(429), "Json < Throttled >".to_string()),
+            (Some(404), "Json < UserMissing >".to_string()),
+            (None, "Json < UserError >".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn error_responses_reject_non_error_status() {
+    let err = analyze_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            GET Ping -> Json<()> on_error 302 Json<Redirect>;
+        }
+        "#,
+    );
+    assert!(
+        err.to_string()
+            .contains("`on_error` status must be a 4xx or 5xx status code"),
+        "{err}"
+    );
+}
+
+#[test]
+fn error_responses_reject_unbuffered_families() {
+    let err = analyze_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            GET Ping -> Json<()> on_error Stream<OctetStream>;
+        }
+        "#,
+    );
+    assert!(
+        err.to_string()
+            .contains("`on_error` expects a buffered codec such as `Json<T>`"),
+        "{err}"
+    );
+}
+
+#[test]
+fn error_responses_reject_duplicate_mapping_at_one_site() {
+    let err = analyze_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            GET Ping on_error 404 Json<A> -> Json<()> on_error 404 Json<B>;
+        }
+        "#,
+    );
+    assert!(
+        err.to_string()
+            .contains("duplicate `on_error` mapping for status 404"),
+        "{err}"
+    );
+}
@@ -0,0 +1,66 @@
+use super::helpers::{analyze_err, analyze_ok, client_policy, endpoint_policy, scope_policy};
+use crate::sema::HedgeResolved;
+
+#[test]
+fn hedge_lowers_client_scope_endpoint_clauses() {
+    let api = analyze_ok(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+                default {
+                    hedge after 2s
+                }
+            }
+
+            scope catalog {
+                path ["catalog"]
+                hedge off
+
+                GET Item path ["item"] hedge after 50ms -> Json<String>;
+                GET List -> Json<String>;
+            }
+        }
+        "#,
+    );
+
+    assert_eq!(
+        client_policy(&api).hedge,
+        Some(HedgeResolved::AfterMillis(2000))
+    );
+    let item = endpoint_policy(&api, "Item");
+    assert_eq!(scope_policy(item, 0).hedge, Some(HedgeResolved::Off));
+    assert_eq!(item.endpoint.hedge, Some(HedgeResolved::AfterMillis(50)));
+    assert_eq!(endpoint_policy(&api, "List").endpoint.hedge, None);
+}
+
+#[test]
+fn hedge_rejects_zero_delay_and_non_get_endpoints() {
+    let err = analyze_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            GET Item hedge after 0ms -> Json<()>;
+        }
+        "#,
+    );
+    assert!(
+        err.to_string()
+            .contains("hedge delay must be greater than zero"),
+        "{err}"
+    );
+
+    let err = analyze_err(
+        r#"
+        api! {
+            client Api { base "https://example.com" }
+            DELETE Item hedge after 50ms -> Json<()>;
+        }
+        "#,
+    );
+    assert!(
+        err.to_string()
+            .contains("hedge applies only to GET endpoints"),
+        "{err}"
+    );
+}
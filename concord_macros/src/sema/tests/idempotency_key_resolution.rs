@@ -0,0 +1,74 @@
+use super::helpers::{analyze_err, analyze_ok, client_policy, endpoint_policy, scope_policy};
+use crate::sema::IdempotencyKeyResolved;
+
+#[test]
+fn idempotency_key_lowers_client_scope_endpoint_clauses() {
+    let api = analyze_ok(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+                default {
+                    idempotency_key header "Idempotency-Key"
+                }
+            }
+
+            scope payments {
+                path ["payments"]
+                idempotency_key header "X-Payment-Attempt"
+
+                POST Refund path ["refund"] idempotency_key off -> Json<String>;
+                POST Charge -> Json<String>;
+            }
+        }
+        "#,
+    );
+
+    assert_eq!(
+        client_policy(&api).idempotency_key,
+        Some(IdempotencyKeyResolved::Header(
+            "idempotency-key".to_string()
+        ))
+    );
+    let refund = endpoint_policy(&api, "Refund");
+    assert_eq!(
+        scope_policy(refund, 0).idempotency_key,
+        Some(IdempotencyKeyResolved::Header(
+            "x-payment-attempt".to_string()
+        ))
+    );
+    assert_eq!(
+        refund.endpoint.idempotency_key,
+        Some(IdempotencyKeyResolved::Off)
+    );
+    assert_eq!(
+        endpoint_policy(&api, "Charge").endpoint.idempotency_key,
+        None
+    );
+}
+
+#[test]
+fn idempotency_key_rejects_invalid_and_owned_headers() {
+    for (header, expected) in [
+        ("Idempotency Key", "not a valid header name"),
+        (
+            "Authorization",
+            "owned by authentication or the HTTP transport",
+        ),
+        (
+            "Content-Type",
+            "owned by authentication or the HTTP transport",
+        ),
+    ] {
+        let source = format!(
+            r#"
+            api! {{
+                client Api {{ base "https://example.com" }}
+                POST Charge idempotency_key header "{header}" -> Json<()>;
+            }}
+            "#
+        );
+        let err = analyze_err(&source);
+        assert!(err.to_string().contains(expected), "{err}");
+    }
+}
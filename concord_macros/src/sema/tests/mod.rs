@@ -3,9 +3,14 @@ mod auth_endpoint_backed;
 mod auth_inheritance;
 mod auth_materialization;
 mod auth_uses;
+mod cache_resolution;
+mod coalesce_resolution;
 mod descriptor_resolution;
 mod diagnostics;
+mod error_response_resolution;
+mod hedge_resolution;
 mod helpers;
+mod idempotency_key_resolution;
 mod normalize_routes;
 mod normalize_spans;
 mod normalize_tree;
@@ -149,3 +149,32 @@ fn rate_limit_diagnostics_reject_duplicate_rate_limit_profiles() {
 
     assert_error_contains(&err, "duplicate rate_limit profile `read`");
 }
+
+#[test]
+fn rate_limit_diagnostics_reject_zero_concurrent_limit() {
+    let err = analyze_err(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+
+                rate_limit exports {
+                    bucket method by [endpoint] {
+                        concurrent 0
+                    }
+                }
+            }
+
+            GET Export
+                path ["export"]
+                rate_limit exports
+                -> Json<()>
+        }
+        "#,
+    );
+
+    assert_error_contains(
+        &err,
+        "rate_limit concurrent limit must be greater than zero",
+    );
+}
@@ -96,6 +96,40 @@ fn rate_limit_resolution_lowers_observer_path() {
     assert!(quote::quote!(#observer).to_string().contains("Observer"));
 }
 
+#[test]
+fn rate_limit_resolution_maps_builtin_header_observers() {
+    for (name, ty) in [
+        ("ietf_headers", "IetfRateLimitHeaders"),
+        ("x_ratelimit_headers", "XRateLimitHeaders"),
+        ("riot_headers", "RiotRateLimitHeaders"),
+    ] {
+        let api = analyze_ok(&format!(
+            r#"
+            api! {{
+                client Api {{
+                    base "https://example.com"
+
+                    observe rate_limit {name}
+                }}
+
+                GET Ping
+                    path ["ping"]
+                    -> Json<()>
+            }}
+            "#
+        ));
+
+        let observer = api
+            .rate_limit_response_policy
+            .as_ref()
+            .expect("rate limit observer");
+        assert_eq!(
+            quote::quote!(#observer).to_string(),
+            format!(":: concord_core :: advanced :: {ty}")
+        );
+    }
+}
+
 #[test]
 fn rate_limit_keys_resolve_route_host_and_endpoint_fields() {
     let api = analyze_ok(
@@ -232,3 +266,32 @@ fn rate_limit_keys_resolve_endpoint_profile_key_binding() {
             if name == "match_key" && *field == "match_id"
     ));
 }
+
+#[test]
+fn rate_limit_resolution_lowers_concurrent_only_bucket() {
+    let api = analyze_ok(
+        r#"
+        api! {
+            client Api {
+                base "https://example.com"
+
+                rate_limit exports {
+                    bucket method by [endpoint, "tenant"] {
+                        concurrent 4
+                    }
+                }
+            }
+
+            GET Export
+                path ["export"]
+                rate_limit exports
+                -> Json<()>
+        }
+        "#,
+    );
+    let endpoint = single_endpoint(&api);
+
+    let bucket = &rate_limit_plan(endpoint.policy.endpoint.rate_limit.as_ref().unwrap()).buckets[0];
+    assert_eq!(bucket.concurrency, Some(4));
+    assert!(bucket.windows.is_empty());
+}
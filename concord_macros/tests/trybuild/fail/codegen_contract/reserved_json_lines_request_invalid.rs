@@ -0,0 +1,11 @@
+use concord_macros::api;
+
+api! {
+    client ReservedJsonLinesRequestInvalidApi { base "https://example.com" }
+
+    POST Create(body: JsonLines<Row>)
+        path ["create"]
+        -> Json<()>
+}
+
+fn main() {}
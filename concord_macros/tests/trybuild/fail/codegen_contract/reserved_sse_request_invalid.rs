@@ -0,0 +1,11 @@
+use concord_macros::api;
+
+api! {
+    client ReservedSseRequestInvalidApi { base "https://example.com" }
+
+    POST Create(body: Sse<Event>)
+        path ["create"]
+        -> Json<()>
+}
+
+fn main() {}
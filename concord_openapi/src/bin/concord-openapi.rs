@@ -0,0 +1,65 @@
+//! `concord-openapi <document> --client <Name> [--base-url <url>]
+//! [--skip-unsupported] [--out <file>]`
+//!
+//! Prints the generated `api!` source, or writes it to `--out`.
+//! Diagnostics go to stderr.
+
+use concord_openapi::Generator;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: concord-openapi <document> --client <Name> [--base-url <url>] [--skip-unsupported] [--out <file>]";
+
+fn main() -> ExitCode {
+    let mut document = None;
+    let mut client = None;
+    let mut base_url = None;
+    let mut skip_unsupported = false;
+    let mut out = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--client" => client = args.next(),
+            "--base-url" => base_url = args.next(),
+            "--out" => out = args.next(),
+            "--skip-unsupported" => skip_unsupported = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return ExitCode::SUCCESS;
+            }
+            _ if document.is_none() && !arg.starts_with('-') => document = Some(arg),
+            _ => {
+                eprintln!("unexpected argument `{arg}`\n{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let (Some(document), Some(client)) = (document, client) else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let mut generator = Generator::new(client).skip_unsupported(skip_unsupported);
+    if let Some(base_url) = base_url {
+        generator = generator.base_url(base_url);
+    }
+    let generated = match generator.generate_path(&document) {
+        Ok(generated) => generated,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    for diagnostic in generated.diagnostics() {
+        eprintln!("{diagnostic}");
+    }
+    match out {
+        Some(out) => {
+            if let Err(err) = std::fs::write(&out, generated.source()) {
+                eprintln!("failed to write `{out}`: {err}");
+                return ExitCode::FAILURE;
+            }
+        }
+        None => print!("{}", generated.source()),
+    }
+    ExitCode::SUCCESS
+}
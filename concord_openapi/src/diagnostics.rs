@@ -0,0 +1,116 @@
+use std::fmt;
+
+/// How a [`Diagnostic`] affects generation.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Severity {
+    /// The construct cannot be expressed faithfully; generation fails unless
+    /// [`Generator::skip_unsupported`](crate::Generator::skip_unsupported)
+    /// drops the affected operation.
+    Error,
+    /// The output is usable but looser than the document, for example an
+    /// untyped `serde_json::Value` in place of a `oneOf` schema.
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+        })
+    }
+}
+
+/// A message about one construct of the OpenAPI document.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    severity: Severity,
+    pointer: String,
+    message: String,
+}
+
+impl Diagnostic {
+    pub(crate) fn error(pointer: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            pointer: pointer.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn warning(pointer: &str, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            pointer: pointer.to_string(),
+            message: message.into(),
+        }
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// JSON pointer to the construct, e.g. `#/paths/~1pets/get`.
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub(crate) fn downgrade(mut self) -> Self {
+        self.severity = Severity::Warning;
+        self
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} at `{}`",
+            self.severity, self.message, self.pointer
+        )
+    }
+}
+
+/// Diagnostics collected while lowering one construct.
+#[derive(Default)]
+pub(crate) struct Diagnostics {
+    items: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub(crate) fn error(&mut self, pointer: &str, message: impl Into<String>) {
+        self.items.push(Diagnostic::error(pointer, message));
+    }
+
+    pub(crate) fn warning(&mut self, pointer: &str, message: impl Into<String>) {
+        self.items.push(Diagnostic::warning(pointer, message));
+    }
+
+    pub(crate) fn has_errors(&self) -> bool {
+        self.items
+            .iter()
+            .any(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    pub(crate) fn append(&mut self, other: Diagnostics) {
+        self.items.extend(other.items);
+    }
+
+    pub(crate) fn push(&mut self, diagnostic: Diagnostic) {
+        self.items.push(diagnostic);
+    }
+
+    pub(crate) fn into_vec(self) -> Vec<Diagnostic> {
+        self.items
+    }
+}
+
+/// Escapes one JSON-pointer reference token and appends it to `base`.
+pub(crate) fn pointer(base: &str, token: &str) -> String {
+    format!("{base}/{}", token.replace('~', "~0").replace('/', "~1"))
+}
@@ -0,0 +1,52 @@
+use crate::diagnostics::Diagnostic;
+use serde_json::Value;
+
+/// Longest `$ref` chain followed before reporting a cycle.
+const MAX_REF_CHAIN: usize = 32;
+
+/// Read-only view over the parsed document root.
+#[derive(Clone, Copy)]
+pub(crate) struct Document<'a> {
+    root: &'a Value,
+}
+
+impl<'a> Document<'a> {
+    pub(crate) fn new(root: &'a Value) -> Self {
+        Self { root }
+    }
+
+    pub(crate) fn root(&self) -> &'a Value {
+        self.root
+    }
+
+    /// Follows a chain of local `$ref`s, returning the target and its JSON
+    /// pointer. A value without `$ref` resolves to itself.
+    pub(crate) fn deref(
+        &self,
+        mut value: &'a Value,
+        at: &str,
+    ) -> Result<(&'a Value, String), Diagnostic> {
+        let mut at = at.to_string();
+        for _ in 0..MAX_REF_CHAIN {
+            let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+                return Ok((value, at));
+            };
+            let Some(local) = reference.strip_prefix('#') else {
+                return Err(Diagnostic::error(
+                    &at,
+                    format!("external reference `{reference}` is not supported"),
+                ));
+            };
+            value = self.root.pointer(local).ok_or_else(|| {
+                Diagnostic::error(&at, format!("unresolved reference `{reference}`"))
+            })?;
+            at = reference.to_string();
+        }
+        Err(Diagnostic::error(&at, "reference chain does not terminate"))
+    }
+}
+
+/// Decodes `~1` and `~0` escapes of one JSON-pointer reference token.
+pub(crate) fn unescape_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
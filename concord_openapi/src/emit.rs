@@ -0,0 +1,318 @@
+use crate::operations::{Endpoint, Location, PathPiece};
+use crate::schema::{Field, Model, ModelKind};
+use crate::security::{AuthUse, Security};
+use std::fmt::Write as _;
+
+/// Indentation-aware line writer for generated source.
+#[derive(Default)]
+pub(crate) struct Writer {
+    out: String,
+    indent: usize,
+}
+
+impl Writer {
+    pub(crate) fn line(&mut self, text: &str) {
+        if text.is_empty() {
+            self.out.push('\n');
+            return;
+        }
+        for _ in 0..self.indent {
+            self.out.push_str("    ");
+        }
+        self.out.push_str(text);
+        self.out.push('\n');
+    }
+
+    pub(crate) fn open(&mut self, text: &str) {
+        self.line(text);
+        self.indent += 1;
+    }
+
+    pub(crate) fn close(&mut self, text: &str) {
+        self.indent -= 1;
+        self.line(text);
+    }
+
+    fn doc(&mut self, text: Option<&str>) {
+        for line in text.into_iter().flat_map(str::lines) {
+            self.line(format!("/// {line}").trim_end());
+        }
+    }
+
+    fn finish(self) -> String {
+        self.out
+    }
+}
+
+/// Endpoints of one scope, keyed by scope identifier and path segment;
+/// `None` holds endpoints outside any scope.
+pub(crate) type ScopeGroup = (Option<(String, String)>, Vec<Endpoint>);
+
+/// Everything one generated file contains.
+pub(crate) struct Output<'g> {
+    pub(crate) title: String,
+    pub(crate) client: &'g str,
+    pub(crate) origin: &'g str,
+    pub(crate) base_path: &'g [String],
+    pub(crate) models: &'g [Model],
+    pub(crate) security: &'g Security,
+    pub(crate) groups: &'g [ScopeGroup],
+}
+
+pub(crate) fn render(output: &Output<'_>) -> String {
+    let mut w = Writer::default();
+    w.line(&format!(
+        "// @generated by concord_openapi from {}; do not edit.",
+        output.title
+    ));
+    w.line("");
+    w.line("use concord_core::prelude::*;");
+    w.line("use concord_macros::api;");
+    w.line("");
+
+    if !output.models.is_empty() {
+        w.open("pub mod models {");
+        w.line("use serde::{Deserialize, Serialize};");
+        for model in output.models {
+            w.line("");
+            render_model(&mut w, model);
+        }
+        w.close("}");
+        w.line("");
+    }
+
+    let endpoints = output.groups.iter().flat_map(|(_, endpoints)| endpoints);
+    let mut auth_uses = endpoints.map(|endpoint| &endpoint.auth);
+    let shared_auth = auth_uses
+        .next()
+        .filter(|first| !first.is_empty() && auth_uses.all(|other| other == *first));
+
+    w.open("api! {");
+    w.open(&format!("client {} {{", output.client));
+    w.line(&format!("base {:?}", output.origin));
+    let declarations = output.security.declarations();
+    if !declarations.is_empty() {
+        w.line("");
+        w.open("auth {");
+        for declaration in declarations {
+            for line in declaration.lines() {
+                match line {
+                    "}" => w.close(line),
+                    _ if line.ends_with('{') => w.open(line),
+                    _ => w.line(line),
+                }
+            }
+        }
+        w.close("}");
+    }
+    if let Some(auth) = shared_auth {
+        w.line("");
+        w.open("default {");
+        for placement in output.security.placements(auth) {
+            w.line(&placement);
+        }
+        w.close("}");
+    }
+    w.close("}");
+
+    for (scope, endpoints) in output.groups {
+        match scope {
+            None => {
+                for endpoint in endpoints {
+                    w.line("");
+                    render_endpoint(&mut w, endpoint, output, output.base_path, shared_auth);
+                }
+            }
+            Some((ident, segment)) => {
+                w.line("");
+                w.open(&format!("scope {ident} {{"));
+                let path = output
+                    .base_path
+                    .iter()
+                    .chain([segment])
+                    .map(|segment| format!("{segment:?}"))
+                    .collect::<Vec<_>>();
+                w.line(&format!("path [{}]", path.join(", ")));
+                for endpoint in endpoints {
+                    w.line("");
+                    render_endpoint(&mut w, endpoint, output, &[], shared_auth);
+                }
+                w.close("}");
+            }
+        }
+    }
+    w.close("}");
+    w.line("");
+    w.line(&format!(
+        "pub use self::{}::*;",
+        client_module(output.client)
+    ));
+    w.finish()
+}
+
+/// The module `api!` declares for a client, mirroring the macro's naming:
+/// every uppercase letter after the first starts a new `_` segment.
+fn client_module(client: &str) -> String {
+    let mut module = String::new();
+    for (index, ch) in client.chars().enumerate() {
+        if ch.is_ascii_uppercase() && index != 0 {
+            module.push('_');
+        }
+        module.push(ch.to_ascii_lowercase());
+    }
+    module
+}
+
+fn render_model(w: &mut Writer, model: &Model) {
+    w.doc(model.description.as_deref());
+    let name = &model.name;
+    match &model.kind {
+        ModelKind::Struct(fields) => {
+            w.line("#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]");
+            w.open(&format!("pub struct {name} {{"));
+            for field in fields {
+                render_field(w, field);
+            }
+            w.close("}");
+        }
+        ModelKind::Enum(variants) => {
+            w.line("#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]");
+            w.open(&format!("pub enum {name} {{"));
+            for variant in variants {
+                if variant.ident != variant.wire {
+                    w.line(&format!("#[serde(rename = {:?})]", variant.wire));
+                }
+                w.line(&format!("{},", variant.ident));
+            }
+            w.close("}");
+            w.line("");
+            w.open(&format!("impl core::fmt::Display for {name} {{"));
+            w.open("fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {");
+            w.open("f.write_str(match self {");
+            for variant in variants {
+                w.line(&format!("Self::{} => {:?},", variant.ident, variant.wire));
+            }
+            w.close("})");
+            w.close("}");
+            w.close("}");
+        }
+        ModelKind::Alias(ty) => w.line(&format!("pub type {name} = {};", ty.render(false))),
+    }
+}
+
+fn render_field(w: &mut Writer, field: &Field) {
+    w.doc(field.description.as_deref());
+    let mut attrs = Vec::new();
+    if field.ident != field.wire {
+        attrs.push(format!("rename = {:?}", field.wire));
+    }
+    if !field.required {
+        attrs.push("default".to_string());
+        attrs.push("skip_serializing_if = \"Option::is_none\"".to_string());
+    }
+    if !attrs.is_empty() {
+        w.line(&format!("#[serde({})]", attrs.join(", ")));
+    }
+    let ty = field.ty.render(false);
+    let ty = if field.required && !field.nullable {
+        ty
+    } else {
+        format!("Option<{ty}>")
+    };
+    w.line(&format!("pub {}: {ty},", field.ident));
+}
+
+fn render_endpoint(
+    w: &mut Writer,
+    endpoint: &Endpoint,
+    output: &Output<'_>,
+    base_path: &[String],
+    shared_auth: Option<&AuthUse>,
+) {
+    if let Some(summary) = &endpoint.summary {
+        w.line(&format!("// {summary}"));
+    }
+    let mut signature = endpoint
+        .params
+        .iter()
+        .map(|param| {
+            let marker = if param.required { "" } else { "?" };
+            format!("{}{marker}: {}", param.ident, param.ty.render(true))
+        })
+        .collect::<Vec<_>>();
+    if let Some(body) = &endpoint.body {
+        signature.push(format!("body: {body}"));
+    }
+    let mut head = format!("{} {}", endpoint.method, endpoint.name);
+    if !signature.is_empty() {
+        let _ = write!(head, "({})", signature.join(", "));
+    }
+    w.open(&head);
+
+    let path = base_path
+        .iter()
+        .map(|segment| format!("{segment:?}"))
+        .chain(endpoint.path.iter().map(render_piece))
+        .collect::<Vec<_>>();
+    if !path.is_empty() {
+        w.line(&format!("path [{}]", path.join(", ")));
+    }
+
+    let bound = |location| {
+        endpoint
+            .params
+            .iter()
+            .filter(move |param| param.location == location)
+    };
+    if bound(Location::Query).next().is_some() {
+        w.open("query {");
+        for param in bound(Location::Query) {
+            if param.ident == param.wire {
+                w.line(&param.ident);
+            } else {
+                w.line(&format!("{:?} = {}", param.wire, param.ident));
+            }
+        }
+        w.close("}");
+    }
+    let headers = bound(Location::Header)
+        .map(|param| format!("{:?} = {}", param.wire, param.ident))
+        .collect::<Vec<_>>();
+    if !headers.is_empty() {
+        w.open("headers {");
+        for (index, header) in headers.iter().enumerate() {
+            let separator = if index + 1 < headers.len() { "," } else { "" };
+            w.line(&format!("{header}{separator}"));
+        }
+        w.close("}");
+    }
+    if shared_auth.is_none() {
+        for placement in output.security.placements(&endpoint.auth) {
+            w.line(&placement);
+        }
+    }
+
+    w.line(&format!("-> {}", endpoint.response));
+    for (status, spec) in &endpoint.errors {
+        match status {
+            Some(status) => w.line(&format!("on_error {status} {spec}")),
+            None => w.line(&format!("on_error {spec}")),
+        }
+    }
+    w.indent -= 1;
+}
+
+fn render_piece(piece: &PathPiece) -> String {
+    match piece {
+        PathPiece::Literal(literal) => format!("{literal:?}"),
+        PathPiece::Param(ident) => ident.clone(),
+        PathPiece::Fmt(parts) => format!(
+            "fmt[{}]",
+            parts
+                .iter()
+                .map(render_piece)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
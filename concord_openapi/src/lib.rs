@@ -0,0 +1,398 @@
+//! Generates a Concord `api!` definition from an OpenAPI 3.0 or 3.1 document.
+//!
+//! The output is ordinary Rust source: a `models` module of serde types for
+//! the document's schemas followed by one `api!` invocation with a scope per
+//! leading path segment, an endpoint per operation, and `auth` credentials
+//! for the security schemes those operations use. It is meant to be written
+//! to `OUT_DIR` from a build script and included into its own module:
+//!
+//! ```no_run
+//! // build.rs
+//! let out = std::path::PathBuf::from(std::env::var("OUT_DIR").unwrap());
+//! println!("cargo::rerun-if-changed=openapi/petstore.yaml");
+//! let generated = concord_openapi::Generator::new("PetstoreApi")
+//!     .generate_path("openapi/petstore.yaml")
+//!     .unwrap_or_else(|err| panic!("{err}"));
+//! for diagnostic in generated.diagnostics() {
+//!     println!("cargo::warning={diagnostic}");
+//! }
+//! std::fs::write(out.join("petstore.rs"), generated.source()).unwrap();
+//! ```
+//!
+//! ```ignore
+//! // src/petstore.rs
+//! include!(concat!(env!("OUT_DIR"), "/petstore.rs"));
+//! ```
+//!
+//! The file ends with `pub use` of the generated client module, so the
+//! including module exposes the client type, `endpoints`, and request
+//! extension traits directly.
+//!
+//! The generated source needs `concord_core` (with its `json` feature),
+//! `concord_macros`, `serde`, and `serde_json` as dependencies. The
+//! `concord-openapi` binary wraps the same generator for one-off
+//! transcription.
+//!
+//! Constructs the DSL cannot express faithfully (cookie parameters, object
+//! query parameters, multipart bodies, unsupported security schemes, ...) are
+//! reported as [`Severity::Error`] diagnostics and fail generation. Schemas
+//! that only lose precision, such as `oneOf`, become `serde_json::Value` with
+//! a [`Severity::Warning`].
+
+mod diagnostics;
+mod document;
+mod emit;
+mod names;
+mod operations;
+mod schema;
+mod security;
+
+pub use diagnostics::{Diagnostic, Severity};
+
+use diagnostics::{Diagnostics, pointer};
+use document::Document;
+use names::NameSet;
+use operations::{Endpoint, Lowering, METHODS, PathPiece};
+use schema::Models;
+use security::Security;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Errors that stop generation.
+#[derive(Debug, thiserror::Error)]
+#[non_exhaustive]
+pub enum GenerateError {
+    #[error("failed to read OpenAPI document `{}`", path.display())]
+    Read {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+    #[error("OpenAPI document is not valid JSON or YAML: {message}")]
+    Syntax { message: String },
+    #[error("unsupported OpenAPI version `{version}`; expected 3.0.x or 3.1.x")]
+    Version { version: String },
+    #[error("client name `{name}` is not a PascalCase Rust identifier")]
+    ClientName { name: String },
+    #[error("{}", UnsupportedList(diagnostics))]
+    Unsupported { diagnostics: Vec<Diagnostic> },
+}
+
+struct UnsupportedList<'a>(&'a [Diagnostic]);
+
+impl fmt::Display for UnsupportedList<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors = self
+            .0
+            .iter()
+            .filter(|diagnostic| diagnostic.severity() == Severity::Error);
+        write!(f, "OpenAPI document uses unsupported constructs")?;
+        for diagnostic in errors {
+            write!(f, "\n  {diagnostic}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Source produced by [`Generator`], with the warnings raised on the way.
+#[derive(Clone, Debug)]
+pub struct Generated {
+    source: String,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Generated {
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn into_source(self) -> String {
+        self.source
+    }
+}
+
+/// Configures and runs OpenAPI-to-`api!` generation.
+#[derive(Clone, Debug)]
+pub struct Generator {
+    client: String,
+    base_url: Option<String>,
+    skip_unsupported: bool,
+}
+
+impl Generator {
+    /// A generator for a client type named `client`, e.g. `"PetstoreApi"`.
+    pub fn new(client: impl Into<String>) -> Self {
+        Self {
+            client: client.into(),
+            base_url: None,
+            skip_unsupported: false,
+        }
+    }
+
+    /// Uses `url` instead of the document's first `servers` entry. Needed
+    /// when the document declares no servers or only relative ones.
+    pub fn base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// Omits operations that use unsupported constructs instead of failing.
+    /// Their errors are reported as warnings.
+    pub fn skip_unsupported(mut self, skip: bool) -> Self {
+        self.skip_unsupported = skip;
+        self
+    }
+
+    /// Reads and generates from a `.json`, `.yaml`, or `.yml` document.
+    pub fn generate_path(&self, path: impl AsRef<Path>) -> Result<Generated, GenerateError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path).map_err(|source| GenerateError::Read {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        self.generate_str(&source)
+    }
+
+    /// Generates from document text; JSON is detected by a leading `{`,
+    /// anything else is parsed as YAML.
+    pub fn generate_str(&self, source: &str) -> Result<Generated, GenerateError> {
+        let root: Value = if source.trim_start().starts_with('{') {
+            serde_json::from_str(source).map_err(|err| GenerateError::Syntax {
+                message: err.to_string(),
+            })?
+        } else {
+            serde_yaml_ng::from_str(source).map_err(|err| GenerateError::Syntax {
+                message: err.to_string(),
+            })?
+        };
+        self.generate_value(&root)
+    }
+
+    fn generate_value(&self, root: &Value) -> Result<Generated, GenerateError> {
+        let version = root
+            .get("openapi")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        if !version.starts_with("3.0") && !version.starts_with("3.1") {
+            let version = root
+                .get("swagger")
+                .or_else(|| root.get("openapi"))
+                .map(|version| {
+                    version
+                        .as_str()
+                        .map_or_else(|| version.to_string(), str::to_string)
+                })
+                .unwrap_or_else(|| "missing".to_string());
+            return Err(GenerateError::Version { version });
+        }
+        let mut chars = self.client.chars();
+        let valid_client = chars.next().is_some_and(|ch| ch.is_ascii_uppercase())
+            && chars.all(|ch| ch.is_ascii_alphanumeric());
+        if !valid_client {
+            return Err(GenerateError::ClientName {
+                name: self.client.clone(),
+            });
+        }
+
+        let doc = Document::new(root);
+        let mut diagnostics = Diagnostics::default();
+        let Some((origin, base_path)) = self.server(root, &mut diagnostics) else {
+            return Err(GenerateError::Unsupported {
+                diagnostics: diagnostics.into_vec(),
+            });
+        };
+        let mut models = Models::new(doc);
+        models.define_components();
+        let mut security = Security::new(root, &mut diagnostics);
+
+        let mut groups: BTreeMap<Option<String>, Vec<Endpoint>> = BTreeMap::new();
+        let paths = root.get("paths").and_then(Value::as_object);
+        for (template, item) in paths.into_iter().flatten() {
+            let item_at = pointer("#/paths", template);
+            let (item, item_at) = match doc.deref(item, &item_at) {
+                Ok(resolved) => resolved,
+                Err(diagnostic) => {
+                    self.report(&mut diagnostics, vec![diagnostic], &item_at);
+                    continue;
+                }
+            };
+            for method in METHODS {
+                let Some(operation) = item.get(*method) else {
+                    continue;
+                };
+                let at = pointer(&item_at, method);
+                let mut pieces = operations::split_template(template);
+                let scope = match pieces.first() {
+                    Some(PathPiece::Literal(segment)) => Some(segment.clone()),
+                    _ => None,
+                };
+                if scope.is_some() {
+                    pieces.remove(0);
+                }
+                let mut op_diagnostics = Diagnostics::default();
+                let checkpoint = models.checkpoint();
+                let mut lowering = Lowering {
+                    doc,
+                    models: &mut models,
+                    security: &security,
+                };
+                let endpoint = lowering.operation(
+                    method,
+                    template,
+                    pieces,
+                    item,
+                    operation,
+                    &at,
+                    &mut op_diagnostics,
+                );
+                if op_diagnostics.has_errors() {
+                    models.rollback(checkpoint);
+                    self.report(&mut diagnostics, op_diagnostics.into_vec(), &at);
+                    continue;
+                }
+                diagnostics.append(op_diagnostics);
+                security.commit(&endpoint.auth);
+                groups.entry(scope).or_default().push(endpoint);
+            }
+        }
+        let (models, model_diagnostics) = models.into_models();
+        diagnostics.append(model_diagnostics);
+        if diagnostics.has_errors() {
+            return Err(GenerateError::Unsupported {
+                diagnostics: diagnostics.into_vec(),
+            });
+        }
+
+        let mut scope_names = NameSet::default();
+        let groups = groups
+            .into_iter()
+            .map(|(segment, mut endpoints)| {
+                let mut endpoint_names = NameSet::default();
+                for endpoint in &mut endpoints {
+                    let name = std::mem::take(&mut endpoint.name);
+                    endpoint.name = endpoint_names.claim(name);
+                }
+                let scope =
+                    segment.map(|segment| (scope_names.claim(names::dsl_ident(&segment)), segment));
+                (scope, endpoints)
+            })
+            .collect::<Vec<_>>();
+        let info = root.get("info");
+        let field = |key| info.and_then(|info| info.get(key)).and_then(Value::as_str);
+        let title = format!(
+            "`{}` {}",
+            field("title")
+                .unwrap_or("untitled")
+                .replace(['\n', '\r'], " "),
+            field("version")
+                .unwrap_or_default()
+                .replace(['\n', '\r'], " "),
+        );
+        let source = emit::render(&emit::Output {
+            title: title.trim_end().to_string(),
+            client: &self.client,
+            origin: &origin,
+            base_path: &base_path,
+            models: &models,
+            security: &security,
+            groups: &groups,
+        });
+        Ok(Generated {
+            source,
+            diagnostics: diagnostics.into_vec(),
+        })
+    }
+
+    /// Records the errors of one skipped construct: as errors, or as
+    /// warnings plus a skip notice when [`Self::skip_unsupported`] is set.
+    fn report(&self, diagnostics: &mut Diagnostics, errors: Vec<Diagnostic>, at: &str) {
+        if !self.skip_unsupported {
+            errors.into_iter().for_each(|error| diagnostics.push(error));
+            return;
+        }
+        errors
+            .into_iter()
+            .for_each(|error| diagnostics.push(error.downgrade()));
+        diagnostics.warning(
+            at,
+            "operation skipped because it uses unsupported constructs",
+        );
+    }
+
+    /// The client origin and the path segments every route starts with.
+    fn server(&self, root: &Value, diagnostics: &mut Diagnostics) -> Option<(String, Vec<String>)> {
+        let servers = root.get("servers").and_then(Value::as_array);
+        let url = match (&self.base_url, servers.and_then(|servers| servers.first())) {
+            (Some(url), _) => url.clone(),
+            (None, Some(server)) => {
+                if servers.is_some_and(|servers| servers.len() > 1) {
+                    diagnostics.warning(
+                        "#/servers",
+                        "only the first server is used as the client base",
+                    );
+                }
+                server_url(server, diagnostics)?
+            }
+            (None, None) => {
+                diagnostics.error("#/servers", "document declares no servers; set a base URL");
+                return None;
+            }
+        };
+        let Some((scheme, rest)) = url.split_once("://") else {
+            diagnostics.error(
+                "#/servers/0",
+                format!("server URL `{url}` is relative; set an absolute base URL"),
+            );
+            return None;
+        };
+        let rest = rest.split(['?', '#']).next().unwrap_or_default();
+        let (host, path) = rest.split_once('/').unwrap_or((rest, ""));
+        if host.is_empty() || !matches!(scheme, "http" | "https") {
+            diagnostics.error(
+                "#/servers/0",
+                format!("server URL `{url}` is not an HTTP URL"),
+            );
+            return None;
+        }
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(str::to_string)
+            .collect();
+        Some((format!("{scheme}://{host}"), segments))
+    }
+}
+
+/// Substitutes server variables with their defaults.
+fn server_url(server: &Value, diagnostics: &mut Diagnostics) -> Option<String> {
+    let Some(mut url) = server
+        .get("url")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+    else {
+        diagnostics.error("#/servers/0", "server has no `url`");
+        return None;
+    };
+    let variables = server.get("variables").and_then(Value::as_object);
+    for (name, variable) in variables.into_iter().flatten() {
+        let Some(default) = variable.get("default").and_then(Value::as_str) else {
+            diagnostics.error(
+                "#/servers/0/variables",
+                format!("server variable `{name}` has no default"),
+            );
+            return None;
+        };
+        url = url.replace(&format!("{{{name}}}"), default);
+        diagnostics.warning(
+            "#/servers/0/variables",
+            format!("server variable `{name}` is fixed to its default `{default}`"),
+        );
+    }
+    Some(url)
+}
@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Identifiers the `api!` DSL parses as keywords or rejects in public
+/// request-shaping expressions. Parameters and credentials never use them.
+const DSL_RESERVED: &[&str] = &[
+    "access_token",
+    "after",
+    "api",
+    "api_key",
+    "auth",
+    "base",
+    "basic",
+    "bearer",
+    "body",
+    "bucket",
+    "by",
+    "cache",
+    "client",
+    "coalesce",
+    "concurrent",
+    "cost",
+    "credential",
+    "ctx",
+    "cx",
+    "default",
+    "endpoint",
+    "ep",
+    "extends",
+    "fmt",
+    "header",
+    "headers",
+    "hedge",
+    "host",
+    "idempotency_key",
+    "ignore",
+    "key",
+    "max_age",
+    "observe",
+    "off",
+    "on_error",
+    "only",
+    "paginate",
+    "params",
+    "path",
+    "policies",
+    "policy",
+    "prefix",
+    "profile",
+    "profiles",
+    "query",
+    "rate_limit",
+    "req",
+    "request",
+    "runtime",
+    "scope",
+    "secret",
+    "secrets",
+    "timeout",
+    "transport",
+    "url",
+    "var",
+    "vars",
+];
+
+/// Splits `listPets`, `pet-store`, `HTTPServer` and `v2_items` into words.
+fn words(raw: &str) -> Vec<String> {
+    let chars = raw.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for (i, &ch) in chars.iter().enumerate() {
+        if !ch.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        if let Some(prev) = current.chars().last() {
+            let next_lower = chars.get(i + 1).is_some_and(char::is_ascii_lowercase);
+            let boundary = ch.is_ascii_uppercase()
+                && (prev.is_ascii_lowercase()
+                    || prev.is_ascii_digit()
+                    || (prev.is_ascii_uppercase() && next_lower));
+            if boundary {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.push(ch);
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+pub(crate) fn pascal(raw: &str) -> String {
+    let mut out = String::new();
+    for word in words(raw) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars.map(|ch| ch.to_ascii_lowercase()));
+        }
+    }
+    if out.starts_with(|ch: char| ch.is_ascii_digit()) {
+        out.insert(0, 'V');
+    }
+    out
+}
+
+pub(crate) fn snake(raw: &str) -> String {
+    let mut out = words(raw)
+        .iter()
+        .map(|word| word.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_");
+    if out.starts_with(|ch: char| ch.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    out
+}
+
+/// A snake-case identifier usable as a struct field.
+pub(crate) fn field_ident(raw: &str) -> String {
+    let ident = snake(raw);
+    if ident.is_empty() {
+        "value".to_string()
+    } else if RUST_KEYWORDS.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else {
+        ident
+    }
+}
+
+/// A snake-case identifier usable as an endpoint parameter, scope, or
+/// credential name inside `api!`.
+pub(crate) fn dsl_ident(raw: &str) -> String {
+    let ident = field_ident(raw);
+    if DSL_RESERVED.contains(&ident.as_str()) {
+        format!("{ident}_")
+    } else {
+        ident
+    }
+}
+
+/// A PascalCase type or variant name; `fallback` is used when `raw` holds
+/// no alphanumeric characters.
+pub(crate) fn type_ident(raw: &str, fallback: &str) -> String {
+    let ident = pascal(raw);
+    if ident.is_empty() {
+        fallback.to_string()
+    } else if ident == "Self" {
+        "Self_".to_string()
+    } else {
+        ident
+    }
+}
+
+/// Hands out unique names by appending a numeric suffix to repeats.
+#[derive(Default)]
+pub(crate) struct NameSet {
+    taken: HashSet<String>,
+}
+
+impl NameSet {
+    pub(crate) fn claim(&mut self, base: String) -> String {
+        if self.taken.insert(base.clone()) {
+            return base;
+        }
+        let separator = if base.ends_with(|ch: char| ch.is_ascii_digit()) {
+            "_"
+        } else {
+            ""
+        };
+        (2..)
+            .map(|n| format!("{base}{separator}{n}"))
+            .find(|candidate| self.taken.insert(candidate.clone()))
+            .expect("unbounded suffix search")
+    }
+
+    pub(crate) fn release(&mut self, name: &str) {
+        self.taken.remove(name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identifiers_follow_rust_casing_and_avoid_reserved_words() {
+        assert_eq!(pascal("listPets"), "ListPets");
+        assert_eq!(pascal("get-HTTPServer_status"), "GetHttpServerStatus");
+        assert_eq!(pascal("2fa"), "V2fa");
+        assert_eq!(snake("petId"), "pet_id");
+        assert_eq!(snake("X-Request-ID"), "x_request_id");
+        assert_eq!(field_ident("type"), "type_");
+        assert_eq!(dsl_ident("url"), "url_");
+        assert_eq!(dsl_ident("limit"), "limit");
+        assert_eq!(type_ident("---", "Empty"), "Empty");
+
+        let mut names = NameSet::default();
+        assert_eq!(names.claim("Pet".into()), "Pet");
+        assert_eq!(names.claim("Pet".into()), "Pet2");
+        assert_eq!(names.claim("V1".into()), "V1");
+        assert_eq!(names.claim("V1".into()), "V1_2");
+    }
+}
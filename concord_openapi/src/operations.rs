@@ -0,0 +1,498 @@
+use crate::diagnostics::{Diagnostics, pointer};
+use crate::document::Document;
+use crate::names::{self, NameSet};
+use crate::schema::{ModelKind, Models, Ty};
+use crate::security::{AuthUse, Security};
+use serde_json::Value;
+
+/// Path-item keys in the order their operations are generated.
+pub(crate) const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// One operation lowered to `api!` endpoint pieces.
+pub(crate) struct Endpoint {
+    pub(crate) method: String,
+    pub(crate) name: String,
+    pub(crate) summary: Option<String>,
+    pub(crate) path: Vec<PathPiece>,
+    pub(crate) params: Vec<Param>,
+    pub(crate) body: Option<String>,
+    pub(crate) response: String,
+    pub(crate) errors: Vec<(Option<String>, String)>,
+    pub(crate) auth: AuthUse,
+}
+
+pub(crate) enum PathPiece {
+    Literal(String),
+    Param(String),
+    Fmt(Vec<PathPiece>),
+}
+
+impl PathPiece {
+    fn binds(&self, ident: &str) -> bool {
+        match self {
+            Self::Param(bound) => bound == ident,
+            Self::Fmt(parts) => parts.iter().any(|part| part.binds(ident)),
+            Self::Literal(_) => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub(crate) enum Location {
+    Path,
+    Query,
+    Header,
+}
+
+pub(crate) struct Param {
+    pub(crate) ident: String,
+    pub(crate) wire: String,
+    pub(crate) ty: Ty,
+    pub(crate) required: bool,
+    pub(crate) location: Location,
+}
+
+/// Inputs shared by every operation of a document.
+pub(crate) struct Lowering<'a, 'g> {
+    pub(crate) doc: Document<'a>,
+    pub(crate) models: &'g mut Models<'a>,
+    pub(crate) security: &'g Security,
+}
+
+/// Splits a path template into pieces, e.g. `/files/{id}.json` into
+/// `"files"` and `fmt[id, ".json"]`. Parameters hold their wire names.
+pub(crate) fn split_template(template: &str) -> Vec<PathPiece> {
+    template
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| {
+            let mut parts = Vec::new();
+            let mut rest = segment;
+            while let Some(open) = rest.find('{') {
+                let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+                    break;
+                };
+                if open > 0 {
+                    parts.push(PathPiece::Literal(rest[..open].to_string()));
+                }
+                parts.push(PathPiece::Param(rest[open + 1..close].to_string()));
+                rest = &rest[close + 1..];
+            }
+            if !rest.is_empty() {
+                parts.push(PathPiece::Literal(rest.to_string()));
+            }
+            match parts.len() {
+                1 => parts.pop().expect("one piece"),
+                _ => PathPiece::Fmt(parts),
+            }
+        })
+        .collect()
+}
+
+impl<'a> Lowering<'a, '_> {
+    /// Lowers one operation. Unsupported constructs are reported as errors
+    /// in `diagnostics`; the returned endpoint is only usable without them.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn operation(
+        &mut self,
+        method: &str,
+        template: &str,
+        path_pieces: Vec<PathPiece>,
+        item: &'a Value,
+        operation: &'a Value,
+        at: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Endpoint {
+        if method == "trace" {
+            diagnostics.error(at, "TRACE operations are not supported");
+        }
+        let name = match operation.get("operationId").and_then(Value::as_str) {
+            Some(id) => names::type_ident(id, "Operation"),
+            None => names::type_ident(&format!("{method} {template}"), "Operation"),
+        };
+
+        let params = self.params(&name, item, operation, at, diagnostics);
+        let path = path_pieces
+            .into_iter()
+            .map(|piece| bind_path(piece, &params, at, diagnostics))
+            .collect::<Vec<_>>();
+        for param in params.iter().filter(|p| p.location == Location::Path) {
+            if !path.iter().any(|piece| piece.binds(&param.ident)) {
+                diagnostics.error(
+                    at,
+                    format!(
+                        "path parameter `{}` does not appear in the path template",
+                        param.wire
+                    ),
+                );
+            }
+        }
+        let body = operation.get("requestBody").and_then(|body| {
+            self.request_body(&name, body, &pointer(at, "requestBody"), diagnostics)
+        });
+        let (response, errors) = self.responses(&name, method, operation, at, diagnostics);
+
+        let security = operation
+            .get("security")
+            .or_else(|| self.doc.root().get("security"));
+        let auth = self.security.requirement(security, at, diagnostics);
+
+        Endpoint {
+            method: method.to_ascii_uppercase(),
+            name,
+            summary: operation
+                .get("summary")
+                .and_then(Value::as_str)
+                .and_then(|summary| summary.lines().next())
+                .map(str::trim)
+                .filter(|summary| !summary.is_empty())
+                .map(str::to_string),
+            path,
+            params,
+            body,
+            response,
+            errors,
+            auth,
+        }
+    }
+
+    fn params(
+        &mut self,
+        op_name: &str,
+        item: &'a Value,
+        operation: &'a Value,
+        at: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Vec<Param> {
+        // Operation parameters override path-item parameters with the same
+        // name and location.
+        let mut declared: Vec<(&'a Value, String)> = Vec::new();
+        let item_at = at.rsplit_once('/').map_or(at, |(item_at, _)| item_at);
+        let sources = [
+            (item.get("parameters"), pointer(item_at, "parameters")),
+            (operation.get("parameters"), pointer(at, "parameters")),
+        ];
+        for (list, list_at) in sources {
+            for (index, param) in list
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                let param_at = pointer(&list_at, &index.to_string());
+                let (param, param_at) = match self.doc.deref(param, &param_at) {
+                    Ok(resolved) => resolved,
+                    Err(diagnostic) => {
+                        diagnostics.push(diagnostic);
+                        continue;
+                    }
+                };
+                let key = |p: &Value| (p.get("name").cloned(), p.get("in").cloned());
+                declared.retain(|(existing, _)| key(existing) != key(param));
+                declared.push((param, param_at));
+            }
+        }
+
+        let mut idents = NameSet::default();
+        let mut params = Vec::new();
+        for (param, param_at) in declared {
+            if let Some(param) = self.param(op_name, param, &param_at, &mut idents, diagnostics) {
+                params.push(param);
+            }
+        }
+        // Path parameters lead the signature, then required before optional.
+        params.sort_by_key(|param| (param.location != Location::Path, !param.required));
+        params
+    }
+
+    fn param(
+        &mut self,
+        op_name: &str,
+        param: &'a Value,
+        at: &str,
+        idents: &mut NameSet,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<Param> {
+        let Some(wire) = param.get("name").and_then(Value::as_str) else {
+            diagnostics.error(at, "parameter has no `name`");
+            return None;
+        };
+        let location = match param.get("in").and_then(Value::as_str) {
+            Some("path") => Location::Path,
+            Some("query") => Location::Query,
+            Some("header") => {
+                // The specification ignores these header parameters; the
+                // runtime sets them from the body codec and `auth`.
+                if ["accept", "content-type", "authorization"]
+                    .contains(&wire.to_ascii_lowercase().as_str())
+                {
+                    return None;
+                }
+                Location::Header
+            }
+            Some(other) => {
+                diagnostics.error(
+                    at,
+                    format!("parameter `{wire}` in `{other}` is not supported"),
+                );
+                return None;
+            }
+            None => {
+                diagnostics.error(at, format!("parameter `{wire}` has no `in`"));
+                return None;
+            }
+        };
+        let Some(schema) = param.get("schema") else {
+            diagnostics.error(
+                at,
+                format!("parameter `{wire}` without a `schema` is not supported"),
+            );
+            return None;
+        };
+        let hint = format!("{op_name}{}", names::pascal(wire));
+        let ty = self.models.type_of(schema, &pointer(at, "schema"), &hint);
+
+        let style = param.get("style").and_then(Value::as_str);
+        let explode = param.get("explode").and_then(Value::as_bool);
+        let supported = match (location, &ty) {
+            (Location::Query, Ty::Vec(item)) => {
+                matches!(style, None | Some("form")) && explode != Some(false) && self.scalar(item)
+            }
+            (Location::Query, ty) => matches!(style, None | Some("form")) && self.scalar(ty),
+            (_, ty) => matches!(style, None | Some("simple")) && self.scalar(ty),
+        };
+        if !supported {
+            let shape = match &ty {
+                Ty::Vec(_) if location == Location::Query => {
+                    "array query parameters other than `style: form, explode: true`".to_string()
+                }
+                Ty::Vec(_) => "array parameters outside the query".to_string(),
+                ty if !self.scalar(ty) => "object-valued parameters".to_string(),
+                _ => format!("`style: {}` parameters", style.unwrap_or_default()),
+            };
+            diagnostics.error(at, format!("parameter `{wire}`: {shape} are not supported"));
+            return None;
+        }
+
+        Some(Param {
+            ident: idents.claim(names::dsl_ident(wire)),
+            wire: wire.to_string(),
+            ty,
+            required: location == Location::Path
+                || param.get("required").and_then(Value::as_bool) == Some(true),
+            location,
+        })
+    }
+
+    /// Whether values of `ty` render as a single `Display` string.
+    fn scalar(&self, ty: &Ty) -> bool {
+        match ty {
+            Ty::Prim(_) => true,
+            Ty::Model(name) => match self.models.kind(name) {
+                Some(ModelKind::Enum(_)) => true,
+                Some(ModelKind::Alias(inner)) => self.scalar(inner),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn request_body(
+        &mut self,
+        op_name: &str,
+        body: &'a Value,
+        at: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> Option<String> {
+        let (body, at) = match self.doc.deref(body, at) {
+            Ok(resolved) => resolved,
+            Err(diagnostic) => {
+                diagnostics.push(diagnostic);
+                return None;
+            }
+        };
+        let content = body.get("content").and_then(Value::as_object)?;
+        let content_at = pointer(&at, "content");
+        if body.get("required").and_then(Value::as_bool) != Some(true) {
+            diagnostics.warning(&at, "optional request body is generated as a required body");
+        }
+        let hint = format!("{op_name}Request");
+        if let Some((media, entry)) = content.iter().find(|(media, _)| is_json(media)) {
+            let ty = self.media_type(entry, &pointer(&content_at, media), &hint);
+            return Some(format!("Json<{}>", ty.render(true)));
+        }
+        if let Some(entry) = content.get("application/x-www-form-urlencoded") {
+            let media_at = pointer(&content_at, "application/x-www-form-urlencoded");
+            let ty = self.media_type(entry, &media_at, &hint);
+            if matches!(&ty, Ty::Model(name) if matches!(self.models.kind(name), Some(ModelKind::Struct(_))))
+            {
+                return Some(format!("Form<{}>", ty.render(true)));
+            }
+            diagnostics.error(&media_at, "form request bodies must be object schemas");
+            return None;
+        }
+        if content.contains_key("text/plain") {
+            return Some("Text<String>".to_string());
+        }
+        let media = content
+            .keys()
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join("`, `");
+        diagnostics.error(
+            &content_at,
+            format!("request body media type `{media}` is not supported"),
+        );
+        None
+    }
+
+    fn responses(
+        &mut self,
+        op_name: &str,
+        method: &str,
+        operation: &'a Value,
+        at: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> (String, Vec<(Option<String>, String)>) {
+        let responses_at = pointer(at, "responses");
+        let mut resolved = Vec::new();
+        for (status, response) in operation
+            .get("responses")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+        {
+            match self.doc.deref(response, &pointer(&responses_at, status)) {
+                Ok((response, response_at)) => {
+                    resolved.push((status.as_str(), response, response_at))
+                }
+                Err(diagnostic) => diagnostics.push(diagnostic),
+            }
+        }
+
+        let success = resolved
+            .iter()
+            .filter(|(status, ..)| status.starts_with('2'))
+            .min_by_key(|(status, ..)| *status)
+            .or_else(|| resolved.iter().find(|(status, ..)| *status == "default"));
+        let response = match success {
+            _ if method == "head" => "NoContent".to_string(),
+            Some((_, response, response_at)) => {
+                self.success_response(&format!("{op_name}Response"), response, response_at)
+            }
+            None => {
+                diagnostics.warning(
+                    &responses_at,
+                    "no success response is declared; generated as raw `Bytes`",
+                );
+                "Bytes".to_string()
+            }
+        };
+        let success_status = success.map(|(status, ..)| *status);
+
+        let mut errors = Vec::new();
+        let mut ranges = Vec::new();
+        for (status, response, response_at) in &resolved {
+            if Some(*status) == success_status {
+                continue;
+            }
+            let specific = status.len() == 3
+                && status.starts_with(['4', '5'])
+                && status.bytes().all(|b| b.is_ascii_digit());
+            let priority = ["default", "4XX", "5XX"]
+                .iter()
+                .position(|range| range == status);
+            if !specific && priority.is_none() {
+                continue;
+            }
+            let hint = format!("{op_name}Error");
+            let Some(ty) = self.json_response(response, response_at, &hint) else {
+                continue;
+            };
+            let spec = format!("Json<{}>", ty.render(true));
+            match priority {
+                Some(priority) => ranges.push((priority, *status, spec, response_at)),
+                None => errors.push((Some(status.to_string()), spec)),
+            }
+        }
+        // Ranges share the one status-less fallback; `default` wins.
+        ranges.sort_by_key(|(priority, ..)| *priority);
+        if let Some((_, fallback_status, fallback, _)) = ranges.first() {
+            for (_, status, spec, response_at) in &ranges[1..] {
+                if spec != fallback {
+                    diagnostics.warning(
+                        response_at,
+                        format!("`{status}` error schema differs from `{fallback_status}`; only one fallback `on_error` is generated"),
+                    );
+                }
+            }
+            errors.retain(|(_, specific)| specific != fallback);
+            errors.insert(0, (None, fallback.clone()));
+        }
+        (response, errors)
+    }
+
+    fn success_response(&mut self, hint: &str, response: &'a Value, at: &str) -> String {
+        let content = response.get("content").and_then(Value::as_object);
+        let Some(content) = content.filter(|content| !content.is_empty()) else {
+            return "NoContent".to_string();
+        };
+        if let Some(ty) = self.json_response(response, at, hint) {
+            return format!("Json<{}>", ty.render(true));
+        }
+        if content.keys().any(|media| media.starts_with("text/")) {
+            return "Text<String>".to_string();
+        }
+        "Bytes".to_string()
+    }
+
+    fn json_response(&mut self, response: &'a Value, at: &str, hint: &str) -> Option<Ty> {
+        let content = response.get("content").and_then(Value::as_object)?;
+        let (media, entry) = content.iter().find(|(media, _)| is_json(media))?;
+        let media_at = pointer(&pointer(at, "content"), media);
+        Some(self.media_type(entry, &media_at, hint))
+    }
+
+    fn media_type(&mut self, entry: &'a Value, at: &str, hint: &str) -> Ty {
+        match entry.get("schema") {
+            Some(schema) => self.models.type_of(schema, &pointer(at, "schema"), hint),
+            None => Ty::Value,
+        }
+    }
+}
+
+fn bind_path(
+    piece: PathPiece,
+    params: &[Param],
+    at: &str,
+    diagnostics: &mut Diagnostics,
+) -> PathPiece {
+    match piece {
+        PathPiece::Param(wire) => {
+            let bound = params
+                .iter()
+                .find(|param| param.location == Location::Path && param.wire == wire);
+            match bound {
+                Some(param) => PathPiece::Param(param.ident.clone()),
+                None => {
+                    diagnostics.error(at, format!("path parameter `{wire}` is not declared"));
+                    PathPiece::Param(wire)
+                }
+            }
+        }
+        PathPiece::Fmt(parts) => PathPiece::Fmt(
+            parts
+                .into_iter()
+                .map(|part| bind_path(part, params, at, diagnostics))
+                .collect(),
+        ),
+        literal @ PathPiece::Literal(_) => literal,
+    }
+}
+
+fn is_json(media: &str) -> bool {
+    let essence = media.split(';').next().unwrap_or_default().trim();
+    essence == "application/json" || essence.ends_with("+json")
+}
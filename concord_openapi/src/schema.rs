@@ -0,0 +1,522 @@
+use crate::diagnostics::{Diagnostics, pointer};
+use crate::document::{Document, unescape_token};
+use crate::names::{self, NameSet};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Deepest inline schema nesting lowered before falling back to
+/// `serde_json::Value`.
+const MAX_SCHEMA_DEPTH: usize = 64;
+
+const COMPONENT_SCHEMAS: &str = "#/components/schemas/";
+
+/// A Rust type produced for a schema.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum Ty {
+    Prim(&'static str),
+    Model(String),
+    Value,
+    Vec(Box<Ty>),
+    Map(Box<Ty>),
+    Boxed(Box<Ty>),
+}
+
+impl Ty {
+    /// Renders the type; `qualify` prefixes model names with `models::` for
+    /// use outside the models module.
+    pub(crate) fn render(&self, qualify: bool) -> String {
+        match self {
+            Self::Prim(name) => (*name).to_string(),
+            Self::Model(name) if qualify => format!("models::{name}"),
+            Self::Model(name) => name.clone(),
+            Self::Value => "serde_json::Value".to_string(),
+            Self::Vec(inner) => format!("Vec<{}>", inner.render(qualify)),
+            Self::Map(inner) => format!(
+                "std::collections::HashMap<String, {}>",
+                inner.render(qualify)
+            ),
+            Self::Boxed(inner) => format!("Box<{}>", inner.render(qualify)),
+        }
+    }
+
+    /// The model a field holds inline (not behind a `Vec` or map), if any.
+    fn direct_model(&self) -> Option<&str> {
+        match self {
+            Self::Model(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+pub(crate) struct Model {
+    pub(crate) name: String,
+    pub(crate) description: Option<String>,
+    pub(crate) kind: ModelKind,
+}
+
+pub(crate) enum ModelKind {
+    Struct(Vec<Field>),
+    Enum(Vec<Variant>),
+    Alias(Ty),
+}
+
+pub(crate) struct Field {
+    pub(crate) ident: String,
+    pub(crate) wire: String,
+    pub(crate) ty: Ty,
+    pub(crate) required: bool,
+    pub(crate) nullable: bool,
+    pub(crate) description: Option<String>,
+}
+
+pub(crate) struct Variant {
+    pub(crate) ident: String,
+    pub(crate) wire: String,
+}
+
+/// Lowers `components.schemas` and inline schemas into model declarations.
+///
+/// Schemas the DSL cannot type precisely become `serde_json::Value` with a
+/// warning; schema lowering never fails.
+pub(crate) struct Models<'a> {
+    doc: Document<'a>,
+    components: BTreeMap<String, String>,
+    names: NameSet,
+    models: Vec<Model>,
+    /// Inline models by schema pointer, so a schema reached through several
+    /// `allOf` merges is declared once.
+    inline: HashMap<String, Ty>,
+    depth: usize,
+    pub(crate) diagnostics: Diagnostics,
+}
+
+impl<'a> Models<'a> {
+    pub(crate) fn new(doc: Document<'a>) -> Self {
+        let mut models = Self {
+            doc,
+            components: BTreeMap::new(),
+            names: NameSet::default(),
+            models: Vec::new(),
+            inline: HashMap::new(),
+            depth: 0,
+            diagnostics: Diagnostics::default(),
+        };
+        let schemas = doc.root().pointer("/components/schemas");
+        for key in schemas
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(k, _)| k)
+        {
+            let name = models.names.claim(names::type_ident(key, "Schema"));
+            models.components.insert(key.clone(), name);
+        }
+        models
+    }
+
+    /// Declares every component schema, in document key order.
+    pub(crate) fn define_components(&mut self) {
+        let Some(schemas) = self
+            .doc
+            .root()
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+        else {
+            return;
+        };
+        for (key, schema) in schemas {
+            let name = self.components[key].clone();
+            let at = pointer("#/components/schemas", key);
+            let kind = if let Some(values) = string_enum(schema) {
+                Some(enum_kind(values))
+            } else if struct_shaped(schema) {
+                Some(ModelKind::Struct(self.struct_fields(&name, schema, &at)))
+            } else {
+                None
+            };
+            let kind = kind.unwrap_or_else(|| ModelKind::Alias(self.type_of(schema, &at, &name)));
+            self.models.push(Model {
+                name,
+                description: description(schema),
+                kind,
+            });
+        }
+    }
+
+    /// Marks the current set of models so a skipped operation can discard
+    /// the inline models it declared.
+    pub(crate) fn checkpoint(&self) -> usize {
+        self.models.len()
+    }
+
+    pub(crate) fn rollback(&mut self, checkpoint: usize) {
+        for model in self.models.drain(checkpoint..) {
+            self.names.release(&model.name);
+            self.inline
+                .retain(|_, ty| *ty != Ty::Model(model.name.clone()));
+        }
+    }
+
+    pub(crate) fn kind(&self, name: &str) -> Option<&ModelKind> {
+        self.models
+            .iter()
+            .find(|model| model.name == name)
+            .map(|model| &model.kind)
+    }
+
+    pub(crate) fn into_models(mut self) -> (Vec<Model>, Diagnostics) {
+        self.box_recursive_fields();
+        (self.models, self.diagnostics)
+    }
+
+    /// The Rust type for `schema`. Inline objects and enums are declared as
+    /// new models named after `hint`.
+    pub(crate) fn type_of(&mut self, schema: &'a Value, at: &str, hint: &str) -> Ty {
+        if self.depth >= MAX_SCHEMA_DEPTH {
+            self.diagnostics.warning(
+                at,
+                "schema nesting is too deep; generated as `serde_json::Value`",
+            );
+            return Ty::Value;
+        }
+        self.depth += 1;
+        let ty = self.lower(schema, at, hint);
+        self.depth -= 1;
+        ty
+    }
+
+    fn lower(&mut self, schema: &'a Value, at: &str, hint: &str) -> Ty {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            if let Some(name) = reference
+                .strip_prefix(COMPONENT_SCHEMAS)
+                .and_then(|key| self.components.get(&unescape_token(key)))
+            {
+                return Ty::Model(name.clone());
+            }
+            return match self.doc.deref(schema, at) {
+                Ok((target, target_at)) => self.type_of(target, &target_at, hint),
+                Err(diagnostic) => {
+                    self.diagnostics.warning(
+                        at,
+                        format!("{}; generated as `serde_json::Value`", diagnostic.message()),
+                    );
+                    Ty::Value
+                }
+            };
+        }
+        for keyword in ["oneOf", "anyOf"] {
+            let Some(variants) = schema.get(keyword).and_then(Value::as_array) else {
+                continue;
+            };
+            let mut non_null = variants
+                .iter()
+                .enumerate()
+                .filter(|(_, v)| !is_null_schema(v));
+            if let (Some((index, only)), None) = (non_null.next(), non_null.next()) {
+                let variant_at = pointer(&pointer(at, keyword), &index.to_string());
+                return self.type_of(only, &variant_at, hint);
+            }
+            self.diagnostics.warning(
+                at,
+                format!("`{keyword}` is generated as untyped `serde_json::Value`"),
+            );
+            return Ty::Value;
+        }
+        if schema.get("not").is_some() {
+            self.diagnostics
+                .warning(at, "`not` is generated as untyped `serde_json::Value`");
+            return Ty::Value;
+        }
+        if let Some(ty) = self.inline.get(at) {
+            return ty.clone();
+        }
+        if struct_shaped(schema) {
+            return self.inline_struct(schema, at, hint);
+        }
+        if let Some([only]) = schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .map(Vec::as_slice)
+        {
+            return self.type_of(only, &pointer(&pointer(at, "allOf"), "0"), hint);
+        }
+        if let Some(values) = string_enum(schema) {
+            return self.inline_enum(schema, at, values, hint);
+        }
+        let ty = match primary_type(schema) {
+            Ok(ty) => ty,
+            Err(types) => {
+                self.diagnostics.warning(
+                    at,
+                    format!("union type `{types}` is generated as untyped `serde_json::Value`"),
+                );
+                return Ty::Value;
+            }
+        };
+        let format = schema.get("format").and_then(Value::as_str);
+        match ty {
+            Some("string") => Ty::Prim("String"),
+            Some("boolean") => Ty::Prim("bool"),
+            Some("integer") if format == Some("int32") => Ty::Prim("i32"),
+            Some("integer") => Ty::Prim("i64"),
+            Some("number") if format == Some("float") => Ty::Prim("f32"),
+            Some("number") => Ty::Prim("f64"),
+            Some("array") => match schema.get("items") {
+                Some(items) => Ty::Vec(Box::new(self.type_of(
+                    items,
+                    &pointer(at, "items"),
+                    &format!("{hint}Item"),
+                ))),
+                None => Ty::Vec(Box::new(Ty::Value)),
+            },
+            Some("object") => match schema.get("additionalProperties") {
+                Some(values) if values.is_object() => Ty::Map(Box::new(self.type_of(
+                    values,
+                    &pointer(at, "additionalProperties"),
+                    &format!("{hint}Value"),
+                ))),
+                _ => Ty::Map(Box::new(Ty::Value)),
+            },
+            _ => Ty::Value,
+        }
+    }
+
+    fn inline_struct(&mut self, schema: &'a Value, at: &str, hint: &str) -> Ty {
+        let name = self.names.claim(names::type_ident(hint, "Object"));
+        let fields = self.struct_fields(&name, schema, at);
+        self.models.push(Model {
+            name: name.clone(),
+            description: description(schema),
+            kind: ModelKind::Struct(fields),
+        });
+        self.inline.insert(at.to_string(), Ty::Model(name.clone()));
+        Ty::Model(name)
+    }
+
+    fn inline_enum(&mut self, schema: &'a Value, at: &str, values: Vec<&str>, hint: &str) -> Ty {
+        let kind = enum_kind(values);
+        let name = self.names.claim(names::type_ident(hint, "Enum"));
+        self.models.push(Model {
+            name: name.clone(),
+            description: description(schema),
+            kind,
+        });
+        self.inline.insert(at.to_string(), Ty::Model(name.clone()));
+        Ty::Model(name)
+    }
+
+    fn struct_fields(&mut self, owner: &str, schema: &'a Value, at: &str) -> Vec<Field> {
+        let mut properties = Vec::new();
+        let mut required = HashSet::new();
+        self.collect_object(schema, at, &mut properties, &mut required);
+
+        let mut idents = NameSet::default();
+        let mut seen = HashSet::new();
+        let mut fields = Vec::new();
+        for (wire, property, property_at) in properties {
+            if !seen.insert(wire) {
+                continue;
+            }
+            let ident = idents.claim(names::field_ident(wire));
+            let hint = format!("{owner}{}", names::pascal(wire));
+            fields.push(Field {
+                ident,
+                wire: wire.to_string(),
+                ty: self.type_of(property, &property_at, &hint),
+                required: required.contains(wire),
+                nullable: is_nullable(property),
+                description: description(property),
+            });
+        }
+        fields
+    }
+
+    /// Gathers `properties` and `required` from `schema` and its `allOf`
+    /// parts, resolving references along the way.
+    fn collect_object(
+        &mut self,
+        schema: &'a Value,
+        at: &str,
+        properties: &mut Vec<(&'a str, &'a Value, String)>,
+        required: &mut HashSet<&'a str>,
+    ) {
+        let (schema, at) = match self.doc.deref(schema, at) {
+            Ok(resolved) => resolved,
+            Err(diagnostic) => {
+                self.diagnostics.warning(
+                    at,
+                    format!("{}; its properties are omitted", diagnostic.message()),
+                );
+                return;
+            }
+        };
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            let parts_at = pointer(&at, "allOf");
+            for (index, part) in parts.iter().enumerate() {
+                self.collect_object(
+                    part,
+                    &pointer(&parts_at, &index.to_string()),
+                    properties,
+                    required,
+                );
+            }
+        }
+        for keyword in ["oneOf", "anyOf"] {
+            if schema.get(keyword).is_some() {
+                self.diagnostics.warning(
+                    &at,
+                    format!("`{keyword}` inside an object schema is ignored; only its declared properties are generated"),
+                );
+            }
+        }
+        if let Some(props) = schema.get("properties").and_then(Value::as_object) {
+            let props_at = pointer(&at, "properties");
+            for (wire, property) in props {
+                properties.push((wire, property, pointer(&props_at, wire)));
+            }
+        }
+        if let Some(names) = schema.get("required").and_then(Value::as_array) {
+            required.extend(names.iter().filter_map(Value::as_str));
+        }
+    }
+
+    /// Boxes struct fields that hold a model inline when that model can reach
+    /// the struct again, so recursive schemas produce sized types.
+    fn box_recursive_fields(&mut self) {
+        let mut edges: HashMap<String, Vec<String>> = HashMap::new();
+        for model in &self.models {
+            let targets = match &model.kind {
+                ModelKind::Struct(fields) => fields
+                    .iter()
+                    .filter_map(|field| field.ty.direct_model())
+                    .map(str::to_string)
+                    .collect(),
+                ModelKind::Alias(ty) => ty.direct_model().map(str::to_string).into_iter().collect(),
+                ModelKind::Enum(_) => Vec::new(),
+            };
+            edges.insert(model.name.clone(), targets);
+        }
+        for model in &mut self.models {
+            let ModelKind::Struct(fields) = &mut model.kind else {
+                continue;
+            };
+            for field in fields {
+                let cyclic = field
+                    .ty
+                    .direct_model()
+                    .is_some_and(|target| reaches(&edges, target, &model.name));
+                if cyclic {
+                    field.ty = Ty::Boxed(Box::new(field.ty.clone()));
+                }
+            }
+        }
+    }
+}
+
+fn enum_kind(values: Vec<&str>) -> ModelKind {
+    let mut idents = NameSet::default();
+    let variants = values
+        .into_iter()
+        .map(|wire| Variant {
+            ident: idents.claim(names::type_ident(wire, "Empty")),
+            wire: wire.to_string(),
+        })
+        .collect();
+    ModelKind::Enum(variants)
+}
+
+fn reaches(edges: &HashMap<String, Vec<String>>, from: &str, to: &str) -> bool {
+    let mut stack = vec![from];
+    let mut visited = HashSet::new();
+    while let Some(node) = stack.pop() {
+        if node == to {
+            return true;
+        }
+        if visited.insert(node) {
+            stack.extend(edges.get(node).into_iter().flatten().map(String::as_str));
+        }
+    }
+    false
+}
+
+/// Whether `schema` lowers to a struct: it declares properties, or merges
+/// several `allOf` parts.
+fn struct_shaped(schema: &Value) -> bool {
+    let has_properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .is_some_and(|props| !props.is_empty());
+    let all_of = schema
+        .get("allOf")
+        .and_then(Value::as_array)
+        .map_or(0, Vec::len);
+    has_properties || all_of > 1
+}
+
+/// The values of a string `enum`, ignoring `null`. `None` when the schema
+/// has no enum or any value is not a string.
+fn string_enum(schema: &Value) -> Option<Vec<&str>> {
+    let values = schema.get("enum")?.as_array()?;
+    let strings = values
+        .iter()
+        .filter(|value| !value.is_null())
+        .map(Value::as_str)
+        .collect::<Option<Vec<_>>>()?;
+    (!strings.is_empty()).then_some(strings)
+}
+
+/// The single non-null `type`. 3.1 type arrays with several non-null
+/// members are returned as `Err` with the joined names.
+fn primary_type(schema: &Value) -> Result<Option<&str>, String> {
+    match schema.get("type") {
+        Some(Value::String(ty)) => Ok(Some(ty)),
+        Some(Value::Array(types)) => {
+            let non_null = types
+                .iter()
+                .filter_map(Value::as_str)
+                .filter(|ty| *ty != "null")
+                .collect::<Vec<_>>();
+            match non_null.as_slice() {
+                [] => Ok(None),
+                [only] => Ok(Some(only)),
+                many => Err(many.join(" | ")),
+            }
+        }
+        _ => Ok(None),
+    }
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+/// Whether `null` is an accepted value: 3.0 `nullable`, a 3.1 `null` type
+/// member, a `null` enum value, or a `null` `oneOf`/`anyOf` branch.
+pub(crate) fn is_nullable(schema: &Value) -> bool {
+    let flag = schema.get("nullable").and_then(Value::as_bool) == Some(true);
+    let in_type = schema
+        .get("type")
+        .and_then(Value::as_array)
+        .is_some_and(|types| types.iter().any(|ty| ty == "null"));
+    let in_enum = schema
+        .get("enum")
+        .and_then(Value::as_array)
+        .is_some_and(|values| values.iter().any(Value::is_null));
+    let in_union = ["oneOf", "anyOf"].iter().any(|keyword| {
+        schema
+            .get(keyword)
+            .and_then(Value::as_array)
+            .is_some_and(|variants| variants.iter().any(is_null_schema))
+    });
+    flag || in_type || in_enum || in_union
+}
+
+pub(crate) fn description(schema: &Value) -> Option<String> {
+    schema
+        .get("description")
+        .or_else(|| schema.get("title"))
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
@@ -0,0 +1,275 @@
+use crate::diagnostics::{Diagnostic, Diagnostics, pointer};
+use crate::names::{self, NameSet};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+enum SchemeKind {
+    ApiKey { header: bool, name: String },
+    Bearer,
+    Basic,
+    OAuthClient { token_url: String },
+    OAuthRefresh { token_url: String },
+}
+
+struct Scheme {
+    credential: String,
+    secret_base: String,
+    kind: SchemeKind,
+    scopes: BTreeSet<String>,
+    used: bool,
+}
+
+/// The security schemes one operation requires, keyed by scheme name.
+#[derive(Clone, Default, Eq, PartialEq)]
+pub(crate) struct AuthUse {
+    schemes: BTreeMap<String, Vec<String>>,
+}
+
+impl AuthUse {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.schemes.is_empty()
+    }
+}
+
+/// Lowers `components.securitySchemes` into `auth` credentials and
+/// placements.
+pub(crate) struct Security {
+    schemes: BTreeMap<String, Result<Scheme, Diagnostic>>,
+}
+
+impl Security {
+    pub(crate) fn new(root: &Value, diagnostics: &mut Diagnostics) -> Self {
+        let mut credentials = NameSet::default();
+        let mut schemes = BTreeMap::new();
+        let declared = root
+            .pointer("/components/securitySchemes")
+            .and_then(Value::as_object);
+        for (name, scheme) in declared.into_iter().flatten() {
+            let at = pointer("#/components/securitySchemes", name);
+            let lowered = scheme_kind(scheme, &at, diagnostics).map(|kind| Scheme {
+                credential: credentials.claim(names::dsl_ident(name)),
+                secret_base: names::field_ident(name),
+                kind,
+                scopes: BTreeSet::new(),
+                used: false,
+            });
+            schemes.insert(name.clone(), lowered);
+        }
+        Self { schemes }
+    }
+
+    /// Resolves an operation's effective `security` requirement. Only the
+    /// first alternative is generated because `auth` clauses are additive.
+    pub(crate) fn requirement(
+        &self,
+        security: Option<&Value>,
+        at: &str,
+        diagnostics: &mut Diagnostics,
+    ) -> AuthUse {
+        let mut auth = AuthUse::default();
+        let Some(alternatives) = security.and_then(Value::as_array) else {
+            return auth;
+        };
+        if alternatives.len() > 1 {
+            diagnostics.warning(
+                at,
+                "only the first of several alternative security requirements is generated",
+            );
+        }
+        let Some(requirement) = alternatives.first().and_then(Value::as_object) else {
+            return auth;
+        };
+        for (name, scopes) in requirement {
+            match self.schemes.get(name) {
+                None => diagnostics.error(at, format!("undeclared security scheme `{name}`")),
+                Some(Err(unsupported)) => diagnostics.push(unsupported.clone()),
+                Some(Ok(_)) => {
+                    let scopes = scopes
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect();
+                    auth.schemes.insert(name.clone(), scopes);
+                }
+            }
+        }
+        auth
+    }
+
+    /// Records that a generated operation attaches `auth`.
+    pub(crate) fn commit(&mut self, auth: &AuthUse) {
+        for (name, scopes) in &auth.schemes {
+            if let Some(Ok(scheme)) = self.schemes.get_mut(name) {
+                scheme.used = true;
+                scheme.scopes.extend(scopes.iter().cloned());
+            }
+        }
+    }
+
+    /// `auth` placement clauses for one operation.
+    pub(crate) fn placements(&self, auth: &AuthUse) -> Vec<String> {
+        auth.schemes
+            .keys()
+            .filter_map(|name| self.schemes.get(name)?.as_ref().ok())
+            .map(|scheme| {
+                let credential = &scheme.credential;
+                match &scheme.kind {
+                    SchemeKind::ApiKey { header: true, name } => {
+                        format!("auth header {name:?} = {credential}")
+                    }
+                    SchemeKind::ApiKey {
+                        header: false,
+                        name,
+                    } => format!("auth query {name:?} = {credential}"),
+                    SchemeKind::Basic => format!("auth basic {credential}"),
+                    SchemeKind::Bearer
+                    | SchemeKind::OAuthClient { .. }
+                    | SchemeKind::OAuthRefresh { .. } => format!("auth bearer {credential}"),
+                }
+            })
+            .collect()
+    }
+
+    /// `secret` and `credential` lines for every scheme an operation uses.
+    pub(crate) fn declarations(&self) -> Vec<String> {
+        let mut secrets = Vec::new();
+        let mut credentials = Vec::new();
+        for scheme in self.schemes.values().filter_map(|s| s.as_ref().ok()) {
+            if !scheme.used {
+                continue;
+            }
+            let base = &scheme.secret_base;
+            let credential = &scheme.credential;
+            match &scheme.kind {
+                SchemeKind::ApiKey { .. } => {
+                    secrets.push(format!("secret {base}: String"));
+                    credentials.push(format!("credential {credential} = api_key(secret.{base})"));
+                }
+                SchemeKind::Bearer => {
+                    secrets.push(format!("secret {base}_token: String"));
+                    credentials.push(format!(
+                        "credential {credential} = bearer(secret.{base}_token)"
+                    ));
+                }
+                SchemeKind::Basic => {
+                    secrets.push(format!("secret {base}_username: String"));
+                    secrets.push(format!("secret {base}_password: String"));
+                    credentials.push(format!(
+                        "credential {credential} = basic(secret.{base}_username, secret.{base}_password)"
+                    ));
+                }
+                SchemeKind::OAuthClient { token_url } | SchemeKind::OAuthRefresh { token_url } => {
+                    let refresh = matches!(scheme.kind, SchemeKind::OAuthRefresh { .. });
+                    secrets.push(format!("secret {base}_client_id: String"));
+                    secrets.push(format!("secret {base}_client_secret: String"));
+                    let mut fields = vec![
+                        format!("token_url: {token_url:?},"),
+                        format!("client_id: secret.{base}_client_id,"),
+                        format!("client_secret: secret.{base}_client_secret,"),
+                    ];
+                    if refresh {
+                        secrets.push(format!("secret {base}_refresh_token: String"));
+                        fields.push(format!("refresh_token: secret.{base}_refresh_token,"));
+                    }
+                    if !scheme.scopes.is_empty() {
+                        let scope = scheme.scopes.iter().cloned().collect::<Vec<_>>().join(" ");
+                        fields.push(format!("scope: {scope:?},"));
+                    }
+                    let provider = if refresh {
+                        "oauth2_refresh"
+                    } else {
+                        "oauth2_client"
+                    };
+                    credentials.push(format!(
+                        "credential {credential} = {provider} {{\n{}\n}}",
+                        fields.join("\n")
+                    ));
+                }
+            }
+        }
+        secrets.extend(credentials);
+        secrets
+    }
+}
+
+fn scheme_kind(
+    scheme: &Value,
+    at: &str,
+    diagnostics: &mut Diagnostics,
+) -> Result<SchemeKind, Diagnostic> {
+    let field = |key: &str| scheme.get(key).and_then(Value::as_str);
+    match field("type") {
+        Some("apiKey") => {
+            let name = field("name")
+                .ok_or_else(|| Diagnostic::error(at, "apiKey scheme has no `name`"))?
+                .to_string();
+            match field("in") {
+                Some("header") => Ok(SchemeKind::ApiKey { header: true, name }),
+                Some("query") => Ok(SchemeKind::ApiKey {
+                    header: false,
+                    name,
+                }),
+                Some(other) => Err(Diagnostic::error(
+                    at,
+                    format!("apiKey credentials in `{other}` are not supported"),
+                )),
+                None => Err(Diagnostic::error(at, "apiKey scheme has no `in`")),
+            }
+        }
+        Some("http") => match field("scheme").map(str::to_ascii_lowercase).as_deref() {
+            Some("bearer") => Ok(SchemeKind::Bearer),
+            Some("basic") => Ok(SchemeKind::Basic),
+            Some(other) => Err(Diagnostic::error(
+                at,
+                format!("HTTP `{other}` authentication is not supported"),
+            )),
+            None => Err(Diagnostic::error(at, "http scheme has no `scheme`")),
+        },
+        Some("oauth2") => {
+            let flows = scheme.get("flows");
+            let token_url = |flow: &str| {
+                let token_url = flows?.get(flow)?.get("tokenUrl")?.as_str()?;
+                Some(token_url.to_string())
+            };
+            let checked = |token_url: String| {
+                if token_url.starts_with("https://") {
+                    Ok(token_url)
+                } else {
+                    Err(Diagnostic::error(
+                        at,
+                        format!("OAuth2 token URL `{token_url}` must be an absolute HTTPS URL"),
+                    ))
+                }
+            };
+            if let Some(token_url) = token_url("clientCredentials") {
+                Ok(SchemeKind::OAuthClient {
+                    token_url: checked(token_url)?,
+                })
+            } else if let Some(token_url) = token_url("authorizationCode") {
+                Ok(SchemeKind::OAuthRefresh {
+                    token_url: checked(token_url)?,
+                })
+            } else {
+                diagnostics.warning(
+                    at,
+                    "OAuth2 flow without a token endpoint is generated as a caller-supplied bearer token",
+                );
+                Ok(SchemeKind::Bearer)
+            }
+        }
+        Some("openIdConnect") => {
+            diagnostics.warning(
+                at,
+                "OpenID Connect is generated as a caller-supplied bearer token",
+            );
+            Ok(SchemeKind::Bearer)
+        }
+        Some(other) => Err(Diagnostic::error(
+            at,
+            format!("security scheme type `{other}` is not supported"),
+        )),
+        None => Err(Diagnostic::error(at, "security scheme has no `type`")),
+    }
+}
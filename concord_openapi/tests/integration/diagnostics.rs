@@ -0,0 +1,199 @@
+use concord_openapi::{GenerateError, Generator, Severity};
+
+fn unsupported(document: &str) -> Vec<(String, String)> {
+    match Generator::new("TestApi").generate_str(document) {
+        Err(GenerateError::Unsupported { diagnostics }) => diagnostics
+            .iter()
+            .filter(|d| d.severity() == Severity::Error)
+            .map(|d| (d.pointer().to_string(), d.message().to_string()))
+            .collect(),
+        other => panic!("expected unsupported constructs, got {other:?}"),
+    }
+}
+
+const UNSUPPORTED_OPERATIONS: &str = r#"
+openapi: 3.0.3
+info: { title: Unsupported, version: "1" }
+servers: [{ url: "https://api.example.com" }]
+paths:
+  /session:
+    get:
+      operationId: getSession
+      parameters:
+        - { name: sid, in: cookie, schema: { type: string } }
+      responses: { 204: { description: none } }
+  /search:
+    get:
+      operationId: search
+      parameters:
+        - name: filter
+          in: query
+          style: deepObject
+          schema: { type: object, properties: { q: { type: string } } }
+      responses: { 204: { description: none } }
+  /upload:
+    post:
+      operationId: upload
+      requestBody:
+        required: true
+        content: { multipart/form-data: { schema: { type: object } } }
+      responses: { 204: { description: none } }
+  /ok:
+    get:
+      operationId: ok
+      responses: { 204: { description: none } }
+"#;
+
+#[test]
+fn unsupported_constructs_fail_with_pointed_errors() {
+    assert_eq!(
+        unsupported(UNSUPPORTED_OPERATIONS),
+        [
+            (
+                "#/paths/~1search/get/parameters/0".to_string(),
+                "parameter `filter`: object-valued parameters are not supported".to_string(),
+            ),
+            (
+                "#/paths/~1session/get/parameters/0".to_string(),
+                "parameter `sid` in `cookie` is not supported".to_string(),
+            ),
+            (
+                "#/paths/~1upload/post/requestBody/content".to_string(),
+                "request body media type `multipart/form-data` is not supported".to_string(),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn skip_unsupported_drops_operations_and_downgrades_errors() {
+    let generated = Generator::new("TestApi")
+        .skip_unsupported(true)
+        .generate_str(UNSUPPORTED_OPERATIONS)
+        .unwrap();
+    let source = generated.source();
+    assert!(source.contains("GET Ok\n"));
+    for skipped in ["GetSession", "Search", "Upload"] {
+        assert!(!source.contains(skipped), "{skipped} was generated");
+    }
+    assert!(
+        generated
+            .diagnostics()
+            .iter()
+            .all(|d| d.severity() == Severity::Warning)
+    );
+    let skipped = generated
+        .diagnostics()
+        .iter()
+        .filter(|d| d.message().starts_with("operation skipped"))
+        .map(|d| d.pointer())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        skipped,
+        [
+            "#/paths/~1search/get",
+            "#/paths/~1session/get",
+            "#/paths/~1upload/post"
+        ]
+    );
+}
+
+#[test]
+fn unsupported_security_and_references_are_reported() {
+    let errors = unsupported(
+        r#"
+openapi: 3.0.3
+info: { title: Security, version: "1" }
+servers: [{ url: "https://api.example.com" }]
+paths:
+  /a:
+    get:
+      operationId: a
+      security: [{ cookie: [] }]
+      responses: { 204: { description: none } }
+  /b:
+    get:
+      operationId: b
+      security: [{ missing: [] }]
+      parameters: [{ $ref: "common.yaml#/Limit" }]
+      responses: { 204: { description: none } }
+  /c/{id}:
+    get:
+      operationId: c
+      security: [{ digest: [] }]
+      responses: { 204: { description: none } }
+components:
+  securitySchemes:
+    cookie: { type: apiKey, in: cookie, name: sid }
+    digest: { type: http, scheme: digest }
+"#,
+    );
+    assert_eq!(
+        errors,
+        [
+            (
+                "#/components/securitySchemes/cookie".to_string(),
+                "apiKey credentials in `cookie` are not supported".to_string(),
+            ),
+            (
+                "#/paths/~1b/get/parameters/0".to_string(),
+                "external reference `common.yaml#/Limit` is not supported".to_string(),
+            ),
+            (
+                "#/paths/~1b/get".to_string(),
+                "undeclared security scheme `missing`".to_string(),
+            ),
+            (
+                "#/paths/~1c~1{id}/get".to_string(),
+                "path parameter `id` is not declared".to_string(),
+            ),
+            (
+                "#/components/securitySchemes/digest".to_string(),
+                "HTTP `digest` authentication is not supported".to_string(),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn documents_outside_the_supported_surface_are_rejected() {
+    let swagger = Generator::new("TestApi").generate_str("swagger: '2.0'\ninfo: {}\n");
+    assert!(matches!(
+        swagger,
+        Err(GenerateError::Version { ref version }) if version == "2.0"
+    ));
+
+    let no_servers = unsupported("openapi: 3.1.0\ninfo: { title: t, version: '1' }\n");
+    assert_eq!(
+        no_servers,
+        [(
+            "#/servers".to_string(),
+            "document declares no servers; set a base URL".to_string()
+        )]
+    );
+
+    let relative = unsupported("openapi: 3.0.0\nservers: [{ url: /api }]\n");
+    assert_eq!(
+        relative[0].1,
+        "server URL `/api` is relative; set an absolute base URL"
+    );
+
+    let client = Generator::new("snake_case").generate_str("openapi: 3.0.0\n");
+    assert!(matches!(client, Err(GenerateError::ClientName { .. })));
+
+    let syntax = Generator::new("TestApi").generate_str("{ not json");
+    assert!(matches!(syntax, Err(GenerateError::Syntax { .. })));
+}
+
+#[test]
+fn unsupported_error_lists_every_error() {
+    let err = Generator::new("TestApi")
+        .generate_str(UNSUPPORTED_OPERATIONS)
+        .unwrap_err();
+    let rendered = err.to_string();
+    assert!(rendered.starts_with("OpenAPI document uses unsupported constructs\n"));
+    assert!(rendered.contains(
+        "\n  error: parameter `sid` in `cookie` is not supported at `#/paths/~1session/get/parameters/0`"
+    ));
+    assert_eq!(rendered.lines().count(), 4);
+}
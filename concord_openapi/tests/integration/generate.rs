@@ -0,0 +1,364 @@
+use concord_openapi::{Generator, Severity};
+
+fn generate(document: &str) -> String {
+    Generator::new("TestApi")
+        .generate_str(document)
+        .unwrap_or_else(|err| panic!("{err}"))
+        .into_source()
+}
+
+fn assert_contains(source: &str, fragments: &[&str]) {
+    for fragment in fragments {
+        assert!(
+            source.contains(fragment),
+            "missing `{fragment}` in generated source:\n{source}"
+        );
+    }
+}
+
+#[test]
+fn json_and_yaml_documents_generate_identical_source() {
+    let yaml = r##"
+openapi: 3.1.0
+info: { title: Users, version: "2" }
+servers: [{ url: "https://api.example.com" }]
+paths:
+  /users/{id}:
+    get:
+      operationId: get-user
+      parameters:
+        - { name: id, in: path, required: true, schema: { type: string } }
+      responses:
+        200:
+          description: ok
+          content:
+            application/json:
+              schema: { $ref: "#/components/schemas/User" }
+components:
+  schemas:
+    User:
+      type: object
+      required: [id]
+      properties:
+        id: { type: string }
+"##;
+    let json = r##"{
+        "openapi": "3.1.0",
+        "info": { "title": "Users", "version": "2" },
+        "servers": [{ "url": "https://api.example.com" }],
+        "paths": { "/users/{id}": { "get": {
+            "operationId": "get-user",
+            "parameters": [{ "name": "id", "in": "path", "required": true, "schema": { "type": "string" } }],
+            "responses": { "200": { "description": "ok", "content": { "application/json": {
+                "schema": { "$ref": "#/components/schemas/User" } } } } }
+        } } },
+        "components": { "schemas": { "User": {
+            "type": "object", "required": ["id"], "properties": { "id": { "type": "string" } }
+        } } }
+    }"##;
+
+    let source = generate(yaml);
+    assert_eq!(source, generate(json));
+    assert_contains(
+        &source,
+        &[
+            "// @generated by concord_openapi from `Users` 2; do not edit.",
+            "pub struct User {\n        pub id: String,\n    }",
+            "client TestApi {\n        base \"https://api.example.com\"\n    }",
+            "scope users {\n        path [\"users\"]",
+            "GET GetUser(id: String)\n            path [id]\n            -> Json<models::User>",
+            "pub use self::test_api::*;",
+        ],
+    );
+}
+
+#[test]
+fn security_schemes_become_credentials_and_placements() {
+    let source = generate(
+        r#"
+openapi: 3.0.3
+info: { title: Secure, version: "1" }
+servers: [{ url: "https://api.example.com/v2" }]
+paths:
+  /reports:
+    get:
+      operationId: listReports
+      security: [{ service: [reports.read] }]
+      responses: { 204: { description: none } }
+  /me:
+    get:
+      operationId: me
+      security: [{ session: [] }, { login: [] }]
+      responses: { 204: { description: none } }
+  /legacy:
+    get:
+      operationId: legacy
+      security: [{ login: [], key: [] }]
+      responses: { 204: { description: none } }
+components:
+  securitySchemes:
+    service:
+      type: oauth2
+      flows:
+        clientCredentials:
+          tokenUrl: https://auth.example.com/token
+          scopes: { reports.read: read }
+    session: { type: http, scheme: bearer }
+    login: { type: http, scheme: Basic }
+    key: { type: apiKey, in: query, name: api_key }
+    unused: { type: apiKey, in: cookie, name: sid }
+"#,
+    );
+    assert_contains(
+        &source,
+        &[
+            "secret key: String",
+            "secret login_username: String",
+            "secret login_password: String",
+            "secret service_client_id: String",
+            "secret session_token: String",
+            "credential key_ = api_key(secret.key)",
+            "credential login = basic(secret.login_username, secret.login_password)",
+            "credential service = oauth2_client {\n                token_url: \"https://auth.example.com/token\",\n                client_id: secret.service_client_id,\n                client_secret: secret.service_client_secret,\n                scope: \"reports.read\",\n            }",
+            "credential session = bearer(secret.session_token)",
+            "path [\"v2\", \"legacy\"]\n\n        GET Legacy\n            auth query \"api_key\" = key_\n            auth basic login\n",
+            "GET Me\n            auth bearer session\n",
+            "GET ListReports\n            auth bearer service\n",
+        ],
+    );
+    assert!(!source.contains("default {"));
+    assert!(!source.contains("sid"));
+}
+
+#[test]
+fn shared_security_is_declared_once_as_a_client_default() {
+    let source = generate(
+        r#"
+openapi: 3.0.3
+info: { title: Shared, version: "1" }
+servers: [{ url: "https://api.example.com" }]
+security: [{ session: [] }]
+paths:
+  /a: { get: { operationId: a, responses: { 204: { description: none } } } }
+  /b: { delete: { operationId: b, responses: { 204: { description: none } } } }
+components:
+  securitySchemes:
+    session:
+      type: oauth2
+      flows:
+        authorizationCode:
+          authorizationUrl: https://auth.example.com/authorize
+          tokenUrl: https://auth.example.com/token
+          scopes: {}
+"#,
+    );
+    assert_contains(
+        &source,
+        &[
+            "credential session = oauth2_refresh {",
+            "refresh_token: secret.session_refresh_token,",
+            "default {\n            auth bearer session\n        }",
+            "GET A\n            -> NoContent",
+        ],
+    );
+    assert!(!source.contains("scope: \""));
+}
+
+#[test]
+fn parameters_bind_path_query_and_header_with_safe_identifiers() {
+    let source = generate(
+        r#"
+openapi: 3.0.3
+info: { title: Params, version: "1" }
+servers: [{ url: "https://api.example.com" }]
+paths:
+  /files/{fileId}.{ext}:
+    get:
+      operationId: getFile
+      parameters:
+        - { name: fileId, in: path, required: true, schema: { type: integer, format: int64 } }
+        - { name: ext, in: path, required: true, schema: { type: string } }
+        - { name: type, in: query, required: true, schema: { type: string, enum: [raw, "2x"] } }
+        - { name: url, in: query, schema: { type: array, items: { type: string } } }
+        - { name: X-Trace-Id, in: header, schema: { type: string } }
+        - { name: Accept, in: header, schema: { type: string } }
+        - { name: If-Match, in: header, required: true, schema: { type: string } }
+      responses:
+        200:
+          description: ok
+          content: { application/octet-stream: {} }
+"#,
+    );
+    assert_contains(
+        &source,
+        &[
+            "GET GetFile(file_id: i64, ext: String, type_: models::GetFileType, if_match: String, url_?: Vec<String>, x_trace_id?: String)",
+            "path [fmt[file_id, \".\", ext]]",
+            "query {\n                \"type\" = type_\n                \"url\" = url_\n            }",
+            "headers {\n                \"If-Match\" = if_match,\n                \"X-Trace-Id\" = x_trace_id\n            }",
+            "-> Bytes",
+            "pub enum GetFileType {\n        #[serde(rename = \"raw\")]\n        Raw,\n        #[serde(rename = \"2x\")]\n        V2x,\n    }",
+            "Self::V2x => \"2x\",",
+        ],
+    );
+    assert!(!source.contains("\"Accept\""));
+}
+
+#[test]
+fn schemas_map_nullability_unions_maps_and_recursion() {
+    let generated = Generator::new("TestApi")
+        .generate_str(
+            r##"
+openapi: 3.1.0
+info: { title: Models, version: "1" }
+servers: [{ url: "https://api.example.com" }]
+paths: {}
+components:
+  schemas:
+    Node:
+      type: object
+      required: [value, next]
+      properties:
+        value: { type: [string, "null"] }
+        next: { $ref: "#/components/schemas/Node" }
+        children: { type: array, items: { $ref: "#/components/schemas/Node" } }
+        labels: { type: object, additionalProperties: { type: number, format: float } }
+        shape: { oneOf: [{ type: string }, { type: integer }] }
+        parent: { anyOf: [{ $ref: "#/components/schemas/Node" }, { type: "null" }] }
+    Id: { type: integer, format: int32 }
+"##,
+        )
+        .unwrap();
+    assert_contains(
+        generated.source(),
+        &[
+            "pub value: Option<String>,",
+            "pub next: Box<Node>,",
+            "pub children: Option<Vec<Node>>,",
+            "pub labels: Option<std::collections::HashMap<String, f32>>,",
+            "pub shape: Option<serde_json::Value>,",
+            "pub parent: Option<Box<Node>>,",
+            "pub type Id = i32;",
+        ],
+    );
+    let warnings = generated
+        .diagnostics()
+        .iter()
+        .filter(|d| d.severity() == Severity::Warning)
+        .map(|d| (d.pointer(), d.message()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        warnings,
+        [(
+            "#/components/schemas/Node/properties/shape",
+            "`oneOf` is generated as untyped `serde_json::Value`"
+        )]
+    );
+}
+
+#[test]
+fn request_bodies_and_error_responses_select_codecs() {
+    let generated = Generator::new("TestApi")
+        .generate_str(
+            r##"
+openapi: 3.0.3
+info: { title: Bodies, version: "1" }
+servers: [{ url: "https://api.example.com" }]
+paths:
+  /tokens:
+    post:
+      operationId: createToken
+      requestBody:
+        content:
+          application/x-www-form-urlencoded:
+            schema:
+              type: object
+              properties: { grant_type: { type: string } }
+      responses:
+        201:
+          description: ok
+          content:
+            application/problem+json:
+              schema: { type: object, properties: { token: { type: string } } }
+        404:
+          description: missing
+          content: { application/json: { schema: { $ref: "#/components/schemas/Problem" } } }
+        4XX:
+          description: client
+          content: { application/json: { schema: { $ref: "#/components/schemas/Problem" } } }
+        5XX:
+          description: server
+          content: { application/json: { schema: { type: object, properties: { retry: { type: boolean } } } } }
+  /notes:
+    put:
+      operationId: putNote
+      requestBody:
+        required: true
+        content: { text/plain: { schema: { type: string } } }
+      responses:
+        200:
+          description: ok
+          content: { text/plain: { schema: { type: string } } }
+components:
+  schemas:
+    Problem: { type: object, properties: { title: { type: string } } }
+"##,
+        )
+        .unwrap();
+    assert_contains(
+        generated.source(),
+        &[
+            "POST CreateToken(body: Form<models::CreateTokenRequest>)",
+            "-> Json<models::CreateTokenResponse>\n            on_error Json<models::Problem>\n",
+            "PUT PutNote(body: Text<String>)\n            -> Text<String>\n",
+        ],
+    );
+    assert!(!generated.source().contains("on_error 404"));
+    let messages = generated
+        .diagnostics()
+        .iter()
+        .map(|d| d.message())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "optional request body is generated as a required body",
+            "`5XX` error schema differs from `4XX`; only one fallback `on_error` is generated",
+        ]
+    );
+}
+
+#[test]
+fn server_variables_and_base_url_override_set_the_client_base() {
+    let document = r#"
+openapi: 3.0.3
+info: { title: Servers, version: "1" }
+servers:
+  - url: "https://{region}.example.com/api/{version}"
+    variables:
+      region: { default: eu }
+      version: { default: v3 }
+  - url: https://backup.example.com
+paths:
+  /ping: { get: { operationId: ping, responses: { 204: { description: none } } } }
+"#;
+    let generated = Generator::new("TestApi").generate_str(document).unwrap();
+    assert_contains(
+        generated.source(),
+        &[
+            "base \"https://eu.example.com\"",
+            "path [\"api\", \"v3\", \"ping\"]",
+        ],
+    );
+    assert_eq!(generated.diagnostics().len(), 3);
+
+    let overridden = Generator::new("TestApi")
+        .base_url("http://localhost:8080/")
+        .generate_str(document)
+        .unwrap();
+    assert_contains(
+        overridden.source(),
+        &["base \"http://localhost:8080\"", "path [\"ping\"]"],
+    );
+    assert!(overridden.diagnostics().is_empty());
+}
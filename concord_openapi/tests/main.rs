@@ -0,0 +1,4 @@
+mod integration {
+    mod diagnostics;
+    mod generate;
+}
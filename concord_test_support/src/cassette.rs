@@ -0,0 +1,676 @@
+//! VCR-style record/replay cassettes.
+//!
+//! [`CassetteRecorder`] forwards a client's materialized requests to a local
+//! upstream server through Concord's in-process transport and keeps a
+//! sanitized copy of every exchange. [`Cassette::replay`] serves the saved
+//! exchanges back through the deterministic executor.
+//!
+//! Nothing reaches a cassette before redaction: request identities keep only
+//! the pre-authentication method, path, non-sensitive query pairs, and a
+//! SHA-256 digest of the body; response headers and JSON or form-encoded body
+//! fields use Concord's redaction rules. A provider response, which usually
+//! carries credentials, fails instead of being recorded when its body is
+//! neither.
+
+use crate::deterministic::{DeterministicMock, MockExecutionHandle, deterministic_mock};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use bytes::Bytes;
+use concord_core::__development::{
+    CapturedNativeRequest, DeterministicExecutionKind, RequestBodyDigest, ScriptedNativeResponse,
+};
+use concord_core::advanced::{SafeReqwestBuilder, SanitizedHeaders, is_sensitive_name};
+use concord_core::in_process::{
+    InProcessBody, InProcessError, InProcessService, LogicalTarget, configure_application_service,
+    configure_provider_service,
+};
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+use http_body_util::BodyExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+
+const REDACTED: &str = "<redacted>";
+
+/// Headers that describe one connection or one encoding of the body rather
+/// than the response itself. Replay recomputes the framing it needs.
+const FRAMING_HEADERS: &[&str] = &[
+    "connection",
+    "content-length",
+    "keep-alive",
+    "transfer-encoding",
+];
+
+/// Which managed execution channel an interaction was recorded on.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CassetteChannel {
+    Application,
+    Provider,
+}
+
+impl From<DeterministicExecutionKind> for CassetteChannel {
+    fn from(kind: DeterministicExecutionKind) -> Self {
+        match kind {
+            DeterministicExecutionKind::Application => Self::Application,
+            DeterministicExecutionKind::Provider => Self::Provider,
+        }
+    }
+}
+
+/// Sanitized identity of one recorded request.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CassetteRequest {
+    pub channel: CassetteChannel,
+    pub method: String,
+    pub path: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub query: Vec<(String, String)>,
+    /// Hex SHA-256 of the request body; absent for empty bodies.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_sha256: Option<String>,
+}
+
+/// Redacted response for one recorded request.
+///
+/// UTF-8 bodies are stored as `body`; anything else is stored as
+/// `body_base64`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CassetteResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_base64: Option<String>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CassetteInteraction {
+    pub request: CassetteRequest,
+    pub response: CassetteResponse,
+}
+
+/// An ordered list of recorded interactions.
+///
+/// Files ending in `.yaml` or `.yml` use YAML; every other path uses JSON.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Cassette {
+    pub interactions: Vec<CassetteInteraction>,
+}
+
+impl Cassette {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read(path)?;
+        if is_yaml(path) {
+            serde_yaml_ng::from_slice(&contents).map_err(io::Error::other)
+        } else {
+            serde_json::from_slice(&contents).map_err(io::Error::other)
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let contents = if is_yaml(path) {
+            serde_yaml_ng::to_string(self)
+                .map_err(io::Error::other)?
+                .into_bytes()
+        } else {
+            let mut contents = serde_json::to_vec_pretty(self).map_err(io::Error::other)?;
+            contents.push(b'\n');
+            contents
+        };
+        std::fs::write(path, contents)
+    }
+
+    /// Serve the recorded interactions through deterministic executors.
+    ///
+    /// Each interaction answers at most one request: the first unused one
+    /// whose channel and identity satisfy `matching`. A request with no match
+    /// is an unexpected execution, and [`MockExecutionHandle::finish`] rejects
+    /// interactions that were never replayed.
+    pub fn replay(&self, matching: RequestMatching) -> (DeterministicMock, MockExecutionHandle) {
+        let (mock, handle) = deterministic_mock().build();
+        let state = Arc::new(ReplayState {
+            interactions: self.interactions.clone(),
+            used: Mutex::new(vec![false; self.interactions.len()]),
+            matching,
+        });
+        for executor in [&mock.application, &mock.provider] {
+            let state = state.clone();
+            executor.route_requests(move |request, digest| state.answer(request, digest));
+        }
+        let pending = state.clone();
+        (mock, handle.with_pending_replies(move || pending.unused()))
+    }
+}
+
+/// Which parts of a request identity must agree during replay.
+///
+/// Method, path, and query matching are on by default. Body matching compares
+/// SHA-256 digests and is opt-in because bodies that embed credentials differ
+/// between recording and replay.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RequestMatching {
+    method: bool,
+    path: bool,
+    query: bool,
+    body: bool,
+}
+
+impl Default for RequestMatching {
+    fn default() -> Self {
+        Self {
+            method: true,
+            path: true,
+            query: true,
+            body: false,
+        }
+    }
+}
+
+impl RequestMatching {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn method(mut self, enabled: bool) -> Self {
+        self.method = enabled;
+        self
+    }
+
+    pub fn path(mut self, enabled: bool) -> Self {
+        self.path = enabled;
+        self
+    }
+
+    pub fn query(mut self, enabled: bool) -> Self {
+        self.query = enabled;
+        self
+    }
+
+    pub fn body_hash(mut self, enabled: bool) -> Self {
+        self.body = enabled;
+        self
+    }
+
+    fn matches(
+        &self,
+        recorded: &CassetteRequest,
+        request: &CapturedNativeRequest,
+        digest: &RequestBodyDigest,
+    ) -> bool {
+        recorded.channel == CassetteChannel::from(request.execution_kind())
+            && (!self.method || recorded.method == request.method().as_str())
+            && (!self.path || recorded.path == request.logical_target().path())
+            && (!self.query || recorded.query == public_query(request.logical_target()))
+            && (!self.body
+                || recorded.body_sha256.as_deref().unwrap_or(EMPTY_BODY_SHA256) == digest.to_hex())
+    }
+}
+
+const EMPTY_BODY_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+struct ReplayState {
+    interactions: Vec<CassetteInteraction>,
+    used: Mutex<Vec<bool>>,
+    matching: RequestMatching,
+}
+
+impl ReplayState {
+    fn answer(
+        &self,
+        request: &CapturedNativeRequest,
+        digest: &RequestBodyDigest,
+    ) -> Option<ScriptedNativeResponse> {
+        let mut used = lock(&self.used);
+        let index = self
+            .interactions
+            .iter()
+            .zip(used.iter())
+            .position(|(interaction, used)| {
+                !used && self.matching.matches(&interaction.request, request, digest)
+            })?;
+        used[index] = true;
+        replay_response(&self.interactions[index].response)
+    }
+
+    fn unused(&self) -> usize {
+        lock(&self.used).iter().filter(|used| !**used).count()
+    }
+}
+
+fn replay_response(recorded: &CassetteResponse) -> Option<ScriptedNativeResponse> {
+    let status = StatusCode::from_u16(recorded.status).ok()?;
+    let body = match (&recorded.body, &recorded.body_base64) {
+        (_, Some(encoded)) => Bytes::from(BASE64.decode(encoded).ok()?),
+        (Some(text), None) => Bytes::from(text.clone()),
+        (None, None) => Bytes::new(),
+    };
+    let length = HeaderValue::from(body.len());
+    let mut response = ScriptedNativeResponse::bytes(status, body);
+    for (name, value) in &recorded.headers {
+        response = response.with_header(
+            HeaderName::from_bytes(name.as_bytes()).ok()?,
+            HeaderValue::from_str(value).ok()?,
+        );
+    }
+    Some(response.with_header(http::header::CONTENT_LENGTH, length))
+}
+
+/// Records sanitized interactions while forwarding requests to a local
+/// upstream server.
+///
+/// Requests keep their path and query but are sent to `upstream`'s origin,
+/// so generated clients with fixed production bases can be recorded against a
+/// loopback test server. Redirects are never followed and no proxy is used.
+#[derive(Clone)]
+pub struct CassetteRecorder {
+    upstream: url::Url,
+    client: reqwest::Client,
+    interactions: Arc<Mutex<Vec<CassetteInteraction>>>,
+}
+
+impl CassetteRecorder {
+    /// # Panics
+    ///
+    /// Panics unless `upstream` is an `http` or `https` origin on a loopback
+    /// host; recording never talks to remote servers.
+    pub fn new(upstream: url::Url) -> Self {
+        assert!(
+            matches!(upstream.scheme(), "http" | "https") && is_loopback(&upstream),
+            "cassette recording requires a loopback http(s) upstream"
+        );
+        let client = reqwest::Client::builder()
+            .no_proxy()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("cassette recording client");
+        Self {
+            upstream,
+            client,
+            interactions: Arc::default(),
+        }
+    }
+
+    pub fn configure_application(&self, builder: SafeReqwestBuilder) -> SafeReqwestBuilder {
+        configure_application_service(builder, self.service(CassetteChannel::Application))
+    }
+
+    pub fn configure_provider(&self, builder: SafeReqwestBuilder) -> SafeReqwestBuilder {
+        configure_provider_service(builder, self.service(CassetteChannel::Provider))
+    }
+
+    pub fn configure_both(&self, builder: SafeReqwestBuilder) -> SafeReqwestBuilder {
+        self.configure_provider(self.configure_application(builder))
+    }
+
+    pub fn cassette(&self) -> Cassette {
+        Cassette {
+            interactions: lock(&self.interactions).clone(),
+        }
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.cassette().save(path)
+    }
+
+    fn service(&self, channel: CassetteChannel) -> InProcessService {
+        InProcessService::new(RecordingService {
+            recorder: self.clone(),
+            channel,
+        })
+    }
+
+    async fn forward(
+        &self,
+        channel: CassetteChannel,
+        request: http::Request<InProcessBody>,
+    ) -> Result<http::Response<reqwest::Body>, InProcessError> {
+        let (parts, body) = request.into_parts();
+        let body = body.collect().await?.to_bytes();
+        let logical = parts
+            .extensions
+            .get::<LogicalTarget>()
+            .ok_or("in-process request has no logical target")?
+            .url()
+            .clone();
+
+        let mut target = self.upstream.clone();
+        target.set_path(parts.uri.path());
+        target.set_query(parts.uri.query());
+        let mut headers = parts.headers;
+        headers.remove(http::header::HOST);
+        let response = self
+            .client
+            .request(parts.method.clone(), target)
+            .headers(headers)
+            .body(body.clone())
+            .send()
+            .await?;
+        let status = response.status();
+        let response_headers = response.headers().clone();
+        let response_body = response.bytes().await?;
+        let sanitized = sanitize_response(channel, status, &response_headers, &response_body)?;
+
+        lock(&self.interactions).push(CassetteInteraction {
+            request: CassetteRequest {
+                channel,
+                method: parts.method.as_str().to_owned(),
+                path: logical.path().to_owned(),
+                query: public_query(&logical),
+                body_sha256: (!body.is_empty()).then(|| hex(&Sha256::digest(&body))),
+            },
+            response: sanitized,
+        });
+
+        let mut replayed = http::Response::new(reqwest::Body::from(response_body));
+        *replayed.status_mut() = status;
+        *replayed.headers_mut() = response_headers;
+        Ok(replayed)
+    }
+}
+
+impl std::fmt::Debug for CassetteRecorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CassetteRecorder")
+            .field("upstream", &self.upstream.origin().ascii_serialization())
+            .field("interactions", &lock(&self.interactions).len())
+            .finish()
+    }
+}
+
+#[derive(Clone)]
+struct RecordingService {
+    recorder: CassetteRecorder,
+    channel: CassetteChannel,
+}
+
+type RecordingFuture =
+    Pin<Box<dyn Future<Output = Result<http::Response<reqwest::Body>, InProcessError>> + Send>>;
+
+impl tower_service::Service<http::Request<InProcessBody>> for RecordingService {
+    type Response = http::Response<reqwest::Body>;
+    type Error = InProcessError;
+    type Future = RecordingFuture;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<InProcessBody>) -> Self::Future {
+        let recorder = self.recorder.clone();
+        let channel = self.channel;
+        Box::pin(async move { recorder.forward(channel, request).await })
+    }
+}
+
+fn sanitize_response(
+    channel: CassetteChannel,
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: &Bytes,
+) -> Result<CassetteResponse, InProcessError> {
+    let headers = SanitizedHeaders::new(headers)
+        .iter()
+        .filter(|(name, _)| !FRAMING_HEADERS.contains(&name.as_str()))
+        .map(|(name, value)| (name.as_str().to_owned(), value.as_str().to_owned()))
+        .collect::<Vec<_>>();
+    let content_type = headers
+        .iter()
+        .find(|(name, _)| name == "content-type")
+        .map(|(_, value)| value.as_str());
+    let body = match redact_body(content_type, body) {
+        Some(redacted) => redacted,
+        None if body.is_empty() || channel == CassetteChannel::Application => body.clone(),
+        None => {
+            return Err(
+                "cassette recording refuses a provider response body it cannot redact".into(),
+            );
+        }
+    };
+    let (body, body_base64) = match std::str::from_utf8(&body) {
+        Ok("") => (None, None),
+        Ok(text) => (Some(text.to_owned()), None),
+        Err(_) => (None, Some(BASE64.encode(&body))),
+    };
+    Ok(CassetteResponse {
+        status: status.as_u16(),
+        headers,
+        body,
+        body_base64,
+    })
+}
+
+/// The body with sensitive fields redacted, or `None` when its media type
+/// has no field structure Concord can redact.
+fn redact_body(content_type: Option<&str>, body: &Bytes) -> Option<Bytes> {
+    let media = content_type?.split(';').next()?.trim().to_ascii_lowercase();
+    if media.contains("json") {
+        let mut value = serde_json::from_slice::<serde_json::Value>(body).ok()?;
+        redact_json(&mut value);
+        return Some(Bytes::from(
+            serde_json::to_vec(&value).expect("redacted JSON body"),
+        ));
+    }
+    if media == "application/x-www-form-urlencoded" {
+        let mut form = url::form_urlencoded::Serializer::new(String::new());
+        for (name, value) in url::form_urlencoded::parse(body) {
+            let value = if is_sensitive_name(&name) {
+                REDACTED
+            } else {
+                &value
+            };
+            form.append_pair(&name, value);
+        }
+        let redacted = form.finish();
+        return Some(Bytes::from(redacted));
+    }
+    None
+}
+
+fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(fields) => {
+            for (name, field) in fields.iter_mut() {
+                if is_sensitive_name(name) {
+                    *field = serde_json::Value::String(REDACTED.to_owned());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+fn public_query(url: &url::Url) -> Vec<(String, String)> {
+    url.query_pairs()
+        .filter(|(name, _)| !is_sensitive_name(name))
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect()
+}
+
+fn is_loopback(url: &url::Url) -> bool {
+    match url.host() {
+        Some(url::Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+        Some(url::Host::Ipv4(address)) => address.is_loopback(),
+        Some(url::Host::Ipv6(address)) => address.is_loopback(),
+        None => false,
+    }
+}
+
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension, "yaml" | "yml"))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "CASSETTE_SECRET_SENTINEL";
+
+    #[test]
+    fn recorded_responses_are_redacted_before_serialization() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        headers.insert(
+            http::header::SET_COOKIE,
+            HeaderValue::from_str(&format!("session={SECRET}")).unwrap(),
+        );
+        headers.insert(http::header::CONTENT_LENGTH, HeaderValue::from(99));
+        let body = Bytes::from(format!(
+            r#"{{"id":1,"access_token":"{SECRET}","nested":[{{"client_secret":"{SECRET}"}}]}}"#
+        ));
+
+        let response =
+            sanitize_response(CassetteChannel::Provider, StatusCode::OK, &headers, &body).unwrap();
+        let cassette = Cassette {
+            interactions: vec![CassetteInteraction {
+                request: CassetteRequest {
+                    channel: CassetteChannel::Provider,
+                    method: "POST".to_owned(),
+                    path: "/token".to_owned(),
+                    query: Vec::new(),
+                    body_sha256: None,
+                },
+                response,
+            }],
+        };
+
+        let rendered = serde_json::to_string(&cassette).unwrap();
+        assert!(!rendered.contains(SECRET), "{rendered}");
+        assert!(!rendered.contains("content-length"), "{rendered}");
+        let response = &cassette.interactions[0].response;
+        assert!(
+            response
+                .headers
+                .contains(&("set-cookie".to_owned(), REDACTED.to_owned()))
+        );
+        assert_eq!(
+            response.body.as_deref(),
+            Some(
+                r#"{"access_token":"<redacted>","id":1,"nested":[{"client_secret":"<redacted>"}]}"#
+            )
+        );
+    }
+
+    #[test]
+    fn form_encoded_responses_are_redacted() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("application/x-www-form-urlencoded; charset=utf-8"),
+        );
+        let body = Bytes::from(format!(
+            "access_token={SECRET}&scope=read&refresh_token={SECRET}"
+        ));
+
+        let response =
+            sanitize_response(CassetteChannel::Provider, StatusCode::OK, &headers, &body).unwrap();
+
+        assert_eq!(
+            response.body.as_deref(),
+            Some("access_token=%3Credacted%3E&scope=read&refresh_token=%3Credacted%3E")
+        );
+    }
+
+    #[test]
+    fn provider_bodies_that_cannot_be_redacted_are_refused() {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain"),
+        );
+        let body = Bytes::from(format!("token {SECRET}"));
+
+        assert!(
+            sanitize_response(CassetteChannel::Provider, StatusCode::OK, &headers, &body).is_err()
+        );
+        assert!(
+            sanitize_response(
+                CassetteChannel::Provider,
+                StatusCode::NO_CONTENT,
+                &headers,
+                &Bytes::new()
+            )
+            .is_ok()
+        );
+        let response = sanitize_response(
+            CassetteChannel::Application,
+            StatusCode::OK,
+            &headers,
+            &body,
+        )
+        .unwrap();
+        assert_eq!(response.body, Some(format!("token {SECRET}")));
+    }
+
+    #[test]
+    fn public_query_drops_sensitive_pairs() {
+        let url = url::Url::parse(&format!(
+            "https://api.example.com/items?page=2&api_key={SECRET}&sort=name"
+        ))
+        .unwrap();
+
+        assert_eq!(
+            public_query(&url),
+            [
+                ("page".to_owned(), "2".to_owned()),
+                ("sort".to_owned(), "name".to_owned())
+            ]
+        );
+    }
+
+    #[test]
+    fn non_utf8_bodies_round_trip_through_base64() {
+        let body = Bytes::from_static(&[0xff, 0x00, 0x7f]);
+        let response = sanitize_response(
+            CassetteChannel::Application,
+            StatusCode::OK,
+            &HeaderMap::new(),
+            &body,
+        )
+        .unwrap();
+
+        assert_eq!(response.body, None);
+        assert!(replay_response(&response).is_some());
+        assert_eq!(
+            BASE64.decode(response.body_base64.unwrap()).unwrap(),
+            body.as_ref()
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "loopback")]
+    fn recording_refuses_remote_upstreams() {
+        CassetteRecorder::new(url::Url::parse("https://api.example.com").unwrap());
+    }
+}
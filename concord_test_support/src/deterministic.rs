@@ -60,6 +60,7 @@ impl fmt::Debug for RecordedExecution {
 pub enum ScriptedResponseStep {
     Chunk(Bytes),
     Gate(ResponseGate),
+    Delay(Duration),
     Failure,
 }
 
@@ -71,6 +72,7 @@ impl fmt::Debug for ScriptedResponseStep {
                 .field(&format_args!("<{} bytes>", bytes.len()))
                 .finish(),
             Self::Gate(gate) => f.debug_tuple("Gate").field(gate).finish(),
+            Self::Delay(delay) => f.debug_tuple("Delay").field(delay).finish(),
             Self::Failure => f.write_str("Failure"),
         }
     }
@@ -234,13 +236,18 @@ impl ScriptedReply {
         self
     }
 
-    fn response(&self) -> ScriptedNativeResponse {
+    pub(crate) fn is_failure(&self) -> bool {
+        self.failure.is_some()
+    }
+
+    pub(crate) fn response(&self) -> ScriptedNativeResponse {
         let mut response = if let Some(steps) = &self.response_steps {
             ScriptedNativeResponse::body_steps(
                 self.status,
                 steps.iter().cloned().map(|step| match step {
                     ScriptedResponseStep::Chunk(bytes) => ScriptedResponseBodyStep::Chunk(bytes),
                     ScriptedResponseStep::Gate(gate) => ScriptedResponseBodyStep::Gate(gate.0),
+                    ScriptedResponseStep::Delay(delay) => ScriptedResponseBodyStep::Delay(delay),
                     ScriptedResponseStep::Failure => ScriptedResponseBodyStep::Failure,
                 }),
             )
@@ -316,8 +323,8 @@ impl fmt::Debug for ScriptedReply {
 
 #[derive(Clone)]
 pub struct DeterministicMock {
-    application: DeterministicNativeExecutor,
-    provider: DeterministicNativeExecutor,
+    pub(crate) application: DeterministicNativeExecutor,
+    pub(crate) provider: DeterministicNativeExecutor,
     gates: Arc<Vec<ResponseGate>>,
 }
 
@@ -352,10 +359,23 @@ pub struct MockExecutionHandle {
     provider: DeterministicNativeExecutor,
     completed: Arc<AtomicUsize>,
     body_observations: Arc<std::sync::Mutex<Vec<RequestBodyTerminalObservation>>>,
+    pending_replies: Option<PendingReplies>,
     finished: bool,
 }
 
+type PendingReplies = Arc<dyn Fn() -> usize + Send + Sync>;
+
 impl MockExecutionHandle {
+    /// Count replies served outside the executor queues, such as cassette
+    /// interactions, as unused scripts until they are consumed.
+    pub(crate) fn with_pending_replies(
+        mut self,
+        pending: impl Fn() -> usize + Send + Sync + 'static,
+    ) -> Self {
+        self.pending_replies = Some(Arc::new(pending));
+        self
+    }
+
     pub fn recorded(&self) -> Vec<RecordedExecution> {
         let mut captures = self.application.captures();
         captures.extend(self.provider.captures());
@@ -389,7 +409,9 @@ impl MockExecutionHandle {
 
     fn assert_clean(&self) {
         assert_eq!(
-            self.application.remaining_scripts() + self.provider.remaining_scripts(),
+            self.application.remaining_scripts()
+                + self.provider.remaining_scripts()
+                + self.pending_replies.as_ref().map_or(0, |pending| pending()),
             0,
             "deterministic scripted replies remain unused"
         );
@@ -550,6 +572,7 @@ impl DeterministicMockBuilder {
                 provider,
                 completed,
                 body_observations,
+                pending_replies: None,
                 finished: false,
             },
         )
@@ -572,13 +595,15 @@ fn reply_gates(reply: &ScriptedReply) -> Vec<ResponseGate> {
                 .flatten()
                 .filter_map(|step| match step {
                     ScriptedResponseStep::Gate(gate) => Some(gate.clone()),
-                    ScriptedResponseStep::Chunk(_) | ScriptedResponseStep::Failure => None,
+                    ScriptedResponseStep::Chunk(_)
+                    | ScriptedResponseStep::Delay(_)
+                    | ScriptedResponseStep::Failure => None,
                 }),
         )
         .collect()
 }
 
-fn recorded_execution(request: CapturedNativeRequest) -> RecordedExecution {
+pub(crate) fn recorded_execution(request: CapturedNativeRequest) -> RecordedExecution {
     RecordedExecution {
         sequence: request.sequence(),
         method: request.method().clone(),
@@ -0,0 +1,264 @@
+//! Seeded fault injection around deterministic responders.
+//!
+//! [`FaultInjection`] wraps the application channel of any
+//! [`DeterministicMock`]: positional scripts, cassette replays, or typed
+//! expectations. Every configured fault is rolled independently for each
+//! execution from one seeded generator, so a seed always reproduces the same
+//! fault sequence for the same request order. Delays run on the tokio clock,
+//! which keeps `#[tokio::test(start_paused = true)]` runs fast.
+
+use crate::deterministic::{DeterministicMock, MockExecutionHandle};
+use bytes::Bytes;
+use concord_core::__development::{InjectedFault, ScriptedNativeResponse};
+use http::{HeaderValue, StatusCode};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+const SERVER_ERRORS: [StatusCode; 4] = [
+    StatusCode::INTERNAL_SERVER_ERROR,
+    StatusCode::BAD_GATEWAY,
+    StatusCode::SERVICE_UNAVAILABLE,
+    StatusCode::GATEWAY_TIMEOUT,
+];
+
+/// One fault applied to one execution.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InjectedFaultKind {
+    /// The response head was delayed by this long.
+    Latency(Duration),
+    /// The responder was bypassed for an empty response with this status.
+    ServerError(StatusCode),
+    /// The body failed part-way, as a reset connection would.
+    ResetMidBody,
+    /// The body ended early without an error.
+    TruncatedBody,
+    /// The body was re-split into small, delayed chunks.
+    SlowDrip,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct FaultRates {
+    latency: Option<(f64, Duration, Duration)>,
+    server_error: Option<f64>,
+    reset: Option<f64>,
+    truncate: Option<f64>,
+    drip: Option<(f64, usize, Duration)>,
+}
+
+/// A seedable, probabilistic fault layer for deterministic mocks.
+///
+/// Server errors replace the response without consuming the wrapped
+/// responder, so a retried request still reaches the scripted reply. Body
+/// faults rewrite whatever the responder returns. Credential-provider
+/// requests are never faulted.
+#[derive(Clone)]
+pub struct FaultInjection {
+    rates: FaultRates,
+    state: Arc<Mutex<FaultState>>,
+}
+
+struct FaultState {
+    rng: SplitMix64,
+    injected: Vec<InjectedFaultKind>,
+}
+
+impl FaultInjection {
+    pub fn seeded(seed: u64) -> Self {
+        Self {
+            rates: FaultRates::default(),
+            state: Arc::new(Mutex::new(FaultState {
+                rng: SplitMix64(seed),
+                injected: Vec::new(),
+            })),
+        }
+    }
+
+    /// Delay the response head by a uniform draw from `min..=max`.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `rate` is within `0.0..=1.0` and `min <= max`.
+    pub fn latency(mut self, rate: f64, min: Duration, max: Duration) -> Self {
+        assert!(min <= max, "latency range must not be empty");
+        self.rates.latency = Some((checked_rate(rate), min, max));
+        self
+    }
+
+    /// Answer with an empty 500, 502, 503, or 504 response.
+    pub fn server_errors(mut self, rate: f64) -> Self {
+        self.rates.server_error = Some(checked_rate(rate));
+        self
+    }
+
+    /// Fail the response body at a random offset.
+    pub fn reset_mid_body(mut self, rate: f64) -> Self {
+        self.rates.reset = Some(checked_rate(rate));
+        self
+    }
+
+    /// End the response body cleanly at a random offset before its end.
+    pub fn truncate_body(mut self, rate: f64) -> Self {
+        self.rates.truncate = Some(checked_rate(rate));
+        self
+    }
+
+    /// Deliver the response body in `chunk_len` pieces, each after
+    /// `interval`.
+    pub fn slow_drip(mut self, rate: f64, chunk_len: usize, interval: Duration) -> Self {
+        self.rates.drip = Some((checked_rate(rate), chunk_len, interval));
+        self
+    }
+
+    /// Install this layer in front of a responder's application channel.
+    pub fn wrap(
+        &self,
+        responder: (DeterministicMock, MockExecutionHandle),
+    ) -> (DeterministicMock, MockExecutionHandle) {
+        let rates = self.rates;
+        let state = self.state.clone();
+        responder
+            .0
+            .application
+            .inject_faults(move |_capture| lock(&state).decide(rates));
+        responder
+    }
+
+    /// Faults injected so far, in execution order.
+    pub fn injected(&self) -> Vec<InjectedFaultKind> {
+        lock(&self.state).injected.clone()
+    }
+}
+
+impl std::fmt::Debug for FaultInjection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FaultInjection")
+            .field("rates", &self.rates)
+            .field("injected", &lock(&self.state).injected.len())
+            .finish()
+    }
+}
+
+impl FaultState {
+    fn decide(&mut self, rates: FaultRates) -> Option<InjectedFault> {
+        let latency = rates
+            .latency
+            .filter(|(rate, _, _)| self.rng.roll(*rate))
+            .map(|(_, min, max)| min + (max - min).mul_f64(self.rng.unit()));
+        if let Some(latency) = latency {
+            self.injected.push(InjectedFaultKind::Latency(latency));
+        }
+        if rates.server_error.is_some_and(|rate| self.rng.roll(rate)) {
+            let status = SERVER_ERRORS[self.rng.below(SERVER_ERRORS.len())];
+            self.injected.push(InjectedFaultKind::ServerError(status));
+            let response = ScriptedNativeResponse::bytes(status, Bytes::new())
+                .with_header(http::header::CONTENT_LENGTH, HeaderValue::from(0_u64))
+                .with_latency(latency.unwrap_or_default());
+            return Some(InjectedFault::Replace(response));
+        }
+        let reset = rates.reset.is_some_and(|rate| self.rng.roll(rate));
+        let truncate = !reset && rates.truncate.is_some_and(|rate| self.rng.roll(rate));
+        let drip = rates.drip.filter(|(rate, _, _)| self.rng.roll(*rate));
+        let cut = self.rng.unit();
+        if reset {
+            self.injected.push(InjectedFaultKind::ResetMidBody);
+        }
+        if truncate {
+            self.injected.push(InjectedFaultKind::TruncatedBody);
+        }
+        if drip.is_some() {
+            self.injected.push(InjectedFaultKind::SlowDrip);
+        }
+        if latency.is_none() && !reset && !truncate && drip.is_none() {
+            return None;
+        }
+        Some(InjectedFault::Rewrite(Box::new(move |mut response| {
+            let cut_at = (response.body_len() as f64 * cut) as u64;
+            if reset {
+                response = response.fail_body_after(cut_at);
+            } else if truncate {
+                response = response.truncate_body(cut_at);
+            }
+            if let Some((_, chunk_len, interval)) = drip {
+                response = response.drip_body(chunk_len, interval);
+            }
+            match latency {
+                Some(latency) => response.with_latency(latency),
+                None => response,
+            }
+        })))
+    }
+}
+
+fn checked_rate(rate: f64) -> f64 {
+    assert!(
+        (0.0..=1.0).contains(&rate),
+        "fault rates must be within 0.0..=1.0"
+    );
+    rate
+}
+
+/// SplitMix64: tiny, seedable, and stable across platforms and releases.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform in `0.0..1.0`.
+    fn unit(&mut self) -> f64 {
+        (self.next() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    fn roll(&mut self, rate: f64) -> bool {
+        self.unit() < rate
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equal_seeds_decide_equal_fault_sequences() {
+        let decide = |seed| {
+            let faults = FaultInjection::seeded(seed)
+                .server_errors(0.3)
+                .reset_mid_body(0.2)
+                .latency(0.5, Duration::from_millis(10), Duration::from_millis(50));
+            let mut state = lock(&faults.state);
+            for _ in 0..64 {
+                state.decide(faults.rates);
+            }
+            state.injected.clone()
+        };
+
+        assert_eq!(decide(11), decide(11));
+        assert_ne!(decide(11), decide(12));
+        assert!(
+            decide(11)
+                .iter()
+                .any(|fault| matches!(fault, InjectedFaultKind::ServerError(_)))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "fault rates must be within 0.0..=1.0")]
+    fn rates_outside_the_unit_interval_are_rejected() {
+        let _ = FaultInjection::seeded(0).server_errors(1.5);
+    }
+}
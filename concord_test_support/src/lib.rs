@@ -1,12 +1,26 @@
+#[cfg(feature = "cassette")]
+mod cassette;
 #[cfg(feature = "dangerous-dev-tools")]
 mod deterministic;
 #[cfg(feature = "dangerous-dev-tools")]
 mod deterministic_assert;
+#[cfg(feature = "dangerous-dev-tools")]
+mod fault_injection;
+mod manual_clock;
+#[cfg(feature = "dangerous-dev-tools")]
+mod typed_mock;
 
+#[cfg(feature = "cassette")]
+pub use cassette::*;
 #[cfg(feature = "dangerous-dev-tools")]
 pub use deterministic::*;
 #[cfg(feature = "dangerous-dev-tools")]
 pub use deterministic_assert::*;
+#[cfg(feature = "dangerous-dev-tools")]
+pub use fault_injection::*;
+pub use manual_clock::*;
+#[cfg(feature = "dangerous-dev-tools")]
+pub use typed_mock::*;
 
 use bytes::Bytes;
 use serde::Serialize;
@@ -0,0 +1,218 @@
+//! A clock that only moves when a test advances it.
+//!
+//! Install a [`ManualClock`] with `RuntimeConfig::clock` and rate-limit
+//! windows, cooldowns, and credential expiry all follow it: a request that
+//! would wait parks until [`ManualClock::advance`] moves time past its
+//! deadline, so tests exercise refills and refreshes without sleeping.
+
+use concord_core::advanced::{Clock, ClockFuture};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll, Waker};
+use std::time::{Duration, Instant};
+
+/// Shared, manually advanced time. Clones observe the same time.
+#[derive(Clone)]
+pub struct ManualClock {
+    state: Arc<Mutex<ManualClockState>>,
+}
+
+struct ManualClockState {
+    now: Instant,
+    next_sleeper: u64,
+    sleepers: BTreeMap<u64, (Option<Instant>, Waker)>,
+}
+
+impl ManualClock {
+    /// Starts at the current system instant so deadlines built from
+    /// `Instant::now()` stay comparable.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(ManualClockState {
+                now: Instant::now(),
+                next_sleeper: 0,
+                sleepers: BTreeMap::new(),
+            })),
+        }
+    }
+
+    pub fn now(&self) -> Instant {
+        lock(&self.state).now
+    }
+
+    /// Moves time forward and wakes every sleep whose deadline has passed.
+    pub fn advance(&self, by: Duration) {
+        let woken = {
+            let mut state = lock(&self.state);
+            state.now += by;
+            let now = state.now;
+            let due = state
+                .sleepers
+                .iter()
+                .filter(|(_, (deadline, _))| deadline.is_some_and(|deadline| deadline <= now))
+                .map(|(id, _)| *id)
+                .collect::<Vec<_>>();
+            due.into_iter()
+                .filter_map(|id| state.sleepers.remove(&id))
+                .map(|(_, waker)| waker)
+                .collect::<Vec<_>>()
+        };
+        for waker in woken {
+            waker.wake();
+        }
+    }
+
+    /// Sleeps currently parked on this clock.
+    pub fn sleepers(&self) -> usize {
+        lock(&self.state).sleepers.len()
+    }
+
+    /// Yields to the runtime until at least `count` sleeps are parked, so a
+    /// following [`ManualClock::advance`] is observed by them.
+    pub async fn wait_for_sleepers(&self, count: usize) {
+        while self.sleepers() < count {
+            YieldNow(false).await;
+        }
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for ManualClock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let state = lock(&self.state);
+        f.debug_struct("ManualClock")
+            .field("now", &state.now)
+            .field("sleepers", &state.sleepers.len())
+            .finish()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        ManualClock::now(self)
+    }
+
+    fn sleep(&self, duration: Duration) -> ClockFuture {
+        let mut state = lock(&self.state);
+        let id = state.next_sleeper;
+        state.next_sleeper += 1;
+        // A deadline past `Instant`'s range never arrives.
+        let deadline = state.now.checked_add(duration);
+        Box::pin(ManualSleep {
+            state: self.state.clone(),
+            id,
+            deadline,
+        })
+    }
+}
+
+struct ManualSleep {
+    state: Arc<Mutex<ManualClockState>>,
+    id: u64,
+    deadline: Option<Instant>,
+}
+
+impl Future for ManualSleep {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = lock(&self.state);
+        if self.deadline.is_some_and(|deadline| state.now >= deadline) {
+            state.sleepers.remove(&self.id);
+            return Poll::Ready(());
+        }
+        state
+            .sleepers
+            .insert(self.id, (self.deadline, cx.waker().clone()));
+        Poll::Pending
+    }
+}
+
+impl Drop for ManualSleep {
+    fn drop(&mut self) {
+        lock(&self.state).sleepers.remove(&self.id);
+    }
+}
+
+/// Returns `Pending` once so other tasks run, without a runtime dependency.
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            return Poll::Ready(());
+        }
+        self.0 = true;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::task::Waker;
+
+    #[test]
+    fn sleeps_complete_only_once_time_passes_their_deadline() {
+        let clock = ManualClock::new();
+        let start = clock.now();
+        let mut sleep = Clock::sleep(&clock, Duration::from_secs(5));
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert!(sleep.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(clock.sleepers(), 1);
+
+        clock.advance(Duration::from_secs(4));
+        assert!(sleep.as_mut().poll(&mut cx).is_pending());
+
+        clock.advance(Duration::from_secs(1));
+        assert_eq!(clock.sleepers(), 0);
+        assert!(sleep.as_mut().poll(&mut cx).is_ready());
+        assert_eq!(clock.now() - start, Duration::from_secs(5));
+    }
+
+    #[test]
+    fn sleeps_past_the_instant_range_never_complete() {
+        let clock = ManualClock::new();
+        let mut sleep = Clock::sleep(&clock, Duration::MAX);
+        let mut cx = Context::from_waker(Waker::noop());
+
+        assert!(sleep.as_mut().poll(&mut cx).is_pending());
+        clock.advance(Duration::from_secs(365 * 24 * 60 * 60));
+        assert!(sleep.as_mut().poll(&mut cx).is_pending());
+        assert_eq!(clock.sleepers(), 1);
+
+        drop(sleep);
+        assert_eq!(clock.sleepers(), 0);
+    }
+
+    #[test]
+    fn dropped_sleeps_stop_counting_as_parked() {
+        let clock = ManualClock::new();
+        let mut sleep = Clock::sleep(&clock, Duration::from_secs(1));
+        assert!(
+            sleep
+                .as_mut()
+                .poll(&mut Context::from_waker(Waker::noop()))
+                .is_pending()
+        );
+        drop(sleep);
+        assert_eq!(clock.sleepers(), 0);
+    }
+}
@@ -0,0 +1,207 @@
+//! Typed, order-independent expectations for generated clients.
+//!
+//! [`TypedMockApi::mock`] collects expectations keyed by generated endpoint
+//! type. The deterministic executor inspects every endpoint value before it
+//! is planned, and each application request is answered by the first unused
+//! expectation that accepts both the endpoint value and its sanitized logical
+//! request. Tests therefore keep passing when call order changes.
+
+use crate::deterministic::{
+    DeterministicMock, MockExecutionHandle, RecordedExecution, ScriptedReply, deterministic_mock,
+    recorded_execution,
+};
+use concord_core::__private::{GeneratedClient, GeneratedEndpoint, GeneratedEndpointFields};
+use serde::Serialize;
+use std::any::{Any, type_name};
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+type EndpointPredicate = Arc<dyn Fn(&dyn Any) -> bool + Send + Sync>;
+type PlanPredicate = Arc<dyn Fn(&RecordedExecution) -> bool + Send + Sync>;
+type ValuePredicate<E> = Arc<dyn Fn(&E) -> bool + Send + Sync>;
+
+struct Expectation {
+    endpoint: &'static str,
+    accepts: EndpointPredicate,
+    plan: Vec<PlanPredicate>,
+    reply: ScriptedReply,
+}
+
+/// Entry point for typed expectations, implemented for every client emitted
+/// by `api!`.
+pub trait TypedMockApi: GeneratedClient {
+    fn mock() -> TypedMock<Self> {
+        TypedMock::new()
+    }
+}
+
+impl<C: GeneratedClient> TypedMockApi for C {}
+
+/// Expectations for one generated client, answered in any order.
+///
+/// Only application executions are routed; credential-provider requests
+/// count as unexpected executions.
+pub struct TypedMock<C> {
+    expectations: Vec<Expectation>,
+    client: PhantomData<fn() -> C>,
+}
+
+impl<C: GeneratedClient> TypedMock<C> {
+    pub fn new() -> Self {
+        Self {
+            expectations: Vec::new(),
+            client: PhantomData,
+        }
+    }
+
+    pub fn expect<E: GeneratedEndpoint<C::Cx>>(self) -> EndpointExpectation<C, E> {
+        EndpointExpectation {
+            mock: self,
+            endpoint: Vec::new(),
+            plan: Vec::new(),
+        }
+    }
+
+    /// Install the expectations on a fresh deterministic executor.
+    ///
+    /// The handle reports unmatched requests as unexpected executions and
+    /// unanswered expectations as unused scripted replies.
+    pub fn build(self) -> (DeterministicMock, MockExecutionHandle) {
+        let (mock, handle) = deterministic_mock().build();
+        let expectations: Arc<[Expectation]> = self.expectations.into();
+        let used = Arc::new(Mutex::new(vec![false; expectations.len()]));
+
+        let matcher = expectations.clone();
+        mock.application.match_endpoints(move |endpoint| {
+            matcher
+                .iter()
+                .enumerate()
+                .filter(|(_, expectation)| (expectation.accepts)(endpoint))
+                .map(|(index, _)| index)
+                .collect()
+        });
+        let router_used = used.clone();
+        mock.application.route_requests(move |capture, _digest| {
+            let recorded = recorded_execution(capture.clone());
+            let mut used = lock(&router_used);
+            let index = capture.endpoint_matches().iter().copied().find(|&index| {
+                !used[index]
+                    && expectations[index]
+                        .plan
+                        .iter()
+                        .all(|predicate| predicate(&recorded))
+            })?;
+            used[index] = true;
+            Some(expectations[index].reply.response())
+        });
+        let pending = handle.with_pending_replies(move || {
+            lock(&used).iter().filter(|consumed| !**consumed).count()
+        });
+        (mock, pending)
+    }
+}
+
+impl<C: GeneratedClient> Default for TypedMock<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C> fmt::Debug for TypedMock<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypedMock")
+            .field(
+                "expectations",
+                &self
+                    .expectations
+                    .iter()
+                    .map(|expectation| expectation.endpoint)
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+/// One pending expectation for endpoint type `E`, completed by a reply.
+pub struct EndpointExpectation<C, E> {
+    mock: TypedMock<C>,
+    endpoint: Vec<ValuePredicate<E>>,
+    plan: Vec<PlanPredicate>,
+}
+
+impl<C: GeneratedClient, E: GeneratedEndpoint<C::Cx>> EndpointExpectation<C, E> {
+    /// Require the endpoint value to satisfy `predicate`, which reads its
+    /// fields through a borrowed view. Every predicate must hold.
+    pub fn with(
+        mut self,
+        predicate: impl for<'e> Fn(E::Fields<'e>) -> bool + Send + Sync + 'static,
+    ) -> Self
+    where
+        E: GeneratedEndpointFields,
+    {
+        self.endpoint
+            .push(Arc::new(move |value: &E| predicate(value.fields())));
+        self
+    }
+
+    /// Require the sanitized logical request planned from the endpoint to
+    /// satisfy `predicate`. Every predicate must hold.
+    pub fn with_plan(
+        mut self,
+        predicate: impl Fn(&RecordedExecution) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.plan.push(Arc::new(predicate));
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics for synthetic failure replies, which cannot be routed; script
+    /// those positionally with [`deterministic_mock`].
+    pub fn reply(self, reply: ScriptedReply) -> TypedMock<C> {
+        assert!(
+            !reply.is_failure(),
+            "typed expectations reply with responses, not synthetic failures"
+        );
+        let Self {
+            mut mock,
+            endpoint,
+            plan,
+        } = self;
+        mock.expectations.push(Expectation {
+            endpoint: type_name::<E>(),
+            accepts: Arc::new(move |value| {
+                value
+                    .downcast_ref::<E>()
+                    .is_some_and(|value| endpoint.iter().all(|predicate| predicate(value)))
+            }),
+            plan,
+            reply,
+        });
+        mock
+    }
+
+    pub fn reply_json(self, value: &E::Response) -> TypedMock<C>
+    where
+        E::Response: Serialize,
+    {
+        self.reply(ScriptedReply::ok_json(crate::json_bytes(value)))
+    }
+}
+
+impl<C, E> fmt::Debug for EndpointExpectation<C, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EndpointExpectation")
+            .field("endpoint", &type_name::<E>())
+            .field("endpoint_predicates", &self.endpoint.len())
+            .field("plan_predicates", &self.plan.len())
+            .finish()
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}